@@ -1,48 +1,217 @@
 #![cfg(feature = "std")]
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use crate::prelude::*;
-use crate::parsing::my_parser::{parse_mac, parse_hex};
+use crate::parsing::my_parser::{parse_mac, parse_hex, parse_vlan_tags, parse_ipv4_options, parse_tcp_options, parse_tcp_flags, parse_escaped_bytes};
+use crate::structs::ethernet::VlanTag;
+use crate::structs::ip::Ipv4Option;
+use crate::structs::tcp::{TcpFlags, TcpOption};
 
 /// Arguments de la ligne de commande CLI
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 pub struct Args {
-    #[arg(short = 'i', help = "format: --src_ip=192.168.25.2", long = "src_ip")]
+    #[arg(short = 'i', help = "format: --src_ip=192.168.25.2 (IPv6 avec une --dst_ip IPv6, defaut ::)", long = "src_ip", global = true)]
     pub src_ip: Option<StringNoStd>,
 
-    #[arg(short = 'd', help = "format: --dst_ip=192.168.1.25", long = "dst_ip")]
+    #[arg(short = 'd', help = "format: --dst_ip=192.168.1.25 ou --dst_ip=2001:db8::1", long = "dst_ip", global = true)]
     pub dst_ip: Option<StringNoStd>,
 
     #[arg(short = 'p', help = "format: --dest_port=8080", long = "dest_port")]
     pub dest_port: Option<u16>,
 
-    #[arg(short = 's', help = "format: --src_mac=aa:bb:cc:dd:ee:ff", long = "src_mac", value_parser = parse_mac)]
+    #[arg(short = 's', help = "format: --src_mac=aa:bb:cc:dd:ee:ff", long = "src_mac", value_parser = parse_mac, global = true)]
     pub src_mac: Option<[u8; 6]>,
 
-    #[arg(short = 'm', help = "format: --dst_mac=11:22:33:44:55:66", long = "dst_mac", value_parser = parse_mac)]
+    #[arg(short = 'm', help = "format: --dst_mac=11:22:33:44:55:66", long = "dst_mac", value_parser = parse_mac, global = true)]
     pub dst_mac: Option<[u8; 6]>,
 
-    #[arg(short = 'l', help = "format: --l4_protocol=udp", long = "l4_protocol")]
+    #[arg(short = 'l', help = "format: --l4_protocol=udp (tcp, udp ou icmp)", long = "l4_protocol")]
     pub l4_protocol: Option<StringNoStd>,
 
-    #[arg(short = 't', long = "timeout_ms", help = "format: --timeout_ms=2000")]
+    #[arg(short = 't', long = "timeout_ms", help = "format: --timeout_ms=2000", global = true)]
     pub timeout_ms: Option<u64>,
 
-    #[arg(short = 'f', long = "debug_file", help = "format: --debug_file=./debug.pcap")]
+    #[arg(short = 'f', long = "debug_file", help = "format: --debug_file=./debug.pcap", global = true)]
     pub debug_file: Option<StringNoStd>,
 
-    #[arg(short = 'g', long = "debug_format", help = "format: --debug_format=json")]
+    #[arg(short = 'g', long = "debug_format", help = "format: --debug_format=json", global = true)]
     pub debug_format: Option<StringNoStd>,
 
     #[arg(short = 'b', long = "ip_bitfield", default_value = "0x00", value_parser = parse_hex)]
     pub ip_bitfield: Option<u8>,
 
-    #[arg(short = 'r', long = "dry_run", help = "format: --dry_run", action = clap::ArgAction::SetTrue)]
+    #[arg(long = "ttl", help = "format: --ttl=64 (hop limit sur IPv6)", global = true)]
+    pub ttl: Option<u8>,
+
+    #[arg(long = "dscp", help = "format: --dscp=46 (0-63)", value_parser = clap::value_parser!(u8).range(0..=63), global = true)]
+    pub dscp: Option<u8>,
+
+    #[arg(long = "ecn", help = "format: --ecn=1 (0-3)", value_parser = clap::value_parser!(u8).range(0..=3), global = true)]
+    pub ecn: Option<u8>,
+
+    #[arg(long = "ip_id", help = "format: --ip_id=4242", global = true)]
+    pub ip_id: Option<u16>,
+
+    #[arg(long = "ip_options", help = "format: --ip_options=rr:4,ra (rr[:n], ts[:n], lsrr:ip/ip, ssrr:ip/ip, ra, nop)", value_parser = parse_ipv4_options, global = true)]
+    pub ip_options: Option<VecNoStd<Ipv4Option>>,
+
+    #[arg(long = "tcp_flags", help = "format: --tcp_flags=SA (F S R P A U E C N, none pour un scan NULL)", value_parser = parse_tcp_flags, global = true)]
+    pub tcp_flags: Option<TcpFlags>,
+
+    #[arg(long = "seq", help = "format: --seq=1000 (numero de sequence TCP)", global = true)]
+    pub seq: Option<u32>,
+
+    #[arg(long = "ack", help = "format: --ack=2000 (numero d'acquittement TCP)", global = true)]
+    pub ack: Option<u32>,
+
+    #[arg(long = "window", help = "format: --window=1024 (fenetre TCP, defaut 65535)", global = true)]
+    pub window: Option<u16>,
+
+    #[arg(long = "urgent_pointer", help = "format: --urgent_pointer=1 (avec le drapeau U)", global = true)]
+    pub urgent_pointer: Option<u16>,
+
+    #[arg(long = "tcp_options", help = "format: --tcp_options=mss:1460,sackok,ts,nop,ws:7 (sack:l-r/l-r, tfo[:cookie], eol)", value_parser = parse_tcp_options, global = true)]
+    pub tcp_options: Option<VecNoStd<TcpOption>>,
+
+    #[arg(long = "vlan", help = "format: --vlan=100, --vlan=100:5 (PCP) ou --vlan=200,100 (QinQ, tag externe d'abord)", value_parser = parse_vlan_tags, global = true)]
+    pub vlan: Option<VecNoStd<VlanTag>>,
+
+    #[arg(long = "interface", help = "format: --interface=eth0 (defaut : interface de la route vers --dst_ip)", global = true)]
+    pub interface: Option<StringNoStd>,
+
+    #[arg(short = 'r', long = "dry_run", help = "format: --dry_run", action = clap::ArgAction::SetTrue, global = true)]
     pub dry_run: bool,
 
+    #[arg(
+        long = "from_json",
+        help = "format: --from_json=./template.json (paquet ou tableau de paquets JSON envoyes tels quels)",
+        conflicts_with_all = [
+            "src_ip", "dst_ip", "dest_port", "src_mac", "dst_mac", "l4_protocol", "ip_bitfield",
+            "ttl", "dscp", "ecn", "ip_id", "ip_options", "tcp_flags", "seq", "ack", "window",
+            "urgent_pointer", "tcp_options", "vlan",
+        ]
+    )]
+    pub from_json: Option<StringNoStd>,
+
     #[arg(long, default_value = "1", hide = true)]
     pub count: u32,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Sous-commandes optionnelles (sans sous-commande : envoi d'un paquet)
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Scan de ports TCP (SYN, FIN, NULL, Xmas, ACK) ou UDP : cargo run -- scan --dst_ip=192.168.1.25 --ports=1-1024
+    Scan(ScanArgs),
+    /// Decouverte d'hotes par ARP : cargo run -- sweep --subnet=192.168.1.0/24
+    Sweep(SweepArgs),
+    /// Banniere d'un service par une connexion TCP en espace utilisateur : cargo run -- banner --dst_ip=192.168.1.25 --port=22
+    Banner(BannerArgs),
+    /// Detection du service et de sa version : cargo run -- service --dst_ip=192.168.1.25 --ports=22,80,443
+    Service(ServiceArgs),
+    /// Identification du systeme d'exploitation : cargo run -- os --dst_ip=192.168.1.25 --port=22 ou --pcap=capture.pcap
+    Os(OsArgs),
+}
+
+/// Arguments de la sous-commande scan
+#[derive(clap::Args, Debug)]
+pub struct ScanArgs {
+    #[arg(long = "ports", help = "format: --ports=22,80,1000-1010", default_value = "1-1024")]
+    pub ports: StringNoStd,
+
+    #[arg(long = "retries", help = "format: --retries=1", default_value = "1")]
+    pub retries: u8,
+
+    #[arg(long = "src_port", help = "format: --src_port=40000", default_value = "40000")]
+    pub src_port: u16,
+
+    #[arg(long = "scan_type", help = "format: --scan_type=<syn|fin|null|xmas|ack|udp> (--tcp_flags remplace SYN avec syn)", default_value = "syn")]
+    pub scan_type: StringNoStd,
+
+    #[arg(long = "rate", help = "format: --rate=100 (sondes UDP par seconde, 0 : pas de limite)", default_value = "100")]
+    pub rate: u32,
+}
+
+/// Arguments de la sous-commande sweep
+#[derive(clap::Args, Debug)]
+pub struct SweepArgs {
+    #[arg(long = "subnet", help = "format: --subnet=192.168.1.0/24")]
+    pub subnet: StringNoStd,
+
+    #[arg(long = "retries", help = "format: --retries=1", default_value = "1")]
+    pub retries: u8,
+
+    #[arg(long = "rate", help = "format: --rate=100 (requetes ARP par seconde, 0 : pas de limite)", default_value = "100")]
+    pub rate: u32,
+}
+
+/// Arguments de la sous-commande banner
+#[derive(clap::Args, Debug)]
+pub struct BannerArgs {
+    #[arg(long = "port", help = "format: --port=22")]
+    pub port: u16,
+
+    #[arg(long = "send", help = "format: --send='GET / HTTP/1.0\\r\\n\\r\\n' (\\r, \\n, \\t, \\xHH)", value_parser = parse_escaped_bytes)]
+    pub send: Option<VecNoStd<u8>>,
+
+    #[arg(long = "src_port", help = "format: --src_port=40000", default_value = "40000")]
+    pub src_port: u16,
+
+    #[arg(long = "retries", help = "format: --retries=1 (renvois du SYN)", default_value = "1")]
+    pub retries: u8,
+
+    #[arg(long = "max_bytes", help = "format: --max_bytes=4096", default_value = "4096")]
+    pub max_bytes: usize,
+
+    #[arg(long = "teardown", help = "format: --teardown=<fin|rst>", default_value = "fin")]
+    pub teardown: StringNoStd,
+}
+
+/// Arguments de la sous-commande service
+#[derive(clap::Args, Debug)]
+pub struct ServiceArgs {
+    #[arg(long = "ports", help = "format: --ports=22,80,1000-1010")]
+    pub ports: StringNoStd,
+
+    #[arg(long = "protocol", help = "format: --protocol=<tcp|udp>", default_value = "tcp")]
+    pub protocol: StringNoStd,
+
+    #[arg(long = "service_db", help = "format: --service_db=./services.db (sondes et signatures ajoutees a la base integree)")]
+    pub service_db: Option<StringNoStd>,
+
+    #[arg(long = "src_port", help = "format: --src_port=40000 (une connexion par sonde, port source incremente)", default_value = "40000")]
+    pub src_port: u16,
+
+    #[arg(long = "retries", help = "format: --retries=1", default_value = "1")]
+    pub retries: u8,
+
+    #[arg(long = "max_bytes", help = "format: --max_bytes=4096", default_value = "4096")]
+    pub max_bytes: usize,
+}
+
+/// Arguments de la sous-commande os
+#[derive(clap::Args, Debug)]
+pub struct OsArgs {
+    #[arg(long = "port", help = "format: --port=22 (port ouvert de la cible, mode actif)")]
+    pub port: Option<u16>,
+
+    #[arg(long = "pcap", help = "format: --pcap=./capture.pcap (pcap, pcapng ou json, mode passif)")]
+    pub pcap: Option<StringNoStd>,
+
+    #[arg(long = "os_db", help = "format: --os_db=./os.db (empreintes ajoutees a la base integree)")]
+    pub os_db: Option<StringNoStd>,
+
+    #[arg(long = "src_port", help = "format: --src_port=40000 (port source incremente par sonde)", default_value = "40000")]
+    pub src_port: u16,
+
+    #[arg(long = "probes", help = "format: --probes=3", default_value = "3")]
+    pub probes: u8,
+
+    #[arg(long = "retries", help = "format: --retries=1", default_value = "1")]
+    pub retries: u8,
 }
 
 impl Args {
@@ -21,6 +21,7 @@ pub type Result<T> = core::result::Result<T, ParseError>;
 pub enum ParseError {
     InvalidMac,
     InvalidIpv4,
+    InvalidIpv6,
     TooManyOctets,
     NotEnoughOctets,
     InvalidHex,
@@ -47,6 +48,37 @@ pub enum ParseError {
     SerdeError(
         StringNoStd
     ),
+    TruncatedPacket {
+        layer: &'static str,
+        needed: usize,
+        available: usize
+    },
+    InvalidHeaderLength {
+        layer: &'static str,
+        length: usize
+    },
+    LengthMismatch {
+        layer: &'static str,
+        declared: usize,
+        actual: usize
+    },
+    InvalidVersion(
+        u8
+    ),
+    UnsupportedEtherType(
+        u16
+    ),
+    UnsupportedProtocol(
+        u8
+    ),
+    UnresolvedNeighbor(
+        [u8; 4]
+    ),
+    InvalidLine {
+        line: usize,
+        reason: &'static str
+    },
+    Fragmented,
 }
 
 /// Affichage des erreur lisible de parsing reseau explicite
@@ -64,6 +96,10 @@ impl fmt::Display for ParseError {
                 f, 
                 "Invalid IPv4 address"
             ),
+            ParseError::InvalidIpv6 => write!(
+                f,
+                "Invalid IPv6 address"
+            ),
             ParseError::TooManyOctets => write!(
                 f,
                 "Too many octets in IPv4"
@@ -131,6 +167,81 @@ impl fmt::Display for ParseError {
                 "Serde error: {}", 
                 msg
             ),
+            ParseError::TruncatedPacket {
+                layer,
+                needed,
+                available
+            } => write!(
+                f,
+                "Truncated {} header: need {} bytes, {} available",
+                layer,
+                needed,
+                available
+            ),
+            ParseError::InvalidHeaderLength {
+                layer,
+                length
+            } => write!(
+                f,
+                "Invalid {} header length {}",
+                layer,
+                length
+            ),
+            ParseError::LengthMismatch {
+                layer,
+                declared,
+                actual
+            } => write!(
+                f,
+                "{} length mismatch: declared {}, actual {}",
+                layer,
+                declared,
+                actual
+            ),
+            ParseError::InvalidVersion(
+                version
+            ) => write!(
+                f,
+                "Invalid IP version {}",
+                version
+            ),
+            ParseError::UnsupportedEtherType(
+                ethertype
+            ) => write!(
+                f,
+                "Unsupported ethertype 0x{:04X}",
+                ethertype
+            ),
+            ParseError::UnsupportedProtocol(
+                protocol
+            ) => write!(
+                f,
+                "Unsupported L4 protocol {}",
+                protocol
+            ),
+            ParseError::UnresolvedNeighbor(
+                ip
+            ) => write!(
+                f,
+                "No MAC address found for next hop {}.{}.{}.{}",
+                ip[0],
+                ip[1],
+                ip[2],
+                ip[3]
+            ),
+            ParseError::InvalidLine {
+                line,
+                reason
+            } => write!(
+                f,
+                "Line {}: {}",
+                line,
+                reason
+            ),
+            ParseError::Fragmented => write!(
+                f,
+                "IPv4 fragment without a complete L4 header, reassembly required"
+            ),
         }
     }
 }
@@ -159,5 +270,17 @@ impl From<serde_json_core::de::Error> for ParseError {
     }
 }
 
+// Conversion des erreurs d'entree/sortie std
+#[cfg(feature = "std")]
+impl From<std::io::Error> for ParseError {
+    fn from(err:
+        std::io::Error
+    ) -> Self {
+        ParseError::IoError(
+            format!("{}", err)
+        )
+    }
+}
+
 #[cfg(feature = "std")]
 impl Error for ParseError {}
\ No newline at end of file
@@ -5,12 +5,21 @@ use crate::{
     errors::errors::Result,
     prelude::*,
 };
+use crate::formats::stream::ByteSink;
 use crate::structs::{
-    formats::FormatType,
+    stream::StreamWriter,
+    formats::{
+        FormatType,
+        PacketRecord
+    },
     pcap::{
         PcapReader,
         PcapWriter
     },
+    pcapng::{
+        PcapNgReader,
+        PcapNgWriter
+    },
     json::{
         JsonSerializer,
         JsonDeserializer
@@ -24,23 +33,31 @@ impl FormatFactory {
         Self
     }
 
-    // Creer un constructeur pour le format specifie (Pcap ou Json)
+    // Creer un constructeur pour le format specifie (Pcap, PcapNg ou Json)
     pub fn create_writer(&self, format_type: FormatType) -> Box<dyn FormatWriter> {
         match format_type {
             FormatType::Pcap => Box::new(PcapWriter::new()),
+            FormatType::PcapNg => Box::new(PcapNgWriter::new()),
             FormatType::Json => Box::new(JsonSerializer::new()),
         }
     }
 
-    // Creer un lecteur pour le format specifie (Pcap ou Json)
+    // Creer un writer qui ecrit directement dans un sink (fichier, socket, buffer...)
+    // L'entete du format est ecrit immediatement
+    pub fn create_stream_writer<S: ByteSink>(&self, format_type: FormatType, sink: S) -> Result<StreamWriter<S>> {
+        StreamWriter::new(self.create_writer(format_type), sink)
+    }
+
+    // Creer un lecteur pour le format specifie (Pcap, PcapNg ou Json)
     pub fn create_reader(&self, format_type: FormatType, data: VecNoStd<u8>) -> Box<dyn FormatReader> {
         match format_type {
             FormatType::Pcap => Box::new(PcapReader::new(data)),
-            FormatType::Json => Box::new(JsonDeserializer::new()),
+            FormatType::PcapNg => Box::new(PcapNgReader::new(data)),
+            FormatType::Json => Box::new(JsonDeserializer::from_data(data)),
         }
     }
 
-    // Ecrire un seul paquet dans le format specifie (Pcap ou Json)
+    // Ecrire un seul paquet dans le format specifie (Pcap, PcapNg ou Json)
     pub fn write_packet(&self, packet: &NetworkPacket, format_type: FormatType) -> Result<VecNoStd<u8>> {
         match format_type {
             FormatType::Pcap => {
@@ -49,6 +66,12 @@ impl FormatFactory {
                 writer.write_packet(packet)?;
                 Ok(writer.into_data())
             }
+            FormatType::PcapNg => {
+                let mut writer = PcapNgWriter::new();
+                writer.write_global_header()?;
+                writer.write_packet(packet)?;
+                Ok(writer.into_data())
+            }
             FormatType::Json => {
                 let serializer = JsonSerializer::new();
                 Ok(serializer.serialize_packet(packet)?.into_bytes())
@@ -56,7 +79,7 @@ impl FormatFactory {
         }
     }
 
-    // Ecrire plusieurs paquets dans le format specifie (Pcap ou Json)
+    // Ecrire plusieurs paquets dans le format specifie (Pcap, PcapNg ou Json)
     pub fn write_packets(&self, packets: &[NetworkPacket], format_type: FormatType) -> Result<VecNoStd<u8>> {
         match format_type {
             FormatType::Pcap => {
@@ -67,6 +90,14 @@ impl FormatFactory {
                 }
                 Ok(writer.into_data())
             }
+            FormatType::PcapNg => {
+                let mut writer = PcapNgWriter::new();
+                writer.write_global_header()?;
+                for packet in packets {
+                    writer.write_packet(packet)?;
+                }
+                Ok(writer.into_data())
+            }
             FormatType::Json => {
                 let serializer = JsonSerializer::new();
                 Ok(serializer.serialize_packets(packets)?.into_bytes())
@@ -76,28 +107,76 @@ impl FormatFactory {
 }
 
 // Trait pour l'ecriture des formats de paquets
+// write_header ouvre le document (entete global PCAP, SHB/IDB PCAPNG, '[' JSON),
+// write_record ecrit une trame capturee avec son propre timestamp, finish le termine (']' JSON), take_data vide les octets produits jusqu'ici
 pub trait FormatWriter {
+    fn write_header(&mut self) -> Result<()>;
     fn write_packet(&mut self, packet: &NetworkPacket) -> Result<()>;
+    fn write_record(&mut self, record: &PacketRecord) -> Result<()>;
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
     fn get_data(&self) -> &[u8];
+    fn take_data(&mut self) -> VecNoStd<u8>;
     fn into_data(self: Box<Self>) -> VecNoStd<u8>;
 }
 
 // Trait pour la lecture des formats de paquets
 pub trait FormatReader {
     fn read_next_packet(&mut self) -> Result<Option<VecNoStd<u8>>>;
+    fn read_next_record(&mut self) -> Result<Option<PacketRecord>>;
     fn has_more_packets(&self) -> bool;
 }
 
 // Implementation de FormatWriter pour PcapWriter
 impl FormatWriter for PcapWriter {
+    fn write_header(&mut self) -> Result<()> {
+        self.write_global_header()
+    }
+
     fn write_packet(&mut self, packet: &NetworkPacket) -> Result<()> {
         self.write_packet(packet)
     }
 
+    fn write_record(&mut self, record: &PacketRecord) -> Result<()> {
+        self.write_record(record)
+    }
+
     fn get_data(&self) -> &[u8] {
         self.get_data()
     }
 
+    fn take_data(&mut self) -> VecNoStd<u8> {
+        self.take_data()
+    }
+
+    fn into_data(self: Box<Self>) -> VecNoStd<u8> {
+        (*self).into_data()
+    }
+}
+
+// Implementation de FormatWriter pour PcapNgWriter
+impl FormatWriter for PcapNgWriter {
+    fn write_header(&mut self) -> Result<()> {
+        self.write_global_header()
+    }
+
+    fn write_packet(&mut self, packet: &NetworkPacket) -> Result<()> {
+        self.write_packet(packet)
+    }
+
+    fn write_record(&mut self, record: &PacketRecord) -> Result<()> {
+        self.write_record(record)
+    }
+
+    fn get_data(&self) -> &[u8] {
+        self.get_data()
+    }
+
+    fn take_data(&mut self) -> VecNoStd<u8> {
+        self.take_data()
+    }
+
     fn into_data(self: Box<Self>) -> VecNoStd<u8> {
         (*self).into_data()
     }
@@ -105,17 +184,32 @@ impl FormatWriter for PcapWriter {
 
 // Implementation de FormatWriter pour JsonSerializer
 impl FormatWriter for JsonSerializer {
+    fn write_header(&mut self) -> Result<()> {
+        self.write_array_start()
+    }
+
     fn write_packet(&mut self, packet: &NetworkPacket) -> Result<()> {
-        let _json = self.serialize_packet(packet)?;
-        Ok(())
+        self.write_packet(packet)
+    }
+
+    fn write_record(&mut self, record: &PacketRecord) -> Result<()> {
+        self.write_record(record)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.write_array_end()
     }
 
     fn get_data(&self) -> &[u8] {
-        &[]
+        self.get_data()
+    }
+
+    fn take_data(&mut self) -> VecNoStd<u8> {
+        self.take_data()
     }
 
     fn into_data(self: Box<Self>) -> VecNoStd<u8> {
-        VecNoStd::new()
+        (*self).into_data()
     }
 }
 
@@ -125,6 +219,25 @@ impl FormatReader for PcapReader {
         self.read_next_packet()
     }
 
+    fn read_next_record(&mut self) -> Result<Option<PacketRecord>> {
+        self.read_next_record()
+    }
+
+    fn has_more_packets(&self) -> bool {
+        self.has_more_packets()
+    }
+}
+
+// Implementation de FormatReader pour PcapNgReader
+impl FormatReader for PcapNgReader {
+    fn read_next_packet(&mut self) -> Result<Option<VecNoStd<u8>>> {
+        self.read_next_packet()
+    }
+
+    fn read_next_record(&mut self) -> Result<Option<PacketRecord>> {
+        self.read_next_record()
+    }
+
     fn has_more_packets(&self) -> bool {
         self.has_more_packets()
     }
@@ -133,10 +246,16 @@ impl FormatReader for PcapReader {
 // Implementation de FormatReader pour JsonDeserializer
 impl FormatReader for JsonDeserializer {
     fn read_next_packet(&mut self) -> Result<Option<VecNoStd<u8>>> {
-        Ok(None)
+        Ok(
+            self.read_next_record()?.map(|record| record.data)
+        )
+    }
+
+    fn read_next_record(&mut self) -> Result<Option<PacketRecord>> {
+        self.read_next_record()
     }
 
     fn has_more_packets(&self) -> bool {
-        false
+        self.has_more_packets()
     }
 }
@@ -1,29 +1,64 @@
 use crate::{
     structs::network_packet::NetworkPacket,
     structs::l4_protocol::L4Data,
+    structs::ethernet::{EthernetHeader, VlanTag},
+    structs::ip::{IpHeader, Ipv4Header, Ipv6Header, Ipv6ExtensionHeader},
+    structs::tcp::TcpHeader,
+    structs::udp::UdpHeader,
+    structs::ipv4::Ipv4Addr,
+    structs::ipv6::Ipv6Addr,
+    structs::icmp::IcmpHeader,
+    structs::icmpv6::Icmpv6Header,
+    structs::formats::PacketRecord,
+    structs::pcap::LinkType,
     structs::json::{
         JsonPacket,
         JsonEthernet,
+        JsonVlanTag,
         JsonIpv4,
+        JsonIpv6,
+        JsonIpv6Extension,
         JsonL4,
         JsonMetadata,
         JsonValue,
         JsonSerializer,
         JsonDeserializer
     },
-    errors::errors::Result,
+    packets::{
+        builder::{
+            icmp_builder::IcmpBuilder,
+            ipv6_builder::Ipv6Builder,
+            packet_assembler::PacketAssembler,
+            tcp_builder::TcpBuilder,
+            udp_builder::UdpBuilder,
+        },
+        ip::ipv4_header_checksum,
+    },
+    errors::errors::{Result, ParseError},
+    parsing::my_parser::{
+        parse_mac,
+        parse_ipv4,
+        parse_ipv6,
+        parse_hex_bytes
+    },
     utils::{
         payload_size::payload_len,
         formating_types::{
             format_mac,
             format_ip,
-            format_bytes,
-            get_timestamp_ms
+            format_ipv6,
+            format_bytes
+        },
+        clock::{
+            Clock,
+            default_clock
         }
     },
     prelude::*,
 };
+use alloc::format;
 use serde_json_core;
+use serde::{Deserialize, Deserializer};
 
 /// Serializer JSON pour les paquets reseau
 impl JsonSerializer {
@@ -31,23 +66,58 @@ impl JsonSerializer {
     /// constructeur JsonSerializer
     pub fn new() -> Self { 
         Self { 
-            include_raw_data: true 
+            include_raw_data: true,
+            clock: default_clock(),
+            buffer: VecNoStd::new(),
+            packets_written: 0,
+            array_open: false,
         }
     }
 
     /// constructeur JsonSerializer no data
     pub fn without_raw_data() -> Self { 
         Self { 
-            include_raw_data: false 
+            include_raw_data: false,
+            clock: default_clock(),
+            buffer: VecNoStd::new(),
+            packets_written: 0,
+            array_open: false,
         } 
     }
 
+    /// Remplace l'horloge utilisee pour le timestamp des metadonnees
+    pub fn with_clock(mut self, clock: Box<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     // Serializer un seul paquet en JSON
     pub fn serialize_packet(&self, packet: &NetworkPacket) -> Result<StringNoStd> {
+        let bytes = self.serialize_packet_bytes(packet)?;
+        Ok(StringNoStd::from_utf8_lossy(&bytes).to_string())
+    }
+
+    // Serializer un paquet dans un buffer qui grandit selon la taille du JSON
+    fn serialize_packet_bytes(&self, packet: &NetworkPacket) -> Result<VecNoStd<u8>> {
         let json_packet = self.convert_to_json_packet(packet)?;
-        let mut buf = [0u8; 4096];
-        let serialized_len = serde_json_core::ser::to_slice(&json_packet, &mut buf)?;
-        Ok(StringNoStd::from_utf8_lossy(&buf[..serialized_len]).to_string())
+        Self::json_packet_bytes(&json_packet, packet.get_packet_size())
+    }
+
+    // Serialise un JsonPacket deja construit
+    fn json_packet_bytes(json_packet: &JsonPacket, packet_size: usize) -> Result<VecNoStd<u8>> {
+        // Estimation : en-tetes + payload et trame brute en hexadecimal
+        let mut capacity = 1024 + packet_size * 6;
+        loop {
+            let mut buf = vec![0u8; capacity];
+            match serde_json_core::ser::to_slice(json_packet, &mut buf) {
+                Ok(serialized_len) => {
+                    buf.truncate(serialized_len);
+                    return Ok(buf);
+                }
+                Err(serde_json_core::ser::Error::BufferFull) => capacity *= 2,
+                Err(err) => return Err(err.into()),
+            }
+        }
     }
 
     // Convertir un paquet en un objet JSON
@@ -60,22 +130,54 @@ impl JsonSerializer {
                 &packet.ethernet.dst_mac
             ),
             ethertype: packet.ethernet.ethertype,
+            vlan_tags: packet.ethernet.vlan_tags
+                .iter()
+                .map(|tag| JsonVlanTag {
+                    vid: tag.vid,
+                    pcp: tag.pcp,
+                    dei: tag.dei,
+                    tpid: tag.tpid,
+                })
+                .collect(),
         };
 
-        let ipv4 = JsonIpv4 {
+        let ipv4 = packet.ipv4().map(|ipv4| JsonIpv4 {
             src_addr: format_ip(
-                &packet.ipv4.src_addr
+                &ipv4.src_addr
             ),
             dst_addr: format_ip(
-                &packet.ipv4.dst_addr
+                &ipv4.dst_addr
             ),
-            protocol: packet.ipv4.protocol,
-            total_length: packet.ipv4.total_length,
-            header_checksum: packet.ipv4.header_checksum,
-            ttl: packet.ipv4.ttl,
-            flags: packet.ipv4.flags,
-            fragment_offset: packet.ipv4.fragment_offset,
-        };
+            protocol: ipv4.protocol,
+            total_length: ipv4.total_length,
+            header_checksum: ipv4.header_checksum,
+            ttl: ipv4.ttl,
+            flags: ipv4.flags,
+            fragment_offset: ipv4.fragment_offset,
+            version: ipv4.version,
+            ihl: ipv4.ihl,
+            dscp: ipv4.dscp,
+            identification: ipv4.identification,
+            options: optional_bytes(&ipv4.options),
+        });
+
+        let ipv6 = packet.ipv6().map(|ipv6| JsonIpv6 {
+            src_addr: format_ipv6(&ipv6.src_addr),
+            dst_addr: format_ipv6(&ipv6.dst_addr),
+            next_header: ipv6.next_header,
+            payload_length: ipv6.payload_length,
+            hop_limit: ipv6.hop_limit,
+            traffic_class: ipv6.traffic_class,
+            flow_label: ipv6.flow_label,
+            extension_headers: ipv6.extension_headers
+                .iter()
+                .map(|extension| JsonIpv6Extension {
+                    header_type: extension.header_type,
+                    next_header: extension.next_header,
+                    data: format_bytes(&extension.data),
+                })
+                .collect(),
+        });
 
         let l4 = match &packet.l4_data {
             L4Data::Tcp(tcp) => {
@@ -104,6 +206,30 @@ impl JsonSerializer {
                         tcp.window as u64
                     )
                 );
+                additional_fields.insert(
+                    "data_offset".to_string(),
+                    JsonValue::U64(
+                        tcp.data_offset as u64
+                    )
+                );
+                additional_fields.insert(
+                    "reserved".to_string(),
+                    JsonValue::U64(
+                        tcp.reserved as u64
+                    )
+                );
+                additional_fields.insert(
+                    "urgent_pointer".to_string(),
+                    JsonValue::U64(
+                        tcp.urgent_pointer as u64
+                    )
+                );
+                additional_fields.insert(
+                    "options".to_string(),
+                    JsonValue::String(
+                        optional_bytes(&tcp.options)
+                    )
+                );
 
                 JsonL4 {
                     protocol_type: "TCP".to_string(),
@@ -112,6 +238,7 @@ impl JsonSerializer {
                     payload_size: payload_len(&tcp.payload),
                     checksum: tcp.checksum,
                     additional_fields,
+                    payload: optional_bytes(&tcp.payload),
                 }
             }
             L4Data::Udp(udp) => {
@@ -125,8 +252,25 @@ impl JsonSerializer {
                     payload_size: payload_len(&udp.payload),
                     checksum: udp.checksum,
                     additional_fields,
+                    payload: optional_bytes(&udp.payload),
                 }
             }
+            L4Data::Icmp(icmp) => icmp_json_l4(
+                "ICMP",
+                icmp.icmp_type,
+                icmp.code,
+                icmp.checksum,
+                icmp.rest_of_header,
+                &icmp.payload
+            ),
+            L4Data::Icmpv6(icmpv6) => icmp_json_l4(
+                "ICMPv6",
+                icmpv6.icmp_type,
+                icmpv6.code,
+                icmpv6.checksum,
+                icmpv6.rest_of_header,
+                &icmpv6.payload
+            ),
         };
 
         let raw_data = if self.include_raw_data {
@@ -138,33 +282,144 @@ impl JsonSerializer {
 
         let metadata = JsonMetadata {
             packet_size: packet.get_packet_size(),
-            timestamp: get_timestamp_ms(),
+            timestamp: self.clock.now_ns() / 1_000_000,
             raw_data,
         };
 
-        Ok(JsonPacket { ethernet, ipv4, l4, metadata })
+        Ok(JsonPacket { ethernet, ipv4, ipv6, l4, metadata })
     }
 
     // Serializer plusieurs paquets en JSON
+    // Chaque paquet est serialise separement puis ajoute au tableau
     pub fn serialize_packets(&self, packets: &[NetworkPacket]) -> Result<StringNoStd> {
-        let mut buf = [0u8; 8192];
-        let mut json_packets = VecNoStd::new();
-        for packet in packets {
-            json_packets.push(
-                self.convert_to_json_packet(packet)?
-            );
+        let mut out = VecNoStd::new();
+        out.push(b'[');
+        for (i, packet) in packets.iter().enumerate() {
+            if i > 0 {
+                out.push(b',');
+            }
+            out.extend_from_slice(&self.serialize_packet_bytes(packet)?);
         }
-        let serialized_len = serde_json_core::ser::to_slice(&json_packets, &mut buf)?;
-        Ok(StringNoStd::from_utf8_lossy(
-            &buf[..serialized_len]
-        ).to_string())
+        out.push(b']');
+        Ok(StringNoStd::from_utf8_lossy(&out).to_string())
+    }
+
+    /// Ecrit un paquet JSON directement dans un flux (fichier, socket...)
+    #[cfg(feature = "std")]
+    pub fn serialize_packet_to_writer<W: std::io::Write>(
+        &self,
+        packet: &NetworkPacket,
+        writer: &mut W
+    ) -> Result<()> {
+        writer.write_all(&self.serialize_packet_bytes(packet)?)?;
+        Ok(())
+    }
+
+    /// Ecrit un tableau JSON paquet par paquet dans un flux,
+    /// sans garder le document complet en memoire
+    #[cfg(feature = "std")]
+    pub fn serialize_packets_to_writer<W: std::io::Write>(
+        &self,
+        packets: &[NetworkPacket],
+        writer: &mut W
+    ) -> Result<()> {
+        writer.write_all(b"[")?;
+        for (i, packet) in packets.iter().enumerate() {
+            if i > 0 {
+                writer.write_all(b",")?;
+            }
+            self.serialize_packet_to_writer(packet, writer)?;
+        }
+        writer.write_all(b"]")?;
+        Ok(())
+    }
+
+    /// Ouvre le tableau JSON dans le buffer interne (sans effet s'il est deja ouvert)
+    pub fn write_array_start(&mut self) -> Result<()> {
+        self.open_array();
+        Ok(())
+    }
+
+    /// Ajoute un paquet au tableau JSON du buffer interne
+    pub fn write_packet(&mut self, packet: &NetworkPacket) -> Result<()> {
+        let bytes = self.serialize_packet_bytes(packet)?;
+        self.push_array_item(&bytes);
+        Ok(())
+    }
+
+    /// Ajoute un enregistrement capture au tableau JSON
+    /// Le timestamp des metadonnees est celui de l'enregistrement (ex: timestamp noyau)
+    pub fn write_record(&mut self, record: &PacketRecord) -> Result<()> {
+        let packet = record.packet.as_ref().ok_or(
+            ParseError::InvalidFormat("record without decoded packet")
+        )?;
+        let mut json_packet = self.convert_to_json_packet(packet)?;
+        json_packet.metadata.timestamp = record.ts_sec as u64 * 1000 + (record.ts_nsec / 1_000_000) as u64;
+        let bytes = Self::json_packet_bytes(&json_packet, packet.get_packet_size())?;
+        self.push_array_item(&bytes);
+        Ok(())
+    }
+
+    // Ajoute un element au tableau JSON (separateur ',' si besoin) ;
+    // le tableau est ouvert au premier element si `write_array_start` n'a pas ete appele
+    fn push_array_item(&mut self, bytes: &[u8]) {
+        self.open_array();
+        if self.packets_written > 0 {
+            self.buffer.push(b',');
+        }
+        self.buffer.extend_from_slice(bytes);
+        self.packets_written += 1;
+    }
+
+    // Ecrit '[' si le tableau n'est pas deja ouvert
+    fn open_array(&mut self) {
+        if !self.array_open {
+            self.buffer.push(b'[');
+            self.array_open = true;
+            self.packets_written = 0;
+        }
+    }
+
+    /// Ferme le tableau JSON du buffer interne (`[]` si rien n'a ete ecrit)
+    pub fn write_array_end(&mut self) -> Result<()> {
+        self.open_array();
+        self.buffer.push(b']');
+        self.array_open = false;
+        Ok(())
+    }
+
+    // retourne une reference vers le buffer JSON
+    pub fn get_data(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Vide le buffer et retourne les octets deja produits
+    pub fn take_data(&mut self) -> VecNoStd<u8> {
+        core::mem::take(&mut self.buffer)
+    }
+
+    // retourne le JSON, tableau ferme s'il est encore ouvert
+    pub fn into_data(mut self) -> VecNoStd<u8> {
+        if self.array_open {
+            self.buffer.push(b']');
+        }
+        self.buffer
     }
 }
 
 /// Deserializer JSON pour les paquets reseau
 impl JsonDeserializer {
     pub fn new() -> Self {
-        JsonDeserializer
+        Self::from_data(VecNoStd::new())
+    }
+
+    /// Constructeur depuis un document JSON (un paquet ou un tableau de paquets)
+    pub fn from_data(data: VecNoStd<u8>) -> Self {
+        JsonDeserializer {
+            data,
+            position: 0,
+            records: None,
+        }
     }
 
     pub fn deserialize_packet(&self, json_str: &str) -> Result<JsonPacket> {
@@ -176,5 +431,365 @@ impl JsonDeserializer {
         let (packets, _rest) = serde_json_core::de::from_str(json_str)?;
         Ok(packets)
     }
+
+    /// Reconstruit un NetworkPacket depuis un paquet JSON
+    pub fn deserialize_network_packet(&self, json_str: &str) -> Result<NetworkPacket> {
+        let json_packet = self.deserialize_packet(json_str)?;
+        self.convert_from_json_packet(&json_packet)
+    }
+
+    /// Reconstruit plusieurs NetworkPacket depuis un tableau JSON
+    pub fn deserialize_network_packets(&self, json_str: &str) -> Result<VecNoStd<NetworkPacket>> {
+        let mut packets = VecNoStd::new();
+        for json_packet in self.deserialize_packets(json_str)? {
+            packets.push(
+                self.convert_from_json_packet(&json_packet)?
+            );
+        }
+        Ok(packets)
+    }
+
+    // Convertir un objet JSON en paquet
+    pub fn convert_from_json_packet(&self, json: &JsonPacket) -> Result<NetworkPacket> {
+        let ethernet = EthernetHeader {
+            dst_mac: parse_mac(&json.ethernet.dst_mac)?,
+            src_mac: parse_mac(&json.ethernet.src_mac)?,
+            vlan_tags: json.ethernet.vlan_tags
+                .iter()
+                .map(|tag| VlanTag {
+                    tpid: tag.tpid,
+                    pcp: tag.pcp,
+                    dei: tag.dei,
+                    vid: tag.vid,
+                })
+                .collect(),
+            ethertype: json.ethernet.ethertype,
+        };
+
+        // Le bloc ipv6 prime : un paquet IPv4 n'en a pas
+        let ip = match (&json.ipv6, &json.ipv4) {
+            (Some(ipv6), _) => {
+                let mut extension_headers = VecNoStd::new();
+                for extension in &ipv6.extension_headers {
+                    extension_headers.push(Ipv6ExtensionHeader {
+                        header_type: extension.header_type,
+                        next_header: extension.next_header,
+                        data: parse_hex_bytes(&extension.data)?,
+                    });
+                }
+                IpHeader::V6(Ipv6Header {
+                    version: 6,
+                    traffic_class: ipv6.traffic_class,
+                    flow_label: ipv6.flow_label,
+                    payload_length: ipv6.payload_length,
+                    next_header: ipv6.next_header,
+                    hop_limit: ipv6.hop_limit,
+                    src_addr: parse_ipv6(&ipv6.src_addr)?.octets,
+                    dst_addr: parse_ipv6(&ipv6.dst_addr)?.octets,
+                    extension_headers,
+                })
+            }
+            (None, Some(ipv4)) => {
+                let options = parse_optional_bytes(&ipv4.options)?;
+                IpHeader::V4(Ipv4Header {
+                    version: if ipv4.version == 0 { 4 } else { ipv4.version },
+                    ihl: header_words("IPv4", ipv4.ihl, &options)?,
+                    dscp: ipv4.dscp,
+                    total_length: ipv4.total_length,
+                    identification: ipv4.identification,
+                    flags: ipv4.flags,
+                    fragment_offset: ipv4.fragment_offset,
+                    ttl: ipv4.ttl,
+                    protocol: ipv4.protocol,
+                    header_checksum: ipv4.header_checksum,
+                    src_addr: parse_ipv4(&ipv4.src_addr)?.octets,
+                    dst_addr: parse_ipv4(&ipv4.dst_addr)?.octets,
+                    options,
+                })
+            }
+            (None, None) => return Err(ParseError::MissingRequiredField("ipv4")),
+        };
+
+        let fields = &json.l4.additional_fields;
+        let payload = parse_optional_bytes(&json.l4.payload)?;
+        let l4_data = if json.l4.protocol_type.eq_ignore_ascii_case("TCP") {
+            let options = match fields.get("options") {
+                Some(JsonValue::String(hex)) => parse_optional_bytes(hex)?,
+                _ => None,
+            };
+            let data_offset = optional_field(fields, "data_offset")?.unwrap_or(0);
+            let reserved: u8 = field(fields, "reserved")?;
+            if reserved > 0x07 {
+                return Err(ParseError::InvalidFormat("TCP reserved field is 3 bits"));
+            }
+            let flags: u16 = field(fields, "flags")?;
+            if flags > 0x01FF {
+                return Err(ParseError::InvalidFormat("TCP flags field is 9 bits"));
+            }
+            L4Data::Tcp(TcpHeader {
+                src_port: json.l4.src_port,
+                dst_port: json.l4.dst_port,
+                sequence_number: field(fields, "sequence_number")?,
+                ack_nowledgment_number: field(fields, "ack_number")?,
+                data_offset: header_words("TCP", data_offset, &options)?,
+                reserved,
+                flags,
+                window: field(fields, "window")?,
+                checksum: json.l4.checksum,
+                urgent_pointer: field(fields, "urgent_pointer")?,
+                options,
+                payload,
+            })
+        } else if json.l4.protocol_type.eq_ignore_ascii_case("UDP") {
+            let length = match optional_field(fields, "length")?.unwrap_or(0) {
+                0 => u16::try_from(8 + payload_len(&payload))
+                    .map_err(|_| ParseError::ValueTooLarge { value: (8 + payload_len(&payload)) as u64, size: 2 })?,
+                value => value,
+            };
+            L4Data::Udp(UdpHeader {
+                src_port: json.l4.src_port,
+                dst_port: json.l4.dst_port,
+                length,
+                checksum: json.l4.checksum,
+                payload,
+            })
+        } else if json.l4.protocol_type.eq_ignore_ascii_case("ICMP") {
+            L4Data::Icmp(IcmpHeader {
+                icmp_type: field(fields, "type")?,
+                code: field(fields, "code")?,
+                checksum: json.l4.checksum,
+                rest_of_header: field(fields, "rest_of_header")?,
+                payload,
+            })
+        } else if json.l4.protocol_type.eq_ignore_ascii_case("ICMPv6") {
+            L4Data::Icmpv6(Icmpv6Header {
+                icmp_type: field(fields, "type")?,
+                code: field(fields, "code")?,
+                checksum: json.l4.checksum,
+                rest_of_header: field(fields, "rest_of_header")?,
+                payload,
+            })
+        } else {
+            return Err(
+                ParseError::InvalidFormat(
+                    "Unknown JSON protocol_type"
+                )
+            );
+        };
+
+        let mut packet = NetworkPacket {
+            ethernet,
+            ip,
+            l4_data,
+        };
+        complete_template(&mut packet)?;
+        Ok(packet)
+    }
+
+    // Lit et convertit tout le document au premier appel
+    fn load_records(&mut self) -> Result<()> {
+        if self.records.is_some() {
+            return Ok(());
+        }
+
+        let json_str = core::str::from_utf8(&self.data).map_err(
+            |_| ParseError::JsonError("JSON data is not valid UTF-8".to_string())
+        )?;
+        let trimmed = json_str.trim_start();
+        let json_packets = if trimmed.is_empty() {
+            VecNoStd::new()
+        } else if trimmed.starts_with('[') {
+            self.deserialize_packets(trimmed)?
+        } else {
+            vec![self.deserialize_packet(trimmed)?]
+        };
+
+        let mut records = VecNoStd::new();
+        for json_packet in &json_packets {
+            let packet = self.convert_from_json_packet(json_packet)?;
+            let data = packet.assemble_packet()?;
+            let timestamp_ms = json_packet.metadata.timestamp;
+            records.push(PacketRecord {
+                ts_sec: (timestamp_ms / 1000) as u32,
+                ts_usec: ((timestamp_ms % 1000) * 1000) as u32,
+                ts_nsec: ((timestamp_ms % 1000) * 1_000_000) as u32,
+                caplen: data.len() as u32,
+                orig_len: data.len() as u32,
+                data,
+                link_type: LinkType::Ethernet,
+                packet: Some(packet),
+                comment: None,
+            });
+        }
+        self.records = Some(records);
+        Ok(())
+    }
+
+    // Lit l'enregistrement suivant du document JSON
+    pub fn read_next_record(&mut self) -> Result<Option<PacketRecord>> {
+        self.load_records()?;
+        let record = self.records
+            .as_ref()
+            .and_then(|records| records.get(self.position))
+            .cloned();
+        if record.is_some() {
+            self.position += 1;
+        }
+        Ok(record)
+    }
+
+    // Verifie packet valide read
+    pub fn has_more_packets(&self) -> bool {
+        match &self.records {
+            Some(records) => self.position < records.len(),
+            None => !self.data.iter().all(|b| b.is_ascii_whitespace()),
+        }
+    }
+}
+
+// Bloc L4 d'un message ICMP ou ICMPv6, sans ports
+fn icmp_json_l4(
+    protocol_type: &str,
+    icmp_type: u8,
+    code: u8,
+    checksum: u16,
+    rest_of_header: u32,
+    payload: &Option<VecNoStd<u8>>,
+) -> JsonL4 {
+    let mut additional_fields = BTreeMap::new();
+    additional_fields.insert("type".to_string(), JsonValue::U64(icmp_type as u64));
+    additional_fields.insert("code".to_string(), JsonValue::U64(code as u64));
+    additional_fields.insert(
+        "rest_of_header".to_string(),
+        JsonValue::U64(
+            rest_of_header as u64
+        )
+    );
+
+    JsonL4 {
+        protocol_type: protocol_type.to_string(),
+        src_port: 0,
+        dst_port: 0,
+        payload_size: payload_len(payload),
+        checksum,
+        additional_fields,
+        payload: optional_bytes(payload),
+    }
 }
 
+// Formate des octets optionnels en hexadecimal ("" si absent)
+fn optional_bytes(bytes: &Option<VecNoStd<u8>>) -> StringNoStd {
+    match bytes {
+        Some(b) => format_bytes(b),
+        None => StringNoStd::new(),
+    }
+}
+
+// Parse une chaine hexadecimale, None si vide
+fn parse_optional_bytes(hex: &str) -> Result<Option<VecNoStd<u8>>> {
+    let bytes = parse_hex_bytes(hex)?;
+    if bytes.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(bytes))
+    }
+}
+
+// Lit un champ numerique de additional_fields, None si absent
+// Un autre type ou une valeur trop grande pour `T` est une erreur
+fn optional_field<T: TryFrom<u64>>(fields: &BTreeMap<StringNoStd, JsonValue>, key: &'static str) -> Result<Option<T>> {
+    let value = match fields.get(key) {
+        Some(JsonValue::U64(value)) => *value,
+        Some(_) => return Err(ParseError::JsonError(format!("additional_fields.{} is not a number", key))),
+        None => return Ok(None),
+    };
+    T::try_from(value)
+        .map(Some)
+        .map_err(|_| ParseError::ValueTooLarge { value, size: core::mem::size_of::<T>() })
+}
+
+// Lit un champ numerique obligatoire de additional_fields
+fn field<T: TryFrom<u64>>(fields: &BTreeMap<StringNoStd, JsonValue>, key: &'static str) -> Result<T> {
+    optional_field(fields, key)?.ok_or(ParseError::MissingRequiredField(key))
+}
+
+// Longueur d'en-tete en mots de 32 bits (ihl IPv4, data offset TCP) : deduite
+// des options si 0, sinon elle doit valoir 5 + options / 4 (au plus 15)
+fn header_words(layer: &'static str, declared: u8, options: &Option<VecNoStd<u8>>) -> Result<u8> {
+    let length = 20 + payload_len(options);
+    if length > 60 || !length.is_multiple_of(4) {
+        return Err(ParseError::InvalidHeaderLength { layer, length });
+    }
+    match declared {
+        0 => Ok((length / 4) as u8),
+        words if words as usize * 4 == length => Ok(words),
+        words => Err(ParseError::InvalidHeaderLength { layer, length: words as usize * 4 }),
+    }
+}
+
+// Recalcule les champs laisses a 0 (ou absents) d'un template edite a la main
+// pour qu'il puisse etre emis : checksum L4, longueur et checksum IPv4,
+// longueur de payload IPv6
+fn complete_template(packet: &mut NetworkPacket) -> Result<()> {
+    let l4_checksum = match &packet.l4_data {
+        L4Data::Tcp(tcp) => tcp.checksum,
+        L4Data::Udp(udp) => udp.checksum,
+        L4Data::Icmp(icmp) => icmp.checksum,
+        L4Data::Icmpv6(icmpv6) => icmpv6.checksum,
+    };
+    if l4_checksum == 0 {
+        match &packet.ip {
+            IpHeader::V6(ipv6) => Ipv6Builder::new(Ipv6Addr { octets: ipv6.src_addr }, Ipv6Addr { octets: ipv6.dst_addr })
+                .update_l4_checksum(&mut packet.l4_data)?,
+            IpHeader::V4(ipv4) => {
+                let src = Ipv4Addr { octets: ipv4.src_addr };
+                let dst = Ipv4Addr { octets: ipv4.dst_addr };
+                match &mut packet.l4_data {
+                    L4Data::Tcp(tcp) => TcpBuilder::new(src, dst).update_checksum(tcp)?,
+                    L4Data::Udp(udp) => UdpBuilder::new(src, dst).update_checksum(udp)?,
+                    L4Data::Icmp(icmp) => IcmpBuilder::new().update_checksum(icmp)?,
+                    L4Data::Icmpv6(_) => return Err(ParseError::InvalidFormat("ICMPv6 requires an IPv6 header")),
+                }
+            }
+        }
+    }
+
+    let l4_len = PacketAssembler::new().assemble_l4(packet)?.len();
+    match &mut packet.ip {
+        IpHeader::V6(ipv6) => {
+            if ipv6.payload_length == 0 {
+                let length = ipv6.extensions_len() + l4_len;
+                ipv6.payload_length = u16::try_from(length)
+                    .map_err(|_| ParseError::ValueTooLarge { value: length as u64, size: 2 })?;
+            }
+        }
+        IpHeader::V4(ipv4) => {
+            if ipv4.total_length == 0 {
+                let length = ipv4.header_len() + l4_len;
+                ipv4.total_length = u16::try_from(length)
+                    .map_err(|_| ParseError::ValueTooLarge { value: length as u64, size: 2 })?;
+            }
+            if ipv4.header_checksum == 0 {
+                ipv4.header_checksum = ipv4_header_checksum(ipv4)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// serde_json_core ne sait pas deserialiser une cle de map en String :
+// les cles sont lues empruntees puis copiees.
+pub(crate) fn deserialize_additional_fields<'de, D>(
+    deserializer: D
+) -> core::result::Result<BTreeMap<StringNoStd, JsonValue>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let borrowed: BTreeMap<&'de str, JsonValue> = BTreeMap::deserialize(deserializer)?;
+    Ok(
+        borrowed
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect()
+    )
+}
@@ -1,3 +1,5 @@
 pub mod json;
 pub mod pcap;
-pub mod format_factory;
\ No newline at end of file
+pub mod pcapng;
+pub mod format_factory;
+pub mod stream;
\ No newline at end of file
@@ -1,51 +1,237 @@
 use crate::{
-    utils::formating_types::get_timestamp_ms,
+    utils::clock::{
+        Clock,
+        default_clock,
+        split_timestamp_ns
+    },
     structs::network_packet::NetworkPacket,
-    structs::pcap::{PcapWriter, PcapReader},
-    errors::errors::Result,
+    structs::pcap::{
+        PcapWriter,
+        PcapReader,
+        PcapOptions,
+        PcapByteOrder,
+        PcapPrecision,
+        LinkType
+    },
+    structs::formats::PacketRecord,
+    packets::builder::packet_dissector::PacketDissector,
+    packets::ethernet::{unpack_ethernet, unpack_linux_sll, unpack_linux_sll2},
+    errors::errors::{Result, ParseError},
     prelude::*,
 };
 
+// Magic numbers PCAP (lus dans l'ordre natif du fichier)
+const MAGIC_MICRO: u32 = 0xA1B2C3D4;
+const MAGIC_NANO: u32 = 0xA1B23C4D;
+
+// Conversion LINKTYPE_* <-> LinkType
+impl LinkType {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => LinkType::Ethernet,
+            101 => LinkType::Raw,
+            113 => LinkType::LinuxSll,
+            276 => LinkType::LinuxSll2,
+            other => LinkType::Other(other),
+        }
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            LinkType::Ethernet => 1,
+            LinkType::Raw => 101,
+            LinkType::LinuxSll => 113,
+            LinkType::LinuxSll2 => 276,
+            LinkType::Other(value) => *value,
+        }
+    }
+}
+
+// Options par defaut : little-endian, microsecondes, Ethernet
+impl Default for PcapOptions {
+    fn default() -> Self {
+        Self {
+            byte_order: PcapByteOrder::LittleEndian,
+            precision: PcapPrecision::Micro,
+            snaplen: 65535,
+            link_type: LinkType::Ethernet,
+        }
+    }
+}
+
+// Encode un u32 selon l'ordre des octets du fichier
+pub(crate) fn u32_to_bytes(value: u32, byte_order: PcapByteOrder) -> [u8; 4] {
+    match byte_order {
+        PcapByteOrder::LittleEndian => value.to_le_bytes(),
+        PcapByteOrder::BigEndian => value.to_be_bytes(),
+    }
+}
+
+// Encode un u16 selon l'ordre des octets du fichier
+pub(crate) fn u16_to_bytes(value: u16, byte_order: PcapByteOrder) -> [u8; 2] {
+    match byte_order {
+        PcapByteOrder::LittleEndian => value.to_le_bytes(),
+        PcapByteOrder::BigEndian => value.to_be_bytes(),
+    }
+}
+
+// Adapte une trame du type de lien `source` a celui du fichier
+// La trame est recopiee telle quelle si les types coincident, sinon l'en-tete
+// de liaison est decode (adresse source, ethertype) puis reconstruit.
+pub(crate) fn link_frame(frame: &[u8], source: LinkType, link_type: LinkType) -> Result<VecNoStd<u8>> {
+    let (src_mac, ethertype, payload) = match source {
+        // Tags 802.1Q / QinQ sautes : l'ethertype est celui qui suit le dernier tag
+        LinkType::Ethernet => {
+            let (ethernet, payload) = unpack_ethernet(frame)?;
+            (ethernet.src_mac, ethernet.ethertype, payload)
+        }
+        LinkType::LinuxSll => {
+            let (ethernet, payload) = unpack_linux_sll(frame)?;
+            (ethernet.src_mac, ethernet.ethertype, payload)
+        }
+        LinkType::LinuxSll2 => {
+            let (ethernet, payload) = unpack_linux_sll2(frame)?;
+            (ethernet.src_mac, ethernet.ethertype, payload)
+        }
+        // La version IP du premier octet choisit l'ethertype
+        LinkType::Raw => match frame.first() {
+            Some(byte) if byte >> 4 == 6 => ([0u8; 6], 0x86DD, frame),
+            Some(_) => ([0u8; 6], 0x0800, frame),
+            None => {
+                return Err(
+                    ParseError::TruncatedPacket {
+                        layer: "IP",
+                        needed: 1,
+                        available: 0
+                    }
+                );
+            }
+        },
+        LinkType::Other(_) => {
+            return Err(
+                ParseError::InvalidFormat(
+                    "Unsupported PCAP link type"
+                )
+            );
+        }
+    };
+    let ethertype = ethertype.to_be_bytes();
+
+    let mut data = VecNoStd::new();
+    match link_type {
+        _ if link_type == source => data.extend_from_slice(frame),
+        LinkType::Ethernet => {
+            data.extend_from_slice(&[0x00; 6]);
+            data.extend_from_slice(&src_mac);
+            data.extend_from_slice(&ethertype);
+            data.extend_from_slice(payload);
+        }
+        LinkType::Raw => data.extend_from_slice(payload),
+        LinkType::LinuxSll => {
+            // packet type 4 = emis par l'hote, ARPHRD_ETHER = 1
+            data.extend_from_slice(&[0x00, 0x04, 0x00, 0x01, 0x00, 0x06]);
+            data.extend_from_slice(&src_mac);
+            data.extend_from_slice(&[0x00, 0x00]);
+            data.extend_from_slice(&ethertype);
+            data.extend_from_slice(payload);
+        }
+        LinkType::LinuxSll2 => {
+            data.extend_from_slice(&ethertype);
+            data.extend_from_slice(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+            data.extend_from_slice(&[0x00, 0x01, 0x04, 0x06]);
+            data.extend_from_slice(&src_mac);
+            data.extend_from_slice(&[0x00, 0x00]);
+            data.extend_from_slice(payload);
+        }
+        LinkType::Other(_) => {
+            return Err(
+                ParseError::InvalidFormat(
+                    "Unsupported PCAP link type"
+                )
+            );
+        }
+    }
+    Ok(data)
+}
+
 // Implémentation des fonctions pour cree des fichiers PCAP
 impl PcapWriter {
 
     /// Constructeur
     pub fn new() -> Self {
+        Self::with_options(PcapOptions::default())
+    }
+
+    /// Constructeur avec options (ordre des octets, precision, snaplen, type de lien)
+    pub fn with_options(options: PcapOptions) -> Self {
         Self {
             buffer: VecNoStd::new(),
+            options,
+            clock: default_clock(),
         }
     }
 
+    /// Remplace l'horloge utilisee pour horodater les paquets
+    pub fn with_clock(mut self, clock: Box<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Ecrit l'entete global du fichier PCAP
     pub fn write_global_header(&mut self) -> Result<()> {
-        let global_header = [
-            0xD4, 0xC3, 0xB2, 0xA1, 
-            0x02, 0x00, 0x04, 0x00, 
-            0x00, 0x00, 0x00, 0x00, 
-            0x00, 0x00, 0x00, 0x00, 
-            0xFF, 0xFF, 0x00, 0x00, 
-            0x01, 0x00, 0x00, 0x00,
-        ];
-        self.buffer.extend_from_slice(&global_header);
+        let byte_order = self.options.byte_order;
+        let magic = match self.options.precision {
+            PcapPrecision::Micro => MAGIC_MICRO,
+            PcapPrecision::Nano => MAGIC_NANO,
+        };
+
+        self.buffer.extend_from_slice(&u32_to_bytes(magic, byte_order));
+        self.buffer.extend_from_slice(&u16_to_bytes(2, byte_order));
+        self.buffer.extend_from_slice(&u16_to_bytes(4, byte_order));
+        self.buffer.extend_from_slice(&u32_to_bytes(0, byte_order));
+        self.buffer.extend_from_slice(&u32_to_bytes(0, byte_order));
+        self.buffer.extend_from_slice(&u32_to_bytes(self.options.snaplen, byte_order));
+        self.buffer.extend_from_slice(&u32_to_bytes(self.options.link_type.as_u32(), byte_order));
         Ok(())
     }
 
     // Ecrit un paquet dans le fichier PCAP
     pub fn write_packet(&mut self, packet: &NetworkPacket) -> Result<()> {
-        let packet_data = packet.assemble_packet()?;
-        let timestamp = get_timestamp_ms();
+        let frame = packet.assemble_packet()?;
+        let (ts_sec, ts_nsec) = split_timestamp_ns(self.clock.now_ns());
+        self.write_frame(&frame, LinkType::Ethernet, frame.len() as u32, ts_sec, ts_nsec)
+    }
+
+    /// Ecrit un enregistrement capture en conservant son timestamp et sa longueur
+    /// d'origine ; la trame est convertie depuis le type de lien de l'enregistrement
+    pub fn write_record(&mut self, record: &PacketRecord) -> Result<()> {
+        self.write_frame(&record.data, record.link_type, record.orig_len, record.ts_sec as u64, record.ts_nsec)
+    }
+
+    // Ecrit l'en-tete d'enregistrement puis la trame convertie au type de lien
+    fn write_frame(&mut self, frame: &[u8], source: LinkType, frame_orig_len: u32, ts_sec: u64, ts_nsec: u32) -> Result<()> {
+        let packet_data = link_frame(frame, source, self.options.link_type)?;
+        let ts_frac = match self.options.precision {
+            PcapPrecision::Micro => ts_nsec / 1000,
+            PcapPrecision::Nano => ts_nsec,
+        };
+
+        // La conversion de lien ne change que l'en-tete, la partie tronquee reste identique
+        let truncated = frame_orig_len.saturating_sub(frame.len() as u32);
+        let orig_len = packet_data.len() as u32 + truncated;
+        let caplen = (packet_data.len() as u32).min(self.options.snaplen);
 
         let packet_header = [
-            (timestamp & 0xFFFFFFFF) as u32,
-            ((timestamp >> 32) & 0xFFFFFFFF) as u32,
-            packet_data.len() as u32,
-            packet_data.len() as u32,
+            ts_sec as u32,
+            ts_frac,
+            caplen,
+            orig_len,
         ];
 
         for &value in &packet_header {
-            self.buffer.extend_from_slice(&value.to_le_bytes());
+            self.buffer.extend_from_slice(&u32_to_bytes(value, self.options.byte_order));
         }
-        self.buffer.extend_from_slice(&packet_data);
+        self.buffer.extend_from_slice(&packet_data[..caplen as usize]);
 
         Ok(())
     }
@@ -55,6 +241,12 @@ impl PcapWriter {
         &self.buffer
     }
 
+    /// Vide le buffer et retourne les octets deja produits
+    /// (utilise par `StreamWriter` pour vider le buffer vers un sink)
+    pub fn take_data(&mut self) -> VecNoStd<u8> {
+        core::mem::take(&mut self.buffer)
+    }
+
     // retourne le PCAP
     pub fn into_data(self) -> VecNoStd<u8> {
         self.buffer
@@ -67,6 +259,10 @@ impl PcapReader {
         Self {
             data,
             position: 0,
+            byte_order: PcapByteOrder::LittleEndian,
+            precision: PcapPrecision::Micro,
+            snaplen: 0,
+            link_type: LinkType::Ethernet,
         }
     }
 
@@ -74,84 +270,122 @@ impl PcapReader {
     pub fn read_global_header(&mut self) -> Result<()> {
         if self.data.len() < 24 {
             return Err(
-                crate::errors::errors::ParseError::InvalidFormat(
+                ParseError::InvalidFormat(
                     "PCAP header too short"
-                ).into()
+                )
             );
         }
 
-        // Verifie le magic number
-        let magic = u32::from_le_bytes([
-            self.data[0], self.data[1], self.data[2], self.data[3]
-        ]);
-
-        // format little-endian
-        if magic != 0xA1B2C3D4 {
+        // Verifie le magic number, il determine l'ordre des octets et la precision
+        let magic = [self.data[0], self.data[1], self.data[2], self.data[3]];
+        let (byte_order, precision) = if u32::from_le_bytes(magic) == MAGIC_MICRO {
+            (PcapByteOrder::LittleEndian, PcapPrecision::Micro)
+        } else if u32::from_be_bytes(magic) == MAGIC_MICRO {
+            (PcapByteOrder::BigEndian, PcapPrecision::Micro)
+        } else if u32::from_le_bytes(magic) == MAGIC_NANO {
+            (PcapByteOrder::LittleEndian, PcapPrecision::Nano)
+        } else if u32::from_be_bytes(magic) == MAGIC_NANO {
+            (PcapByteOrder::BigEndian, PcapPrecision::Nano)
+        } else {
             return Err(
-                crate::errors::errors::ParseError::InvalidFormat(
+                ParseError::InvalidFormat(
                     "Invalid PCAP magic number"
-                ).into()
+                )
             );
-        }
+        };
+
+        self.byte_order = byte_order;
+        self.precision = precision;
+        self.snaplen = self.read_u32(16);
+        self.link_type = LinkType::from_u32(self.read_u32(20));
 
         self.position = 24;
         Ok(())
     }
 
-    // Lit le paquet suivant dans le fichier PCAP
-    pub fn read_next_packet(&mut self) -> Result<Option<VecNoStd<u8>>> {
+    // Lit l'enregistrement suivant (timestamp, longueurs, trame et paquet decode)
+    pub fn read_next_record(&mut self) -> Result<Option<PacketRecord>> {
+        if self.position == 0 {
+            self.read_global_header()?;
+        }
+
         if self.position + 16 > self.data.len() {
             return Ok(None);
         }
 
-        let _timestamp_sec = u32::from_le_bytes([
-            self.data[self.position],
-            self.data[self.position + 1],
-            self.data[self.position + 2],
-            self.data[self.position + 3],
-        ]);
-
-        let _timestamp_usec = u32::from_le_bytes([
-            self.data[self.position + 4],
-            self.data[self.position + 5],
-            self.data[self.position + 6],
-            self.data[self.position + 7],
-        ]);
-
-        let caplen = u32::from_le_bytes([
-            self.data[self.position + 8],
-            self.data[self.position + 9],
-            self.data[self.position + 10],
-            self.data[self.position + 11],
-        ]);
-
-        let _len = u32::from_le_bytes([
-            self.data[self.position + 12],
-            self.data[self.position + 13],
-            self.data[self.position + 14],
-            self.data[self.position + 15],
-        ]);
+        let ts_sec = self.read_u32(self.position);
+        let ts_frac = self.read_u32(self.position + 4);
+        let caplen = self.read_u32(self.position + 8);
+        let orig_len = self.read_u32(self.position + 12);
 
         self.position += 16;
 
         if self.position + caplen as usize > self.data.len() {
             return Err(
-                crate::errors::errors::ParseError::InvalidFormat(
+                ParseError::InvalidFormat(
                     "PCAP packet data truncated"
-                ).into()
+                )
             );
         }
 
-        let packet_data = self.data[self.position..self.position + caplen as usize].to_vec();
+        // Fraction de seconde hors plage : fichier corrompu
+        let (ts_usec, ts_nsec) = match self.precision {
+            PcapPrecision::Micro if ts_frac < 1_000_000 => (ts_frac, ts_frac * 1000),
+            PcapPrecision::Nano if ts_frac < 1_000_000_000 => (ts_frac / 1000, ts_frac),
+            _ => {
+                return Err(
+                    ParseError::InvalidFormat(
+                        "PCAP timestamp fraction out of range"
+                    )
+                );
+            }
+        };
+
+        let data = self.data[self.position..self.position + caplen as usize].to_vec();
         self.position += caplen as usize;
 
+        let packet = PacketDissector::new()
+            .dissect_link_frame(self.link_type, &data)
+            .ok();
+
         Ok(
             Some(
-                packet_data
+                PacketRecord {
+                    ts_sec,
+                    ts_usec,
+                    ts_nsec,
+                    caplen,
+                    orig_len,
+                    data,
+                    link_type: self.link_type,
+                    packet,
+                    comment: None,
+                }
             )
         )
     }
 
+    // Lit le paquet suivant dans le fichier PCAP
+    pub fn read_next_packet(&mut self) -> Result<Option<VecNoStd<u8>>> {
+        Ok(
+            self.read_next_record()?.map(|record| record.data)
+        )
+    }
+
+    // Lit un u32 a la position donnee selon l'ordre des octets du fichier
+    fn read_u32(&self, pos: usize) -> u32 {
+        let bytes = [
+            self.data[pos],
+            self.data[pos + 1],
+            self.data[pos + 2],
+            self.data[pos + 3],
+        ];
+        match self.byte_order {
+            PcapByteOrder::LittleEndian => u32::from_le_bytes(bytes),
+            PcapByteOrder::BigEndian => u32::from_be_bytes(bytes),
+        }
+    }
+
     // Verifie packet valide read
     pub fn has_more_packets(&self) -> bool {
         self.position < self.data.len()
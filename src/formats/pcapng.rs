@@ -0,0 +1,474 @@
+use crate::{
+    utils::clock::{
+        Clock,
+        default_clock
+    },
+    structs::network_packet::NetworkPacket,
+    structs::pcap::{
+        PcapOptions,
+        PcapByteOrder,
+        PcapPrecision,
+        LinkType
+    },
+    structs::pcapng::{
+        PcapNgWriter,
+        PcapNgReader,
+        PcapNgInterface
+    },
+    structs::formats::PacketRecord,
+    packets::builder::packet_dissector::PacketDissector,
+    formats::pcap::{
+        u16_to_bytes,
+        u32_to_bytes,
+        link_frame
+    },
+    errors::errors::{Result, ParseError},
+    prelude::*,
+};
+
+// Types de blocs PCAPNG
+const BLOCK_SECTION_HEADER: u32 = 0x0A0D0D0A;
+const BLOCK_INTERFACE_DESCRIPTION: u32 = 0x00000001;
+const BLOCK_SIMPLE_PACKET: u32 = 0x00000003;
+const BLOCK_ENHANCED_PACKET: u32 = 0x00000006;
+
+// Byte-order magic du Section Header Block
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B3C4D;
+
+// Codes d'options
+const OPT_END_OF_OPT: u16 = 0;
+const OPT_COMMENT: u16 = 1;
+const OPT_IF_TSRESOL: u16 = 9;
+
+// Arrondit une longueur au multiple de 4 superieur
+fn padded_len(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+// Implémentation des fonctions pour cree des fichiers PCAPNG
+impl PcapNgWriter {
+
+    /// Constructeur
+    pub fn new() -> Self {
+        Self::with_options(PcapOptions::default())
+    }
+
+    /// Constructeur avec options (ordre des octets, precision, snaplen, type de lien)
+    pub fn with_options(options: PcapOptions) -> Self {
+        Self {
+            buffer: VecNoStd::new(),
+            options,
+            clock: default_clock(),
+        }
+    }
+
+    /// Remplace l'horloge utilisee pour horodater les paquets
+    pub fn with_clock(mut self, clock: Box<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Ecrit le Section Header Block et l'Interface Description Block
+    pub fn write_global_header(&mut self) -> Result<()> {
+        let byte_order = self.options.byte_order;
+        // Le champ LinkType de l'IDB ne fait que 16 bits
+        let link_type = self.options.link_type.as_u32();
+        let link_type = u16::try_from(link_type)
+            .map_err(|_| ParseError::ValueTooLarge { value: link_type as u64, size: 2 })?;
+
+        let mut shb = VecNoStd::new();
+        shb.extend_from_slice(&u32_to_bytes(BYTE_ORDER_MAGIC, byte_order));
+        shb.extend_from_slice(&u16_to_bytes(1, byte_order));
+        shb.extend_from_slice(&u16_to_bytes(0, byte_order));
+        // Longueur de section inconnue (-1)
+        shb.extend_from_slice(&[0xFF; 8]);
+        self.write_block(BLOCK_SECTION_HEADER, &shb);
+
+        let ts_resolution: u8 = match self.options.precision {
+            PcapPrecision::Micro => 6,
+            PcapPrecision::Nano => 9,
+        };
+        let mut idb = VecNoStd::new();
+        idb.extend_from_slice(&u16_to_bytes(link_type, byte_order));
+        idb.extend_from_slice(&u16_to_bytes(0, byte_order));
+        idb.extend_from_slice(&u32_to_bytes(self.options.snaplen, byte_order));
+        self.push_option(&mut idb, OPT_IF_TSRESOL, &[ts_resolution])?;
+        self.push_option(&mut idb, OPT_END_OF_OPT, &[])?;
+        self.write_block(BLOCK_INTERFACE_DESCRIPTION, &idb);
+
+        Ok(())
+    }
+
+    // Ecrit un paquet dans un Enhanced Packet Block
+    pub fn write_packet(&mut self, packet: &NetworkPacket) -> Result<()> {
+        self.write_packet_with_comment(packet, None)
+    }
+
+    /// Ecrit un paquet avec un commentaire (opt_comment), par exemple les arguments CLI
+    pub fn write_packet_with_comment(&mut self, packet: &NetworkPacket, comment: Option<&str>) -> Result<()> {
+        let frame = packet.assemble_packet()?;
+        let now_ns = self.clock.now_ns();
+        self.write_frame(&frame, LinkType::Ethernet, frame.len() as u32, now_ns, comment)
+    }
+
+    /// Ecrit un enregistrement capture en conservant son timestamp, sa longueur
+    /// d'origine et son commentaire ; la trame est convertie depuis le type de lien
+    /// de l'enregistrement
+    pub fn write_record(&mut self, record: &PacketRecord) -> Result<()> {
+        let timestamp_ns = record.ts_sec as u64 * 1_000_000_000 + record.ts_nsec as u64;
+        self.write_frame(&record.data, record.link_type, record.orig_len, timestamp_ns, record.comment.as_deref())
+    }
+
+    // Ecrit un Enhanced Packet Block avec la trame convertie au type de lien
+    fn write_frame(
+        &mut self,
+        frame: &[u8],
+        source: LinkType,
+        frame_orig_len: u32,
+        timestamp_ns: u64,
+        comment: Option<&str>,
+    ) -> Result<()> {
+        let byte_order = self.options.byte_order;
+        let packet_data = link_frame(frame, source, self.options.link_type)?;
+
+        let timestamp = match self.options.precision {
+            PcapPrecision::Micro => timestamp_ns / 1000,
+            PcapPrecision::Nano => timestamp_ns,
+        };
+
+        // La conversion de lien ne change que l'en-tete, la partie tronquee reste identique
+        let truncated = frame_orig_len.saturating_sub(frame.len() as u32);
+        let orig_len = packet_data.len() as u32 + truncated;
+        let caplen = (packet_data.len() as u32).min(self.options.snaplen);
+
+        let mut epb = VecNoStd::new();
+        epb.extend_from_slice(&u32_to_bytes(0, byte_order));
+        epb.extend_from_slice(&u32_to_bytes((timestamp >> 32) as u32, byte_order));
+        epb.extend_from_slice(&u32_to_bytes((timestamp & 0xFFFFFFFF) as u32, byte_order));
+        epb.extend_from_slice(&u32_to_bytes(caplen, byte_order));
+        epb.extend_from_slice(&u32_to_bytes(orig_len, byte_order));
+        epb.extend_from_slice(&packet_data[..caplen as usize]);
+        epb.resize(padded_len(epb.len()), 0);
+
+        if let Some(text) = comment {
+            self.push_option(&mut epb, OPT_COMMENT, text.as_bytes())?;
+            self.push_option(&mut epb, OPT_END_OF_OPT, &[])?;
+        }
+        self.write_block(BLOCK_ENHANCED_PACKET, &epb);
+
+        Ok(())
+    }
+
+    // Ajoute une option (code, longueur, valeur alignee sur 4 octets)
+    // La longueur tient sur 16 bits : une valeur plus longue est refusee
+    fn push_option(&self, body: &mut VecNoStd<u8>, code: u16, value: &[u8]) -> Result<()> {
+        let byte_order = self.options.byte_order;
+        let length = u16::try_from(value.len())
+            .map_err(|_| ParseError::ValueTooLarge { value: value.len() as u64, size: 2 })?;
+        body.extend_from_slice(&u16_to_bytes(code, byte_order));
+        body.extend_from_slice(&u16_to_bytes(length, byte_order));
+        body.extend_from_slice(value);
+        body.resize(padded_len(body.len()), 0);
+        Ok(())
+    }
+
+    // Ecrit un bloc complet : type, longueur totale, corps, longueur totale
+    fn write_block(&mut self, block_type: u32, body: &[u8]) {
+        let byte_order = self.options.byte_order;
+        let total_len = (12 + body.len()) as u32;
+        self.buffer.extend_from_slice(&u32_to_bytes(block_type, byte_order));
+        self.buffer.extend_from_slice(&u32_to_bytes(total_len, byte_order));
+        self.buffer.extend_from_slice(body);
+        self.buffer.extend_from_slice(&u32_to_bytes(total_len, byte_order));
+    }
+
+    // Obtient les donnees du fichier PCAPNG
+    pub fn get_data(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Vide le buffer et retourne les octets deja produits
+    /// (utilise par `StreamWriter` pour vider le buffer vers un sink)
+    pub fn take_data(&mut self) -> VecNoStd<u8> {
+        core::mem::take(&mut self.buffer)
+    }
+
+    // retourne le PCAPNG
+    pub fn into_data(self) -> VecNoStd<u8> {
+        self.buffer
+    }
+}
+
+impl Default for PcapNgWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Implementation des fonctions pour lire des fichiers PCAPNG
+impl PcapNgReader {
+    pub fn new(data: VecNoStd<u8>) -> Self {
+        Self {
+            data,
+            position: 0,
+            byte_order: PcapByteOrder::LittleEndian,
+            interfaces: VecNoStd::new(),
+        }
+    }
+
+    // Lit l'enregistrement suivant, les blocs SHB/IDB sont traites au passage
+    pub fn read_next_record(&mut self) -> Result<Option<PacketRecord>> {
+        while self.position + 12 <= self.data.len() {
+            let block_start = self.position;
+
+            if self.read_u32(block_start) == BLOCK_SECTION_HEADER {
+                self.read_section_header(block_start)?;
+            }
+
+            let block_type = self.read_u32(block_start);
+            let total_len = self.read_u32(block_start + 4) as usize;
+            if total_len < 12 || !total_len.is_multiple_of(4) {
+                return Err(
+                    ParseError::InvalidFormat(
+                        "Invalid PCAPNG block length"
+                    )
+                );
+            }
+            if block_start + total_len > self.data.len() {
+                return Err(
+                    ParseError::InvalidFormat(
+                        "PCAPNG block truncated"
+                    )
+                );
+            }
+
+            let body_start = block_start + 8;
+            let body_end = block_start + total_len - 4;
+            self.position = block_start + total_len;
+
+            match block_type {
+                BLOCK_INTERFACE_DESCRIPTION => self.read_interface(body_start, body_end)?,
+                BLOCK_ENHANCED_PACKET => {
+                    return self.read_enhanced_packet(body_start, body_end).map(Some);
+                }
+                BLOCK_SIMPLE_PACKET => {
+                    return self.read_simple_packet(body_start, body_end).map(Some);
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    // Lit le paquet suivant dans le fichier PCAPNG
+    pub fn read_next_packet(&mut self) -> Result<Option<VecNoStd<u8>>> {
+        Ok(
+            self.read_next_record()?.map(|record| record.data)
+        )
+    }
+
+    // Verifie packet valide read
+    pub fn has_more_packets(&self) -> bool {
+        self.position < self.data.len()
+    }
+
+    // Lit le byte-order magic d'un Section Header Block et reinitialise les interfaces
+    fn read_section_header(&mut self, block_start: usize) -> Result<()> {
+        if block_start + 12 > self.data.len() {
+            return Err(
+                ParseError::InvalidFormat(
+                    "PCAPNG section header too short"
+                )
+            );
+        }
+        let magic = [
+            self.data[block_start + 8],
+            self.data[block_start + 9],
+            self.data[block_start + 10],
+            self.data[block_start + 11],
+        ];
+        self.byte_order = if u32::from_le_bytes(magic) == BYTE_ORDER_MAGIC {
+            PcapByteOrder::LittleEndian
+        } else if u32::from_be_bytes(magic) == BYTE_ORDER_MAGIC {
+            PcapByteOrder::BigEndian
+        } else {
+            return Err(
+                ParseError::InvalidFormat(
+                    "Invalid PCAPNG byte-order magic"
+                )
+            );
+        };
+        self.interfaces.clear();
+        Ok(())
+    }
+
+    // Lit un Interface Description Block
+    fn read_interface(&mut self, start: usize, end: usize) -> Result<()> {
+        if start + 8 > end {
+            return Err(
+                ParseError::InvalidFormat(
+                    "PCAPNG interface block too short"
+                )
+            );
+        }
+        let link_type = LinkType::from_u32(self.read_u16(start) as u32);
+        let snaplen = self.read_u32(start + 4);
+
+        let mut ts_resolution = 6;
+        for (code, value) in self.read_options(start + 8, end)? {
+            if code == OPT_IF_TSRESOL && !value.is_empty() {
+                ts_resolution = value[0];
+            }
+        }
+
+        self.interfaces.push(PcapNgInterface {
+            link_type,
+            snaplen,
+            ts_resolution,
+        });
+        Ok(())
+    }
+
+    // Lit un Enhanced Packet Block
+    fn read_enhanced_packet(&self, start: usize, end: usize) -> Result<PacketRecord> {
+        if start + 20 > end {
+            return Err(
+                ParseError::InvalidFormat(
+                    "PCAPNG packet block too short"
+                )
+            );
+        }
+        let interface = self.interface(self.read_u32(start) as usize)?;
+        let timestamp = ((self.read_u32(start + 4) as u64) << 32) | self.read_u32(start + 8) as u64;
+        let caplen = self.read_u32(start + 12);
+        let orig_len = self.read_u32(start + 16);
+
+        let data_start = start + 20;
+        let data_end = data_start + caplen as usize;
+        if data_end > end {
+            return Err(
+                ParseError::InvalidFormat(
+                    "PCAPNG packet data truncated"
+                )
+            );
+        }
+
+        let mut comment = None;
+        for (code, value) in self.read_options(data_start + padded_len(caplen as usize), end)? {
+            if code == OPT_COMMENT && comment.is_none() {
+                comment = Some(StringNoStd::from_utf8_lossy(&value).to_string());
+            }
+        }
+
+        Ok(self.build_record(interface, timestamp, caplen, orig_len, data_start, comment))
+    }
+
+    // Lit un Simple Packet Block (interface 0, sans timestamp)
+    fn read_simple_packet(&self, start: usize, end: usize) -> Result<PacketRecord> {
+        if start + 4 > end {
+            return Err(
+                ParseError::InvalidFormat(
+                    "PCAPNG packet block too short"
+                )
+            );
+        }
+        let interface = self.interface(0)?;
+        let orig_len = self.read_u32(start);
+        let mut caplen = orig_len.min((end - start - 4) as u32);
+        if interface.snaplen != 0 {
+            caplen = caplen.min(interface.snaplen);
+        }
+        Ok(self.build_record(interface, 0, caplen, orig_len, start + 4, None))
+    }
+
+    // Construit l'enregistrement et convertit le timestamp selon if_tsresol
+    fn build_record(
+        &self,
+        interface: PcapNgInterface,
+        timestamp: u64,
+        caplen: u32,
+        orig_len: u32,
+        data_start: usize,
+        comment: Option<StringNoStd>,
+    ) -> PacketRecord {
+        let resolution = interface.ts_resolution;
+        let units_per_sec: u64 = if resolution & 0x80 == 0 {
+            10u64.saturating_pow((resolution & 0x7F) as u32)
+        } else {
+            1u64.checked_shl((resolution & 0x7F) as u32).unwrap_or(u64::MAX)
+        };
+        let ts_sec = (timestamp / units_per_sec) as u32;
+        let ts_nsec = ((timestamp % units_per_sec) as u128 * 1_000_000_000 / units_per_sec as u128) as u32;
+
+        let data = self.data[data_start..data_start + caplen as usize].to_vec();
+        let packet = PacketDissector::new()
+            .dissect_link_frame(interface.link_type, &data)
+            .ok();
+
+        PacketRecord {
+            ts_sec,
+            ts_usec: ts_nsec / 1000,
+            ts_nsec,
+            caplen,
+            orig_len,
+            data,
+            link_type: interface.link_type,
+            packet,
+            comment,
+        }
+    }
+
+    // Lit la liste d'options jusqu'a opt_endofopt ou la fin du bloc
+    fn read_options(&self, mut pos: usize, end: usize) -> Result<VecNoStd<(u16, VecNoStd<u8>)>> {
+        let mut options = VecNoStd::new();
+        while pos + 4 <= end {
+            let code = self.read_u16(pos);
+            let len = self.read_u16(pos + 2) as usize;
+            if code == OPT_END_OF_OPT {
+                break;
+            }
+            if pos + 4 + len > end {
+                return Err(
+                    ParseError::InvalidFormat(
+                        "PCAPNG option truncated"
+                    )
+                );
+            }
+            options.push((code, self.data[pos + 4..pos + 4 + len].to_vec()));
+            pos += 4 + padded_len(len);
+        }
+        Ok(options)
+    }
+
+    // Retourne l'interface decrite par l'IDB d'indice donne
+    fn interface(&self, id: usize) -> Result<PcapNgInterface> {
+        self.interfaces.get(id).copied().ok_or(
+            ParseError::InvalidFormat(
+                "PCAPNG packet references unknown interface"
+            )
+        )
+    }
+
+    // Lit un u16 a la position donnee selon l'ordre des octets de la section
+    fn read_u16(&self, pos: usize) -> u16 {
+        let bytes = [self.data[pos], self.data[pos + 1]];
+        match self.byte_order {
+            PcapByteOrder::LittleEndian => u16::from_le_bytes(bytes),
+            PcapByteOrder::BigEndian => u16::from_be_bytes(bytes),
+        }
+    }
+
+    // Lit un u32 a la position donnee selon l'ordre des octets de la section
+    fn read_u32(&self, pos: usize) -> u32 {
+        let bytes = [
+            self.data[pos],
+            self.data[pos + 1],
+            self.data[pos + 2],
+            self.data[pos + 3],
+        ];
+        match self.byte_order {
+            PcapByteOrder::LittleEndian => u32::from_le_bytes(bytes),
+            PcapByteOrder::BigEndian => u32::from_be_bytes(bytes),
+        }
+    }
+}
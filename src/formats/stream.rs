@@ -0,0 +1,127 @@
+use crate::{
+    errors::errors::Result,
+    prelude::*,
+    structs::{
+        network_packet::NetworkPacket,
+        formats::PacketRecord,
+        stream::StreamWriter,
+    },
+    formats::format_factory::FormatWriter,
+};
+#[cfg(feature = "std")]
+use crate::structs::stream::IoSink;
+
+/// Destination d'octets pour les writers de formats
+/// Trait minimal utilisable en no_std (UART, flash, ring buffer...)
+pub trait ByteSink {
+    /// Ecrit tous les octets de `data` dans le sink
+    fn write_bytes(&mut self, data: &[u8]) -> Result<()>;
+
+    /// Force l'ecriture des octets en attente (rien a faire par defaut)
+    fn flush_bytes(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+// Sink en memoire
+impl ByteSink for VecNoStd<u8> {
+    fn write_bytes(&mut self, data: &[u8]) -> Result<()> {
+        self.extend_from_slice(data);
+        Ok(())
+    }
+}
+
+// Permet de preter un sink sans le ceder au StreamWriter
+impl<S: ByteSink + ?Sized> ByteSink for &mut S {
+    fn write_bytes(&mut self, data: &[u8]) -> Result<()> {
+        (**self).write_bytes(data)
+    }
+
+    fn flush_bytes(&mut self) -> Result<()> {
+        (**self).flush_bytes()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> IoSink<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Recupere le flux sous-jacent
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> ByteSink for IoSink<W> {
+    fn write_bytes(&mut self, data: &[u8]) -> Result<()> {
+        self.inner.write_all(data)?;
+        Ok(())
+    }
+
+    fn flush_bytes(&mut self) -> Result<()> {
+        self.inner.flush()?;
+        Ok(())
+    }
+}
+
+impl<S: ByteSink> StreamWriter<S> {
+    /// Cree le writer et ecrit tout de suite l'entete du format dans le sink
+    pub fn new(mut writer: Box<dyn FormatWriter>, sink: S) -> Result<Self> {
+        writer.write_header()?;
+        let mut stream = Self {
+            writer,
+            sink,
+            packets_written: 0,
+        };
+        stream.drain()?;
+        Ok(stream)
+    }
+
+    /// Ecrit un paquet et le pousse dans le sink
+    pub fn write_packet(&mut self, packet: &NetworkPacket) -> Result<()> {
+        self.writer.write_packet(packet)?;
+        self.packets_written += 1;
+        self.drain()
+    }
+
+    /// Ecrit un enregistrement capture (timestamp et longueur d'origine conserves)
+    pub fn write_record(&mut self, record: &PacketRecord) -> Result<()> {
+        self.writer.write_record(record)?;
+        self.packets_written += 1;
+        self.drain()
+    }
+
+    /// Ecrit une suite de paquets
+    pub fn write_packets(&mut self, packets: &[NetworkPacket]) -> Result<()> {
+        for packet in packets {
+            self.write_packet(packet)?;
+        }
+        Ok(())
+    }
+
+    /// Force l'ecriture des octets en attente dans le sink
+    pub fn flush(&mut self) -> Result<()> {
+        self.sink.flush_bytes()
+    }
+
+    /// Termine le document (']' en JSON), vide le sink et le retourne
+    /// A appeler avant d'abandonner le writer : sans lui, un tableau JSON reste ouvert dans le sink
+    pub fn finish(mut self) -> Result<S> {
+        self.writer.finish()?;
+        self.drain()?;
+        self.sink.flush_bytes()?;
+        Ok(self.sink)
+    }
+
+    // Transfere les octets produits par le writer vers le sink
+    fn drain(&mut self) -> Result<()> {
+        let data = self.writer.take_data();
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.sink.write_bytes(&data)
+    }
+}
@@ -8,6 +8,8 @@ pub mod cli;
 pub mod formats;
 #[cfg(feature = "std")]
 pub mod sender;
+#[cfg(feature = "std")]
+pub mod scanner;
 pub mod structs;
 pub mod parsing;
 pub mod errors;
@@ -1,23 +1,58 @@
 #![cfg(feature = "std")]
 use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::process;
 
 use projet_rsns_morissetlarresacha::{
-    cli::Args,
+    cli::{Args, BannerArgs, Command, OsArgs, ScanArgs, ServiceArgs, SweepArgs},
+    parsing::my_parser::{parse_cidr, parse_ipv4, parse_ipv6, parse_ports},
     structs::{
         packet_builder::PacketBuilder,
+        scan::{ArpScanner, NdpScanner, SynScanner, UdpScanner},
         formats::FormatType,
-        socket::RawSocketSender
+        pcapng::PcapNgWriter,
+        json::JsonSerializer,
+        socket::RawSocketSender,
+        stream::{IoSink, StreamWriter},
+        formats::PacketRecord,
+        pcap::LinkType,
+        neighbor::NeighborResolver,
+        interface::NetworkInterface,
+        ipv6::Ipv6Addr,
+        tcp::TcpFlags,
+        tcp_session::{BannerGrabber, Teardown},
+        service::{ServiceDatabase, ServiceDetector},
+        os_fingerprint::{OsDatabase, OsProber, OsReport},
+        l4_protocol::L4Protocol
     },
     formats::format_factory::FormatFactory,
-    sender::raw_socket::get_interface_index
+    packets::ipv6::{is_multicast, link_local_from_mac, multicast_mac},
+    sender::interface::{interface_by_name, select_interface},
+    utils::{
+        clock::{Clock, SystemClock, split_timestamp_ns},
+        formating_types::{format_ip, format_ipv6, format_mac}
+    }
 };
 
 // Main function
 fn main() {
     let args = Args::parse_args();
 
-    match build_and_send_packet(&args) {
+    let result = match args.command.as_ref() {
+        Some(_) if args.from_json.is_some() => Err("--from_json ne s'emploie pas avec une sous-commande".into()),
+        Some(Command::Scan(scan_args)) => run_scan(&args, scan_args),
+        Some(Command::Sweep(sweep_args)) => run_sweep(&args, sweep_args),
+        Some(Command::Banner(banner_args)) => run_banner(&args, banner_args),
+        Some(Command::Service(service_args)) => run_service(&args, service_args),
+        Some(Command::Os(os_args)) => run_os(&args, os_args),
+        None => match args.from_json.as_deref() {
+            Some(path) => send_json_template(&args, path),
+            None => build_and_send_packet(&args),
+        },
+    };
+
+    match result {
         Ok(_) => {
 
         }
@@ -30,54 +65,598 @@ fn main() {
 
 // Construit et envoie le paquet reseau selon les arguments fournis
 fn build_and_send_packet(args: &Args) -> Result<(), Box<dyn Error>> {
+    let iface = select_output_interface(args)?;
+    // Envoi uniquement vers une destination explicite, avec les privileges requis
+    let sender = if !args.dry_run && args.dst_ip.is_some() {
+        RawSocketSender::new().ok()
+    } else {
+        None
+    };
+    let ipv6 = match ipv6_destination(args)? {
+        Some(dst_ip) => Some((ipv6_source(args, iface.as_ref())?, dst_ip)),
+        None => None,
+    };
+    let (src_ip, dst_ip) = match ipv6 {
+        Some(_) => (None, None),
+        None => (source_ip(args, iface.as_ref())?, args.dst_ip.clone()),
+    };
+    // Resolution active seulement si le paquet est emis ; l'envoi etant au mieux,
+    // un voisin muet ne bloque pas le fichier de debug : la trame part en broadcast
+    let dst_mac = resolve_dst_mac(args, iface.as_ref(), sender.is_some()).unwrap_or_else(|e| {
+        eprintln!("Avertissement: {}, MAC destination en broadcast", e);
+        None
+    });
     let packet_builder = PacketBuilder::from_cli_args(
-        args.src_ip.as_deref(),
-        args.dst_ip.as_deref(),
-        args.src_mac.clone(),
-        args.dst_mac.clone(),
+        src_ip.as_deref(),
+        dst_ip.as_deref(),
+        args.src_mac.or(iface.as_ref().map(|iface| iface.mac)),
+        dst_mac,
         Some(12345),
         args.dest_port.clone(),
         args.l4_protocol.as_deref(),
         args.ip_bitfield.clone(),
         Some(b"Hello, Network!".to_vec()),
     )?;
+    let packet_builder = apply_header_args(packet_builder, args)
+        .with_tcp_flags(args.tcp_flags.unwrap_or(TcpFlags::SYN))
+        .with_sequence(args.seq.unwrap_or(0))
+        .with_ack(args.ack.unwrap_or(0))
+        .with_window(args.window.unwrap_or(65535))
+        .with_urgent_pointer(args.urgent_pointer.unwrap_or(0));
 
-    let network_packet = packet_builder.build_packet()?;
+    let network_packet = match ipv6 {
+        Some((src_ip, dst_ip)) => packet_builder.build_ipv6_packet(src_ip, dst_ip, Vec::new())?,
+        None => packet_builder.build_packet()?,
+    };
     let packet_bytes = network_packet.assemble_packet()?;
 
     if let Some(path) = args.debug_file.as_ref() {
-        let factory = FormatFactory::new();
-        let bytes = match args.debug_format.as_deref() {
-            Some("pcap") => factory.write_packet(&network_packet, FormatType::Pcap)?,
-            _ => factory.write_packet(&network_packet, FormatType::Json)?,
-        };
-        std::fs::write(path, &bytes)?;
+        let file = BufWriter::new(File::create(path)?);
+        match args.debug_format.as_deref() {
+            Some("pcap") => {
+                let mut stream = FormatFactory::new()
+                    .create_stream_writer(FormatType::Pcap, IoSink::new(file))?;
+                stream.write_packet(&network_packet)?;
+                stream.finish()?;
+            }
+            Some("pcapng") => {
+                let comment = std::env::args().collect::<Vec<_>>().join(" ");
+                let mut writer = PcapNgWriter::new();
+                writer.write_global_header()?;
+                writer.write_packet_with_comment(&network_packet, Some(&comment))?;
+                let mut file = file;
+                file.write_all(&writer.into_data())?;
+                file.flush()?;
+            }
+            _ => {
+                let mut file = file;
+                JsonSerializer::new().serialize_packet_to_writer(&network_packet, &mut file)?;
+                file.flush()?;
+            }
+        }
+    }
+
+    if let (Some(sender), Some(iface)) = (sender, iface) {
+        let _ = sender.set_write_timeout(args.timeout_ms.as_ref().copied());
+        let _ = sender.send(iface.index, network_packet.ethernet.dst_mac, &packet_bytes);
+    }
+
+    Ok(())
+}
+
+// Envoie les paquets d'un template JSON (un paquet ou un tableau) tels quels
+// Longueurs et checksums absents ou nuls sont recalcules a la lecture ; les paquets
+// sont ecrits dans le fichier de debug et --dry_run n'envoie rien
+fn send_json_template(args: &Args, path: &str) -> Result<(), Box<dyn Error>> {
+    let mut reader = FormatFactory::new().create_reader(FormatType::Json, std::fs::read(path)?);
+    let mut packets = Vec::new();
+    while let Some(record) = reader.read_next_record()? {
+        packets.extend(record.packet);
+    }
+
+    if let Some(mut stream) = debug_stream(args)? {
+        for packet in &packets {
+            stream.write_packet(packet)?;
+        }
+        stream.finish()?;
+    }
+
+    if args.dry_run {
+        return Ok(());
+    }
+
+    let iface = select_output_interface(args)?.ok_or("Aucune interface réseau disponible")?;
+    let sender = RawSocketSender::new()?;
+    sender.set_write_timeout(args.timeout_ms)?;
+    for packet in &packets {
+        sender.send(iface.index, packet.ethernet.dst_mac, &packet.assemble_packet()?)?;
+    }
+    Ok(())
+}
+
+// Scan TCP (SYN, FIN, NULL, Xmas, ACK) ou UDP des ports demandes et affiche l'etat de chaque port
+// Les sondes sont ecrites dans le fichier de debug ; avec --dry_run rien n'est envoye
+fn run_scan(args: &Args, scan_args: &ScanArgs) -> Result<(), Box<dyn Error>> {
+    let ports = parse_ports(&scan_args.ports)?;
+    let (protocol, tcp_flags) = match scan_args.scan_type.as_str() {
+        "syn" => ("tcp", args.tcp_flags.unwrap_or(TcpFlags::SYN)),
+        "fin" => ("tcp", TcpFlags::FIN),
+        "null" => ("tcp", TcpFlags::empty()),
+        "xmas" => ("tcp", TcpFlags::FIN | TcpFlags::PSH | TcpFlags::URG),
+        "ack" => ("tcp", TcpFlags::ACK),
+        "udp" => ("udp", TcpFlags::empty()),
+        _ => return Err("scan_type invalide (syn|fin|null|xmas|ack|udp)".into()),
+    };
+    // Sequence et acquittement servent a correler les reponses
+    match scan_args.scan_type.as_str() {
+        "syn" => reject_tcp_header_args(args, "scan", &["tcp_flags", "window", "urgent_pointer"])?,
+        "udp" => reject_tcp_header_args(args, "scan --scan_type=udp", &[])?,
+        _ => reject_tcp_header_args(args, "scan", &["window", "urgent_pointer"])?,
+    }
+    let iface = select_output_interface(args)?;
+    let template = PacketBuilder::from_cli_args(
+        source_ip(args, iface.as_ref())?.as_deref(),
+        args.dst_ip.as_deref(),
+        args.src_mac.or(iface.as_ref().map(|iface| iface.mac)),
+        resolve_dst_mac(args, iface.as_ref(), !args.dry_run)?,
+        Some(scan_args.src_port),
+        None,
+        Some(protocol),
+        args.ip_bitfield,
+        None,
+    )?;
+    let template = apply_header_args(template, args)
+        .with_window(args.window.unwrap_or(65535))
+        .with_urgent_pointer(args.urgent_pointer.unwrap_or(0));
+    let timeout_ms = args.timeout_ms.unwrap_or(1000);
+    let syn_scanner = SynScanner::new(template.clone())
+        .with_tcp_flags(tcp_flags)
+        .with_retries(scan_args.retries)
+        .with_timeout_ms(timeout_ms);
+    let udp_scanner = UdpScanner::new(template)
+        .with_retries(scan_args.retries)
+        .with_timeout_ms(timeout_ms)
+        .with_rate_limit(scan_args.rate);
+
+    if let Some(mut stream) = debug_stream(args)? {
+        for &port in &ports {
+            let probe = match protocol {
+                "udp" => udp_scanner.build_probe(port)?,
+                _ => syn_scanner.build_probe(port)?,
+            };
+            stream.write_packet(&probe)?;
+        }
+        stream.finish()?;
+    }
+
+    if args.dry_run {
+        return Ok(());
+    }
+
+    let iface = iface.ok_or("Aucune interface réseau disponible")?;
+    let results = match protocol {
+        "udp" => udp_scanner.scan(&iface.name, &ports)?,
+        _ => syn_scanner.scan(&iface.name, &ports)?,
+    };
+    for result in results {
+        println!("{}/{} {}", result.port, protocol, result.state);
+    }
+    Ok(())
+}
+
+// Connexion TCP en espace utilisateur vers --port et affichage de la banniere recue
+// Les segments echanges sont ecrits dans le fichier de debug ; avec --dry_run
+// seul le SYN y est ecrit et rien n'est envoye
+fn run_banner(args: &Args, banner_args: &BannerArgs) -> Result<(), Box<dyn Error>> {
+    let teardown = match banner_args.teardown.as_str() {
+        "fin" => Teardown::Fin,
+        "rst" => Teardown::Rst,
+        _ => return Err("teardown invalide (fin|rst)".into()),
+    };
+    reject_tcp_header_args(args, "banner", &[])?;
+    let iface = select_output_interface(args)?;
+    let template = PacketBuilder::from_cli_args(
+        source_ip(args, iface.as_ref())?.as_deref(),
+        args.dst_ip.as_deref(),
+        args.src_mac.or(iface.as_ref().map(|iface| iface.mac)),
+        resolve_dst_mac(args, iface.as_ref(), !args.dry_run)?,
+        Some(banner_args.src_port),
+        Some(banner_args.port),
+        Some("tcp"),
+        args.ip_bitfield,
+        None,
+    )?;
+    let template = apply_header_args(template, args);
+    let mut grabber = BannerGrabber::new(template)
+        .with_retries(banner_args.retries)
+        .with_timeout_ms(args.timeout_ms.unwrap_or(2000))
+        .with_max_bytes(banner_args.max_bytes)
+        .with_teardown(teardown);
+    if let Some(payload) = banner_args.send.clone() {
+        grabber = grabber.with_payload(payload);
+    }
+
+    let segments = if args.dry_run {
+        vec![grabber.session(banner_args.port).connect()?]
+    } else {
+        let iface = iface.ok_or("Aucune interface réseau disponible")?;
+        let banner = grabber.grab(&iface.name, banner_args.port)?;
+        println!("{}/tcp {}", banner.port, banner.state);
+        if !banner.data.is_empty() {
+            println!("{}", String::from_utf8_lossy(&banner.data).escape_debug());
+        }
+        banner.segments
+    };
+
+    if let Some(mut stream) = debug_stream(args)? {
+        for segment in &segments {
+            stream.write_packet(segment)?;
+        }
+        stream.finish()?;
+    }
+    Ok(())
+}
+
+// Detection du service de chaque port : sondes de la base integree (et de --service_db)
+// jusqu'a une signature reconnue. Le premier paquet de chaque sonde est ecrit dans le
+// fichier de debug ; --dry_run n'envoie rien
+fn run_service(args: &Args, service_args: &ServiceArgs) -> Result<(), Box<dyn Error>> {
+    let ports = parse_ports(&service_args.ports)?;
+    let protocol = match service_args.protocol.as_str() {
+        "tcp" => L4Protocol::Tcp,
+        "udp" => L4Protocol::Udp,
+        _ => return Err("protocol invalide (tcp|udp)".into()),
+    };
+    reject_tcp_header_args(args, "service", &[])?;
+    let mut database = ServiceDatabase::builtin();
+    if let Some(path) = service_args.service_db.as_ref() {
+        database.merge(ServiceDatabase::load(path)?);
     }
+    let iface = select_output_interface(args)?;
+    let template = PacketBuilder::from_cli_args(
+        source_ip(args, iface.as_ref())?.as_deref(),
+        args.dst_ip.as_deref(),
+        args.src_mac.or(iface.as_ref().map(|iface| iface.mac)),
+        resolve_dst_mac(args, iface.as_ref(), !args.dry_run)?,
+        Some(service_args.src_port),
+        None,
+        Some(service_args.protocol.as_str()),
+        args.ip_bitfield,
+        None,
+    )?;
+    let template = apply_header_args(template, args);
+    let detector = ServiceDetector::new(template)
+        .with_database(database)
+        .with_retries(service_args.retries)
+        .with_timeout_ms(args.timeout_ms.unwrap_or(2000))
+        .with_max_bytes(service_args.max_bytes);
 
-    if !args.dry_run && args.src_ip.is_some() && args.dst_ip.is_some() {
-        if let Ok(iface) = detect_interface() {
-            if let Ok(if_index) = get_interface_index(&iface) {
-                if let Ok(sender) = RawSocketSender::new() {
-                    let _ = sender.set_write_timeout(args.timeout_ms.as_ref().copied());
-                    let _ = sender.send(if_index, network_packet.ethernet.dst_mac, &packet_bytes);
-                }
+    if let Some(mut stream) = debug_stream(args)? {
+        for &port in &ports {
+            for probe in detector.build_probes(protocol, port)? {
+                stream.write_packet(&probe)?;
             }
         }
+        stream.finish()?;
+    }
+
+    if args.dry_run {
+        return Ok(());
+    }
+
+    let iface = iface.ok_or("Aucune interface réseau disponible")?;
+    for &port in &ports {
+        println!("{}", detector.detect(&iface.name, protocol, port)?);
+    }
+    Ok(())
+}
+
+// Identification du systeme d'exploitation
+// Passif (--pcap) : empreinte des SYN/SYN-ACK de chaque hote de la capture.
+// Actif (--port) : SYN vers un port ouvert de --dst_ip, empreinte des SYN-ACK ;
+// les sondes sont ecrites dans le fichier de debug et --dry_run n'envoie rien
+fn run_os(args: &Args, os_args: &OsArgs) -> Result<(), Box<dyn Error>> {
+    reject_tcp_header_args(args, "os", &[])?;
+    let mut database = OsDatabase::builtin();
+    if let Some(path) = os_args.os_db.as_ref() {
+        database.merge(OsDatabase::load(path)?);
+    }
+
+    if let Some(path) = os_args.pcap.as_ref() {
+        let data = std::fs::read(path)?;
+        let format = match data.first() {
+            Some(b'[') | Some(b'{') => FormatType::Json,
+            _ if data.starts_with(&[0x0A, 0x0D, 0x0D, 0x0A]) => FormatType::PcapNg,
+            _ => FormatType::Pcap,
+        };
+        let mut reader = FormatFactory::new().create_reader(format, data);
+        let mut packets = Vec::new();
+        while let Some(record) = reader.read_next_record()? {
+            packets.extend(record.packet);
+        }
+        for (host, report) in database.identify_hosts(&packets) {
+            print!("{} ", format_ip(&host));
+            print_os_report(&report);
+        }
+        return Ok(());
+    }
+
+    let port = os_args.port.ok_or("--port ou --pcap requis")?;
+    let iface = select_output_interface(args)?;
+    let template = PacketBuilder::from_cli_args(
+        source_ip(args, iface.as_ref())?.as_deref(),
+        args.dst_ip.as_deref(),
+        args.src_mac.or(iface.as_ref().map(|iface| iface.mac)),
+        resolve_dst_mac(args, iface.as_ref(), !args.dry_run)?,
+        Some(os_args.src_port),
+        Some(port),
+        Some("tcp"),
+        args.ip_bitfield,
+        None,
+    )?;
+    let template = apply_header_args(template, args);
+    let prober = OsProber::new(template)
+        .with_database(database)
+        .with_probes(os_args.probes)
+        .with_retries(os_args.retries)
+        .with_timeout_ms(args.timeout_ms.unwrap_or(1000));
+
+    if let Some(mut stream) = debug_stream(args)? {
+        for index in 0..prober.probes() {
+            stream.write_packet(&prober.build_probe(port, index)?)?;
+        }
+        stream.finish()?;
+    }
+
+    if args.dry_run {
+        return Ok(());
+    }
+
+    let iface = iface.ok_or("Aucune interface réseau disponible")?;
+    print_os_report(&prober.probe(&iface.name, port)?);
+    Ok(())
+}
+
+// Meilleur candidat et empreinte relevee, puis les autres candidats
+fn print_os_report(report: &OsReport) {
+    let Some(fingerprint) = report.fingerprint.as_ref() else {
+        println!("aucun SYN-ACK : port ferme ou filtre");
+        return;
+    };
+    match report.best() {
+        Some(best) => println!("{} [{}]", best, fingerprint),
+        None => println!("inconnu [{}]", fingerprint),
+    }
+    for candidate in report.matches.iter().skip(1).take(3) {
+        println!("    {}", candidate);
+    }
+}
+
+// Balayage ARP du sous-reseau et affichage des hotes ayant repondu
+// Les MAC/IPv4 sources par defaut sont celles de l'interface ; les requetes sont
+// ecrites dans le fichier de debug (pcap ou pcapng) et --dry_run n'envoie rien
+fn run_sweep(args: &Args, sweep_args: &SweepArgs) -> Result<(), Box<dyn Error>> {
+    if sweep_args.subnet.contains(':') {
+        return run_sweep_ipv6(args, sweep_args);
+    }
+    let targets = parse_cidr(&sweep_args.subnet)?;
+    let iface = select_output_interface(args)?.ok_or("Aucune interface réseau disponible")?;
+    let src_mac = args.src_mac.unwrap_or(iface.mac);
+    let src_ip = match args.src_ip.as_deref() {
+        Some(ip) => parse_ipv4(ip)?,
+        None => iface.source_address(targets.first().copied()).ok_or("Aucune adresse IPv4 sur l'interface")?,
+    };
+    let scanner = ArpScanner::new(src_mac, src_ip)
+        .with_retries(sweep_args.retries)
+        .with_timeout_ms(args.timeout_ms.unwrap_or(1000))
+        .with_rate_limit(sweep_args.rate);
+
+    if let Some(path) = args.debug_file.as_ref() {
+        // Le schema JSON decrit des paquets IPv4 : les trames ARP vont en pcap
+        let format = match args.debug_format.as_deref() {
+            Some("pcapng") => FormatType::PcapNg,
+            Some("json") => return Err("format json non supporte pour ARP (pcap|pcapng)".into()),
+            _ => FormatType::Pcap,
+        };
+        let file = BufWriter::new(File::create(path)?);
+        let mut stream = FormatFactory::new().create_stream_writer(format, IoSink::new(file))?;
+        for &target in &targets {
+            let data = scanner.build_probe(target).assemble()?;
+            let (ts_sec, ts_nsec) = split_timestamp_ns(SystemClock.now_ns());
+            stream.write_record(&PacketRecord {
+                ts_sec: ts_sec as u32,
+                ts_usec: ts_nsec / 1000,
+                ts_nsec,
+                caplen: data.len() as u32,
+                orig_len: data.len() as u32,
+                data,
+                link_type: LinkType::Ethernet,
+                packet: None,
+                comment: None,
+            })?;
+        }
+        stream.finish()?;
+    }
+
+    if args.dry_run {
+        return Ok(());
     }
 
+    for host in scanner.sweep(&iface.name, &targets)? {
+        println!("{} {}", format_ip(&host.ip.octets), format_mac(&host.mac));
+    }
     Ok(())
 }
 
-// Detecte automatiquement une interface réseau disponible
-fn detect_interface() -> Result<String, Box<dyn Error>> {
-    let interfaces = ["eth0", "enp0s3", "wlan0", "lo"];
-    for iface in &interfaces {
-        if get_interface_index(iface).is_ok() {
-            return Ok(iface.to_string());
+// Decouverte IPv6 : Neighbor Solicitation des adresses listees, echo request
+// pour un groupe multicast (ff02::1 : tous les noeuds du lien)
+// Source par defaut : link-local EUI-64 de la MAC de l'interface
+fn run_sweep_ipv6(args: &Args, sweep_args: &SweepArgs) -> Result<(), Box<dyn Error>> {
+    let targets = sweep_args.subnet
+        .split(',')
+        .map(|ip| parse_ipv6(ip.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let iface = select_output_interface(args)?.ok_or("Aucune interface réseau disponible")?;
+    let scanner = NdpScanner::new(args.src_mac.unwrap_or(iface.mac), ipv6_source(args, Some(&iface))?)
+        .with_retries(sweep_args.retries)
+        .with_timeout_ms(args.timeout_ms.unwrap_or(1000))
+        .with_rate_limit(sweep_args.rate);
+
+    if let Some(path) = args.debug_file.as_ref() {
+        let format = match args.debug_format.as_deref() {
+            Some("pcapng") => FormatType::PcapNg,
+            Some("json") => FormatType::Json,
+            _ => FormatType::Pcap,
+        };
+        let file = BufWriter::new(File::create(path)?);
+        let mut stream = FormatFactory::new().create_stream_writer(format, IoSink::new(file))?;
+        for &target in &targets {
+            stream.write_packet(&scanner.build_probe(target)?)?;
         }
+        stream.finish()?;
+    }
+
+    if args.dry_run {
+        return Ok(());
+    }
+
+    for host in scanner.discover(&iface.name, &targets)? {
+        println!("{} {}", format_ipv6(&host.ip.octets), format_mac(&host.mac));
+    }
+    Ok(())
+}
+
+// Options d'en-tete communes (VLAN, IPv4, options TCP) appliquees au gabarit
+// de chaque sous-commande
+fn apply_header_args(builder: PacketBuilder, args: &Args) -> PacketBuilder {
+    builder
+        .with_vlan_tags(args.vlan.clone().unwrap_or_default())
+        .with_ttl(args.ttl.unwrap_or(64))
+        .with_dscp(args.dscp.unwrap_or(0))
+        .with_ecn(args.ecn.unwrap_or(0))
+        .with_identification(args.ip_id.unwrap_or(0))
+        .with_ip_options(args.ip_options.clone().unwrap_or_default())
+        .with_tcp_options(args.tcp_options.clone().unwrap_or_default())
+}
+
+// Refuse les options d'en-tete TCP du paquet unique (--tcp_flags, --seq, --ack,
+// --window, --urgent_pointer) que `command` fixe elle-meme ; `allowed` : celles
+// qu'elle applique a ses sondes
+fn reject_tcp_header_args(args: &Args, command: &str, allowed: &[&str]) -> Result<(), Box<dyn Error>> {
+    let given = [
+        ("tcp_flags", args.tcp_flags.is_some()),
+        ("seq", args.seq.is_some()),
+        ("ack", args.ack.is_some()),
+        ("window", args.window.is_some()),
+        ("urgent_pointer", args.urgent_pointer.is_some()),
+    ];
+    match given.iter().find(|(name, set)| *set && !allowed.contains(name)) {
+        Some((name, _)) => Err(format!("--{} ne s'applique pas a {}", name, command).into()),
+        None => Ok(()),
+    }
+}
+
+// Flux d'ecriture du fichier de debug
+type DebugStream = StreamWriter<IoSink<BufWriter<File>>>;
+
+// Flux vers --debug_file au format --debug_format (json par defaut),
+// None sans fichier de debug
+fn debug_stream(args: &Args) -> Result<Option<DebugStream>, Box<dyn Error>> {
+    let Some(path) = args.debug_file.as_ref() else { return Ok(None) };
+    let format = match args.debug_format.as_deref() {
+        Some("pcap") => FormatType::Pcap,
+        Some("pcapng") => FormatType::PcapNg,
+        _ => FormatType::Json,
+    };
+    let file = BufWriter::new(File::create(path)?);
+    Ok(Some(FormatFactory::new().create_stream_writer(format, IoSink::new(file))?))
+}
+
+// Interface de sortie : --interface, sinon celle de la route vers --dst_ip
+// (route par defaut sans destination). Seul un --interface inconnu est une erreur
+fn select_output_interface(args: &Args) -> Result<Option<NetworkInterface>, Box<dyn Error>> {
+    if let Some(name) = args.interface.as_deref() {
+        return Ok(Some(interface_by_name(name)?));
+    }
+    if ipv6_destination(args)?.is_some() {
+        return Ok(select_interface(None).ok());
+    }
+    let dst_ip = args.dst_ip.as_deref().map(parse_ipv4).transpose()?;
+    Ok(select_interface(dst_ip).ok())
+}
+
+// Destination d'un paquet IPv6 si --dst_ip est une IPv6
+fn ipv6_destination(args: &Args) -> Result<Option<Ipv6Addr>, Box<dyn Error>> {
+    Ok(
+        args.dst_ip
+            .as_deref()
+            .filter(|ip| ip.contains(':'))
+            .map(parse_ipv6)
+            .transpose()?
+    )
+}
+
+// IPv6 source : --src_ip, sinon la link-local EUI-64 de la MAC source, a defaut "::"
+fn ipv6_source(args: &Args, iface: Option<&NetworkInterface>) -> Result<Ipv6Addr, Box<dyn Error>> {
+    if let Some(ip) = args.src_ip.as_deref() {
+        return Ok(parse_ipv6(ip)?);
     }
+    Ok(
+        match args.src_mac.or(iface.map(|iface| iface.mac)) {
+            Some(mac) if mac != [0u8; 6] => link_local_from_mac(&mac),
+            _ => parse_ipv6("::")?,
+        }
+    )
+}
 
-    Err(
-        "Aucune interface réseau disponible".into()
+// IPv4 source : --src_ip, sinon l'adresse de l'interface dans le sous-reseau de --dst_ip
+fn source_ip(args: &Args, iface: Option<&NetworkInterface>) -> Result<Option<String>, Box<dyn Error>> {
+    if args.src_ip.is_some() {
+        return Ok(args.src_ip.clone());
+    }
+    let dst_ip = args.dst_ip.as_deref().map(parse_ipv4).transpose()?;
+    Ok(
+        iface
+            .and_then(|iface| iface.source_address(dst_ip))
+            .map(|ip| format_ip(&ip.octets))
     )
 }
+
+// MAC destination : --dst_mac (aucune resolution), sinon MAC du prochain saut vers
+// --dst_ip (cache, /proc/net/arp puis requete ARP si `active`, c'est-a-dire si des
+// trames sont emises). Sans emission seule la table ARP du noyau est consultee,
+// a defaut la trame reste en broadcast.
+// En IPv6 : MAC 33:33:xx d'un groupe multicast, sinon Neighbor Solicitation si `active`
+fn resolve_dst_mac(
+    args: &Args,
+    iface: Option<&NetworkInterface>,
+    active: bool,
+) -> Result<Option<[u8; 6]>, Box<dyn Error>> {
+    if args.dst_mac.is_some() {
+        return Ok(args.dst_mac);
+    }
+    if let Some(dst_ip) = ipv6_destination(args)? {
+        if is_multicast(&dst_ip) {
+            return Ok(Some(multicast_mac(&dst_ip)));
+        }
+        let Some(iface) = iface.filter(|_| active) else {
+            return Ok(None);
+        };
+        let scanner = NdpScanner::new(args.src_mac.unwrap_or(iface.mac), ipv6_source(args, Some(iface))?)
+            .with_timeout_ms(args.timeout_ms.unwrap_or(1000));
+        let host = scanner.discover(&iface.name, &[dst_ip])?.into_iter().next();
+        return Ok(Some(host.ok_or("Aucun Neighbor Advertisement de la destination IPv6")?.mac));
+    }
+    let (Some(dst_ip), Some(iface)) = (args.dst_ip.as_deref(), iface) else {
+        return Ok(None);
+    };
+    let dst_ip = parse_ipv4(dst_ip)?;
+
+    let mut resolver = NeighborResolver::from_interface(iface)
+        .with_timeout_ms(args.timeout_ms.unwrap_or(1000));
+    if active {
+        Ok(Some(resolver.resolve(dst_ip)?))
+    } else {
+        Ok(resolver.lookup(dst_ip))
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::{
+    utils::{
+        convert_bytes::{
+            convert_n_to_bytes,
+            convert_bytes_to_n
+        },
+        push_bytes::push_bytes,
+    },
+    packets::ethernet::{
+        pack_ethernet,
+        unpack_ethernet
+    },
+    structs::arp::{
+        ArpFrame,
+        ArpPacket,
+        ETHERTYPE_ARP
+    },
+    errors::errors::{
+        Result,
+        ParseError
+    },
+};
+
+// Taille d'un paquet ARP Ethernet/IPv4
+const ARP_LEN: usize = 28;
+
+/// Emballe un paquet ARP en un vecteur doctets
+pub fn pack_arp(packet: &ArpPacket) -> Result<Vec<u8>> {
+    let mut bytes = vec![
+        0u8;
+        ARP_LEN
+    ];
+    let mut offset = 0;
+
+    let fields = [
+        (packet.hardware_type as u64, 2),
+        (packet.protocol_type as u64, 2),
+        (packet.hardware_len as u64, 1),
+        (packet.protocol_len as u64, 1),
+        (packet.operation as u64, 2),
+    ];
+    for &(field, size) in &fields {
+        offset = push_bytes(
+            &mut bytes,
+            offset,
+            &convert_n_to_bytes(field, size)?
+        );
+    }
+
+    offset = push_bytes(&mut bytes, offset, &packet.sender_mac);
+    offset = push_bytes(&mut bytes, offset, &packet.sender_ip);
+    offset = push_bytes(&mut bytes, offset, &packet.target_mac);
+    push_bytes(&mut bytes, offset, &packet.target_ip);
+
+    Ok(
+        bytes
+    )
+}
+
+/// Decode un paquet ARP Ethernet/IPv4 (le padding Ethernet est ignore)
+pub fn unpack_arp(data: &[u8]) -> Result<ArpPacket> {
+    if data.len() < ARP_LEN {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "ARP",
+                needed: ARP_LEN,
+                available: data.len()
+            }
+        );
+    }
+
+    let hardware_type = convert_bytes_to_n(&data[0..2])? as u16;
+    let protocol_type = convert_bytes_to_n(&data[2..4])? as u16;
+    if hardware_type != 1 || protocol_type != 0x0800 || data[4] != 6 || data[5] != 4 {
+        return Err(
+            ParseError::InvalidFormat(
+                "ARP packet is not Ethernet/IPv4"
+            )
+        );
+    }
+
+    let mut packet = ArpPacket {
+        hardware_type,
+        protocol_type,
+        hardware_len: data[4],
+        protocol_len: data[5],
+        operation: convert_bytes_to_n(&data[6..8])? as u16,
+        sender_mac: [0u8; 6],
+        sender_ip: [0u8; 4],
+        target_mac: [0u8; 6],
+        target_ip: [0u8; 4],
+    };
+    push_bytes(&mut packet.sender_mac, 0, &data[8..14]);
+    push_bytes(&mut packet.sender_ip, 0, &data[14..18]);
+    push_bytes(&mut packet.target_mac, 0, &data[18..24]);
+    push_bytes(&mut packet.target_ip, 0, &data[24..28]);
+
+    Ok(
+        packet
+    )
+}
+
+// Assemblage et decodage des trames ARP
+impl ArpFrame {
+
+    /// Assemble la trame Ethernet + ARP
+    pub fn assemble(&self) -> Result<Vec<u8>> {
+        pack_ethernet(&self.ethernet, &pack_arp(&self.arp)?)
+    }
+
+    /// Decode une trame Ethernet contenant un paquet ARP
+    pub fn parse(frame: &[u8]) -> Result<Self> {
+        let (ethernet, payload) = unpack_ethernet(frame)?;
+        if ethernet.ethertype != ETHERTYPE_ARP {
+            return Err(
+                ParseError::UnsupportedEtherType(
+                    ethernet.ethertype
+                )
+            );
+        }
+        Ok(
+            Self {
+                ethernet,
+                arp: unpack_arp(payload)?,
+            }
+        )
+    }
+}
@@ -0,0 +1,73 @@
+use crate::structs::{
+    arp::{
+        ArpFrame,
+        ArpPacket,
+        ARP_REPLY,
+        ARP_REQUEST,
+        ETHERTYPE_ARP
+    },
+    ipv4::Ipv4Addr,
+};
+use super::ethernet_builder::EthernetBuilder;
+
+// MAC de broadcast et MAC cible inconnue d'une requete
+const BROADCAST_MAC: [u8; 6] = [0xFF; 6];
+const UNKNOWN_MAC: [u8; 6] = [0x00; 6];
+
+// Constructeur de trames ARP
+pub struct ArpBuilder {
+    src_mac: [u8; 6],
+    src_ip: Ipv4Addr,
+}
+
+// Implementation de ArpBuilder
+impl ArpBuilder {
+
+    // Constructor : MAC et IPv4 de l'emetteur
+    pub fn new(src_mac: [u8; 6], src_ip: Ipv4Addr) -> Self {
+        Self { src_mac, src_ip }
+    }
+
+    /// Construit une requete "who-has target_ip" en broadcast
+    pub fn build_request(&self, target_ip: Ipv4Addr) -> ArpFrame {
+        self.build_frame(ARP_REQUEST, BROADCAST_MAC, UNKNOWN_MAC, target_ip)
+    }
+
+    /// Construit une reponse "src_ip is-at src_mac" en unicast vers le demandeur
+    pub fn build_reply(&self, target_mac: [u8; 6], target_ip: Ipv4Addr) -> ArpFrame {
+        self.build_frame(ARP_REPLY, target_mac, target_mac, target_ip)
+    }
+
+    /// Construit un ARP gratuit : requete en broadcast annoncant sa propre adresse
+    pub fn build_gratuitous(&self) -> ArpFrame {
+        self.build_frame(ARP_REQUEST, BROADCAST_MAC, UNKNOWN_MAC, self.src_ip)
+    }
+
+    // Trame ARP Ethernet/IPv4
+    fn build_frame(
+        &self,
+        operation: u16,
+        dst_mac: [u8; 6],
+        target_mac: [u8; 6],
+        target_ip: Ipv4Addr,
+    ) -> ArpFrame {
+        ArpFrame {
+            ethernet: EthernetBuilder::new().build_ethernet_header_with_type(
+                self.src_mac,
+                dst_mac,
+                ETHERTYPE_ARP,
+            ),
+            arp: ArpPacket {
+                hardware_type: 1,
+                protocol_type: 0x0800,
+                hardware_len: 6,
+                protocol_len: 4,
+                operation,
+                sender_mac: self.src_mac,
+                sender_ip: self.src_ip.octets,
+                target_mac,
+                target_ip: target_ip.octets,
+            },
+        }
+    }
+}
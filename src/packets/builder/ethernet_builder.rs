@@ -3,15 +3,32 @@
 /// Constructeur de l'header Ethernet
 ///////////////////////////////////////////////////////////////////////////
 
-use crate::structs::ethernet::EthernetHeader;
+use alloc::vec::Vec;
+use crate::structs::ethernet::{EthernetHeader, VlanTag};
 
 
-pub struct EthernetBuilder;
+pub struct EthernetBuilder {
+    vlan_tags: Vec<VlanTag>,
+}
 
 impl EthernetBuilder {
-    /// Constructeur de l'header Ethernet
+    /// Constructeur de l'header Ethernet (trames non taguees)
     pub fn new() -> Self {
-        Self
+        Self {
+            vlan_tags: Vec::new(),
+        }
+    }
+
+    /// Ajoute un tag VLAN apres ceux deja presents (le premier est le plus externe)
+    pub fn with_vlan(mut self, tag: VlanTag) -> Self {
+        self.vlan_tags.push(tag);
+        self
+    }
+
+    /// Remplace les tags VLAN, du plus externe au plus interne
+    pub fn with_vlan_tags(mut self, vlan_tags: Vec<VlanTag>) -> Self {
+        self.vlan_tags = vlan_tags;
+        self
     }
 
     /// Construit l'header Ethernet avec les MAC
@@ -19,11 +36,28 @@ impl EthernetBuilder {
         &self, 
         src_mac: [u8; 6]
         , dst_mac: [u8; 6]
+    ) -> EthernetHeader {
+        self.build_ethernet_header_with_type(src_mac, dst_mac, 0x0800)
+    }
+
+    /// Construit l'header Ethernet pour un autre ethertype (ex: 0x0806 pour ARP)
+    pub fn build_ethernet_header_with_type(
+        &self,
+        src_mac: [u8; 6],
+        dst_mac: [u8; 6],
+        ethertype: u16
     ) -> EthernetHeader {
         EthernetHeader {
             dst_mac,
             src_mac,
-            ethertype: 0x0800,
+            vlan_tags: self.vlan_tags.clone(),
+            ethertype,
         }
     }
-}
\ No newline at end of file
+}
+
+impl Default for EthernetBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
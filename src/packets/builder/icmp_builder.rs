@@ -0,0 +1,135 @@
+use alloc::vec::Vec;
+use crate::{
+    structs::{
+        icmp::{
+            IcmpHeader,
+            ICMP_DEST_UNREACHABLE,
+            ICMP_ECHO_REPLY,
+            ICMP_ECHO_REQUEST,
+            ICMP_TIME_EXCEEDED
+        },
+        network_packet::NetworkPacket,
+    },
+    packets::{
+        builder::packet_assembler::PacketAssembler,
+        l4::icmp::pack_icmp,
+    },
+    utils::checksum::internet_checksum,
+    errors::errors::Result,
+};
+
+// Octets de la couche 4 cites apres l'header IPv4 d'origine (RFC 792)
+const QUOTED_L4_BYTES: usize = 8;
+
+// Constructeur de messages ICMP
+pub struct IcmpBuilder;
+
+// Implementation de IcmpBuilder
+impl IcmpBuilder {
+
+    // Constructor
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Construit un echo request (ping)
+    pub fn build_echo_request(
+        &self,
+        identifier: u16,
+        sequence: u16,
+        payload: Option<Vec<u8>>,
+    ) -> Result<IcmpHeader> {
+        self.build_echo(ICMP_ECHO_REQUEST, identifier, sequence, payload)
+    }
+
+    /// Construit un echo reply
+    pub fn build_echo_reply(
+        &self,
+        identifier: u16,
+        sequence: u16,
+        payload: Option<Vec<u8>>,
+    ) -> Result<IcmpHeader> {
+        self.build_echo(ICMP_ECHO_REPLY, identifier, sequence, payload)
+    }
+
+    /// Construit un destination unreachable citant le datagramme d'origine
+    /// (code 3 : port unreachable, 13 : communication interdite...)
+    pub fn build_destination_unreachable(
+        &self,
+        code: u8,
+        original: &NetworkPacket,
+    ) -> Result<IcmpHeader> {
+        self.build_error(ICMP_DEST_UNREACHABLE, code, original)
+    }
+
+    /// Construit un time exceeded citant le datagramme d'origine
+    /// (code 0 : TTL expire en transit, 1 : reassemblage expire)
+    pub fn build_time_exceeded(
+        &self,
+        code: u8,
+        original: &NetworkPacket,
+    ) -> Result<IcmpHeader> {
+        self.build_error(ICMP_TIME_EXCEEDED, code, original)
+    }
+
+    /// Recalcule le checksum d'un header ICMP modifie apres construction
+    pub fn update_checksum(&self, icmp_header: &mut IcmpHeader) -> Result<()> {
+        icmp_header.checksum = 0;
+        icmp_header.checksum = self.calculate_icmp_checksum(icmp_header)?;
+        Ok(())
+    }
+
+    // Echo request/reply : identifier et sequence dans rest_of_header
+    fn build_echo(
+        &self,
+        icmp_type: u8,
+        identifier: u16,
+        sequence: u16,
+        payload: Option<Vec<u8>>,
+    ) -> Result<IcmpHeader> {
+        let mut icmp_header = IcmpHeader {
+            icmp_type,
+            code: 0,
+            checksum: 0,
+            rest_of_header: ((identifier as u32) << 16) | sequence as u32,
+            payload,
+        };
+        self.update_checksum(&mut icmp_header)?;
+        Ok(icmp_header)
+    }
+
+    // Message d'erreur : header IPv4 d'origine + 8 premiers octets de sa couche 4
+    fn build_error(
+        &self,
+        icmp_type: u8,
+        code: u8,
+        original: &NetworkPacket,
+    ) -> Result<IcmpHeader> {
+        let datagram = PacketAssembler::new().assemble_ipv4(original)?;
+        let header_len = original.ipv4().map_or(0, |ipv4| ipv4.header_len());
+        let quoted_len = (header_len + QUOTED_L4_BYTES).min(datagram.len());
+
+        let mut icmp_header = IcmpHeader {
+            icmp_type,
+            code,
+            checksum: 0,
+            rest_of_header: 0,
+            payload: Some(datagram[..quoted_len].to_vec()),
+        };
+        self.update_checksum(&mut icmp_header)?;
+        Ok(icmp_header)
+    }
+
+    // Calcule le checksum ICMP (header + donnees, sans pseudo-header)
+    fn calculate_icmp_checksum(&self, icmp_header: &IcmpHeader) -> Result<u16> {
+        Ok(
+            internet_checksum(&pack_icmp(icmp_header)?)
+        )
+    }
+}
+
+impl Default for IcmpBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,163 @@
+use alloc::vec::Vec;
+use crate::{
+    structs::{
+        icmpv6::{
+            Icmpv6Header,
+            IPPROTO_ICMPV6,
+            ICMPV6_ECHO_REPLY,
+            ICMPV6_ECHO_REQUEST,
+            ICMPV6_NEIGHBOR_ADVERTISEMENT,
+            ICMPV6_NEIGHBOR_SOLICITATION,
+            ICMPV6_ROUTER_SOLICITATION,
+            NDP_OPTION_SOURCE_LL_ADDR,
+            NDP_OPTION_TARGET_LL_ADDR,
+        },
+        ip::IpHeader,
+        ipv6::Ipv6Addr,
+        l4_protocol::L4Data,
+        network_packet::NetworkPacket,
+    },
+    packets::{
+        builder::{ethernet_builder::EthernetBuilder, ipv6_builder::Ipv6Builder},
+        l4::icmpv6::pack_icmpv6,
+    },
+    errors::errors::Result,
+};
+
+// Hop limit impose aux messages Neighbor Discovery (RFC 4861)
+const NDP_HOP_LIMIT: u8 = 255;
+
+// Constructeur de messages ICMPv6
+// Le checksum couvre le pseudo-header IPv6 : les adresses font partie du builder.
+pub struct Icmpv6Builder {
+    src_ip: Ipv6Addr,
+    dst_ip: Ipv6Addr,
+}
+
+// Implementation de Icmpv6Builder
+impl Icmpv6Builder {
+
+    // Constructor
+    pub fn new(src_ip: Ipv6Addr, dst_ip: Ipv6Addr) -> Self {
+        Self { src_ip, dst_ip }
+    }
+
+    /// Construit un echo request (ping6)
+    pub fn build_echo_request(
+        &self,
+        identifier: u16,
+        sequence: u16,
+        payload: Option<Vec<u8>>,
+    ) -> Result<Icmpv6Header> {
+        self.build_message(
+            ICMPV6_ECHO_REQUEST,
+            ((identifier as u32) << 16) | sequence as u32,
+            payload,
+        )
+    }
+
+    /// Construit un echo reply
+    pub fn build_echo_reply(
+        &self,
+        identifier: u16,
+        sequence: u16,
+        payload: Option<Vec<u8>>,
+    ) -> Result<Icmpv6Header> {
+        self.build_message(
+            ICMPV6_ECHO_REPLY,
+            ((identifier as u32) << 16) | sequence as u32,
+            payload,
+        )
+    }
+
+    /// Construit un Neighbor Solicitation pour `target`
+    /// L'option Source Link-Layer Address est omise sans `src_mac` (DAD).
+    pub fn build_neighbor_solicitation(
+        &self,
+        target: Ipv6Addr,
+        src_mac: Option<[u8; 6]>,
+    ) -> Result<Icmpv6Header> {
+        let mut payload = target.octets.to_vec();
+        if let Some(mac) = src_mac {
+            push_link_layer_option(&mut payload, NDP_OPTION_SOURCE_LL_ADDR, mac);
+        }
+        self.build_message(ICMPV6_NEIGHBOR_SOLICITATION, 0, Some(payload))
+    }
+
+    /// Construit un Neighbor Advertisement annoncant `target_mac` pour `target`
+    /// `flags` combine `NA_FLAG_ROUTER`, `NA_FLAG_SOLICITED` et `NA_FLAG_OVERRIDE`.
+    pub fn build_neighbor_advertisement(
+        &self,
+        target: Ipv6Addr,
+        target_mac: [u8; 6],
+        flags: u32,
+    ) -> Result<Icmpv6Header> {
+        let mut payload = target.octets.to_vec();
+        push_link_layer_option(&mut payload, NDP_OPTION_TARGET_LL_ADDR, target_mac);
+        self.build_message(ICMPV6_NEIGHBOR_ADVERTISEMENT, flags & 0xE000_0000, Some(payload))
+    }
+
+    /// Construit un Router Solicitation (destination usuelle : ff02::2)
+    pub fn build_router_solicitation(
+        &self,
+        src_mac: Option<[u8; 6]>,
+    ) -> Result<Icmpv6Header> {
+        let mut payload = Vec::new();
+        if let Some(mac) = src_mac {
+            push_link_layer_option(&mut payload, NDP_OPTION_SOURCE_LL_ADDR, mac);
+        }
+        let payload = if payload.is_empty() { None } else { Some(payload) };
+        self.build_message(ICMPV6_ROUTER_SOLICITATION, 0, payload)
+    }
+
+    /// Recalcule le checksum d'un message modifie apres construction
+    pub fn update_checksum(&self, icmpv6_header: &mut Icmpv6Header) -> Result<()> {
+        icmpv6_header.checksum = 0;
+        icmpv6_header.checksum = Ipv6Builder::new(self.src_ip, self.dst_ip)
+            .pseudo_header_checksum(IPPROTO_ICMPV6, &pack_icmpv6(icmpv6_header)?);
+        Ok(())
+    }
+
+    /// Encapsule un message dans une trame Ethernet/IPv6 (hop limit 255)
+    pub fn build_packet(
+        &self,
+        src_mac: [u8; 6],
+        dst_mac: [u8; 6],
+        icmpv6_header: Icmpv6Header,
+    ) -> Result<NetworkPacket> {
+        let l4_data = L4Data::Icmpv6(icmpv6_header);
+        let ipv6_header = Ipv6Builder::new(self.src_ip, self.dst_ip)
+            .with_hop_limit(NDP_HOP_LIMIT)
+            .build_ipv6_header(&l4_data, Vec::new())?;
+        Ok(NetworkPacket {
+            ethernet: EthernetBuilder::new().build_ethernet_header_with_type(src_mac, dst_mac, 0x86DD),
+            ip: IpHeader::V6(ipv6_header),
+            l4_data,
+        })
+    }
+
+    // Message ICMPv6 avec checksum calcule
+    fn build_message(
+        &self,
+        icmp_type: u8,
+        rest_of_header: u32,
+        payload: Option<Vec<u8>>,
+    ) -> Result<Icmpv6Header> {
+        let mut icmpv6_header = Icmpv6Header {
+            icmp_type,
+            code: 0,
+            checksum: 0,
+            rest_of_header,
+            payload,
+        };
+        self.update_checksum(&mut icmpv6_header)?;
+        Ok(icmpv6_header)
+    }
+}
+
+// Ajoute une option ND d'adresse de lien (8 octets : type, longueur 1, MAC)
+fn push_link_layer_option(payload: &mut Vec<u8>, option_type: u8, mac: [u8; 6]) {
+    payload.push(option_type);
+    payload.push(1);
+    payload.extend_from_slice(&mac);
+}
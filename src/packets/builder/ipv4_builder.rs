@@ -1,25 +1,31 @@
+use alloc::vec::Vec;
 use crate::{
     structs::{
-        ip::Ipv4Header,
+        ip::{Ipv4Header, Ipv4Option},
         ipv4::Ipv4Addr,
         l4_protocol::L4Data,
     },
-    packets::ip::pack_ipv4,
-    utils::{
-        checksum::internet_checksum,
-        payload_size::payload_len
-    },
-    errors::errors::Result,
+    packets::ip::{ipv4_header_checksum, pack_ipv4_options},
+    utils::payload_size::payload_len,
+    errors::errors::{Result, ParseError},
 };
 
+#[derive(Clone)]
 pub struct Ipv4Builder {
     src_ip: Ipv4Addr,
     dst_ip: Ipv4Addr,
     ip_bitfield: u8,
+    ttl: u8,
+    dscp: u8,
+    ecn: u8,
+    identification: u16,
+    options: Vec<Ipv4Option>,
 }
 
 // Implementation de Ipv4Builder
 impl Ipv4Builder {
+
+    // Constructor : TTL 64, DSCP/ECN 0, identification 0, sans option
     pub fn new(
         src_ip: Ipv4Addr, 
         dst_ip: Ipv4Addr, 
@@ -29,10 +35,47 @@ impl Ipv4Builder {
             src_ip,
             dst_ip,
             ip_bitfield,
+            ttl: 64,
+            dscp: 0,
+            ecn: 0,
+            identification: 0,
+            options: Vec::new(),
         }
     }
 
+    /// Time To Live
+    pub fn with_ttl(mut self, ttl: u8) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Differentiated Services Code Point (6 bits, ex: 46 pour EF)
+    pub fn with_dscp(mut self, dscp: u8) -> Self {
+        self.dscp = dscp;
+        self
+    }
+
+    /// Explicit Congestion Notification (2 bits)
+    pub fn with_ecn(mut self, ecn: u8) -> Self {
+        self.ecn = ecn;
+        self
+    }
+
+    /// Champ identification
+    pub fn with_identification(mut self, identification: u16) -> Self {
+        self.identification = identification;
+        self
+    }
+
+    /// Options IPv4, l'ihl et le padding sont deduits a la construction
+    pub fn with_options(mut self, options: Vec<Ipv4Option>) -> Self {
+        self.options = options;
+        self
+    }
+
     /// Construit l'header IPv4
+    /// Le champ `dscp` de l'header porte l'octet complet (DSCP << 2 | ECN) ;
+    /// le checksum couvre l'header entier, options comprises.
     pub fn build_ipv4_header(
         &self, 
         l4_data: &L4Data
@@ -40,17 +83,31 @@ impl Ipv4Builder {
     Result<
         Ipv4Header
     > {
+        if self.dscp > 0x3F {
+            return Err(ParseError::InvalidFormat("DSCP out of range (0-63)"));
+        }
+        if self.ecn > 0x03 {
+            return Err(ParseError::InvalidFormat("ECN out of range (0-3)"));
+        }
+        let options = pack_ipv4_options(&self.options)?;
+        let header_length = 20 + options.len();
+
         let l4_length = match l4_data {
-            L4Data::Tcp(tcp) => payload_len(&tcp.payload) + 20,
+            L4Data::Tcp(tcp) => tcp.header_len() + payload_len(&tcp.payload),
             L4Data::Udp(udp) => payload_len(&udp.payload) + 8,
+            L4Data::Icmp(icmp) => payload_len(&icmp.payload) + 8,
+            L4Data::Icmpv6(icmpv6) => payload_len(&icmpv6.payload) + 8,
         };
-        let total_length = 20 + l4_length;
+        let total_length = header_length + l4_length;
+        if total_length > u16::MAX as usize {
+            return Err(ParseError::ValueTooLarge { value: total_length as u64, size: 2 });
+        }
         let mut ipv4_header = Ipv4Header {
             version: 4,
-            ihl: 5,
-            dscp: 0,
+            ihl: (header_length / 4) as u8,
+            dscp: (self.dscp << 2) | self.ecn,
             total_length: total_length as u16,
-            identification: 0,
+            identification: self.identification,
             flags: (
                 self.ip_bitfield >> 5
             ) & 0x07,
@@ -59,7 +116,7 @@ impl Ipv4Builder {
                     self.ip_bitfield & 0x1F
                 ) as u16
             ) << 8,
-            ttl: 64,
+            ttl: self.ttl,
             protocol: match l4_data {
                 L4Data::Tcp(
                     _
@@ -67,14 +124,20 @@ impl Ipv4Builder {
                 L4Data::Udp(
                     _
                 ) => 17,
+                L4Data::Icmp(
+                    _
+                ) => 1,
+                L4Data::Icmpv6(
+                    _
+                ) => 58,
             },
             header_checksum: 0,
             src_addr: self.src_ip.octets,
             dst_addr: self.dst_ip.octets,
-            options: None,
+            options: if options.is_empty() { None } else { Some(options) },
         };
 
-        ipv4_header.header_checksum = self.calculate_ipv4_checksum(
+        ipv4_header.header_checksum = ipv4_header_checksum(
             &ipv4_header
         )?;
 
@@ -82,23 +145,4 @@ impl Ipv4Builder {
             ipv4_header
         )
     }
-
-    /// Calcule le checksum de l'header IPv4
-    fn calculate_ipv4_checksum(
-        &self, 
-        ipv4_header: &Ipv4Header
-    ) -> 
-    Result<
-        u16
-    > {
-        let mut temp_header = ipv4_header.clone();
-        temp_header.header_checksum = 0;
-        let ip_header_bytes = pack_ipv4(&temp_header, &[])?;
-        let header_for_checksum = &ip_header_bytes[..20];
-        Ok(
-            internet_checksum(
-                header_for_checksum
-            )
-        )
-    }
 }
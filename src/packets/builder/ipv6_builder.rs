@@ -0,0 +1,119 @@
+use alloc::vec::Vec;
+use crate::{
+    structs::{
+        ip::{Ipv6Header, Ipv6ExtensionHeader},
+        ipv6::Ipv6Addr,
+        icmpv6::IPPROTO_ICMPV6,
+        l4_protocol::L4Data,
+    },
+    packets::l4::{tcp::pack_tcp, udp::pack_udp, icmpv6::pack_icmpv6},
+    utils::checksum::internet_checksum,
+    errors::errors::{Result, ParseError},
+};
+
+// Constructeur de l'header IPv6
+pub struct Ipv6Builder {
+    src_ip: Ipv6Addr,
+    dst_ip: Ipv6Addr,
+    hop_limit: u8,
+}
+
+// Implementation de Ipv6Builder
+impl Ipv6Builder {
+
+    // Constructor : hop limit de 64 par defaut
+    pub fn new(src_ip: Ipv6Addr, dst_ip: Ipv6Addr) -> Self {
+        Self {
+            src_ip,
+            dst_ip,
+            hop_limit: 64,
+        }
+    }
+
+    /// Hop limit des paquets construits
+    pub fn with_hop_limit(mut self, hop_limit: u8) -> Self {
+        self.hop_limit = hop_limit;
+        self
+    }
+
+    /// Construit l'header IPv6 et chaine les extensions dans l'ordre donne :
+    /// chaque next_header designe l'extension suivante, la derniere le protocole L4.
+    pub fn build_ipv6_header(
+        &self,
+        l4_data: &L4Data,
+        extension_headers: Vec<Ipv6ExtensionHeader>,
+    ) -> Result<Ipv6Header> {
+        let (protocol, l4_length) = match l4_data {
+            L4Data::Tcp(tcp) => (6, pack_tcp(tcp)?.len()),
+            L4Data::Udp(udp) => (17, pack_udp(udp)?.len()),
+            L4Data::Icmpv6(icmpv6) => (IPPROTO_ICMPV6, pack_icmpv6(icmpv6)?.len()),
+            L4Data::Icmp(_) => {
+                return Err(ParseError::InvalidFormat("ICMP over IPv6 requires ICMPv6"));
+            }
+        };
+
+        let mut extension_headers = extension_headers;
+        let mut next_header = protocol;
+        for extension in extension_headers.iter_mut().rev() {
+            extension.next_header = next_header;
+            next_header = extension.header_type;
+        }
+
+        let mut ipv6_header = Ipv6Header {
+            version: 6,
+            traffic_class: 0,
+            flow_label: 0,
+            payload_length: 0,
+            next_header,
+            hop_limit: self.hop_limit,
+            src_addr: self.src_ip.octets,
+            dst_addr: self.dst_ip.octets,
+            extension_headers,
+        };
+        let payload_length = ipv6_header.extensions_len() + l4_length;
+        if payload_length > u16::MAX as usize {
+            return Err(ParseError::ValueTooLarge { value: payload_length as u64, size: 2 });
+        }
+        ipv6_header.payload_length = payload_length as u16;
+
+        Ok(ipv6_header)
+    }
+
+    /// Recalcule le checksum TCP/UDP/ICMPv6 avec le pseudo-header IPv6
+    /// Un checksum UDP nul est transmis 0xFFFF, le zero etant interdit en IPv6.
+    pub fn update_l4_checksum(&self, l4_data: &mut L4Data) -> Result<()> {
+        match l4_data {
+            L4Data::Tcp(tcp) => {
+                tcp.checksum = 0;
+                tcp.checksum = self.pseudo_header_checksum(6, &pack_tcp(tcp)?);
+            }
+            L4Data::Udp(udp) => {
+                udp.checksum = 0;
+                udp.checksum = match self.pseudo_header_checksum(17, &pack_udp(udp)?) {
+                    0 => 0xFFFF,
+                    checksum => checksum,
+                };
+            }
+            L4Data::Icmpv6(icmpv6) => {
+                icmpv6.checksum = 0;
+                icmpv6.checksum = self.pseudo_header_checksum(IPPROTO_ICMPV6, &pack_icmpv6(icmpv6)?);
+            }
+            L4Data::Icmp(_) => {
+                return Err(ParseError::InvalidFormat("ICMP over IPv6 requires ICMPv6"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checksum d'un segment L4 precede du pseudo-header IPv6 (RFC 8200 8.1) :
+    /// adresses, longueur sur 32 bits, trois octets nuls et next_header.
+    pub fn pseudo_header_checksum(&self, next_header: u8, segment: &[u8]) -> u16 {
+        let mut checksum_data = Vec::with_capacity(40 + segment.len());
+        checksum_data.extend_from_slice(&self.src_ip.octets);
+        checksum_data.extend_from_slice(&self.dst_ip.octets);
+        checksum_data.extend_from_slice(&(segment.len() as u32).to_be_bytes());
+        checksum_data.extend_from_slice(&[0, 0, 0, next_header]);
+        checksum_data.extend_from_slice(segment);
+        internet_checksum(&checksum_data)
+    }
+}
@@ -2,7 +2,12 @@ pub mod packet_builder;
 pub mod packet_network;
 pub mod tcp_builder;
 pub mod udp_builder;
+pub mod icmp_builder;
+pub mod icmpv6_builder;
+pub mod arp_builder;
 pub mod ipv4_builder;
+pub mod ipv6_builder;
 pub mod ethernet_builder;
 pub mod packet_assembler;
+pub mod packet_dissector;
 pub mod packet_factory;
\ No newline at end of file
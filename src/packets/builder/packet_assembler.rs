@@ -4,13 +4,15 @@ use crate::{
     packets::{
         ethernet::pack_ethernet,
         ip::pack_ipv4,
-        l4::{tcp::pack_tcp, udp::pack_udp},
+        ipv6::{pack_ipv6, IPV6_HEADER_LEN},
+        l4::{tcp::pack_tcp, udp::pack_udp, icmp::pack_icmp, icmpv6::pack_icmpv6},
     },
     structs::{
+        ip::IpHeader,
         network_packet::NetworkPacket,
         l4_protocol::L4Data,
     },
-    errors::errors::Result,
+    errors::errors::{Result, ParseError},
 };
 
 // Rend l'assembleur public
@@ -26,22 +28,43 @@ impl PacketAssembler {
 
     /// Assemble un paquet reseau complet
     pub fn assemble_packet(&self, packet: &NetworkPacket) -> Result<Vec<u8>> {
-        let l4_data = match &packet.l4_data {
-            L4Data::Tcp(tcp_header) => pack_tcp(tcp_header)?,
-            L4Data::Udp(udp_header) => pack_udp(udp_header)?,
+        let ip_packet = match &packet.ip {
+            IpHeader::V4(ipv4) => pack_ipv4(ipv4, &self.assemble_l4(packet)?)?,
+            IpHeader::V6(ipv6) => pack_ipv6(ipv6, &self.assemble_l4(packet)?)?,
         };
-        let ip_packet = pack_ipv4(&packet.ipv4, &l4_data)?;
         let ethernet_packet = pack_ethernet(&packet.ethernet, &ip_packet)?;
         Ok(ethernet_packet)
     }
 
-    // Calcule la taille totale du paquet reseau
+    /// Assemble le datagramme IPv4 seul (sans header Ethernet)
+    pub fn assemble_ipv4(&self, packet: &NetworkPacket) -> Result<Vec<u8>> {
+        let ipv4 = packet.ipv4().ok_or(ParseError::InvalidFormat("Not an IPv4 packet"))?;
+        pack_ipv4(ipv4, &self.assemble_l4(packet)?)
+    }
+
+    // Emballe la couche 4
+    pub(crate) fn assemble_l4(&self, packet: &NetworkPacket) -> Result<Vec<u8>> {
+        match &packet.l4_data {
+            L4Data::Tcp(tcp_header) => pack_tcp(tcp_header),
+            L4Data::Udp(udp_header) => pack_udp(udp_header),
+            L4Data::Icmp(icmp_header) => pack_icmp(icmp_header),
+            L4Data::Icmpv6(icmpv6_header) => pack_icmpv6(icmpv6_header),
+        }
+    }
+
+    // Calcule la taille totale du paquet reseau (tags VLAN compris)
     pub fn get_packet_size(&self, packet: &NetworkPacket) -> usize {
         let l4_size = match &packet.l4_data {
-            L4Data::Tcp(tcp) => 20 + payload_len(&tcp.payload),
+            L4Data::Tcp(tcp) => tcp.header_len() + payload_len(&tcp.payload),
             L4Data::Udp(udp) => 8 + payload_len(&udp.payload),
+            L4Data::Icmp(icmp) => 8 + payload_len(&icmp.payload),
+            L4Data::Icmpv6(icmpv6) => 8 + payload_len(&icmpv6.payload),
         };
 
-        14 + 20 + l4_size
+        let ip_size = match &packet.ip {
+            IpHeader::V4(ipv4) => ipv4.header_len(),
+            IpHeader::V6(ipv6) => IPV6_HEADER_LEN + ipv6.extensions_len(),
+        };
+        packet.ethernet.header_len() + ip_size + l4_size
     }
 }
@@ -1,7 +1,12 @@
 use alloc::vec::Vec;
 use crate::{
     structs::{
+        ethernet::VlanTag,
+        ip::Ipv4Option,
+        tcp::{TcpFlags, TcpOption},
         l4_protocol::L4Protocol,
+        ipv6::Ipv6Addr,
+        ip::Ipv6ExtensionHeader,
         packet_builder::PacketBuilder,
     },
     parsing::my_parser::parse_ipv4,
@@ -20,6 +25,96 @@ impl PacketBuilder {
         );
         factory.build_packet(self)
     }
+
+    /// Tague les trames construites (premier tag : le plus externe, QinQ a partir de deux)
+    pub fn with_vlan_tags(mut self, vlan_tags: Vec<VlanTag>) -> Self {
+        self.vlan_tags = vlan_tags;
+        self
+    }
+
+    /// Time To Live IPv4, repris comme hop limit sur IPv6
+    pub fn with_ttl(mut self, ttl: u8) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// DSCP (0-63) de l'header IPv4
+    pub fn with_dscp(mut self, dscp: u8) -> Self {
+        self.dscp = dscp;
+        self
+    }
+
+    /// ECN (0-3) de l'header IPv4
+    pub fn with_ecn(mut self, ecn: u8) -> Self {
+        self.ecn = ecn;
+        self
+    }
+
+    /// Champ identification de l'header IPv4
+    pub fn with_identification(mut self, identification: u16) -> Self {
+        self.identification = identification;
+        self
+    }
+
+    /// Options IPv4 (Record Route, Timestamp, Source Route, Router Alert...)
+    pub fn with_ip_options(mut self, ip_options: Vec<Ipv4Option>) -> Self {
+        self.ip_options = ip_options;
+        self
+    }
+
+    /// Options TCP des segments construits (MSS, window scale, SACK, timestamps, TFO...)
+    pub fn with_tcp_options(mut self, tcp_options: Vec<TcpOption>) -> Self {
+        self.tcp_options = tcp_options;
+        self
+    }
+
+    /// Drapeaux TCP (SYN par defaut ; vide pour un scan NULL)
+    pub fn with_tcp_flags(mut self, tcp_flags: TcpFlags) -> Self {
+        self.tcp_flags = tcp_flags;
+        self
+    }
+
+    /// Numero de sequence TCP
+    pub fn with_sequence(mut self, sequence_number: u32) -> Self {
+        self.sequence_number = sequence_number;
+        self
+    }
+
+    /// Numero d'acquittement TCP
+    pub fn with_ack(mut self, ack_number: u32) -> Self {
+        self.ack_number = ack_number;
+        self
+    }
+
+    /// Fenetre TCP annoncee
+    pub fn with_window(mut self, window: u16) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Pointeur urgent TCP
+    pub fn with_urgent_pointer(mut self, urgent_pointer: u16) -> Self {
+        self.urgent_pointer = urgent_pointer;
+        self
+    }
+
+    /// Construit le meme paquet sur IPv6 entre `src_ip` et `dst_ip`
+    /// Les extensions sont chainees dans l'ordre donne, avant la couche 4.
+    pub fn build_ipv6_packet(
+        &self,
+        src_ip: Ipv6Addr,
+        dst_ip: Ipv6Addr,
+        extension_headers: Vec<Ipv6ExtensionHeader>,
+    ) -> Result<crate::structs::network_packet::NetworkPacket> {
+        let factory = super::packet_factory::PacketFactory::new(
+            self.src_ip,
+            self.dst_ip,
+            self.ip_bitfield,
+        );
+        let ipv6_builder = super::ipv6_builder::Ipv6Builder::new(src_ip, dst_ip)
+            .with_hop_limit(self.ttl);
+        factory.build_ipv6_packet(self, &ipv6_builder, extension_headers)
+    }
 }
 
 /// Implementation de PacketBuilder
@@ -55,6 +150,7 @@ impl PacketBuilder {
         let protocol = match l4_protocol.as_deref() {
             Some("tcp") => L4Protocol::Tcp,
             Some("udp") => L4Protocol::Udp,
+            Some("icmp") => L4Protocol::Icmp,
             _ => L4Protocol::Tcp,
         };
         let ip_bitfield = ip_bitfield.unwrap_or(0x00);
@@ -68,6 +164,18 @@ impl PacketBuilder {
             protocol,
             ip_bitfield,
             payload,
+            vlan_tags: Vec::new(),
+            ttl: 64,
+            dscp: 0,
+            ecn: 0,
+            identification: 0,
+            ip_options: Vec::new(),
+            tcp_flags: TcpFlags::SYN,
+            sequence_number: 0,
+            ack_number: 0,
+            window: 65535,
+            urgent_pointer: 0,
+            tcp_options: Vec::new(),
         })
     }
 }
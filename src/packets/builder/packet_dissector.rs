@@ -0,0 +1,112 @@
+use alloc::vec::Vec;
+use crate::{
+    packets::{
+        ethernet::{
+            unpack_ethernet,
+            unpack_linux_sll,
+            unpack_linux_sll2
+        },
+        ip::unpack_ipv4,
+        ipv6::unpack_ipv6,
+        l4::{tcp::unpack_tcp, udp::unpack_udp, icmp::unpack_icmp, icmpv6::unpack_icmpv6},
+    },
+    structs::{
+        ethernet::EthernetHeader,
+        ip::IpHeader,
+        network_packet::NetworkPacket,
+        pcap::LinkType,
+        l4_protocol::L4Data,
+    },
+    errors::errors::{Result, ParseError},
+};
+
+// Rend le dissecteur public
+pub struct PacketDissector;
+
+// Dissecteur de paquets réseau, operation inverse de PacketAssembler
+impl PacketDissector {
+
+    // Constructor
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Decode une trame Ethernet/IPv4|IPv6/TCP|UDP|ICMP|ICMPv6 en NetworkPacket
+    pub fn dissect_packet(&self, frame: &[u8]) -> Result<NetworkPacket> {
+        self.dissect_link_frame(LinkType::Ethernet, frame)
+    }
+
+    /// Decode une trame selon son type de lien (Ethernet, IP brut, Linux SLL/SLL2).
+    /// Pour les liens sans header Ethernet, un header equivalent est reconstruit.
+    pub fn dissect_link_frame(&self, link_type: LinkType, frame: &[u8]) -> Result<NetworkPacket> {
+        let (ethernet, ip_data) = match link_type {
+            LinkType::Ethernet => unpack_ethernet(frame)?,
+            LinkType::LinuxSll => unpack_linux_sll(frame)?,
+            LinkType::LinuxSll2 => unpack_linux_sll2(frame)?,
+            // La version IP du premier octet choisit l'ethertype reconstruit
+            LinkType::Raw => (
+                EthernetHeader {
+                    dst_mac: [0u8; 6],
+                    src_mac: [0u8; 6],
+                    vlan_tags: Vec::new(),
+                    ethertype: match frame.first() {
+                        Some(byte) if byte >> 4 == 6 => 0x86DD,
+                        _ => 0x0800,
+                    },
+                },
+                frame
+            ),
+            LinkType::Other(_) => {
+                return Err(ParseError::InvalidFormat("Unsupported link type"));
+            }
+        };
+
+        match ethernet.ethertype {
+            0x0800 => {
+                let (ipv4, l4_bytes) = unpack_ipv4(ip_data)?;
+                // Seul un premier fragment contenant tout l'en-tete L4 se decode :
+                // les autres passent par Ipv4Reassembler
+                if ipv4.fragment_offset != 0 {
+                    return Err(ParseError::Fragmented);
+                }
+                let l4_data = match ipv4.protocol {
+                    1 => unpack_icmp(l4_bytes).map(L4Data::Icmp),
+                    6 => unpack_tcp(l4_bytes).map(L4Data::Tcp),
+                    17 => unpack_udp(l4_bytes).map(L4Data::Udp),
+                    other => return Err(ParseError::UnsupportedProtocol(other)),
+                };
+                let l4_data = match l4_data {
+                    Err(ParseError::TruncatedPacket { .. } | ParseError::LengthMismatch { .. })
+                        if ipv4.more_fragments() => return Err(ParseError::Fragmented),
+                    l4_data => l4_data?,
+                };
+                Ok(NetworkPacket {
+                    ethernet,
+                    ip: IpHeader::V4(ipv4),
+                    l4_data,
+                })
+            }
+            0x86DD => {
+                let (ipv6, l4_bytes) = unpack_ipv6(ip_data)?;
+                let l4_data = match ipv6.upper_layer_protocol() {
+                    6 => L4Data::Tcp(unpack_tcp(l4_bytes)?),
+                    17 => L4Data::Udp(unpack_udp(l4_bytes)?),
+                    58 => L4Data::Icmpv6(unpack_icmpv6(l4_bytes)?),
+                    other => return Err(ParseError::UnsupportedProtocol(other)),
+                };
+                Ok(NetworkPacket {
+                    ethernet,
+                    ip: IpHeader::V6(ipv6),
+                    l4_data,
+                })
+            }
+            other => Err(ParseError::UnsupportedEtherType(other)),
+        }
+    }
+}
+
+impl Default for PacketDissector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,10 +1,15 @@
 use alloc::vec::Vec;
 use crate::{
+    packets::builder::ethernet_builder::EthernetBuilder,
     structs::{
+        ethernet::EthernetHeader,
         ipv4::Ipv4Addr,
+        ip::{IpHeader, Ipv6ExtensionHeader},
+        icmpv6::{Icmpv6Header, ICMPV6_ECHO_REQUEST},
         l4_protocol::{L4Data, L4Protocol},
         network_packet::NetworkPacket,
         packet_builder::PacketBuilder,
+        tcp::TcpFlags,
     },
     parsing::my_parser::parse_ipv4,
     errors::errors::{Result, ParseError},
@@ -14,8 +19,8 @@ use crate::{
 pub struct PacketFactory {
     tcp_builder: super::tcp_builder::TcpBuilder,
     udp_builder: super::udp_builder::UdpBuilder,
+    icmp_builder: super::icmp_builder::IcmpBuilder,
     ipv4_builder: super::ipv4_builder::Ipv4Builder,
-    ethernet_builder: super::ethernet_builder::EthernetBuilder,
 }
 
 // Implementation de PacketFactory
@@ -26,20 +31,84 @@ impl PacketFactory {
         Self {
             tcp_builder: super::tcp_builder::TcpBuilder::new(src_ip, dst_ip),
             udp_builder: super::udp_builder::UdpBuilder::new(src_ip, dst_ip),
+            icmp_builder: super::icmp_builder::IcmpBuilder::new(),
             ipv4_builder: super::ipv4_builder::Ipv4Builder::new(src_ip, dst_ip, ip_bitfield),
-            ethernet_builder: super::ethernet_builder::EthernetBuilder::new(),
         }
     }
 
     /// Construit un paquet reseau complet à partir des information du PacketBuilder
     pub fn build_packet(&self, builder: &PacketBuilder) -> Result<NetworkPacket> {
+        let l4_data = self.build_l4(builder)?;
+        let ipv4_header = self.ipv4_builder
+            .clone()
+            .with_ttl(builder.ttl)
+            .with_dscp(builder.dscp)
+            .with_ecn(builder.ecn)
+            .with_identification(builder.identification)
+            .with_options(builder.ip_options.clone())
+            .build_ipv4_header(&l4_data)?;
+        let ethernet_header = self.build_ethernet(builder, 0x0800);
+
+        Ok(NetworkPacket {
+            ethernet: ethernet_header,
+            ip: IpHeader::V4(ipv4_header),
+            l4_data,
+        })
+    }
+
+    /// Construit un paquet IPv6 : ports, protocole, payload et MAC viennent du PacketBuilder,
+    /// les adresses et le hop limit de l'Ipv6Builder. `L4Protocol::Icmp` produit un echo request ICMPv6.
+    pub fn build_ipv6_packet(
+        &self,
+        builder: &PacketBuilder,
+        ipv6_builder: &super::ipv6_builder::Ipv6Builder,
+        extension_headers: Vec<Ipv6ExtensionHeader>,
+    ) -> Result<NetworkPacket> {
+        let mut l4_data = match self.build_l4(builder)? {
+            L4Data::Icmp(icmp) => L4Data::Icmpv6(Icmpv6Header {
+                icmp_type: ICMPV6_ECHO_REQUEST,
+                code: 0,
+                checksum: 0,
+                rest_of_header: icmp.rest_of_header,
+                payload: icmp.payload,
+            }),
+            l4_data => l4_data,
+        };
+        ipv6_builder.update_l4_checksum(&mut l4_data)?;
+        let ipv6_header = ipv6_builder.build_ipv6_header(&l4_data, extension_headers)?;
+        let ethernet_header = self.build_ethernet(builder, 0x86DD);
+
+        Ok(NetworkPacket {
+            ethernet: ethernet_header,
+            ip: IpHeader::V6(ipv6_header),
+            l4_data,
+        })
+    }
+
+    // Construit l'header Ethernet du PacketBuilder, tags VLAN compris
+    fn build_ethernet(&self, builder: &PacketBuilder, ethertype: u16) -> EthernetHeader {
+        EthernetBuilder::new()
+            .with_vlan_tags(builder.vlan_tags.clone())
+            .build_ethernet_header_with_type(builder.src_mac, builder.dst_mac, ethertype)
+    }
+
+    // Construit la couche 4 du PacketBuilder
+    fn build_l4(&self, builder: &PacketBuilder) -> Result<L4Data> {
         let l4_data = match builder.protocol {
             L4Protocol::Tcp => {
-                let tcp_header = self.tcp_builder.build_tcp_header(
-                    builder.src_port,
-                    builder.dst_port,
-                    builder.payload.clone(),
-                )?;
+                let tcp_header = self.tcp_builder
+                    .clone()
+                    .with_flags(builder.tcp_flags)
+                    .with_sequence(builder.sequence_number)
+                    .with_ack(builder.ack_number)
+                    .with_window(builder.window)
+                    .with_urgent_pointer(builder.urgent_pointer)
+                    .with_options(builder.tcp_options.clone())
+                    .build_tcp_header(
+                        builder.src_port,
+                        builder.dst_port,
+                        builder.payload.clone(),
+                    )?;
                 L4Data::Tcp(tcp_header)
             }
             L4Protocol::Udp => {
@@ -50,19 +119,17 @@ impl PacketFactory {
                 )?;
                 L4Data::Udp(udp_header)
             }
+            // Echo request : le port source sert d'identifiant
+            L4Protocol::Icmp => {
+                let icmp_header = self.icmp_builder.build_echo_request(
+                    builder.src_port,
+                    1,
+                    builder.payload.clone(),
+                )?;
+                L4Data::Icmp(icmp_header)
+            }
         };
-
-        let ipv4_header = self.ipv4_builder.build_ipv4_header(&l4_data)?;
-        let ethernet_header = self.ethernet_builder.build_ethernet_header(
-            builder.src_mac,
-            builder.dst_mac,
-        );
-
-        Ok(NetworkPacket {
-            ethernet: ethernet_header,
-            ipv4: ipv4_header,
-            l4_data,
-        })
+        Ok(l4_data)
     }
 
     pub fn from_cli_args(
@@ -96,6 +163,7 @@ impl PacketFactory {
         let protocol = match l4_protocol.as_deref() {
             Some("tcp") => L4Protocol::Tcp,
             Some("udp") => L4Protocol::Udp,
+            Some("icmp") => L4Protocol::Icmp,
             _ => L4Protocol::Tcp,
         };
         let ip_bitfield = ip_bitfield.unwrap_or(0x00);
@@ -110,6 +178,18 @@ impl PacketFactory {
             protocol,
             ip_bitfield,
             payload,
+            vlan_tags: Vec::new(),
+            ttl: 64,
+            dscp: 0,
+            ecn: 0,
+            identification: 0,
+            ip_options: Vec::new(),
+            tcp_flags: TcpFlags::SYN,
+            sequence_number: 0,
+            ack_number: 0,
+            window: 65535,
+            urgent_pointer: 0,
+            tcp_options: Vec::new(),
         };
 
         let factory = Self::new(src_ip, dst_ip, ip_bitfield);
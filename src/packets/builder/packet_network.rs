@@ -1,11 +1,47 @@
 use alloc::vec::Vec;
 use crate::{
-    structs::network_packet::NetworkPacket,
+    structs::{
+        ip::{IpHeader, Ipv4Header, Ipv6Header},
+        network_packet::NetworkPacket,
+    },
     errors::errors::Result,
 };
 
 // Extension de NetworkPacket pour l'assemblage
 impl NetworkPacket {
+
+    /// Header IPv4, None pour un paquet IPv6
+    pub fn ipv4(&self) -> Option<&Ipv4Header> {
+        match &self.ip {
+            IpHeader::V4(ipv4) => Some(ipv4),
+            IpHeader::V6(_) => None,
+        }
+    }
+
+    /// Header IPv4 modifiable, None pour un paquet IPv6
+    pub fn ipv4_mut(&mut self) -> Option<&mut Ipv4Header> {
+        match &mut self.ip {
+            IpHeader::V4(ipv4) => Some(ipv4),
+            IpHeader::V6(_) => None,
+        }
+    }
+
+    /// Header IPv6, None pour un paquet IPv4
+    pub fn ipv6(&self) -> Option<&Ipv6Header> {
+        match &self.ip {
+            IpHeader::V6(ipv6) => Some(ipv6),
+            IpHeader::V4(_) => None,
+        }
+    }
+
+    /// Header IPv6 modifiable, None pour un paquet IPv4
+    pub fn ipv6_mut(&mut self) -> Option<&mut Ipv6Header> {
+        match &mut self.ip {
+            IpHeader::V6(ipv6) => Some(ipv6),
+            IpHeader::V4(_) => None,
+        }
+    }
+
     pub fn assemble_packet(
         &self
     ) -> 
@@ -24,4 +60,15 @@ impl NetworkPacket {
             self
         )
     }
+
+    /// Decode une trame brute en NetworkPacket
+    pub fn parse(
+        frame: &[u8]
+    ) ->
+    Result<Self> {
+        let dissector = super::packet_dissector::PacketDissector::new();
+        dissector.dissect_packet(
+            frame
+        )
+    }
 }
@@ -1,29 +1,79 @@
 use alloc::vec::Vec;
 use crate::{
     structs::{
-        tcp::TcpHeader,
+        tcp::{TcpFlags, TcpHeader, TcpOption},
         ipv4::Ipv4Addr,
     },
-    packets::l4::tcp::pack_tcp,
-    utils::{
-        checksum::internet_checksum,
-        payload_size::payload_len
-    },
+    packets::l4::tcp::{pack_tcp, pack_tcp_options},
+    utils::checksum::internet_checksum,
     errors::errors::Result,
 };
 
 // Constructeur de paquets TCP
+#[derive(Clone)]
 pub struct TcpBuilder {
     src_ip: Ipv4Addr,
     dst_ip: Ipv4Addr,
+    flags: TcpFlags,
+    sequence_number: u32,
+    ack_number: u32,
+    window: u16,
+    urgent_pointer: u16,
+    options: Vec<TcpOption>,
 }
 
 // Implementation de TcpBuilder
 impl TcpBuilder {
 
-    // Constructor
+    // Constructor : SYN, sequence et ack a 0, fenetre 65535
     pub fn new(src_ip: Ipv4Addr, dst_ip: Ipv4Addr) -> Self {
-        Self { src_ip, dst_ip }
+        Self {
+            src_ip,
+            dst_ip,
+            flags: TcpFlags::SYN,
+            sequence_number: 0,
+            ack_number: 0,
+            window: 65535,
+            urgent_pointer: 0,
+            options: Vec::new(),
+        }
+    }
+
+    /// Drapeaux du segment (ex: `TcpFlags::FIN | TcpFlags::PSH | TcpFlags::URG` pour un Xmas)
+    pub fn with_flags(mut self, flags: TcpFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Numero de sequence
+    pub fn with_sequence(mut self, sequence_number: u32) -> Self {
+        self.sequence_number = sequence_number;
+        self
+    }
+
+    /// Numero d'acquittement
+    pub fn with_ack(mut self, ack_number: u32) -> Self {
+        self.ack_number = ack_number;
+        self
+    }
+
+    /// Fenetre annoncee
+    pub fn with_window(mut self, window: u16) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Pointeur urgent (significatif avec URG)
+    pub fn with_urgent_pointer(mut self, urgent_pointer: u16) -> Self {
+        self.urgent_pointer = urgent_pointer;
+        self
+    }
+
+    /// Options TCP (MSS, window scale, SACK, timestamps...), dans l'ordre donne
+    /// Le data offset et le padding sont deduits a la construction.
+    pub fn with_options(mut self, options: Vec<TcpOption>) -> Self {
+        self.options = options;
+        self
     }
 
     /// Construit l'header TCP
@@ -33,18 +83,19 @@ impl TcpBuilder {
         dst_port: u16,
         payload: Option<Vec<u8>>,
     ) -> Result<TcpHeader> {
+        let options = pack_tcp_options(&self.options)?;
         let mut tcp_header = TcpHeader {
             src_port,
             dst_port,
-            sequence_number: 0,
-            ack_nowledgment_number: 0,
-            data_offset: 5,
+            sequence_number: self.sequence_number,
+            ack_nowledgment_number: self.ack_number,
+            data_offset: 5 + (options.len() / 4) as u8,
             reserved: 0,
-            flags: 0x02,
-            window: 65535,
+            flags: self.flags.bits(),
+            window: self.window,
             checksum: 0,
-            urgent_pointer: 0,
-            options: None,
+            urgent_pointer: self.urgent_pointer,
+            options: if options.is_empty() { None } else { Some(options) },
             payload,
         };
         tcp_header.checksum = self.calculate_tcp_checksum(&tcp_header)?;
@@ -52,17 +103,25 @@ impl TcpBuilder {
         Ok(tcp_header)
     }
 
+    /// Recalcule le checksum d'un header TCP modifie apres construction
+    /// (ex: numero de sequence d'une sonde de scan)
+    pub fn update_checksum(&self, tcp_header: &mut TcpHeader) -> Result<()> {
+        tcp_header.checksum = 0;
+        tcp_header.checksum = self.calculate_tcp_checksum(tcp_header)?;
+        Ok(())
+    }
+
     /// Calcule le checksum TCP
+    /// La longueur du pseudo-header est celle du segment emballe, options comprises.
     fn calculate_tcp_checksum(&self, tcp_header: &TcpHeader) -> Result<u16> {
+        let tcp_segment = pack_tcp(tcp_header)?;
+
         let mut pseudo_header = Vec::new();
         pseudo_header.extend_from_slice(&self.src_ip.octets);
         pseudo_header.extend_from_slice(&self.dst_ip.octets);
         pseudo_header.push(0);
         pseudo_header.push(6);
-        let tcp_length = 20 + payload_len(&tcp_header.payload);
-        pseudo_header.extend_from_slice(&(tcp_length as u16).to_be_bytes());
-
-        let tcp_segment = pack_tcp(tcp_header)?;
+        pseudo_header.extend_from_slice(&(tcp_segment.len() as u16).to_be_bytes());
 
         let mut checksum_data = pseudo_header;
         checksum_data.extend_from_slice(&tcp_segment);
@@ -57,6 +57,13 @@ impl UdpBuilder {
         Ok(udp_header)
     }
 
+    /// Recalcule le checksum d'un header UDP modifie apres construction
+    pub fn update_checksum(&self, udp_header: &mut UdpHeader) -> Result<()> {
+        udp_header.checksum = 0;
+        udp_header.checksum = self.calculate_udp_checksum(udp_header)?;
+        Ok(())
+    }
+
     // Calcule le checksum UDP
     fn calculate_udp_checksum(&self, udp_header: &UdpHeader) -> Result<u16> {
         let mut pseudo_header = Vec::new();
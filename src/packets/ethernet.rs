@@ -2,13 +2,104 @@ use alloc::vec;
 use alloc::vec::Vec;
 use crate::{
     utils::{
-        convert_bytes::convert_n_to_bytes,
+        convert_bytes::{
+            convert_n_to_bytes,
+            convert_bytes_to_n
+        },
         push_bytes::push_bytes,
     },
-    structs::ethernet::EthernetHeader,
-    errors::errors::Result,
+    structs::ethernet::{
+        EthernetHeader,
+        VlanTag,
+        ETHERTYPE_VLAN,
+        ETHERTYPE_QINQ,
+        ETHERTYPE_QINQ_LEGACY
+    },
+    errors::errors::{
+        Result,
+        ParseError
+    },
 };
 
+// Taille d'un tag VLAN (TPID + TCI)
+const VLAN_TAG_LEN: usize = 4;
+
+/// Indique si un ethertype annonce un tag VLAN (802.1Q, 802.1ad ou QinQ pre-standard)
+pub fn is_vlan_tpid(ethertype: u16) -> bool {
+    matches!(ethertype, ETHERTYPE_VLAN | ETHERTYPE_QINQ | ETHERTYPE_QINQ_LEGACY)
+}
+
+impl EthernetHeader {
+
+    /// Taille de l'header : 14 octets plus 4 par tag VLAN
+    pub fn header_len(&self) -> usize {
+        14 + VLAN_TAG_LEN * self.vlan_tags.len()
+    }
+
+    /// VLAN ID du tag le plus interne (celui du reseau du paquet)
+    pub fn vlan_id(&self) -> Option<u16> {
+        self.vlan_tags.last().map(|tag| tag.vid)
+    }
+}
+
+impl VlanTag {
+
+    /// Tag 802.1Q (TPID 0x8100), PCP 0, DEI a 0
+    pub fn new(vid: u16) -> Self {
+        Self {
+            tpid: ETHERTYPE_VLAN,
+            pcp: 0,
+            dei: false,
+            vid,
+        }
+    }
+
+    /// Tag de service 802.1ad (TPID 0x88A8), tag externe d'une trame QinQ
+    pub fn service(vid: u16) -> Self {
+        Self {
+            tpid: ETHERTYPE_QINQ,
+            ..Self::new(vid)
+        }
+    }
+
+    /// Priorite 802.1p (0 a 7)
+    pub fn with_pcp(mut self, pcp: u8) -> Self {
+        self.pcp = pcp;
+        self
+    }
+
+    /// Drop Eligible Indicator
+    pub fn with_dei(mut self, dei: bool) -> Self {
+        self.dei = dei;
+        self
+    }
+
+    /// Tag Control Information : PCP | DEI | VID
+    pub fn tci(&self) -> Result<u16> {
+        if self.pcp > 7 {
+            return Err(
+                ParseError::InvalidFormat("VLAN PCP out of range (0-7)")
+            );
+        }
+        if self.vid > 0x0FFF {
+            return Err(
+                ParseError::InvalidFormat("VLAN ID out of range (0-4095)")
+            );
+        }
+        Ok(((self.pcp as u16) << 13) | ((self.dei as u16) << 12) | self.vid)
+    }
+
+    /// Decode un tag depuis son TPID et son TCI
+    pub fn from_tci(tpid: u16, tci: u16) -> Self {
+        Self {
+            tpid,
+            pcp: (tci >> 13) as u8,
+            dei: tci & 0x1000 != 0,
+            vid: tci & 0x0FFF,
+        }
+    }
+}
+
 // Emballe un header Ethernet en un vecteur doctets
 pub fn pack_ethernet(
     header: &EthernetHeader, 
@@ -19,7 +110,7 @@ Result<
     > 
 {
     let total_len 
-        = 14 + payload.len();
+        = header.header_len() + payload.len();
     let mut packet 
         = vec![
             0u8; total_len
@@ -42,6 +133,23 @@ Result<
         &header.src_mac
     );
 
+    for tag in &header.vlan_tags {
+        if !is_vlan_tpid(tag.tpid) {
+            return Err(
+                ParseError::InvalidFormat("Invalid VLAN TPID")
+            );
+        }
+        let tag_bytes = convert_n_to_bytes(
+            ((tag.tpid as u64) << 16) | tag.tci()? as u64,
+            4
+        )?;
+        offset = push_bytes(
+            &mut packet,
+            offset,
+            &tag_bytes
+        );
+    }
+
     offset = push_bytes(
         &mut packet, 
         offset, 
@@ -58,3 +166,129 @@ Result<
         packet
     )
 }
+
+/// Decode un header Ethernet et retourne le reste de la trame
+/// Les tags VLAN (0x8100, 0x88A8, 0x9100) sont lus tant que l'ethertype en annonce un.
+pub fn unpack_ethernet(
+    frame: &[u8]
+) ->
+Result<
+    (EthernetHeader, &[u8])
+>
+{
+    if frame.len() < 14 {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "Ethernet",
+                needed: 14,
+                available: frame.len()
+            }
+        );
+    }
+
+    let mut dst_mac = [0u8; 6];
+    let mut src_mac = [0u8; 6];
+    push_bytes(&mut dst_mac, 0, &frame[0..6]);
+    push_bytes(&mut src_mac, 0, &frame[6..12]);
+    let mut ethertype = convert_bytes_to_n(&frame[12..14])? as u16;
+    let mut offset = 14;
+    let mut vlan_tags = Vec::new();
+    while is_vlan_tpid(ethertype) {
+        if frame.len() < offset + VLAN_TAG_LEN {
+            return Err(
+                ParseError::TruncatedPacket {
+                    layer: "VLAN",
+                    needed: offset + VLAN_TAG_LEN,
+                    available: frame.len()
+                }
+            );
+        }
+        let tci = convert_bytes_to_n(&frame[offset..offset + 2])? as u16;
+        vlan_tags.push(VlanTag::from_tci(ethertype, tci));
+        ethertype = convert_bytes_to_n(&frame[offset + 2..offset + 4])? as u16;
+        offset += VLAN_TAG_LEN;
+    }
+
+    Ok(
+        (
+            EthernetHeader {
+                dst_mac,
+                src_mac,
+                vlan_tags,
+                ethertype,
+            },
+            &frame[offset..]
+        )
+    )
+}
+
+/// Decode un header Linux cooked capture v1 (LINKTYPE_LINUX_SLL, 16 octets).
+/// L'adresse de la couche liaison devient src_mac, dst_mac reste a zero.
+pub fn unpack_linux_sll(
+    frame: &[u8]
+) ->
+Result<
+    (EthernetHeader, &[u8])
+>
+{
+    if frame.len() < 16 {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "Linux SLL",
+                needed: 16,
+                available: frame.len()
+            }
+        );
+    }
+
+    let mut src_mac = [0u8; 6];
+    push_bytes(&mut src_mac, 0, &frame[6..12]);
+    let ethertype = convert_bytes_to_n(&frame[14..16])? as u16;
+
+    Ok(
+        (
+            EthernetHeader {
+                dst_mac: [0u8; 6],
+                src_mac,
+                vlan_tags: Vec::new(),
+                ethertype,
+            },
+            &frame[16..]
+        )
+    )
+}
+
+/// Decode un header Linux cooked capture v2 (LINKTYPE_LINUX_SLL2, 20 octets)
+pub fn unpack_linux_sll2(
+    frame: &[u8]
+) ->
+Result<
+    (EthernetHeader, &[u8])
+>
+{
+    if frame.len() < 20 {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "Linux SLL2",
+                needed: 20,
+                available: frame.len()
+            }
+        );
+    }
+
+    let mut src_mac = [0u8; 6];
+    push_bytes(&mut src_mac, 0, &frame[12..18]);
+    let ethertype = convert_bytes_to_n(&frame[0..2])? as u16;
+
+    Ok(
+        (
+            EthernetHeader {
+                dst_mac: [0u8; 6],
+                src_mac,
+                vlan_tags: Vec::new(),
+                ethertype,
+            },
+            &frame[20..]
+        )
+    )
+}
@@ -0,0 +1,342 @@
+use crate::{
+    prelude::*,
+    packets::{
+        builder::{packet_assembler::PacketAssembler, packet_dissector::PacketDissector},
+        ethernet::{pack_ethernet, unpack_ethernet},
+        ip::{ipv4_header_checksum, pack_ipv4, unpack_ipv4},
+    },
+    structs::{
+        fragment::{FragmentBuffer, FragmentKey, FragmentMode, Ipv4Fragment, Ipv4Fragmenter, Ipv4Reassembler},
+        ip::{Ipv4Header, IPV4_FLAG_MF},
+        network_packet::NetworkPacket,
+        pcap::LinkType,
+    },
+    utils::clock::{default_clock, Clock},
+    errors::errors::{Result, ParseError},
+};
+
+// Taille maximale d'un datagramme IPv4
+const IPV4_MAX_LEN: usize = 65535;
+
+// Assemblage et decodage des fragments
+impl Ipv4Fragment {
+
+    /// Assemble la trame Ethernet + fragment IPv4
+    pub fn assemble(&self) -> Result<VecNoStd<u8>> {
+        pack_ethernet(&self.ethernet, &pack_ipv4(&self.ipv4, &self.payload)?)
+    }
+
+    /// Decode une trame Ethernet/IPv4 sans interpreter la couche 4
+    pub fn parse(frame: &[u8]) -> Result<Self> {
+        let (ethernet, ip_data) = unpack_ethernet(frame)?;
+        if ethernet.ethertype != 0x0800 {
+            return Err(ParseError::UnsupportedEtherType(ethernet.ethertype));
+        }
+        let (ipv4, payload) = unpack_ipv4(ip_data)?;
+        Ok(Self {
+            ethernet,
+            ipv4,
+            payload: payload.to_vec(),
+        })
+    }
+
+    /// Position du premier octet du fragment dans le datagramme d'origine
+    pub fn byte_offset(&self) -> usize {
+        self.ipv4.fragment_offset as usize * 8
+    }
+
+    /// Cle de reassemblage du fragment
+    pub fn key(&self) -> FragmentKey {
+        FragmentKey {
+            src_addr: self.ipv4.src_addr,
+            dst_addr: self.ipv4.dst_addr,
+            identification: self.ipv4.identification,
+            protocol: self.ipv4.protocol,
+        }
+    }
+}
+
+impl Ipv4Fragmenter {
+
+    /// Constructeur : decoupage normal pour une MTU IP (ex: 1500)
+    pub fn new(mtu: u16) -> Self {
+        Self {
+            mtu,
+            mode: FragmentMode::Normal,
+        }
+    }
+
+    /// Decoupage minuscule ou chevauchant pour tester les IDS
+    pub fn with_mode(mut self, mode: FragmentMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Decoupe un paquet IPv4 en fragments, dans l'ordre des offsets
+    /// Le premier fragment garde toutes les options, les suivants celles dont le bit
+    /// "copied" est positionne. Un paquet qui tient dans la MTU n'est decoupe qu'en
+    /// mode `Tiny` ou `Overlap`. Un paquet deja fragmente garde son offset de base
+    /// et son bit MF sur le dernier fragment.
+    pub fn fragment(&self, packet: &NetworkPacket) -> Result<VecNoStd<Ipv4Fragment>> {
+        let ipv4 = packet.ipv4().ok_or(ParseError::InvalidFormat("IPv4 fragmentation requires an IPv4 packet"))?;
+        let datagram = PacketAssembler::new().assemble_ipv4(packet)?;
+        let data = &datagram[ipv4.header_len()..];
+        if self.mode == FragmentMode::Normal && datagram.len() <= self.mtu as usize {
+            return Ok(vec![self.build_fragment(packet, ipv4, 0, data, false)?]);
+        }
+        if ipv4.dont_fragment() {
+            return Err(ParseError::InvalidFormat("Cannot fragment a packet with DF set"));
+        }
+
+        let first_header = ipv4.clone();
+        let mut next_header = ipv4.clone();
+        next_header.options = copied_options(ipv4.options.as_deref().unwrap_or(&[]));
+        next_header.ihl = 5 + (next_header.options.as_ref().map_or(0, |o| o.len()) / 4) as u8;
+
+        let overlap = match self.mode {
+            FragmentMode::Overlap { bytes } => {
+                if bytes == 0 || !bytes.is_multiple_of(8) {
+                    return Err(ParseError::InvalidFormat("Fragment overlap must be a non-zero multiple of 8"));
+                }
+                bytes as usize
+            }
+            _ => 0,
+        };
+
+        let mut fragments = VecNoStd::new();
+        let mut start = 0;
+        loop {
+            let header = if start == 0 { &first_header } else { &next_header };
+            let capacity = (self.mtu as usize).saturating_sub(header.header_len()) / 8 * 8;
+            let size = match self.mode {
+                FragmentMode::Tiny if start == 0 => 8,
+                _ => capacity,
+            };
+            if size == 0 || size <= overlap {
+                return Err(ParseError::InvalidFormat("MTU too small for fragmentation"));
+            }
+            let end = (start + size).min(data.len());
+            let last = end == data.len();
+            fragments.push(self.build_fragment(packet, header, start, &data[start..end], !last)?);
+            if last {
+                break;
+            }
+            // Le fragment suivant reprend les `overlap` derniers octets
+            start = end - overlap;
+        }
+        Ok(fragments)
+    }
+
+    // Fragment couvrant `data` a partir de l'octet `start` du payload d'origine
+    fn build_fragment(
+        &self,
+        packet: &NetworkPacket,
+        header: &Ipv4Header,
+        start: usize,
+        data: &[u8],
+        more_fragments: bool,
+    ) -> Result<Ipv4Fragment> {
+        let offset = header.fragment_offset as usize + start / 8;
+        if offset > 0x1FFF {
+            return Err(ParseError::ValueTooLarge { value: offset as u64, size: 2 });
+        }
+        let mut ipv4 = header.clone();
+        ipv4.total_length = (header.header_len() + data.len()) as u16;
+        ipv4.fragment_offset = offset as u16;
+        if more_fragments {
+            ipv4.flags |= IPV4_FLAG_MF;
+        }
+        ipv4.header_checksum = ipv4_header_checksum(&ipv4)?;
+        Ok(Ipv4Fragment {
+            ethernet: packet.ethernet.clone(),
+            ipv4,
+            payload: data.to_vec(),
+        })
+    }
+}
+
+// Options a recopier dans les fragments suivants (bit "copied"), completees a 4 octets
+fn copied_options(options: &[u8]) -> Option<VecNoStd<u8>> {
+    let mut copied = VecNoStd::new();
+    let mut i = 0;
+    while i < options.len() {
+        match options[i] {
+            0 => break,
+            1 => i += 1,
+            option_type => {
+                let len = options.get(i + 1).copied().unwrap_or(0) as usize;
+                if len < 2 || i + len > options.len() {
+                    break;
+                }
+                if option_type & 0x80 != 0 {
+                    copied.extend_from_slice(&options[i..i + len]);
+                }
+                i += len;
+            }
+        }
+    }
+    if copied.is_empty() {
+        return None;
+    }
+    while !copied.len().is_multiple_of(4) {
+        copied.push(0);
+    }
+    Some(copied)
+}
+
+impl Ipv4Reassembler {
+
+    /// Constructeur : delai de 30 s (RFC 791 recommande 15 s au minimum), horloge par defaut
+    pub fn new() -> Self {
+        Self {
+            timeout_ms: 30_000,
+            clock: default_clock(),
+            buffers: BTreeMap::new(),
+        }
+    }
+
+    /// Delai d'abandon d'un datagramme incomplet
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Horloge utilisee par `push`
+    pub fn with_clock(mut self, clock: Box<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Nombre de datagrammes en attente de fragments
+    pub fn pending(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Ajoute un fragment recu maintenant
+    pub fn push(&mut self, fragment: Ipv4Fragment) -> Result<Option<NetworkPacket>> {
+        let now_ns = self.clock.now_ns();
+        self.push_at(fragment, now_ns)
+    }
+
+    /// Ajoute un fragment recu a `now_ns` (ex: timestamp d'une capture)
+    /// Retourne le paquet reconstruit des qu'il est complet. En cas de chevauchement
+    /// les octets deja recus sont conserves. Un datagramme non fragmente est
+    /// retourne tel quel.
+    pub fn push_at(&mut self, fragment: Ipv4Fragment, now_ns: u64) -> Result<Option<NetworkPacket>> {
+        self.expire(now_ns);
+        if !fragment.ipv4.is_fragment() {
+            return rebuild(&fragment, &fragment.payload).map(Some);
+        }
+
+        let start = fragment.byte_offset();
+        let end = start + fragment.payload.len();
+        if end > IPV4_MAX_LEN - fragment.ipv4.header_len() {
+            return Err(ParseError::InvalidFormat("Fragment exceeds the maximum IPv4 datagram size"));
+        }
+        let key = fragment.key();
+        let buffer = self.buffers.entry(key).or_insert_with(|| FragmentBuffer {
+            first_seen_ns: now_ns,
+            first: None,
+            data: VecNoStd::new(),
+            ranges: VecNoStd::new(),
+            total_len: None,
+        });
+        if !fragment.ipv4.more_fragments() {
+            buffer.total_len = Some(end);
+        }
+        buffer.insert(start, &fragment.payload);
+        if start == 0 && buffer.first.is_none() {
+            buffer.first = Some(Ipv4Fragment {
+                payload: VecNoStd::new(),
+                ..fragment
+            });
+        }
+
+        if !buffer.is_complete() {
+            return Ok(None);
+        }
+        let buffer = self.buffers.remove(&key).ok_or(ParseError::InvalidFormat("Missing fragment buffer"))?;
+        let total_len = buffer.total_len.unwrap_or(0);
+        let first = buffer.first.ok_or(ParseError::InvalidFormat("Missing first fragment"))?;
+        rebuild(&first, &buffer.data[..total_len]).map(Some)
+    }
+
+    /// Abandonne les datagrammes incomplets dont le delai est depasse a `now_ns`
+    /// Retourne les cles abandonnees.
+    pub fn expire(&mut self, now_ns: u64) -> VecNoStd<FragmentKey> {
+        let timeout_ns = self.timeout_ms.saturating_mul(1_000_000);
+        let expired: VecNoStd<FragmentKey> = self.buffers
+            .iter()
+            .filter(|(_, buffer)| now_ns.saturating_sub(buffer.first_seen_ns) > timeout_ns)
+            .map(|(key, _)| *key)
+            .collect();
+        for key in &expired {
+            self.buffers.remove(key);
+        }
+        expired
+    }
+}
+
+impl Default for Ipv4Reassembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FragmentBuffer {
+
+    // Copie les octets de `data` non encore recus puis fusionne l'intervalle
+    fn insert(&mut self, start: usize, data: &[u8]) {
+        let end = start + data.len();
+        if self.data.len() < end {
+            self.data.resize(end, 0);
+        }
+        let mut position = start;
+        for &(range_start, range_end) in &self.ranges {
+            if range_end <= position {
+                continue;
+            }
+            if range_start >= end {
+                break;
+            }
+            if range_start > position {
+                self.data[position..range_start].copy_from_slice(&data[position - start..range_start - start]);
+            }
+            position = position.max(range_end);
+        }
+        if position < end {
+            self.data[position..end].copy_from_slice(&data[position - start..]);
+        }
+
+        self.ranges.push((start, end));
+        self.ranges.sort_unstable();
+        let mut merged: VecNoStd<(usize, usize)> = VecNoStd::new();
+        for &(range_start, range_end) in &self.ranges {
+            match merged.last_mut() {
+                Some(last) if range_start <= last.1 => last.1 = last.1.max(range_end),
+                _ => merged.push((range_start, range_end)),
+            }
+        }
+        self.ranges = merged;
+    }
+
+    // Tous les octets de 0 a la fin du dernier fragment sont recus
+    fn is_complete(&self) -> bool {
+        match self.total_len {
+            Some(total_len) => self.first.is_some() && self.ranges.first() == Some(&(0, total_len)),
+            None => false,
+        }
+    }
+}
+
+// Reconstruit le paquet a partir de l'header du premier fragment et du payload complet
+fn rebuild(first: &Ipv4Fragment, payload: &[u8]) -> Result<NetworkPacket> {
+    let mut ipv4 = first.ipv4.clone();
+    ipv4.flags &= !IPV4_FLAG_MF;
+    ipv4.fragment_offset = 0;
+    ipv4.total_length = (ipv4.header_len() + payload.len()) as u16;
+    ipv4.header_checksum = ipv4_header_checksum(&ipv4)?;
+    let mut packet = PacketDissector::new().dissect_link_frame(LinkType::Raw, &pack_ipv4(&ipv4, payload)?)?;
+    packet.ethernet = first.ethernet.clone();
+    Ok(packet)
+}
@@ -2,13 +2,318 @@ use alloc::vec;
 use alloc::vec::Vec;
 use crate::{
     utils::{
-        convert_bytes::convert_n_to_bytes,
+        convert_bytes::{
+            convert_n_to_bytes,
+            convert_bytes_to_n
+        },
         push_bytes::push_bytes,
+        checksum::internet_checksum,
+    },
+    structs::ip::{
+        Ipv4Header,
+        Ipv4Option,
+        Ipv4TimestampEntry,
+        IPV4_FLAG_DF,
+        IPV4_FLAG_MF,
+        IPV4_OPT_END,
+        IPV4_OPT_NOP,
+        IPV4_OPT_RECORD_ROUTE,
+        IPV4_OPT_TIMESTAMP,
+        IPV4_OPT_LOOSE_SOURCE_ROUTE,
+        IPV4_OPT_STRICT_SOURCE_ROUTE,
+        IPV4_OPT_ROUTER_ALERT,
+    },
+    errors::errors::{
+        Result,
+        ParseError
     },
-    structs::ip::Ipv4Header,
-    errors::errors::Result,
 };
 
+impl Ipv4Header {
+
+    /// Taille de l'header en octets (ihl * 4)
+    pub fn header_len(&self) -> usize {
+        self.ihl as usize * 4
+    }
+
+    /// Bit Don't Fragment
+    pub fn dont_fragment(&self) -> bool {
+        self.flags & IPV4_FLAG_DF != 0
+    }
+
+    /// Bit More Fragments
+    pub fn more_fragments(&self) -> bool {
+        self.flags & IPV4_FLAG_MF != 0
+    }
+
+    /// Vrai pour tout fragment d'un datagramme decoupe (MF ou offset non nul)
+    pub fn is_fragment(&self) -> bool {
+        self.more_fragments() || self.fragment_offset != 0
+    }
+
+    /// Remplace les options et ajuste l'ihl (padding a 4 octets compris)
+    /// total_length et le checksum ne sont pas recalcules.
+    pub fn set_options(&mut self, options: &[Ipv4Option]) -> Result<()> {
+        let bytes = pack_ipv4_options(options)?;
+        self.ihl = 5 + (bytes.len() / 4) as u8;
+        self.options = if bytes.is_empty() { None } else { Some(bytes) };
+        Ok(())
+    }
+
+    /// Options decodees de l'header
+    pub fn typed_options(&self) -> Result<Vec<Ipv4Option>> {
+        unpack_ipv4_options(self.options.as_deref().unwrap_or(&[]))
+    }
+}
+
+// Taille maximale des options (ihl 15)
+const IPV4_OPTIONS_MAX_LEN: usize = 40;
+
+impl Ipv4Option {
+
+    /// Record Route avec `slots` emplacements vides
+    pub fn record_route(slots: usize) -> Self {
+        Ipv4Option::RecordRoute {
+            pointer: 4,
+            route: vec![[0u8; 4]; slots],
+        }
+    }
+
+    /// Timestamp (flag 0) avec `slots` horodatages vides
+    pub fn timestamp(slots: usize) -> Self {
+        Ipv4Option::Timestamp {
+            pointer: 5,
+            overflow: 0,
+            flag: 0,
+            entries: vec![Ipv4TimestampEntry { address: None, timestamp: 0 }; slots],
+        }
+    }
+
+    /// Loose Source Route par les adresses donnees
+    pub fn loose_source_route(route: Vec<[u8; 4]>) -> Self {
+        Ipv4Option::LooseSourceRoute { pointer: 4, route }
+    }
+
+    /// Strict Source Route par les adresses donnees
+    pub fn strict_source_route(route: Vec<[u8; 4]>) -> Self {
+        Ipv4Option::StrictSourceRoute { pointer: 4, route }
+    }
+
+    /// Router Alert (valeur 0 : le routeur doit examiner le paquet)
+    pub fn router_alert() -> Self {
+        Ipv4Option::RouterAlert(0)
+    }
+
+    /// Type de l'option
+    pub fn option_type(&self) -> u8 {
+        match self {
+            Ipv4Option::EndOfList => IPV4_OPT_END,
+            Ipv4Option::NoOperation => IPV4_OPT_NOP,
+            Ipv4Option::RecordRoute { .. } => IPV4_OPT_RECORD_ROUTE,
+            Ipv4Option::Timestamp { .. } => IPV4_OPT_TIMESTAMP,
+            Ipv4Option::LooseSourceRoute { .. } => IPV4_OPT_LOOSE_SOURCE_ROUTE,
+            Ipv4Option::StrictSourceRoute { .. } => IPV4_OPT_STRICT_SOURCE_ROUTE,
+            Ipv4Option::RouterAlert(_) => IPV4_OPT_ROUTER_ALERT,
+            Ipv4Option::Unknown { option_type, .. } => *option_type,
+        }
+    }
+}
+
+/// Emballe une liste d'options IPv4, completee par des zeros (End of List) a un multiple de 4
+/// Au-dela de 40 octets les options ne tiennent pas dans l'header.
+pub fn pack_ipv4_options(
+    options: &[Ipv4Option]
+) ->
+Result<
+    Vec<u8>
+>
+{
+    let mut bytes = Vec::new();
+    for option in options {
+        let option_type = option.option_type();
+        let (pointer, body) = match option {
+            Ipv4Option::EndOfList | Ipv4Option::NoOperation => {
+                bytes.push(option_type);
+                continue;
+            }
+            Ipv4Option::RecordRoute { pointer, route }
+            | Ipv4Option::LooseSourceRoute { pointer, route }
+            | Ipv4Option::StrictSourceRoute { pointer, route } => (
+                Some(*pointer),
+                route.concat()
+            ),
+            Ipv4Option::Timestamp { pointer, overflow, flag, entries } => {
+                let mut body = vec![(overflow << 4) | (flag & 0x0F)];
+                for entry in entries {
+                    if let Some(address) = entry.address {
+                        body.extend_from_slice(&address);
+                    }
+                    body.extend_from_slice(&entry.timestamp.to_be_bytes());
+                }
+                (Some(*pointer), body)
+            }
+            Ipv4Option::RouterAlert(value) => (None, value.to_be_bytes().to_vec()),
+            Ipv4Option::Unknown { data, .. } => (None, data.clone()),
+        };
+        let length = 2 + pointer.is_some() as usize + body.len();
+        if length > IPV4_OPTIONS_MAX_LEN {
+            return Err(
+                ParseError::InvalidHeaderLength {
+                    layer: "IPv4 options",
+                    length
+                }
+            );
+        }
+        bytes.push(option_type);
+        bytes.push(length as u8);
+        bytes.extend(pointer);
+        bytes.extend_from_slice(&body);
+    }
+
+    while !bytes.len().is_multiple_of(4) {
+        bytes.push(IPV4_OPT_END);
+    }
+    if bytes.len() > IPV4_OPTIONS_MAX_LEN {
+        return Err(
+            ParseError::InvalidHeaderLength {
+                layer: "IPv4 options",
+                length: bytes.len()
+            }
+        );
+    }
+    Ok(bytes)
+}
+
+/// Decode les options IPv4 jusqu'a End of List (le padding qui suit est ignore)
+pub fn unpack_ipv4_options(
+    data: &[u8]
+) ->
+Result<
+    Vec<Ipv4Option>
+>
+{
+    let mut options = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let option_type = data[offset];
+        match option_type {
+            IPV4_OPT_END => break,
+            IPV4_OPT_NOP => {
+                options.push(Ipv4Option::NoOperation);
+                offset += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        if offset + 2 > data.len() {
+            return Err(
+                ParseError::TruncatedPacket {
+                    layer: "IPv4 option",
+                    needed: offset + 2,
+                    available: data.len()
+                }
+            );
+        }
+        let length = data[offset + 1] as usize;
+        if length < 2 {
+            return Err(
+                ParseError::InvalidHeaderLength {
+                    layer: "IPv4 option",
+                    length
+                }
+            );
+        }
+        if offset + length > data.len() {
+            return Err(
+                ParseError::TruncatedPacket {
+                    layer: "IPv4 option",
+                    needed: offset + length,
+                    available: data.len()
+                }
+            );
+        }
+        let body = &data[offset + 2..offset + length];
+        let invalid = ParseError::InvalidHeaderLength {
+            layer: "IPv4 option",
+            length
+        };
+
+        let option = match option_type {
+            IPV4_OPT_RECORD_ROUTE | IPV4_OPT_LOOSE_SOURCE_ROUTE | IPV4_OPT_STRICT_SOURCE_ROUTE => {
+                let Some((&pointer, addresses)) = body.split_first() else {
+                    return Err(invalid);
+                };
+                if !addresses.len().is_multiple_of(4) {
+                    return Err(invalid);
+                }
+                let route = addresses
+                    .chunks(4)
+                    .map(|chunk| [chunk[0], chunk[1], chunk[2], chunk[3]])
+                    .collect();
+                match option_type {
+                    IPV4_OPT_RECORD_ROUTE => Ipv4Option::RecordRoute { pointer, route },
+                    IPV4_OPT_LOOSE_SOURCE_ROUTE => Ipv4Option::LooseSourceRoute { pointer, route },
+                    _ => Ipv4Option::StrictSourceRoute { pointer, route },
+                }
+            }
+            IPV4_OPT_TIMESTAMP => {
+                if body.len() < 2 {
+                    return Err(invalid);
+                }
+                let flag = body[1] & 0x0F;
+                let entry_len = if flag == 0 { 4 } else { 8 };
+                if !(body.len() - 2).is_multiple_of(entry_len) {
+                    return Err(invalid);
+                }
+                let entries = body[2..]
+                    .chunks(entry_len)
+                    .map(|chunk| {
+                        let (address, timestamp) = chunk.split_at(entry_len - 4);
+                        Ipv4TimestampEntry {
+                            address: (entry_len == 8).then(|| [address[0], address[1], address[2], address[3]]),
+                            timestamp: u32::from_be_bytes([timestamp[0], timestamp[1], timestamp[2], timestamp[3]]),
+                        }
+                    })
+                    .collect();
+                Ipv4Option::Timestamp {
+                    pointer: body[0],
+                    overflow: body[1] >> 4,
+                    flag,
+                    entries,
+                }
+            }
+            IPV4_OPT_ROUTER_ALERT if body.len() == 2 => {
+                Ipv4Option::RouterAlert(u16::from_be_bytes([body[0], body[1]]))
+            }
+            _ => Ipv4Option::Unknown {
+                option_type,
+                data: body.to_vec(),
+            },
+        };
+        options.push(option);
+        offset += length;
+    }
+    Ok(options)
+}
+
+/// Checksum de l'header IPv4 complet (options comprises), champ checksum a zero
+pub fn ipv4_header_checksum(
+    header: &Ipv4Header
+) ->
+Result<
+    u16
+>
+{
+    let mut temp_header = header.clone();
+    temp_header.header_checksum = 0;
+    Ok(
+        internet_checksum(
+            &pack_ipv4(&temp_header, &[])?
+        )
+    )
+}
+
 /// Emballe un header IPv4 en un vecteur doctets
 pub fn pack_ipv4(
     header: &Ipv4Header, 
@@ -78,3 +383,137 @@ Result<
 
     Ok(packet)
 }
+
+/// Decode un header IPv4 (options comprises) et retourne le payload L4.
+/// Le payload est borne par total_length, ce qui retire le padding Ethernet.
+pub fn unpack_ipv4(
+    data: &[u8]
+) ->
+Result<
+    (Ipv4Header, &[u8])
+>
+{
+    decode_ipv4(data, false)
+}
+
+/// Decode le datagramme d'origine cite dans un message d'erreur ICMP.
+/// Seuls l'header et les premiers octets du payload sont cites :
+/// le payload est borne par les octets disponibles.
+pub fn unpack_quoted_ipv4(
+    data: &[u8]
+) ->
+Result<
+    (Ipv4Header, &[u8])
+>
+{
+    decode_ipv4(data, true)
+}
+
+// Decodage commun, `quoted` tolere un datagramme tronque
+fn decode_ipv4(
+    data: &[u8],
+    quoted: bool
+) ->
+Result<
+    (Ipv4Header, &[u8])
+>
+{
+    if data.len() < 20 {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "IPv4",
+                needed: 20,
+                available: data.len()
+            }
+        );
+    }
+
+    let version = data[0] >> 4;
+    if version != 4 {
+        return Err(
+            ParseError::InvalidVersion(
+                version
+            )
+        );
+    }
+
+    let ihl = data[0] & 0x0F;
+    let header_len = (ihl as usize) * 4;
+    if ihl < 5 {
+        return Err(
+            ParseError::InvalidHeaderLength {
+                layer: "IPv4",
+                length: header_len
+            }
+        );
+    }
+    if data.len() < header_len {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "IPv4",
+                needed: header_len,
+                available: data.len()
+            }
+        );
+    }
+
+    let total_length = convert_bytes_to_n(&data[2..4])? as u16;
+    if (total_length as usize) < header_len {
+        return Err(
+            ParseError::LengthMismatch {
+                layer: "IPv4",
+                declared: total_length as usize,
+                actual: header_len
+            }
+        );
+    }
+    if !quoted && (total_length as usize) > data.len() {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "IPv4",
+                needed: total_length as usize,
+                available: data.len()
+            }
+        );
+    }
+
+    let flags_fragment = convert_bytes_to_n(&data[6..8])? as u16;
+
+    let mut src_addr = [0u8; 4];
+    let mut dst_addr = [0u8; 4];
+    push_bytes(&mut src_addr, 0, &data[12..16]);
+    push_bytes(&mut dst_addr, 0, &data[16..20]);
+
+    let options = if header_len > 20 {
+        Some(
+            data[20..header_len].to_vec()
+        )
+    } else {
+        None
+    };
+
+    let header = Ipv4Header {
+        version,
+        ihl,
+        dscp: data[1],
+        total_length,
+        identification: convert_bytes_to_n(&data[4..6])? as u16,
+        flags: (
+            flags_fragment >> 13
+        ) as u8,
+        fragment_offset: flags_fragment & 0x1FFF,
+        ttl: data[8],
+        protocol: data[9],
+        header_checksum: convert_bytes_to_n(&data[10..12])? as u16,
+        src_addr,
+        dst_addr,
+        options,
+    };
+
+    Ok(
+        (
+            header,
+            &data[header_len..(total_length as usize).min(data.len())]
+        )
+    )
+}
@@ -0,0 +1,284 @@
+use alloc::vec::Vec;
+use crate::{
+    utils::push_bytes::push_bytes,
+    structs::ipv6::Ipv6Addr,
+    structs::ip::{
+        Ipv6Header,
+        Ipv6ExtensionHeader,
+        IPV6_HOP_BY_HOP,
+        IPV6_ROUTING,
+        IPV6_FRAGMENT,
+        IPV6_AUTH,
+        IPV6_DEST_OPTIONS,
+    },
+    errors::errors::{
+        Result,
+        ParseError
+    },
+};
+
+/// Taille de l'header IPv6 fixe
+pub const IPV6_HEADER_LEN: usize = 40;
+
+/// Groupes multicast de lien : tous les noeuds (ff02::1) et tous les routeurs (ff02::2)
+pub const ALL_NODES_MULTICAST: Ipv6Addr = Ipv6Addr {
+    octets: [0xFF, 0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01],
+};
+pub const ALL_ROUTERS_MULTICAST: Ipv6Addr = Ipv6Addr {
+    octets: [0xFF, 0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x02],
+};
+
+/// Adresse multicast (ff00::/8)
+pub fn is_multicast(ip: &Ipv6Addr) -> bool {
+    ip.octets[0] == 0xFF
+}
+
+/// Adresse multicast sollicited-node d'une cible (ff02::1:ffXX:XXXX, RFC 4291)
+pub fn solicited_node_multicast(ip: &Ipv6Addr) -> Ipv6Addr {
+    let mut octets = [0xFF, 0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0xFF, 0, 0, 0];
+    octets[13..].copy_from_slice(&ip.octets[13..]);
+    Ipv6Addr { octets }
+}
+
+/// MAC Ethernet d'un groupe multicast IPv6 : 33:33 suivi des 32 bits de poids faible (RFC 2464)
+pub fn multicast_mac(ip: &Ipv6Addr) -> [u8; 6] {
+    [0x33, 0x33, ip.octets[12], ip.octets[13], ip.octets[14], ip.octets[15]]
+}
+
+/// Adresse link-local fe80::/64 derivee d'une MAC (EUI-64 modifie, RFC 4291)
+pub fn link_local_from_mac(mac: &[u8; 6]) -> Ipv6Addr {
+    let mut octets = [0u8; 16];
+    octets[0] = 0xFE;
+    octets[1] = 0x80;
+    octets[8..11].copy_from_slice(&[mac[0] ^ 0x02, mac[1], mac[2]]);
+    octets[11] = 0xFF;
+    octets[12] = 0xFE;
+    octets[13..].copy_from_slice(&mac[3..]);
+    Ipv6Addr { octets }
+}
+
+/// Indique si un next_header designe une extension IPv6
+pub fn is_extension_header(next_header: u8) -> bool {
+    matches!(
+        next_header,
+        IPV6_HOP_BY_HOP | IPV6_ROUTING | IPV6_FRAGMENT | IPV6_AUTH | IPV6_DEST_OPTIONS
+    )
+}
+
+/// Emballe un header d'extension IPv6
+/// Le champ longueur est deduit de `data` : unites de 8 octets (4 pour AH),
+/// octet reserve pour l'extension Fragment (taille fixe de 8 octets).
+pub fn pack_ipv6_extension(
+    extension: &Ipv6ExtensionHeader
+) ->
+Result<
+    Vec<u8>
+>
+{
+    let total_len = 2 + extension.data.len();
+    let length_field = match extension.header_type {
+        IPV6_FRAGMENT if total_len == 8 => 0,
+        IPV6_AUTH if total_len.is_multiple_of(4) && total_len >= 8 => total_len / 4 - 2,
+        IPV6_HOP_BY_HOP | IPV6_ROUTING | IPV6_DEST_OPTIONS
+            if total_len.is_multiple_of(8) && total_len <= 2048 => total_len / 8 - 1,
+        IPV6_FRAGMENT | IPV6_AUTH | IPV6_HOP_BY_HOP | IPV6_ROUTING | IPV6_DEST_OPTIONS => {
+            return Err(
+                ParseError::InvalidHeaderLength {
+                    layer: "IPv6 extension",
+                    length: total_len
+                }
+            );
+        }
+        other => return Err(ParseError::UnsupportedProtocol(other)),
+    };
+
+    let mut packet = Vec::with_capacity(total_len);
+    packet.push(extension.next_header);
+    packet.push(length_field as u8);
+    packet.extend_from_slice(&extension.data);
+    Ok(packet)
+}
+
+/// Emballe un header IPv6 et sa chaine d'extensions en un vecteur doctets
+pub fn pack_ipv6(
+    header: &Ipv6Header,
+    payload: &[u8]
+) ->
+Result<
+    Vec<u8>
+>
+{
+    let mut packet = Vec::with_capacity(IPV6_HEADER_LEN + payload.len());
+    let version_class_flow = ((header.version as u32) << 28)
+        | ((header.traffic_class as u32) << 20)
+        | (header.flow_label & 0x000F_FFFF);
+    packet.extend_from_slice(&version_class_flow.to_be_bytes());
+    packet.extend_from_slice(&header.payload_length.to_be_bytes());
+    packet.push(header.next_header);
+    packet.push(header.hop_limit);
+    packet.extend_from_slice(&header.src_addr);
+    packet.extend_from_slice(&header.dst_addr);
+
+    for extension in &header.extension_headers {
+        packet.extend_from_slice(&pack_ipv6_extension(extension)?);
+    }
+    packet.extend_from_slice(payload);
+
+    Ok(packet)
+}
+
+/// Decode un header IPv6, parcourt la chaine d'extensions et retourne le payload L4.
+/// Le payload est borne par payload_length, ce qui retire le padding Ethernet.
+pub fn unpack_ipv6(
+    data: &[u8]
+) ->
+Result<
+    (Ipv6Header, &[u8])
+>
+{
+    if data.len() < IPV6_HEADER_LEN {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "IPv6",
+                needed: IPV6_HEADER_LEN,
+                available: data.len()
+            }
+        );
+    }
+
+    let version_class_flow = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+    let version = (version_class_flow >> 28) as u8;
+    if version != 6 {
+        return Err(
+            ParseError::InvalidVersion(
+                version
+            )
+        );
+    }
+
+    let payload_length = u16::from_be_bytes([data[4], data[5]]);
+    let end = IPV6_HEADER_LEN + payload_length as usize;
+    if end > data.len() {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "IPv6",
+                needed: end,
+                available: data.len()
+            }
+        );
+    }
+
+    let mut src_addr = [0u8; 16];
+    let mut dst_addr = [0u8; 16];
+    push_bytes(&mut src_addr, 0, &data[8..24]);
+    push_bytes(&mut dst_addr, 0, &data[24..40]);
+
+    let mut extension_headers = Vec::new();
+    let mut header_type = data[6];
+    let mut offset = IPV6_HEADER_LEN;
+    while is_extension_header(header_type) {
+        if offset + 8 > end {
+            return Err(
+                ParseError::TruncatedPacket {
+                    layer: "IPv6 extension",
+                    needed: offset + 8,
+                    available: end
+                }
+            );
+        }
+        let length_field = data[offset + 1] as usize;
+        let total_len = match header_type {
+            IPV6_FRAGMENT => 8,
+            IPV6_AUTH => (length_field + 2) * 4,
+            _ => (length_field + 1) * 8,
+        };
+        if offset + total_len > end {
+            return Err(
+                ParseError::TruncatedPacket {
+                    layer: "IPv6 extension",
+                    needed: offset + total_len,
+                    available: end
+                }
+            );
+        }
+        let next_header = data[offset];
+        extension_headers.push(Ipv6ExtensionHeader {
+            header_type,
+            next_header,
+            data: data[offset + 2..offset + total_len].to_vec(),
+        });
+        header_type = next_header;
+        offset += total_len;
+    }
+
+    let header = Ipv6Header {
+        version,
+        traffic_class: (version_class_flow >> 20) as u8,
+        flow_label: version_class_flow & 0x000F_FFFF,
+        payload_length,
+        next_header: data[6],
+        hop_limit: data[7],
+        src_addr,
+        dst_addr,
+        extension_headers,
+    };
+
+    Ok(
+        (
+            header,
+            &data[offset..end]
+        )
+    )
+}
+
+impl Ipv6Header {
+    /// Protocole L4 transporte : next_header de la derniere extension
+    pub fn upper_layer_protocol(&self) -> u8 {
+        self.extension_headers
+            .last()
+            .map_or(self.next_header, |extension| extension.next_header)
+    }
+
+    /// Taille de la chaine d'extensions en octets
+    pub fn extensions_len(&self) -> usize {
+        self.extension_headers
+            .iter()
+            .map(|extension| 2 + extension.data.len())
+            .sum()
+    }
+}
+
+impl Ipv6ExtensionHeader {
+    /// Extension Fragment (8 octets) : offset en unites de 8 octets, drapeau M et identifiant
+    pub fn fragment(offset: u16, more_fragments: bool, identification: u32) -> Self {
+        let offset_flags = (offset << 3) | more_fragments as u16;
+        let mut data = Vec::with_capacity(6);
+        data.extend_from_slice(&offset_flags.to_be_bytes());
+        data.extend_from_slice(&identification.to_be_bytes());
+        Self {
+            header_type: IPV6_FRAGMENT,
+            next_header: 0,
+            data,
+        }
+    }
+
+    /// Extension d'options (Hop-by-Hop ou Destination), completee par du padding PadN
+    pub fn options(header_type: u8, options: &[u8]) -> Self {
+        let mut data = options.to_vec();
+        let padding = (8 - (2 + data.len()) % 8) % 8;
+        match padding {
+            0 => {}
+            1 => data.push(0),
+            n => {
+                data.push(1);
+                data.push((n - 2) as u8);
+                data.resize(data.len() + n - 2, 0);
+            }
+        }
+        Self {
+            header_type,
+            next_header: 0,
+            data,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::{
+    utils::{
+        convert_bytes::{
+            convert_n_to_bytes,
+            convert_bytes_to_n
+        },
+        push_bytes::push_bytes
+    },
+    packets::ip::unpack_quoted_ipv4,
+    structs::{
+        icmp::{
+            IcmpHeader,
+            ICMP_DEST_UNREACHABLE,
+            ICMP_TIME_EXCEEDED
+        },
+        ip::Ipv4Header,
+    },
+    errors::errors::{
+        Result,
+        ParseError
+    }
+};
+
+/// Emballe un header ICMP en un vecteur doctets
+pub fn pack_icmp(header: &IcmpHeader) -> Result<Vec<u8>> {
+    let payload: &[u8] = match &header.payload {
+        Some(p) => p,
+        None => &[],
+    };
+
+    let mut packet = vec![
+        0u8;
+        8 + payload.len()
+    ];
+
+    let fields = [
+        (header.icmp_type as u64, 1),
+        (header.code as u64, 1),
+        (header.checksum as u64, 2),
+        (header.rest_of_header as u64, 4),
+    ];
+
+    let mut offset = 0;
+    for &(field, size) in &fields {
+        offset = push_bytes(
+            &mut packet,
+            offset,
+            &convert_n_to_bytes(
+                field,
+                size
+            )?
+        );
+    }
+
+    push_bytes(
+        &mut packet,
+        offset,
+        payload
+    );
+
+    Ok(
+        packet
+    )
+}
+
+/// Decode un message ICMP, le payload s'etend jusqu'a la fin du datagramme IP
+pub fn unpack_icmp(message: &[u8]) -> Result<IcmpHeader> {
+    if message.len() < 8 {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "ICMP",
+                needed: 8,
+                available: message.len()
+            }
+        );
+    }
+
+    let payload = if message.len() > 8 {
+        Some(
+            message[8..].to_vec()
+        )
+    } else {
+        None
+    };
+
+    Ok(
+        IcmpHeader {
+            icmp_type: message[0],
+            code: message[1],
+            checksum: convert_bytes_to_n(&message[2..4])? as u16,
+            rest_of_header: convert_bytes_to_n(&message[4..8])? as u32,
+            payload,
+        }
+    )
+}
+
+// Accesseurs ICMP
+impl IcmpHeader {
+
+    /// Identifiant d'un echo request/reply
+    pub fn identifier(&self) -> u16 {
+        (self.rest_of_header >> 16) as u16
+    }
+
+    /// Numero de sequence d'un echo request/reply
+    pub fn sequence(&self) -> u16 {
+        self.rest_of_header as u16
+    }
+
+    /// Message d'erreur citant un datagramme (destination unreachable, time exceeded)
+    pub fn is_error(&self) -> bool {
+        matches!(self.icmp_type, ICMP_DEST_UNREACHABLE | ICMP_TIME_EXCEEDED)
+    }
+
+    /// Datagramme d'origine cite par un message d'erreur :
+    /// header IPv4 et premiers octets de la couche 4 (au moins 8).
+    pub fn quoted_datagram(&self) -> Option<(Ipv4Header, &[u8])> {
+        if !self.is_error() {
+            return None;
+        }
+        let quoted = self.payload.as_deref()?;
+        unpack_quoted_ipv4(quoted).ok()
+    }
+}
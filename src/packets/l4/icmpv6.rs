@@ -0,0 +1,118 @@
+use alloc::vec::Vec;
+use crate::{
+    structs::icmpv6::{
+        Icmpv6Header,
+        ICMPV6_ECHO_REPLY,
+        ICMPV6_ECHO_REQUEST,
+        ICMPV6_NEIGHBOR_ADVERTISEMENT,
+        ICMPV6_NEIGHBOR_SOLICITATION,
+        ICMPV6_ROUTER_ADVERTISEMENT,
+        ICMPV6_ROUTER_SOLICITATION,
+    },
+    errors::errors::{
+        Result,
+        ParseError
+    }
+};
+
+/// Emballe un header ICMPv6 en un vecteur doctets
+pub fn pack_icmpv6(header: &Icmpv6Header) -> Result<Vec<u8>> {
+    let payload: &[u8] = match &header.payload {
+        Some(p) => p,
+        None => &[],
+    };
+
+    let mut packet = Vec::with_capacity(8 + payload.len());
+    packet.push(header.icmp_type);
+    packet.push(header.code);
+    packet.extend_from_slice(&header.checksum.to_be_bytes());
+    packet.extend_from_slice(&header.rest_of_header.to_be_bytes());
+    packet.extend_from_slice(payload);
+
+    Ok(
+        packet
+    )
+}
+
+/// Decode un message ICMPv6, le payload s'etend jusqu'a la fin du paquet IPv6
+pub fn unpack_icmpv6(message: &[u8]) -> Result<Icmpv6Header> {
+    if message.len() < 8 {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "ICMPv6",
+                needed: 8,
+                available: message.len()
+            }
+        );
+    }
+
+    let payload = if message.len() > 8 {
+        Some(
+            message[8..].to_vec()
+        )
+    } else {
+        None
+    };
+
+    Ok(
+        Icmpv6Header {
+            icmp_type: message[0],
+            code: message[1],
+            checksum: u16::from_be_bytes([message[2], message[3]]),
+            rest_of_header: u32::from_be_bytes([message[4], message[5], message[6], message[7]]),
+            payload,
+        }
+    )
+}
+
+// Accesseurs ICMPv6
+impl Icmpv6Header {
+
+    /// Identifiant d'un echo request/reply
+    pub fn identifier(&self) -> u16 {
+        (self.rest_of_header >> 16) as u16
+    }
+
+    /// Numero de sequence d'un echo request/reply
+    pub fn sequence(&self) -> u16 {
+        self.rest_of_header as u16
+    }
+
+    /// Message d'echo (request ou reply)
+    pub fn is_echo(&self) -> bool {
+        matches!(self.icmp_type, ICMPV6_ECHO_REQUEST | ICMPV6_ECHO_REPLY)
+    }
+
+    /// Adresse cible d'un Neighbor Solicitation/Advertisement
+    pub fn target_address(&self) -> Option<[u8; 16]> {
+        if !matches!(self.icmp_type, ICMPV6_NEIGHBOR_SOLICITATION | ICMPV6_NEIGHBOR_ADVERTISEMENT) {
+            return None;
+        }
+        self.payload.as_deref()?.get(..16)?.try_into().ok()
+    }
+
+    /// Adresse de lien portee par une option ND (source ou cible)
+    /// Les options suivent l'adresse cible (NS/NA), les temporisations (RA)
+    /// ou commencent le payload (RS).
+    pub fn link_layer_option(&self, option_type: u8) -> Option<[u8; 6]> {
+        let options_offset = match self.icmp_type {
+            ICMPV6_ROUTER_SOLICITATION => 0,
+            ICMPV6_ROUTER_ADVERTISEMENT => 8,
+            ICMPV6_NEIGHBOR_SOLICITATION | ICMPV6_NEIGHBOR_ADVERTISEMENT => 16,
+            _ => return None,
+        };
+        let mut options = self.payload.as_deref()?.get(options_offset..)?;
+        // Chaque option : type, longueur en unites de 8 octets, valeur
+        while options.len() >= 8 {
+            let length = options[1] as usize * 8;
+            if length == 0 || length > options.len() {
+                return None;
+            }
+            if options[0] == option_type {
+                return options[2..8].try_into().ok();
+            }
+            options = &options[length..];
+        }
+        None
+    }
+}
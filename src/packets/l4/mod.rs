@@ -1,2 +1,4 @@
 pub mod tcp;
-pub mod udp;
\ No newline at end of file
+pub mod udp;
+pub mod icmp;
+pub mod icmpv6;
\ No newline at end of file
@@ -1,14 +1,324 @@
 use alloc::vec;
 use alloc::vec::Vec;
+use core::{fmt, ops};
 use crate::{
     utils::{
-        convert_bytes::convert_n_to_bytes,
+        convert_bytes::{
+            convert_n_to_bytes,
+            convert_bytes_to_n
+        },
         push_bytes::push_bytes
     },
-    structs::tcp::TcpHeader,
-    errors::errors::Result,
+    structs::tcp::{
+        TcpHeader,
+        TcpFlags,
+        TcpOption,
+        TCP_OPT_END,
+        TCP_OPT_NOP,
+        TCP_OPT_MSS,
+        TCP_OPT_WINDOW_SCALE,
+        TCP_OPT_SACK_PERMITTED,
+        TCP_OPT_SACK,
+        TCP_OPT_TIMESTAMPS,
+        TCP_OPT_FAST_OPEN,
+    },
+    errors::errors::{
+        Result,
+        ParseError
+    },
 };
 
+impl TcpHeader {
+
+    /// Taille de l'header en octets, options comprises
+    pub fn header_len(&self) -> usize {
+        20 + self.options.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Remplace les options et ajuste le data offset (padding a 4 octets compris)
+    /// Le checksum n'est pas recalcule.
+    pub fn set_options(&mut self, options: &[TcpOption]) -> Result<()> {
+        let bytes = pack_tcp_options(options)?;
+        self.data_offset = 5 + (bytes.len() / 4) as u8;
+        self.options = if bytes.is_empty() { None } else { Some(bytes) };
+        Ok(())
+    }
+
+    /// Options decodees de l'header
+    pub fn typed_options(&self) -> Result<Vec<TcpOption>> {
+        unpack_tcp_options(self.options.as_deref().unwrap_or(&[]))
+    }
+
+    /// Drapeaux de l'header
+    pub fn tcp_flags(&self) -> TcpFlags {
+        TcpFlags::from_bits(self.flags)
+    }
+
+    /// Remplace les drapeaux (le checksum n'est pas recalcule)
+    pub fn set_tcp_flags(&mut self, flags: TcpFlags) {
+        self.flags = flags.bits();
+    }
+}
+
+// Lettres des drapeaux, dans l'ordre des bits (celles de parse_tcp_flags)
+const TCP_FLAG_LETTERS: [(char, TcpFlags); 9] = [
+    ('F', TcpFlags::FIN),
+    ('S', TcpFlags::SYN),
+    ('R', TcpFlags::RST),
+    ('P', TcpFlags::PSH),
+    ('A', TcpFlags::ACK),
+    ('U', TcpFlags::URG),
+    ('E', TcpFlags::ECE),
+    ('C', TcpFlags::CWR),
+    ('N', TcpFlags::NS),
+];
+
+impl TcpFlags {
+
+    /// Aucun drapeau (scan NULL)
+    pub const fn empty() -> Self {
+        TcpFlags(0)
+    }
+
+    /// Valeur brute ; les bits hors des 9 drapeaux sont ignores
+    pub const fn from_bits(bits: u16) -> Self {
+        TcpFlags(bits & 0x01FF)
+    }
+
+    /// Valeur brute du champ flags
+    pub const fn bits(&self) -> u16 {
+        self.0
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Vrai si tous les drapeaux de `other` sont presents
+    pub const fn contains(&self, other: TcpFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Vrai si au moins un drapeau de `other` est present
+    pub const fn intersects(&self, other: TcpFlags) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub fn insert(&mut self, other: TcpFlags) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: TcpFlags) {
+        self.0 &= !other.0;
+    }
+}
+
+impl ops::BitOr for TcpFlags {
+    type Output = TcpFlags;
+    fn bitor(self, other: TcpFlags) -> TcpFlags {
+        TcpFlags(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for TcpFlags {
+    fn bitor_assign(&mut self, other: TcpFlags) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for TcpFlags {
+    type Output = TcpFlags;
+    fn bitand(self, other: TcpFlags) -> TcpFlags {
+        TcpFlags(self.0 & other.0)
+    }
+}
+
+impl ops::Not for TcpFlags {
+    type Output = TcpFlags;
+    fn not(self) -> TcpFlags {
+        TcpFlags(!self.0 & 0x01FF)
+    }
+}
+
+/// Lettres des drapeaux presents (`"SA"`), `"none"` sans drapeau
+impl fmt::Display for TcpFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "none");
+        }
+        for (letter, flag) in TCP_FLAG_LETTERS {
+            if self.contains(flag) {
+                write!(f, "{}", letter)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Taille maximale des options (data offset 15)
+const TCP_OPTIONS_MAX_LEN: usize = 40;
+
+impl TcpOption {
+
+    /// Type (kind) de l'option
+    pub fn kind(&self) -> u8 {
+        match self {
+            TcpOption::EndOfList => TCP_OPT_END,
+            TcpOption::NoOperation => TCP_OPT_NOP,
+            TcpOption::Mss(_) => TCP_OPT_MSS,
+            TcpOption::WindowScale(_) => TCP_OPT_WINDOW_SCALE,
+            TcpOption::SackPermitted => TCP_OPT_SACK_PERMITTED,
+            TcpOption::Sack(_) => TCP_OPT_SACK,
+            TcpOption::Timestamps { .. } => TCP_OPT_TIMESTAMPS,
+            TcpOption::FastOpen(_) => TCP_OPT_FAST_OPEN,
+            TcpOption::Unknown { kind, .. } => *kind,
+        }
+    }
+}
+
+/// Emballe une liste d'options TCP, completee par End of List (zeros) a un multiple de 4
+/// L'ordre et les NOP d'alignement sont ceux donnes, comme dans un SYN reel.
+pub fn pack_tcp_options(
+    options: &[TcpOption]
+) ->
+Result<
+    Vec<u8>
+>
+{
+    let mut bytes = Vec::new();
+    for option in options {
+        let body = match option {
+            TcpOption::EndOfList | TcpOption::NoOperation => {
+                bytes.push(option.kind());
+                continue;
+            }
+            TcpOption::Mss(mss) => mss.to_be_bytes().to_vec(),
+            TcpOption::WindowScale(shift) => vec![*shift],
+            TcpOption::SackPermitted => Vec::new(),
+            TcpOption::Sack(blocks) => blocks
+                .iter()
+                .flat_map(|(left, right)| [left.to_be_bytes(), right.to_be_bytes()])
+                .flatten()
+                .collect(),
+            TcpOption::Timestamps { value, echo_reply } => {
+                let mut body = value.to_be_bytes().to_vec();
+                body.extend_from_slice(&echo_reply.to_be_bytes());
+                body
+            }
+            TcpOption::FastOpen(cookie) => cookie.clone(),
+            TcpOption::Unknown { data, .. } => data.clone(),
+        };
+        let length = 2 + body.len();
+        if length > TCP_OPTIONS_MAX_LEN {
+            return Err(
+                ParseError::InvalidHeaderLength {
+                    layer: "TCP options",
+                    length
+                }
+            );
+        }
+        bytes.push(option.kind());
+        bytes.push(length as u8);
+        bytes.extend_from_slice(&body);
+    }
+
+    while !bytes.len().is_multiple_of(4) {
+        bytes.push(TCP_OPT_END);
+    }
+    if bytes.len() > TCP_OPTIONS_MAX_LEN {
+        return Err(
+            ParseError::InvalidHeaderLength {
+                layer: "TCP options",
+                length: bytes.len()
+            }
+        );
+    }
+    Ok(bytes)
+}
+
+/// Decode les options TCP jusqu'a End of List (le padding qui suit est ignore)
+pub fn unpack_tcp_options(
+    data: &[u8]
+) ->
+Result<
+    Vec<TcpOption>
+>
+{
+    let mut options = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let kind = data[offset];
+        match kind {
+            TCP_OPT_END => break,
+            TCP_OPT_NOP => {
+                options.push(TcpOption::NoOperation);
+                offset += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        if offset + 2 > data.len() {
+            return Err(
+                ParseError::TruncatedPacket {
+                    layer: "TCP option",
+                    needed: offset + 2,
+                    available: data.len()
+                }
+            );
+        }
+        let length = data[offset + 1] as usize;
+        if length < 2 {
+            return Err(
+                ParseError::InvalidHeaderLength {
+                    layer: "TCP option",
+                    length
+                }
+            );
+        }
+        if offset + length > data.len() {
+            return Err(
+                ParseError::TruncatedPacket {
+                    layer: "TCP option",
+                    needed: offset + length,
+                    available: data.len()
+                }
+            );
+        }
+        let body = &data[offset + 2..offset + length];
+        let word = |at: usize| u32::from_be_bytes([body[at], body[at + 1], body[at + 2], body[at + 3]]);
+
+        let option = match (kind, body.len()) {
+            (TCP_OPT_MSS, 2) => TcpOption::Mss(u16::from_be_bytes([body[0], body[1]])),
+            (TCP_OPT_WINDOW_SCALE, 1) => TcpOption::WindowScale(body[0]),
+            (TCP_OPT_SACK_PERMITTED, 0) => TcpOption::SackPermitted,
+            (TCP_OPT_SACK, len) if len > 0 && len.is_multiple_of(8) => TcpOption::Sack(
+                (0..len).step_by(8).map(|at| (word(at), word(at + 4))).collect()
+            ),
+            (TCP_OPT_TIMESTAMPS, 8) => TcpOption::Timestamps {
+                value: word(0),
+                echo_reply: word(4),
+            },
+            (TCP_OPT_FAST_OPEN, _) => TcpOption::FastOpen(body.to_vec()),
+            (TCP_OPT_MSS | TCP_OPT_WINDOW_SCALE | TCP_OPT_SACK_PERMITTED | TCP_OPT_SACK | TCP_OPT_TIMESTAMPS, _) => {
+                return Err(
+                    ParseError::InvalidHeaderLength {
+                        layer: "TCP option",
+                        length
+                    }
+                );
+            }
+            _ => TcpOption::Unknown {
+                kind,
+                data: body.to_vec(),
+            },
+        };
+        options.push(option);
+        offset += length;
+    }
+    Ok(options)
+}
+
 /// Emballe un header TCP en un vecteur doctets
 pub fn pack_tcp(header: &TcpHeader) -> Result<Vec<u8>> {
     let payload: &[u8];
@@ -89,3 +399,77 @@ pub fn pack_tcp(header: &TcpHeader) -> Result<Vec<u8>> {
         packet
     )
 }
+
+/// Decode un segment TCP (options et payload compris)
+pub fn unpack_tcp(segment: &[u8]) -> Result<TcpHeader> {
+    if segment.len() < 20 {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "TCP",
+                needed: 20,
+                available: segment.len()
+            }
+        );
+    }
+
+    let combined_offset_reserved_flags = convert_bytes_to_n(&segment[12..14])? as u16;
+    let data_offset = (
+        combined_offset_reserved_flags >> 12
+    ) as u8;
+    let header_len = (data_offset as usize) * 4;
+
+    if data_offset < 5 {
+        return Err(
+            ParseError::InvalidHeaderLength {
+                layer: "TCP",
+                length: header_len
+            }
+        );
+    }
+    if segment.len() < header_len {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "TCP",
+                needed: header_len,
+                available: segment.len()
+            }
+        );
+    }
+
+    let options = if header_len > 20 {
+        Some(
+            segment[20..header_len].to_vec()
+        )
+    } else {
+        None
+    };
+
+    let payload = if segment.len() > header_len {
+        Some(
+            segment[header_len..].to_vec()
+        )
+    } else {
+        None
+    };
+
+    Ok(
+        TcpHeader {
+            src_port: convert_bytes_to_n(&segment[0..2])? as u16,
+            dst_port: convert_bytes_to_n(&segment[2..4])? as u16,
+            sequence_number: convert_bytes_to_n(&segment[4..8])? as u32,
+            ack_nowledgment_number: convert_bytes_to_n(&segment[8..12])? as u32,
+            data_offset,
+            reserved: (
+                (
+                    combined_offset_reserved_flags >> 9
+                ) & 0x07
+            ) as u8,
+            flags: combined_offset_reserved_flags & 0x01FF,
+            window: convert_bytes_to_n(&segment[14..16])? as u16,
+            checksum: convert_bytes_to_n(&segment[16..18])? as u16,
+            urgent_pointer: convert_bytes_to_n(&segment[18..20])? as u16,
+            options,
+            payload,
+        }
+    )
+}
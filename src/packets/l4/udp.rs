@@ -2,12 +2,16 @@ use alloc::vec;
 use alloc::vec::Vec;
 use crate::{
     utils::{
-        convert_bytes::convert_n_to_bytes,
+        convert_bytes::{
+            convert_n_to_bytes,
+            convert_bytes_to_n
+        },
         push_bytes::push_bytes
     },
     structs::udp::UdpHeader,
     errors::errors::{
-        Result
+        Result,
+        ParseError
     }
 };
 
@@ -59,3 +63,53 @@ pub fn pack_udp(header: &UdpHeader) -> Result<Vec<u8>> {
     )
 
 }
+
+/// Decode un datagramme UDP, le payload est borne par le champ length
+pub fn unpack_udp(datagram: &[u8]) -> Result<UdpHeader> {
+    if datagram.len() < 8 {
+        return Err(
+            ParseError::TruncatedPacket {
+                layer: "UDP",
+                needed: 8,
+                available: datagram.len()
+            }
+        );
+    }
+
+    let length = convert_bytes_to_n(&datagram[4..6])? as u16;
+    if length < 8 {
+        return Err(
+            ParseError::InvalidHeaderLength {
+                layer: "UDP",
+                length: length as usize
+            }
+        );
+    }
+    if (length as usize) > datagram.len() {
+        return Err(
+            ParseError::LengthMismatch {
+                layer: "UDP",
+                declared: length as usize,
+                actual: datagram.len()
+            }
+        );
+    }
+
+    let payload = if length > 8 {
+        Some(
+            datagram[8..length as usize].to_vec()
+        )
+    } else {
+        None
+    };
+
+    Ok(
+        UdpHeader {
+            src_port: convert_bytes_to_n(&datagram[0..2])? as u16,
+            dst_port: convert_bytes_to_n(&datagram[2..4])? as u16,
+            length,
+            checksum: convert_bytes_to_n(&datagram[6..8])? as u16,
+            payload,
+        }
+    )
+}
@@ -1,4 +1,9 @@
 pub mod l4;
 pub mod ethernet;
 pub mod ip;
-pub mod builder;
+pub mod fragment;
+pub mod ipv6;
+pub mod arp;
+pub mod tcp_session;
+pub mod os_fingerprint;
+pub mod builder;
\ No newline at end of file
@@ -0,0 +1,372 @@
+use crate::{
+    errors::errors::{Result, ParseError},
+    prelude::*,
+    structs::{
+        ip::Ipv4Header,
+        l4_protocol::L4Data,
+        network_packet::NetworkPacket,
+        os_fingerprint::{
+            IpIdBehavior, OsDatabase, OsMatch, OsProber, OsReport, OsSignature, TcpFingerprint, WindowSignature,
+        },
+        tcp::{TcpFlags, TcpHeader, TcpOption},
+    },
+};
+
+// Poids des criteres dans la confiance (total 100)
+const WEIGHT_TTL: u32 = 20;
+const WEIGHT_WINDOW: u32 = 25;
+const WEIGHT_OPTIONS: u32 = 25;
+const WEIGHT_WINDOW_SCALE: u32 = 10;
+const WEIGHT_DF: u32 = 10;
+const WEIGHT_IP_ID: u32 = 10;
+
+// Ecart maximal entre deux identifications consecutives d'un compteur global
+const IP_ID_MAX_STEP: u16 = 1000;
+
+/// Base integree, au format lu par `OsDatabase::parse`
+/// Empreintes de SYN-ACK et de SYN des piles courantes (valeurs par defaut).
+pub const BUILTIN_OS_DATABASE: &str = r#"
+# os "<nom>" ttl=<n> window=<n|mss*n>[,...] ws=<n|none> options=<disposition|none> df=<0|1> ipid=<zero|incr|random>
+# critere absent ou `*` : indifferent
+os "Linux 3.x-6.x" ttl=64 window=mss*44,mss*45,64240,65160,65483,29200,28960,43690 ws=7 options=mss,sok,ts,nop,ws df=1 ipid=zero
+os "Linux 2.6" ttl=64 window=mss*4,5840,5792,14480 options=mss,sok,ts,nop,ws df=1
+os "Android" ttl=64 window=65535,mss*44 ws=8 options=mss,sok,ts,nop,ws df=1 ipid=zero
+os "Windows 10/11" ttl=128 window=64240,65535,65392 ws=8 options=mss,nop,ws,nop,nop,sok df=1 ipid=incr
+os "Windows 7/8, Server 2008-2012" ttl=128 window=8192 ws=8 options=mss,nop,ws,nop,nop,sok df=1 ipid=incr
+os "Windows XP" ttl=128 window=65535,64240,16384 ws=none options=mss,nop,nop,sok df=1 ipid=incr
+os "FreeBSD 9.x-14.x" ttl=64 window=65535 ws=6 options=mss,nop,ws,sok,ts df=1 ipid=random
+os "OpenBSD" ttl=64 window=16384 ws=3 options=mss,nop,nop,sok,nop,ws,nop,nop,ts df=1 ipid=random
+os "macOS / iOS" ttl=64 window=65535 ws=6 options=mss,nop,ws,nop,nop,ts,sok df=1 ipid=random
+os "Solaris 11" ttl=64 window=64240,32850 ws=1 options=nop,ws,nop,nop,ts,nop,nop,sok,mss df=1 ipid=incr
+os "Cisco IOS" ttl=255 window=4128 ws=none options=mss df=0
+"#;
+
+/// TTL d'origine le plus probable : 32, 64, 128 ou 255
+pub fn initial_ttl(ttl: u8) -> u8 {
+    [32, 64, 128].into_iter().find(|&initial| ttl <= initial).unwrap_or(255)
+}
+
+/// Disposition des options TCP (`mss,sok,ts,nop,ws`), `?<kind>` pour une option inconnue
+pub fn options_layout(options: &[TcpOption]) -> StringNoStd {
+    let names: VecNoStd<StringNoStd> = options
+        .iter()
+        .map(|option| match option {
+            TcpOption::EndOfList => "eol".into(),
+            TcpOption::NoOperation => "nop".into(),
+            TcpOption::Mss(_) => "mss".into(),
+            TcpOption::WindowScale(_) => "ws".into(),
+            TcpOption::SackPermitted => "sok".into(),
+            TcpOption::Sack(_) => "sack".into(),
+            TcpOption::Timestamps { .. } => "ts".into(),
+            TcpOption::FastOpen(_) => "tfo".into(),
+            TcpOption::Unknown { kind, .. } => alloc::format!("?{}", kind),
+        })
+        .collect();
+    names.join(",")
+}
+
+impl IpIdBehavior {
+
+    /// Comportement deduit des identifications successives d'un meme hote
+    pub fn from_ids(ids: &[u16]) -> Self {
+        if !ids.is_empty() && ids.iter().all(|&id| id == 0) {
+            return IpIdBehavior::Zero;
+        }
+        if ids.len() < 2 {
+            return IpIdBehavior::Unknown;
+        }
+        let incremental = ids
+            .windows(2)
+            .all(|pair| (1..=IP_ID_MAX_STEP).contains(&pair[1].wrapping_sub(pair[0])));
+        if incremental {
+            IpIdBehavior::Incremental
+        } else {
+            IpIdBehavior::Random
+        }
+    }
+}
+
+impl TcpFingerprint {
+
+    /// Empreinte d'un SYN ou d'un SYN-ACK IPv4 ; None pour un autre paquet
+    pub fn from_packet(packet: &NetworkPacket) -> Option<Self> {
+        Self::from_packets(core::slice::from_ref(packet))
+    }
+
+    /// Empreinte des SYN/SYN-ACK d'un meme hote : champs du premier,
+    /// comportement de l'identification IP sur l'ensemble
+    pub fn from_packets(packets: &[NetworkPacket]) -> Option<Self> {
+        let syns: VecNoStd<(&Ipv4Header, &TcpHeader)> = packets
+            .iter()
+            .filter_map(|packet| match (packet.ipv4(), &packet.l4_data) {
+                (Some(ipv4), L4Data::Tcp(tcp)) if tcp.tcp_flags().contains(TcpFlags::SYN) => Some((ipv4, tcp)),
+                _ => None,
+            })
+            .collect();
+        let &(ipv4, tcp) = syns.first()?;
+        // Options illisibles : disposition vide plutot que pas d'empreinte
+        let options = tcp.typed_options().unwrap_or_default();
+        let ids: VecNoStd<u16> = syns.iter().map(|(ipv4, _)| ipv4.identification).collect();
+        Some(Self {
+            ttl: ipv4.ttl,
+            initial_ttl: initial_ttl(ipv4.ttl),
+            window: tcp.window,
+            mss: options.iter().find_map(|option| match option {
+                TcpOption::Mss(mss) => Some(*mss),
+                _ => None,
+            }),
+            window_scale: options.iter().find_map(|option| match option {
+                TcpOption::WindowScale(shift) => Some(*shift),
+                _ => None,
+            }),
+            options: options_layout(&options),
+            df: ipv4.dont_fragment(),
+            ip_id: IpIdBehavior::from_ids(&ids),
+        })
+    }
+
+    /// Nombre de routeurs traverses depuis l'emetteur
+    pub fn hops(&self) -> u8 {
+        self.initial_ttl - self.ttl
+    }
+}
+
+impl OsSignature {
+
+    /// Confiance (0-100) que l'empreinte provienne de ce systeme
+    /// Seuls les criteres observables comptent ; un critere indifferent n'apporte rien,
+    /// une signature precise l'emporte donc sur une signature vague.
+    pub fn score(&self, fingerprint: &TcpFingerprint) -> u8 {
+        let mut total = 0;
+        let mut matched = 0;
+        let mut check = |weight: u32, observable: bool, result: Option<bool>| {
+            if observable {
+                total += weight;
+                if result == Some(true) {
+                    matched += weight;
+                }
+            }
+        };
+        check(WEIGHT_TTL, true, self.initial_ttl.map(|ttl| ttl == fingerprint.initial_ttl));
+        check(
+            WEIGHT_WINDOW,
+            true,
+            self.windows.as_ref().map(|windows| windows.iter().any(|window| window.matches(fingerprint))),
+        );
+        check(WEIGHT_OPTIONS, true, self.options.as_ref().map(|options| *options == fingerprint.options));
+        check(WEIGHT_WINDOW_SCALE, true, self.window_scale.map(|shift| shift == fingerprint.window_scale));
+        check(WEIGHT_DF, true, self.df.map(|df| df == fingerprint.df));
+        check(
+            WEIGHT_IP_ID,
+            fingerprint.ip_id != IpIdBehavior::Unknown,
+            self.ip_id.map(|ip_id| ip_id == fingerprint.ip_id),
+        );
+        (matched * 100 / total) as u8
+    }
+}
+
+impl WindowSignature {
+
+    /// Vrai si la fenetre observee correspond
+    pub fn matches(&self, fingerprint: &TcpFingerprint) -> bool {
+        match *self {
+            WindowSignature::Value(window) => window == fingerprint.window,
+            WindowSignature::MssMultiple(factor) => fingerprint
+                .mss
+                .is_some_and(|mss| mss as u32 * factor as u32 == fingerprint.window as u32),
+        }
+    }
+}
+
+impl OsDatabase {
+
+    /// Base vide
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Base integree (Linux, Android, Windows, BSD, macOS, Solaris, Cisco IOS)
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_OS_DATABASE).expect("builtin OS database")
+    }
+
+    /// Parse une base texte : une ligne `os "<nom>" critere=valeur ...` par systeme,
+    /// lignes vides et commentaires `#` ignores
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut database = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let signature = parse_signature(line)
+                .map_err(|reason| ParseError::InvalidLine { line: index + 1, reason })?;
+            database.add(signature);
+        }
+        Ok(database)
+    }
+
+    /// Ajoute une signature apres les existantes
+    pub fn add(&mut self, signature: OsSignature) {
+        self.signatures.push(signature);
+    }
+
+    /// Integre une base utilisateur ; ses signatures passent avant les existantes a score egal
+    pub fn merge(&mut self, other: OsDatabase) {
+        let mut signatures = other.signatures;
+        signatures.append(&mut self.signatures);
+        self.signatures = signatures;
+    }
+
+    /// Candidats par confiance decroissante (ordre de la base a egalite), confiance nulle exclue
+    pub fn identify(&self, fingerprint: &TcpFingerprint) -> VecNoStd<OsMatch> {
+        let mut matches: VecNoStd<OsMatch> = self
+            .signatures
+            .iter()
+            .map(|signature| OsMatch {
+                name: signature.name.clone(),
+                confidence: signature.score(fingerprint),
+            })
+            .filter(|candidate| candidate.confidence > 0)
+            .collect();
+        matches.sort_by_key(|candidate| core::cmp::Reverse(candidate.confidence));
+        matches
+    }
+
+    /// Identification passive : une empreinte par adresse source, a partir des SYN
+    /// et SYN-ACK observes (capture, trafic recu)
+    pub fn identify_hosts(&self, packets: &[NetworkPacket]) -> BTreeMap<[u8; 4], OsReport> {
+        let mut hosts: BTreeMap<[u8; 4], VecNoStd<NetworkPacket>> = BTreeMap::new();
+        for packet in packets {
+            if let Some(ipv4) = packet.ipv4()
+                && TcpFingerprint::from_packet(packet).is_some()
+            {
+                hosts.entry(ipv4.src_addr).or_default().push(packet.clone());
+            }
+        }
+        hosts
+            .into_iter()
+            .map(|(host, packets)| (host, self.report(TcpFingerprint::from_packets(&packets))))
+            .collect()
+    }
+
+    /// Rapport d'identification pour une empreinte
+    pub fn report(&self, fingerprint: Option<TcpFingerprint>) -> OsReport {
+        let matches = fingerprint
+            .as_ref()
+            .map(|fingerprint| self.identify(fingerprint))
+            .unwrap_or_default();
+        OsReport { fingerprint, matches }
+    }
+}
+
+impl OsReport {
+
+    /// Meilleur candidat
+    pub fn best(&self) -> Option<&OsMatch> {
+        self.matches.first()
+    }
+}
+
+impl OsProber {
+
+    /// Nombre de SYN envoyes par vague (2 au minimum)
+    pub fn probes(&self) -> u8 {
+        self.probes
+    }
+}
+
+// os "<nom>" critere=valeur ...
+fn parse_signature(line: &str) -> core::result::Result<OsSignature, &'static str> {
+    let rest = line.strip_prefix("os").ok_or("expected os")?.trim_start();
+    let rest = rest.strip_prefix('"').ok_or("OS name must be quoted")?;
+    let (name, rest) = rest.split_once('"').ok_or("OS name must be quoted")?;
+    if name.is_empty() {
+        return Err("empty OS name");
+    }
+    let mut signature = OsSignature {
+        name: name.into(),
+        initial_ttl: None,
+        windows: None,
+        window_scale: None,
+        options: None,
+        df: None,
+        ip_id: None,
+    };
+
+    for field in rest.split_whitespace() {
+        let (key, value) = field.split_once('=').ok_or("expected key=value")?;
+        if value == "*" {
+            continue;
+        }
+        match key {
+            "ttl" => signature.initial_ttl = Some(value.parse().map_err(|_| "invalid ttl")?),
+            "window" => signature.windows = Some(
+                value.split(',').map(parse_window).collect::<core::result::Result<_, _>>()?
+            ),
+            "ws" => signature.window_scale = Some(match value {
+                "none" => None,
+                shift => Some(shift.parse::<u8>().ok().filter(|&shift| shift <= 14).ok_or("invalid window scale")?),
+            }),
+            "options" => signature.options = Some(match value {
+                "none" => StringNoStd::new(),
+                layout => layout.into(),
+            }),
+            "df" => signature.df = Some(match value {
+                "0" => false,
+                "1" => true,
+                _ => return Err("df must be 0 or 1"),
+            }),
+            "ipid" => signature.ip_id = Some(match value {
+                "zero" => IpIdBehavior::Zero,
+                "incr" => IpIdBehavior::Incremental,
+                "random" => IpIdBehavior::Random,
+                _ => return Err("ipid must be zero, incr or random"),
+            }),
+            _ => return Err("unknown criterion"),
+        }
+    }
+    Ok(signature)
+}
+
+// <n> ou mss*<n>
+fn parse_window(value: &str) -> core::result::Result<WindowSignature, &'static str> {
+    match value.strip_prefix("mss*") {
+        Some(factor) => factor.parse().map(WindowSignature::MssMultiple),
+        None => value.parse().map(WindowSignature::Value),
+    }
+    .map_err(|_| "invalid window")
+}
+
+impl fmt::Display for IpIdBehavior {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpIdBehavior::Zero => write!(f, "zero"),
+            IpIdBehavior::Incremental => write!(f, "incr"),
+            IpIdBehavior::Random => write!(f, "random"),
+            IpIdBehavior::Unknown => write!(f, "*"),
+        }
+    }
+}
+
+/// Criteres au format de la base, pour completer une base utilisateur
+impl fmt::Display for TcpFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ttl={} window={}", self.initial_ttl, self.window)?;
+        match self.window_scale {
+            Some(shift) => write!(f, " ws={}", shift)?,
+            None => write!(f, " ws=none")?,
+        }
+        match self.options.as_str() {
+            "" => write!(f, " options=none")?,
+            options => write!(f, " options={}", options)?,
+        }
+        write!(f, " df={} ipid={}", self.df as u8, self.ip_id)
+    }
+}
+
+impl fmt::Display for OsMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}%)", self.name, self.confidence)
+    }
+}
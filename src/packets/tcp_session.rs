@@ -0,0 +1,264 @@
+use crate::{
+    errors::errors::{Result, ParseError},
+    prelude::*,
+    structs::{
+        l4_protocol::{L4Data, L4Protocol},
+        network_packet::NetworkPacket,
+        packet_builder::PacketBuilder,
+        tcp::TcpFlags,
+        tcp_session::{TcpSession, TcpState},
+    },
+};
+
+// Au-dela de cet ecart, un segment est considere comme anterieur a rcv_nxt
+const SEQ_HALF_SPACE: u32 = 0x8000_0000;
+
+impl TcpSession {
+
+    /// Connexion fermee vers `template.dst_ip:template.dst_port`, sequence initiale `iss`
+    pub fn new(template: PacketBuilder, iss: u32) -> Self {
+        Self {
+            template,
+            state: TcpState::Closed,
+            iss,
+            snd_una: iss,
+            snd_nxt: iss,
+            irs: 0,
+            rcv_nxt: 0,
+            received: VecNoStd::new(),
+            reset: false,
+            out_of_order: BTreeMap::new(),
+            peer_fin: None,
+        }
+    }
+
+    /// Vrai si des donnees peuvent encore etre envoyees
+    pub fn is_open(&self) -> bool {
+        matches!(self.state, TcpState::Established | TcpState::CloseWait)
+    }
+
+    /// Vrai si la connexion est terminee (fermee, reinitialisee ou en TIME-WAIT)
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state, TcpState::Closed | TcpState::TimeWait)
+    }
+
+    /// Vrai une fois le FIN du pair recu (tout ce qui le precede compris)
+    pub fn peer_closed(&self) -> bool {
+        self.peer_fin == Some(self.received.len() as u32)
+    }
+
+    /// Segment SYN d'ouverture ; rappele en SYN-SENT, il reconstruit le meme SYN (retransmission)
+    /// Les options TCP du template (MSS, window scale...) ne sont emises que sur le SYN.
+    pub fn connect(&mut self) -> Result<NetworkPacket> {
+        if !matches!(self.state, TcpState::Closed | TcpState::SynSent) || self.reset {
+            return Err(
+                ParseError::InvalidFormat("TCP session already open")
+            );
+        }
+        self.state = TcpState::SynSent;
+        self.snd_una = self.iss;
+        self.snd_nxt = self.iss.wrapping_add(1);
+        self.segment(TcpFlags::SYN, self.iss, 0, None)
+    }
+
+    /// Segment de donnees (PSH|ACK)
+    pub fn send(&mut self, data: &[u8]) -> Result<NetworkPacket> {
+        if !self.is_open() {
+            return Err(
+                ParseError::InvalidFormat("TCP session not established")
+            );
+        }
+        let segment = self.segment(
+            TcpFlags::PSH | TcpFlags::ACK,
+            self.snd_nxt,
+            self.rcv_nxt,
+            Some(data.to_vec()),
+        )?;
+        self.snd_nxt = self.snd_nxt.wrapping_add(data.len() as u32);
+        Ok(segment)
+    }
+
+    /// Ferme notre cote de la connexion (FIN|ACK)
+    pub fn close(&mut self) -> Result<NetworkPacket> {
+        let next = match self.state {
+            TcpState::Established => TcpState::FinWait1,
+            TcpState::CloseWait => TcpState::LastAck,
+            _ => return Err(
+                ParseError::InvalidFormat("TCP session not established")
+            ),
+        };
+        let segment = self.segment(TcpFlags::FIN | TcpFlags::ACK, self.snd_nxt, self.rcv_nxt, None)?;
+        self.snd_nxt = self.snd_nxt.wrapping_add(1);
+        self.state = next;
+        Ok(segment)
+    }
+
+    /// Abandonne la connexion (RST, avec ACK une fois synchronisee)
+    pub fn abort(&mut self) -> Result<NetworkPacket> {
+        let segment = match self.state {
+            TcpState::Closed | TcpState::SynSent => self.segment(TcpFlags::RST, self.snd_nxt, 0, None)?,
+            _ => self.segment(TcpFlags::RST | TcpFlags::ACK, self.snd_nxt, self.rcv_nxt, None)?,
+        };
+        self.state = TcpState::Closed;
+        Ok(segment)
+    }
+
+    /// Vrai si le paquet vient du pair de cette connexion (adresses et ports inverses)
+    pub fn matches(&self, packet: &NetworkPacket) -> bool {
+        let L4Data::Tcp(tcp) = &packet.l4_data else {
+            return false;
+        };
+        packet.ipv4().is_some_and(|ipv4| {
+            ipv4.src_addr == self.template.dst_ip.octets && ipv4.dst_addr == self.template.src_ip.octets
+        })
+            && tcp.src_port == self.template.dst_port
+            && tcp.dst_port == self.template.src_port
+    }
+
+    /// Traite un segment recu et retourne l'acquittement a emettre le cas echeant
+    /// Les donnees hors ordre sont mises en attente, les doublons ignores ;
+    /// un RST hors fenetre ou n'acquittant pas notre SYN est ignore.
+    pub fn handle_segment(&mut self, packet: &NetworkPacket) -> Result<Option<NetworkPacket>> {
+        if !self.matches(packet) {
+            return Ok(None);
+        }
+        let L4Data::Tcp(tcp) = &packet.l4_data else {
+            return Ok(None);
+        };
+        let flags = tcp.tcp_flags();
+
+        match self.state {
+            TcpState::Closed => return Ok(None),
+            TcpState::SynSent => {
+                if flags.contains(TcpFlags::ACK) && tcp.ack_nowledgment_number != self.snd_nxt {
+                    return Ok(None);
+                }
+                if flags.contains(TcpFlags::RST) {
+                    if flags.contains(TcpFlags::ACK) {
+                        self.state = TcpState::Closed;
+                        self.reset = true;
+                    }
+                    return Ok(None);
+                }
+                if !flags.contains(TcpFlags::SYN | TcpFlags::ACK) {
+                    return Ok(None);
+                }
+                self.irs = tcp.sequence_number;
+                self.rcv_nxt = tcp.sequence_number.wrapping_add(1);
+                self.snd_una = tcp.ack_nowledgment_number;
+                self.state = TcpState::Established;
+                return self.acknowledgment().map(Some);
+            }
+            _ => {}
+        }
+
+        if flags.contains(TcpFlags::RST) {
+            let window = (self.template.window as u32).max(1);
+            if tcp.sequence_number.wrapping_sub(self.rcv_nxt) < window {
+                self.state = TcpState::Closed;
+                self.reset = true;
+            }
+            return Ok(None);
+        }
+        // SYN-ACK retransmis : notre ACK s'est perdu
+        if flags.contains(TcpFlags::SYN) {
+            return self.acknowledgment().map(Some);
+        }
+
+        if flags.contains(TcpFlags::ACK) {
+            let acked = tcp.ack_nowledgment_number.wrapping_sub(self.snd_una);
+            if acked <= self.snd_nxt.wrapping_sub(self.snd_una) {
+                self.snd_una = tcp.ack_nowledgment_number;
+            }
+            if self.snd_una == self.snd_nxt {
+                self.state = match self.state {
+                    TcpState::FinWait1 => TcpState::FinWait2,
+                    TcpState::Closing => TcpState::TimeWait,
+                    TcpState::LastAck => TcpState::Closed,
+                    state => state,
+                };
+            }
+        }
+
+        let payload = tcp.payload.as_deref().unwrap_or(&[]);
+        let fin = flags.contains(TcpFlags::FIN);
+        if payload.is_empty() && !fin {
+            return Ok(None);
+        }
+        if self.state != TcpState::Closed {
+            self.receive(tcp.sequence_number, payload, fin);
+        }
+        self.acknowledgment().map(Some)
+    }
+
+    // Range un segment de donnees et livre ce qui devient contigu
+    fn receive(&mut self, sequence_number: u32, payload: &[u8], fin: bool) {
+        let offset = sequence_number.wrapping_sub(self.irs.wrapping_add(1));
+        let delivered = self.received.len() as u32;
+        // Segment entierement anterieur au debut du flux
+        if offset >= SEQ_HALF_SPACE {
+            return;
+        }
+        if fin && self.peer_fin.is_none() {
+            self.peer_fin = Some(offset.wrapping_add(payload.len() as u32));
+        }
+        if !payload.is_empty() && offset + payload.len() as u32 > delivered {
+            let entry = self.out_of_order.entry(offset).or_default();
+            if entry.len() < payload.len() {
+                *entry = payload.to_vec();
+            }
+        }
+
+        while let Some((&start, _)) = self.out_of_order.first_key_value() {
+            let delivered = self.received.len() as u32;
+            if start > delivered {
+                break;
+            }
+            let Some(data) = self.out_of_order.remove(&start) else {
+                break;
+            };
+            let skip = (delivered - start) as usize;
+            if skip < data.len() {
+                self.received.extend_from_slice(&data[skip..]);
+            }
+        }
+
+        self.rcv_nxt = self.irs
+            .wrapping_add(1)
+            .wrapping_add(self.received.len() as u32);
+        if self.peer_closed() {
+            self.rcv_nxt = self.rcv_nxt.wrapping_add(1);
+            self.state = match self.state {
+                TcpState::Established => TcpState::CloseWait,
+                TcpState::FinWait1 => TcpState::Closing,
+                TcpState::FinWait2 => TcpState::TimeWait,
+                state => state,
+            };
+        }
+    }
+
+    // ACK de ce qui a ete recu dans l'ordre
+    fn acknowledgment(&self) -> Result<NetworkPacket> {
+        self.segment(TcpFlags::ACK, self.snd_nxt, self.rcv_nxt, None)
+    }
+
+    // Construit un segment de la connexion a partir du template
+    fn segment(
+        &self,
+        flags: TcpFlags,
+        sequence_number: u32,
+        ack_number: u32,
+        payload: Option<VecNoStd<u8>>,
+    ) -> Result<NetworkPacket> {
+        let mut builder = self.template.clone();
+        builder.protocol = L4Protocol::Tcp;
+        builder.tcp_flags = flags;
+        builder.sequence_number = sequence_number;
+        builder.ack_number = ack_number;
+        builder.payload = payload;
+        if !flags.contains(TcpFlags::SYN) {
+            builder.tcp_options = VecNoStd::new();
+        }
+        builder.build_packet()
+    }
+}
@@ -1,4 +1,8 @@
 use crate::structs::ipv4::Ipv4Addr;
+use crate::structs::ipv6::Ipv6Addr;
+use crate::structs::ethernet::VlanTag;
+use crate::structs::ip::Ipv4Option;
+use crate::structs::tcp::{TcpFlags, TcpOption};
 use crate::errors::errors::ParseError;
 
 /// Functions of parsing
@@ -76,6 +80,92 @@ Result<
     )
 }
 
+/// parse une IPv6 string -> Ipv6Addr
+/// Accepte la compression `::` et une IPv4 en fin d'adresse ("::ffff:192.0.2.1").
+pub fn parse_ipv6(
+    ip: &str
+) ->
+Result<
+    Ipv6Addr, ParseError
+>
+{
+    let (head, tail) = match ip.split_once("::") {
+        Some((head, tail)) => {
+            if tail.contains("::") {
+                return Err(
+                    ParseError::InvalidIpv6
+                );
+            }
+            (head, Some(tail))
+        }
+        None => (ip, None),
+    };
+
+    let head = parse_ipv6_groups(head)?;
+    let tail = match tail {
+        Some(tail) => Some(parse_ipv6_groups(tail)?),
+        None => None,
+    };
+
+    let mut groups = [0u16; 8];
+    match tail {
+        // "::" remplace au moins un groupe nul
+        Some(tail) if head.len() + tail.len() < 8 => {
+            groups[..head.len()].copy_from_slice(&head);
+            groups[8 - tail.len()..].copy_from_slice(&tail);
+        }
+        None if head.len() == 8 => groups.copy_from_slice(&head),
+        _ => {
+            return Err(
+                ParseError::InvalidIpv6
+            );
+        }
+    }
+
+    let mut octets = [0u8; 16];
+    for (i, group) in groups.iter().enumerate() {
+        octets[2 * i..2 * i + 2].copy_from_slice(&group.to_be_bytes());
+    }
+    Ok(
+        Ipv6Addr {
+            octets
+        }
+    )
+}
+
+// Groupes hexadecimaux d'un cote de "::", une IPv4 finale compte pour deux groupes
+fn parse_ipv6_groups(
+    part: &str
+) ->
+Result<
+    alloc::vec::Vec<u16>,
+    ParseError
+>
+{
+    let mut groups = alloc::vec::Vec::new();
+    if part.is_empty() {
+        return Ok(groups);
+    }
+
+    let count = part.split(':').count();
+    for (i, group) in part.split(':').enumerate() {
+        if i == count - 1 && group.contains('.') {
+            let ipv4 = parse_ipv4(group).map_err(|_| ParseError::InvalidIpv6)?;
+            groups.push(u16::from_be_bytes([ipv4.octets[0], ipv4.octets[1]]));
+            groups.push(u16::from_be_bytes([ipv4.octets[2], ipv4.octets[3]]));
+            continue;
+        }
+        if group.is_empty() || group.len() > 4 {
+            return Err(
+                ParseError::InvalidIpv6
+            );
+        }
+        groups.push(
+            u16::from_str_radix(group, 16).map_err(|_| ParseError::InvalidIpv6)?
+        );
+    }
+    Ok(groups)
+}
 
 /// parse une hex string -> u8
 pub fn parse_hex(
@@ -96,4 +186,355 @@ Result<
     |_
         |ParseError::InvalidHex
     )
-}
\ No newline at end of file
+}
+
+/// parse une chaine hexadecimale ("AA BB CC" ou "AABBCC") -> Vec<u8>
+pub fn parse_hex_bytes(
+    hex: &str
+) ->
+Result<
+    alloc::vec::Vec<u8>,
+    ParseError
+>
+{
+    let mut bytes = alloc::vec::Vec::new();
+    let mut high: Option<u8> = None;
+
+    for c in hex.chars() {
+        if c.is_ascii_whitespace() {
+            continue;
+        }
+        let nibble = c.to_digit(16).ok_or(
+            ParseError::InvalidHex
+        )? as u8;
+        match high.take() {
+            Some(h) => bytes.push((h << 4) | nibble),
+            None => high = Some(nibble),
+        }
+    }
+
+    if high.is_some() {
+        return Err(
+            ParseError::InvalidHex
+        );
+    }
+    Ok(bytes)
+}
+
+/// parse une liste de ports ("22,80,1000-1010") -> ports tries sans doublon
+pub fn parse_ports(
+    ports: &str
+) ->
+Result<
+    alloc::vec::Vec<u16>,
+    ParseError
+>
+{
+    let mut result = alloc::vec::Vec::new();
+
+    for item in ports.split(',') {
+        let item = item.trim();
+        let (start, end) = match item.split_once('-') {
+            Some((start, end)) => (start.trim(), end.trim()),
+            None => (item, item),
+        };
+        let start = start.parse::<u16>().map_err(
+            |_| ParseError::InvalidFormat("invalid port")
+        )?;
+        let end = end.parse::<u16>().map_err(
+            |_| ParseError::InvalidFormat("invalid port")
+        )?;
+        if start == 0 || start > end {
+            return Err(
+                ParseError::InvalidFormat("invalid port range")
+            );
+        }
+        result.extend(start..=end);
+    }
+
+    result.sort_unstable();
+    result.dedup();
+    Ok(result)
+}
+
+/// parse un sous-reseau CIDR ("192.168.1.0/24") ou une IPv4 seule -> adresses hotes
+/// Les adresses reseau et broadcast sont exclues jusqu'au /30, le prefixe minimal est /16.
+pub fn parse_cidr(
+    cidr: &str
+) ->
+Result<
+    alloc::vec::Vec<Ipv4Addr>,
+    ParseError
+>
+{
+    let (ip, prefix) = match cidr.trim().split_once('/') {
+        Some((ip, prefix)) => (
+            ip,
+            prefix.trim().parse::<u8>().map_err(
+                |_| ParseError::InvalidFormat("invalid prefix length")
+            )?
+        ),
+        None => (cidr.trim(), 32),
+    };
+    if !(16..=32).contains(&prefix) {
+        return Err(
+            ParseError::InvalidFormat("prefix length must be between 16 and 32")
+        );
+    }
+
+    let base = u32::from_be_bytes(parse_ipv4(ip.trim())?.octets);
+    let mask = u32::MAX << (32 - prefix as u32);
+    let network = base & mask;
+    let broadcast = network | !mask;
+    let (first, last) = if prefix <= 30 {
+        (network + 1, broadcast - 1)
+    } else {
+        (network, broadcast)
+    };
+
+    Ok(
+        (first..=last)
+            .map(|host| Ipv4Addr { octets: host.to_be_bytes() })
+            .collect()
+    )
+}
+
+/// parse une pile de tags VLAN ("100", "100:5", "100:5:1", QinQ "200,100") -> tags
+/// Chaque tag est `vid[:pcp[:dei]]`, du plus externe au plus interne. Le tag interne
+/// est un 802.1Q (0x8100), les tags externes des 802.1ad (0x88A8).
+pub fn parse_vlan_tags(
+    vlan: &str
+) ->
+Result<
+    alloc::vec::Vec<VlanTag>,
+    ParseError
+>
+{
+    let items: alloc::vec::Vec<&str> = vlan.split(',').map(str::trim).collect();
+    let mut result = alloc::vec::Vec::new();
+
+    for (i, item) in items.iter().enumerate() {
+        let mut fields = item.split(':');
+        let vid = fields.next().unwrap_or("").parse::<u16>().map_err(
+            |_| ParseError::InvalidFormat("invalid VLAN ID")
+        )?;
+        let pcp = match fields.next() {
+            Some(pcp) => pcp.parse::<u8>().map_err(
+                |_| ParseError::InvalidFormat("invalid VLAN PCP")
+            )?,
+            None => 0,
+        };
+        let dei = match fields.next() {
+            Some("0") | None => false,
+            Some("1") => true,
+            Some(_) => return Err(
+                ParseError::InvalidFormat("invalid VLAN DEI")
+            ),
+        };
+        if fields.next().is_some() {
+            return Err(
+                ParseError::InvalidFormat("invalid VLAN tag")
+            );
+        }
+        if vid > 0x0FFF || pcp > 7 {
+            return Err(
+                ParseError::InvalidFormat("VLAN ID (0-4095) or PCP (0-7) out of range")
+            );
+        }
+
+        let tag = if i + 1 < items.len() {
+            VlanTag::service(vid)
+        } else {
+            VlanTag::new(vid)
+        };
+        result.push(tag.with_pcp(pcp).with_dei(dei));
+    }
+
+    Ok(result)
+}
+
+/// Parse une liste d'options IPv4 separees par des virgules
+/// `rr[:slots]`, `ts[:slots]` (9 emplacements par defaut), `lsrr:ip/ip`, `ssrr:ip/ip`, `ra` et `nop`
+pub fn parse_ipv4_options(
+    options: &str
+) ->
+Result<
+    alloc::vec::Vec<Ipv4Option>,
+    ParseError
+>
+{
+    let mut result = alloc::vec::Vec::new();
+
+    for item in options.split(',').map(str::trim) {
+        let (name, value) = match item.split_once(':') {
+            Some((name, value)) => (name, Some(value)),
+            None => (item, None),
+        };
+        let slots = |value: Option<&str>| match value {
+            Some(slots) => match slots.parse::<usize>() {
+                Ok(slots) if (1..=9).contains(&slots) => Ok(slots),
+                _ => Err(ParseError::InvalidFormat("IPv4 option slots out of range (1-9)")),
+            },
+            None => Ok(9),
+        };
+        let route = |value: Option<&str>| {
+            value
+                .ok_or(ParseError::InvalidFormat("missing IPv4 source route"))?
+                .split('/')
+                .map(|ip| parse_ipv4(ip).map(|ip| ip.octets))
+                .collect::<Result<alloc::vec::Vec<[u8; 4]>, ParseError>>()
+        };
+
+        let option = match (name, value) {
+            ("rr", _) => Ipv4Option::record_route(slots(value)?),
+            ("ts", _) => Ipv4Option::timestamp(slots(value)?),
+            ("lsrr", _) => Ipv4Option::loose_source_route(route(value)?),
+            ("ssrr", _) => Ipv4Option::strict_source_route(route(value)?),
+            ("ra", None) => Ipv4Option::router_alert(),
+            ("nop", None) => Ipv4Option::NoOperation,
+            _ => return Err(
+                ParseError::InvalidFormat("unknown IPv4 option")
+            ),
+        };
+        result.push(option);
+    }
+
+    Ok(result)
+}
+
+/// Parse une liste d'options TCP separees par des virgules, dans l'ordre d'emission
+/// `mss:1460`, `ws:7`, `sackok`, `sack:gauche-droite[/gauche-droite]`, `ts[:valeur[:echo]]`,
+/// `tfo[:cookie hexa]` (sans cookie : demande), `nop` et `eol`
+pub fn parse_tcp_options(
+    options: &str
+) ->
+Result<
+    alloc::vec::Vec<TcpOption>,
+    ParseError
+>
+{
+    let mut result = alloc::vec::Vec::new();
+
+    for item in options.split(',').map(str::trim) {
+        let mut fields = item.split(':');
+        let name = fields.next().unwrap_or("");
+        let values: alloc::vec::Vec<&str> = fields.collect();
+        let number = |value: &str| value.parse::<u32>().map_err(
+            |_| ParseError::InvalidFormat("invalid TCP option value")
+        );
+
+        let option = match (name, values.as_slice()) {
+            ("mss", [mss]) => TcpOption::Mss(
+                u16::try_from(number(mss)?).map_err(|_| ParseError::InvalidFormat("TCP MSS out of range"))?
+            ),
+            ("ws", [shift]) => match number(shift)? {
+                shift @ 0..=14 => TcpOption::WindowScale(shift as u8),
+                _ => return Err(ParseError::InvalidFormat("TCP window scale out of range (0-14)")),
+            },
+            ("sackok", []) => TcpOption::SackPermitted,
+            ("sack", [blocks]) => TcpOption::Sack(
+                blocks
+                    .split('/')
+                    .map(|block| match block.split_once('-') {
+                        Some((left, right)) => Ok((number(left)?, number(right)?)),
+                        None => Err(ParseError::InvalidFormat("invalid TCP SACK block")),
+                    })
+                    .collect::<Result<alloc::vec::Vec<(u32, u32)>, ParseError>>()?
+            ),
+            ("ts", []) => TcpOption::Timestamps { value: 0, echo_reply: 0 },
+            ("ts", [value]) => TcpOption::Timestamps { value: number(value)?, echo_reply: 0 },
+            ("ts", [value, echo_reply]) => TcpOption::Timestamps {
+                value: number(value)?,
+                echo_reply: number(echo_reply)?,
+            },
+            ("tfo", []) => TcpOption::FastOpen(alloc::vec::Vec::new()),
+            ("tfo", [cookie]) => TcpOption::FastOpen(parse_hex_bytes(cookie)?),
+            ("nop", []) => TcpOption::NoOperation,
+            ("eol", []) => TcpOption::EndOfList,
+            _ => return Err(
+                ParseError::InvalidFormat("unknown TCP option")
+            ),
+        };
+        result.push(option);
+    }
+
+    Ok(result)
+}
+
+/// Parse des drapeaux TCP en lettres (`"SA"`, `"FPU"`, insensible a la casse)
+/// F S R P A U E C N ; une chaine vide ou `"none"` ne donne aucun drapeau (scan NULL).
+pub fn parse_tcp_flags(
+    flags: &str
+) ->
+Result<
+    TcpFlags,
+    ParseError
+>
+{
+    let mut result = TcpFlags::empty();
+    if flags.trim().eq_ignore_ascii_case("none") {
+        return Ok(result);
+    }
+
+    for c in flags.trim().chars() {
+        let flag = match c.to_ascii_uppercase() {
+            'F' => TcpFlags::FIN,
+            'S' => TcpFlags::SYN,
+            'R' => TcpFlags::RST,
+            'P' => TcpFlags::PSH,
+            'A' => TcpFlags::ACK,
+            'U' => TcpFlags::URG,
+            'E' => TcpFlags::ECE,
+            'C' => TcpFlags::CWR,
+            'N' => TcpFlags::NS,
+            _ => return Err(
+                ParseError::InvalidFormat("unknown TCP flag (FSRPAUECN)")
+            ),
+        };
+        result.insert(flag);
+    }
+
+    Ok(result)
+}
+
+/// Parse un texte avec sequences d'echappement en octets
+/// `\r`, `\n`, `\t`, `\0`, `\\` et `\xHH` (ex: `"GET / HTTP/1.0\r\n\r\n"`)
+pub fn parse_escaped_bytes(
+    text: &str
+) ->
+Result<
+    alloc::vec::Vec<u8>,
+    ParseError
+>
+{
+    let mut bytes = alloc::vec::Vec::new();
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0u8; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        let byte = match chars.next() {
+            Some('r') => b'\r',
+            Some('n') => b'\n',
+            Some('t') => b'\t',
+            Some('0') => 0,
+            Some('\\') => b'\\',
+            Some('x') => {
+                let hex: alloc::string::String = chars.by_ref().take(2).collect();
+                match parse_hex_bytes(&hex)?.as_slice() {
+                    [byte] => *byte,
+                    _ => return Err(ParseError::InvalidHex),
+                }
+            }
+            _ => return Err(
+                ParseError::InvalidFormat("invalid escape sequence")
+            ),
+        };
+        bytes.push(byte);
+    }
+
+    Ok(bytes)
+}
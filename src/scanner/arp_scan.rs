@@ -0,0 +1,129 @@
+use crate::{
+    errors::errors::Result,
+    packets::builder::arp_builder::ArpBuilder,
+    prelude::*,
+    sender::raw_socket::get_interface_index,
+    structs::{
+        arp::{ArpFrame, ARP_REPLY, ETHERTYPE_ARP},
+        ipv4::Ipv4Addr,
+        scan::{ArpHost, ArpScanner},
+        socket::{RawSocketReceiver, RawSocketSender},
+    },
+};
+use std::time::{Duration, Instant};
+
+impl ArpScanner {
+
+    /// Constructeur : 1 retry, 1000 ms d'attente et 100 requetes/s par defaut
+    pub fn new(src_mac: [u8; 6], src_ip: Ipv4Addr) -> Self {
+        Self {
+            src_mac,
+            src_ip,
+            retries: 1,
+            timeout_ms: 1000,
+            rate_limit: 100,
+        }
+    }
+
+    /// Nombre de renvois pour les adresses sans reponse
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Attente des reponses apres la derniere requete de chaque vague
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Nombre maximal de requetes par seconde (0 : pas de limite)
+    pub fn with_rate_limit(mut self, requests_per_sec: u32) -> Self {
+        self.rate_limit = requests_per_sec;
+        self
+    }
+
+    /// Construit la requete ARP d'une adresse
+    pub fn build_probe(&self, target: Ipv4Addr) -> ArpFrame {
+        ArpBuilder::new(self.src_mac, self.src_ip).build_request(target)
+    }
+
+    /// Associe une trame recue a une requete
+    /// Seules les reponses ARP adressees a `src_ip` sont retenues.
+    pub fn classify_reply(&self, frame: &[u8]) -> Option<ArpHost> {
+        let reply = ArpFrame::parse(frame).ok()?;
+        if reply.arp.operation != ARP_REPLY || reply.arp.target_ip != self.src_ip.octets {
+            return None;
+        }
+        Some(ArpHost {
+            ip: Ipv4Addr { octets: reply.arp.sender_ip },
+            mac: reply.arp.sender_mac,
+        })
+    }
+
+    /// Balaye les adresses sur une interface (ouvre les sockets brutes)
+    pub fn sweep(&self, interface: &str, targets: &[Ipv4Addr]) -> Result<VecNoStd<ArpHost>> {
+        let if_index = get_interface_index(interface)?;
+        let sender = RawSocketSender::new()?;
+        let mut receiver = RawSocketReceiver::with_protocol(ETHERTYPE_ARP)?;
+        receiver.bind(interface)?;
+        self.sweep_with(&sender, &receiver, if_index, targets)
+    }
+
+    /// Balaye les adresses avec des sockets deja ouvertes
+    /// Les requetes sont espacees selon `rate_limit` et renvoyees `retries` fois
+    /// aux adresses muettes. Les hotes sont retournes tries par adresse.
+    pub fn sweep_with(
+        &self,
+        sender: &RawSocketSender,
+        receiver: &RawSocketReceiver,
+        if_index: i32,
+        targets: &[Ipv4Addr],
+    ) -> Result<VecNoStd<ArpHost>> {
+        let mut hosts: BTreeMap<[u8; 4], ArpHost> = BTreeMap::new();
+        let mut pending: VecNoStd<[u8; 4]> = targets.iter().map(|ip| ip.octets).collect();
+        pending.sort_unstable();
+        pending.dedup();
+        let interval = match self.rate_limit {
+            0 => Duration::ZERO,
+            rate => Duration::from_secs(1) / rate,
+        };
+
+        for _ in 0..=self.retries {
+            if pending.is_empty() {
+                break;
+            }
+            for octets in pending.clone() {
+                let probe = self.build_probe(Ipv4Addr { octets });
+                sender.send(if_index, probe.ethernet.dst_mac, &probe.assemble()?)?;
+                self.collect_replies(receiver, Instant::now() + interval, &mut pending, &mut hosts)?;
+            }
+            let deadline = Instant::now() + Duration::from_millis(self.timeout_ms);
+            self.collect_replies(receiver, deadline, &mut pending, &mut hosts)?;
+        }
+
+        Ok(hosts.into_values().collect())
+    }
+
+    // Lit les reponses jusqu'a `deadline` ou jusqu'a ce que toutes les adresses soient resolues
+    fn collect_replies(
+        &self,
+        receiver: &RawSocketReceiver,
+        deadline: Instant,
+        pending: &mut VecNoStd<[u8; 4]>,
+        hosts: &mut BTreeMap<[u8; 4], ArpHost>,
+    ) -> Result<()> {
+        if pending.is_empty() {
+            return Ok(());
+        }
+        receiver.recv_until(deadline, |frame| {
+            if let Some(host) = self.classify_reply(&frame.record.data)
+                && let Some(position) = pending.iter().position(|&ip| ip == host.ip.octets)
+            {
+                pending.swap_remove(position);
+                hosts.insert(host.ip.octets, host);
+            }
+            Ok(!pending.is_empty())
+        })
+    }
+}
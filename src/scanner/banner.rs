@@ -0,0 +1,191 @@
+use crate::{
+    errors::errors::Result,
+    prelude::*,
+    sender::raw_socket::get_interface_index,
+    structs::{
+        ethernet::ETHERTYPE_IPV4,
+        network_packet::NetworkPacket,
+        packet_builder::PacketBuilder,
+        scan::PortState,
+        socket::{RawSocketReceiver, RawSocketSender},
+        tcp_session::{Banner, BannerGrabber, TcpSession, TcpState, Teardown},
+    },
+    utils::clock::{Clock, SystemClock},
+};
+use std::time::{Duration, Instant};
+
+impl BannerGrabber {
+
+    /// Constructeur : 1 retry du SYN, 2000 ms d'attente, 4096 octets au plus, fermeture par FIN
+    pub fn new(template: PacketBuilder) -> Self {
+        Self {
+            template,
+            retries: 1,
+            timeout_ms: 2000,
+            payload: None,
+            max_bytes: 4096,
+            teardown: Teardown::Fin,
+        }
+    }
+
+    /// Nombre de renvois du SYN sans reponse
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Attente de la reponse au SYN, puis delai d'inactivite de la collecte
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Donnees envoyees une fois la connexion etablie (ex: `GET / HTTP/1.0\r\n\r\n`)
+    pub fn with_payload(mut self, payload: VecNoStd<u8>) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+
+    /// Nombre d'octets au-dela duquel la collecte s'arrete
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Fermeture par FIN (defaut) ou RST
+    pub fn with_teardown(mut self, teardown: Teardown) -> Self {
+        self.teardown = teardown;
+        self
+    }
+
+    /// Session vers `port`, sequence initiale derivee de l'horloge
+    pub fn session(&self, port: u16) -> TcpSession {
+        let mut template = self.template.clone();
+        template.dst_port = port;
+        let now = SystemClock.now_ns();
+        TcpSession::new(template, (now as u32) ^ ((now >> 32) as u32))
+    }
+
+    /// Recupere la banniere d'un port sur une interface (ouvre les sockets brutes)
+    pub fn grab(&self, interface: &str, port: u16) -> Result<Banner> {
+        let if_index = get_interface_index(interface)?;
+        let sender = RawSocketSender::new()?;
+        let mut receiver = RawSocketReceiver::with_protocol(ETHERTYPE_IPV4)?;
+        receiver.bind(interface)?;
+        self.grab_with(&sender, &receiver, if_index, port)
+    }
+
+    /// Recupere la banniere d'un port avec des sockets deja ouvertes
+    /// Poignee de main, envoi du payload, collecte des segments reordonnes puis fermeture.
+    /// Le noyau ignore cette connexion : avec l'adresse de la machine comme source,
+    /// il repond RST au SYN-ACK, a filtrer (pare-feu) pendant la collecte.
+    pub fn grab_with(
+        &self,
+        sender: &RawSocketSender,
+        receiver: &RawSocketReceiver,
+        if_index: i32,
+        port: u16,
+    ) -> Result<Banner> {
+        let mut session = self.session(port);
+        let mut segments = VecNoStd::new();
+
+        for _ in 0..=self.retries {
+            let syn = session.connect()?;
+            self.transmit(sender, if_index, syn, &mut segments)?;
+            self.exchange(&mut session, sender, receiver, if_index, &mut segments, |session| {
+                session.state != TcpState::SynSent
+            })?;
+            if session.state != TcpState::SynSent {
+                break;
+            }
+        }
+
+        let state = match session.state {
+            TcpState::SynSent => PortState::Filtered,
+            TcpState::Closed => PortState::Closed,
+            _ => PortState::Open,
+        };
+        if state != PortState::Open {
+            return Ok(Banner { port, state, data: VecNoStd::new(), segments });
+        }
+
+        if let Some(payload) = self.payload.as_ref().filter(|payload| !payload.is_empty()) {
+            let segment = session.send(payload)?;
+            self.transmit(sender, if_index, segment, &mut segments)?;
+        }
+        self.exchange(&mut session, sender, receiver, if_index, &mut segments, |session| {
+            session.peer_closed() || session.is_finished() || session.received.len() >= self.max_bytes
+        })?;
+
+        match self.teardown {
+            Teardown::Rst if !session.is_finished() => {
+                let rst = session.abort()?;
+                self.transmit(sender, if_index, rst, &mut segments)?;
+            }
+            Teardown::Fin if session.is_open() => {
+                let fin = session.close()?;
+                self.transmit(sender, if_index, fin, &mut segments)?;
+                self.exchange(&mut session, sender, receiver, if_index, &mut segments, |session| {
+                    session.is_finished()
+                })?;
+            }
+            _ => {}
+        }
+
+        let mut data = session.received;
+        data.truncate(self.max_bytes);
+        Ok(Banner { port, state, data, segments })
+    }
+
+    // Emet un segment et le consigne
+    fn transmit(
+        &self,
+        sender: &RawSocketSender,
+        if_index: i32,
+        packet: NetworkPacket,
+        segments: &mut VecNoStd<NetworkPacket>,
+    ) -> Result<()> {
+        sender.send(if_index, self.template.dst_mac, &packet.assemble_packet()?)?;
+        segments.push(packet);
+        Ok(())
+    }
+
+    // Recoit les segments du pair et les acquitte jusqu'a `done`
+    // ou `timeout_ms` sans segment de la connexion
+    fn exchange<F>(
+        &self,
+        session: &mut TcpSession,
+        sender: &RawSocketSender,
+        receiver: &RawSocketReceiver,
+        if_index: i32,
+        segments: &mut VecNoStd<NetworkPacket>,
+        done: F,
+    ) -> Result<()>
+    where
+        F: Fn(&TcpSession) -> bool,
+    {
+        // L'echeance repart de zero a chaque segment de la connexion
+        let mut active = true;
+        while active && !done(session) {
+            active = false;
+            let deadline = Instant::now() + Duration::from_millis(self.timeout_ms);
+            receiver.recv_until(deadline, |frame| {
+                // Les copies sortantes (nos propres trames) sont ignorees
+                if !frame.is_outgoing()
+                    && let Some(packet) = frame.record.packet
+                    && session.matches(&packet)
+                {
+                    let reply = session.handle_segment(&packet)?;
+                    segments.push(packet);
+                    if let Some(reply) = reply {
+                        self.transmit(sender, if_index, reply, segments)?;
+                    }
+                    active = true;
+                    return Ok(false);
+                }
+                Ok(true)
+            })?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,8 @@
+pub mod syn_scan;
+pub mod udp_scan;
+pub mod arp_scan;
+pub mod ndp_scan;
+pub mod banner;
+pub mod service_db;
+pub mod service_detect;
+pub mod os_probe;
@@ -0,0 +1,171 @@
+use crate::{
+    errors::errors::Result,
+    packets::{
+        builder::icmpv6_builder::Icmpv6Builder,
+        ipv6::{is_multicast, multicast_mac, solicited_node_multicast},
+    },
+    prelude::*,
+    sender::raw_socket::get_interface_index,
+    structs::{
+        formats::PacketRecord,
+        icmpv6::{ICMPV6_ECHO_REPLY, ICMPV6_NEIGHBOR_ADVERTISEMENT, NDP_OPTION_TARGET_LL_ADDR},
+        ipv6::Ipv6Addr,
+        l4_protocol::L4Data,
+        network_packet::NetworkPacket,
+        scan::{NdpHost, NdpScanner},
+        socket::{RawSocketReceiver, RawSocketSender},
+    },
+};
+use std::time::{Duration, Instant};
+
+// Ethertype IPv6
+const ETHERTYPE_IPV6: u16 = 0x86DD;
+
+// Identifiant des echo requests de decouverte
+const NDP_ECHO_ID: u16 = 0x5253;
+
+impl NdpScanner {
+
+    /// Constructeur : 1 retry, 1000 ms d'attente et 100 sondes/s par defaut
+    pub fn new(src_mac: [u8; 6], src_ip: Ipv6Addr) -> Self {
+        Self {
+            src_mac,
+            src_ip,
+            retries: 1,
+            timeout_ms: 1000,
+            rate_limit: 100,
+        }
+    }
+
+    /// Nombre de renvois pour les cibles sans reponse
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Attente des reponses apres la derniere sonde de chaque vague
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Nombre maximal de sondes par seconde (0 : pas de limite)
+    pub fn with_rate_limit(mut self, probes_per_sec: u32) -> Self {
+        self.rate_limit = probes_per_sec;
+        self
+    }
+
+    /// Construit la sonde d'une cible : Neighbor Solicitation vers son groupe
+    /// sollicited-node, ou echo request pour une cible multicast
+    pub fn build_probe(&self, target: Ipv6Addr) -> Result<NetworkPacket> {
+        if is_multicast(&target) {
+            let builder = Icmpv6Builder::new(self.src_ip, target);
+            let echo = builder.build_echo_request(NDP_ECHO_ID, 1, None)?;
+            return builder.build_packet(self.src_mac, multicast_mac(&target), echo);
+        }
+        let group = solicited_node_multicast(&target);
+        let builder = Icmpv6Builder::new(self.src_ip, group);
+        let solicitation = builder.build_neighbor_solicitation(target, Some(self.src_mac))?;
+        builder.build_packet(self.src_mac, multicast_mac(&group), solicitation)
+    }
+
+    /// Associe une trame recue a une sonde
+    /// Neighbor Advertisement : hote cible (MAC de l'option Target Link-Layer, a defaut
+    /// celle de la trame) ; echo reply de decouverte : hote emetteur.
+    /// Seules les reponses adressees a `src_ip` sont retenues.
+    pub fn classify_reply(&self, record: &PacketRecord) -> Option<NdpHost> {
+        let packet = record.packet.as_ref()?;
+        let ipv6 = packet.ipv6()?;
+        if ipv6.dst_addr != self.src_ip.octets {
+            return None;
+        }
+        let L4Data::Icmpv6(icmpv6) = &packet.l4_data else {
+            return None;
+        };
+        match icmpv6.icmp_type {
+            ICMPV6_NEIGHBOR_ADVERTISEMENT => Some(NdpHost {
+                ip: Ipv6Addr { octets: icmpv6.target_address()? },
+                mac: icmpv6
+                    .link_layer_option(NDP_OPTION_TARGET_LL_ADDR)
+                    .unwrap_or(packet.ethernet.src_mac),
+            }),
+            ICMPV6_ECHO_REPLY if icmpv6.identifier() == NDP_ECHO_ID => Some(NdpHost {
+                ip: Ipv6Addr { octets: ipv6.src_addr },
+                mac: packet.ethernet.src_mac,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Decouvre les cibles sur une interface (ouvre les sockets brutes)
+    pub fn discover(&self, interface: &str, targets: &[Ipv6Addr]) -> Result<VecNoStd<NdpHost>> {
+        let if_index = get_interface_index(interface)?;
+        let sender = RawSocketSender::new()?;
+        let mut receiver = RawSocketReceiver::with_protocol(ETHERTYPE_IPV6)?;
+        receiver.bind(interface)?;
+        self.discover_with(&sender, &receiver, if_index, targets)
+    }
+
+    /// Decouvre les cibles avec des sockets deja ouvertes
+    /// Les sondes sont espacees selon `rate_limit` et renvoyees `retries` fois aux
+    /// cibles muettes ; une cible multicast attend toujours `timeout_ms` et retient
+    /// tous les repondants. Les hotes sont retournes tries par adresse.
+    pub fn discover_with(
+        &self,
+        sender: &RawSocketSender,
+        receiver: &RawSocketReceiver,
+        if_index: i32,
+        targets: &[Ipv6Addr],
+    ) -> Result<VecNoStd<NdpHost>> {
+        let mut hosts: BTreeMap<[u8; 16], NdpHost> = BTreeMap::new();
+        let mut pending: VecNoStd<[u8; 16]> = targets.iter().map(|ip| ip.octets).collect();
+        pending.sort_unstable();
+        pending.dedup();
+        let interval = match self.rate_limit {
+            0 => Duration::ZERO,
+            rate => Duration::from_secs(1) / rate,
+        };
+
+        for _ in 0..=self.retries {
+            if pending.is_empty() {
+                break;
+            }
+            for octets in pending.clone() {
+                let probe = self.build_probe(Ipv6Addr { octets })?;
+                sender.send(if_index, probe.ethernet.dst_mac, &probe.assemble_packet()?)?;
+                self.collect_replies(receiver, Instant::now() + interval, &mut pending, &mut hosts)?;
+            }
+            let deadline = Instant::now() + Duration::from_millis(self.timeout_ms);
+            self.collect_replies(receiver, deadline, &mut pending, &mut hosts)?;
+        }
+
+        Ok(hosts.into_values().collect())
+    }
+
+    // Lit les reponses jusqu'a `deadline` ou jusqu'a ce que toutes les cibles soient resolues
+    // (jamais le cas d'une cible multicast)
+    fn collect_replies(
+        &self,
+        receiver: &RawSocketReceiver,
+        deadline: Instant,
+        pending: &mut VecNoStd<[u8; 16]>,
+        hosts: &mut BTreeMap<[u8; 16], NdpHost>,
+    ) -> Result<()> {
+        let multicast = pending.iter().any(|octets| octets[0] == 0xFF);
+        if pending.is_empty() {
+            return Ok(());
+        }
+        receiver.recv_until(deadline, |frame| {
+            let Some(host) = self.classify_reply(&frame.record) else {
+                return Ok(true);
+            };
+            if let Some(position) = pending.iter().position(|&ip| ip == host.ip.octets) {
+                pending.swap_remove(position);
+            } else if !multicast {
+                return Ok(true);
+            }
+            hosts.insert(host.ip.octets, host);
+            Ok(!pending.is_empty())
+        })
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{
+    errors::errors::Result,
+    prelude::*,
+    sender::raw_socket::get_interface_index,
+    structs::{
+        ethernet::ETHERTYPE_IPV4,
+        l4_protocol::{L4Data, L4Protocol},
+        network_packet::NetworkPacket,
+        os_fingerprint::{OsDatabase, OsProber, OsReport, TcpFingerprint},
+        packet_builder::PacketBuilder,
+        socket::{RawSocketReceiver, RawSocketSender},
+        tcp::{TcpFlags, TcpOption},
+    },
+};
+use std::time::{Duration, Instant};
+
+// Sequence de la premiere sonde ; les suivantes sont decalees de 2^16
+const OS_PROBE_SEQUENCE: u32 = 0x4F53_0000;
+
+impl OsDatabase {
+
+    /// Charge une base depuis un fichier
+    pub fn load(path: &str) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+}
+
+impl OsProber {
+
+    /// Constructeur : base integree, 3 sondes, 1 retry, 1000 ms d'attente
+    pub fn new(template: PacketBuilder) -> Self {
+        Self {
+            template,
+            database: OsDatabase::builtin(),
+            probes: 3,
+            retries: 1,
+            timeout_ms: 1000,
+        }
+    }
+
+    /// Remplace la base d'empreintes
+    pub fn with_database(mut self, database: OsDatabase) -> Self {
+        self.database = database;
+        self
+    }
+
+    /// Nombre de SYN envoyes, ramene a 2 au minimum pour juger de l'identification IP
+    pub fn with_probes(mut self, probes: u8) -> Self {
+        self.probes = probes.max(2);
+        self
+    }
+
+    /// Renvois des sondes sans reponse
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Attente des SYN-ACK apres chaque vague
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Numero de sequence de la sonde `index`
+    pub fn probe_sequence(&self, index: u8) -> u32 {
+        OS_PROBE_SEQUENCE.wrapping_add((index as u32) << 16)
+    }
+
+    /// SYN `index` vers un port ouvert : port source decale par sonde et options
+    /// MSS, SACK permis, timestamps, NOP, window scale pour obtenir toutes celles du pair
+    pub fn build_probe(&self, port: u16, index: u8) -> Result<NetworkPacket> {
+        let mut builder = self.template.clone()
+            .with_tcp_flags(TcpFlags::SYN)
+            .with_sequence(self.probe_sequence(index))
+            .with_ack(0)
+            .with_tcp_options(vec![
+                TcpOption::Mss(1460),
+                TcpOption::SackPermitted,
+                TcpOption::Timestamps { value: index as u32 + 1, echo_reply: 0 },
+                TcpOption::NoOperation,
+                TcpOption::WindowScale(10),
+            ]);
+        builder.protocol = L4Protocol::Tcp;
+        builder.src_port = self.template.src_port.wrapping_add(index as u16);
+        builder.dst_port = port;
+        builder.payload = None;
+        builder.build_packet()
+    }
+
+    /// Index de la sonde a laquelle repond un SYN-ACK, None pour un autre paquet
+    pub fn classify_reply(&self, packet: &NetworkPacket, port: u16) -> Option<u8> {
+        let L4Data::Tcp(tcp) = &packet.l4_data else {
+            return None;
+        };
+        let ipv4 = packet.ipv4()?;
+        if ipv4.src_addr != self.template.dst_ip.octets
+            || ipv4.dst_addr != self.template.src_ip.octets
+            || tcp.src_port != port
+            || !tcp.tcp_flags().contains(TcpFlags::SYN | TcpFlags::ACK)
+        {
+            return None;
+        }
+        let index = tcp.dst_port.wrapping_sub(self.template.src_port);
+        let index = u8::try_from(index).ok().filter(|&index| index < self.probes)?;
+        (tcp.ack_nowledgment_number == self.probe_sequence(index).wrapping_add(1)).then_some(index)
+    }
+
+    /// Identifie le systeme derriere un port ouvert (ouvre les sockets brutes)
+    pub fn probe(&self, interface: &str, port: u16) -> Result<OsReport> {
+        let if_index = get_interface_index(interface)?;
+        let sender = RawSocketSender::new()?;
+        let mut receiver = RawSocketReceiver::with_protocol(ETHERTYPE_IPV4)?;
+        receiver.bind(interface)?;
+        self.probe_with(&sender, &receiver, if_index, port)
+    }
+
+    /// Identifie le systeme derriere un port ouvert avec des sockets deja ouvertes
+    /// Les SYN-ACK recus sont ranges par sonde ; l'empreinte vient du premier,
+    /// le comportement de l'identification IP de l'ensemble. Sans SYN-ACK
+    /// (port ferme ou filtre), le rapport est vide.
+    pub fn probe_with(
+        &self,
+        sender: &RawSocketSender,
+        receiver: &RawSocketReceiver,
+        if_index: i32,
+        port: u16,
+    ) -> Result<OsReport> {
+        let mut replies: BTreeMap<u8, NetworkPacket> = BTreeMap::new();
+
+        for _ in 0..=self.retries {
+            let pending: VecNoStd<u8> = (0..self.probes).filter(|index| !replies.contains_key(index)).collect();
+            if pending.is_empty() {
+                break;
+            }
+            for index in pending {
+                let frame = self.build_probe(port, index)?.assemble_packet()?;
+                sender.send(if_index, self.template.dst_mac, &frame)?;
+            }
+            let deadline = Instant::now() + Duration::from_millis(self.timeout_ms);
+            receiver.recv_until(deadline, |frame| {
+                if !frame.is_outgoing()
+                    && let Some(packet) = frame.record.packet
+                    && let Some(index) = self.classify_reply(&packet, port)
+                {
+                    replies.entry(index).or_insert(packet);
+                }
+                Ok(replies.len() < self.probes as usize)
+            })?;
+        }
+
+        let replies: VecNoStd<NetworkPacket> = replies.into_values().collect();
+        Ok(self.database.report(TcpFingerprint::from_packets(&replies)))
+    }
+}
@@ -0,0 +1,326 @@
+use crate::{
+    errors::errors::{Result, ParseError},
+    parsing::my_parser::{parse_escaped_bytes, parse_ports},
+    prelude::*,
+    structs::{
+        l4_protocol::L4Protocol,
+        scan::PortState,
+        service::{ServiceDatabase, ServiceMatch, ServiceProbe, ServiceReport, ServiceSignature},
+    },
+};
+use regex::bytes::{Captures, RegexBuilder};
+
+/// Base integree, au format des fichiers charges par `ServiceDatabase::load`
+pub const BUILTIN_SERVICE_DATABASE: &str = r#"
+# probe <nom> <tcp|udp> <ports|*> "<payload>"
+probe NULL tcp * ""
+probe GetRequest tcp 80-81,591,3000,5000,8000,8008,8080-8081,8888 "GET / HTTP/1.0\r\n\r\n"
+probe TLSClientHello tcp 443,465,636,853,993,995,8443 "\x16\x03\x01\x00\x33\x01\x00\x00\x2f\x03\x03RSNSRSNSRSNSRSNSRSNSRSNSRSNSRSNS\x00\x00\x08\xc0\x2f\xc0\x2b\x00\x9c\x00\x2f\x01\x00"
+probe DNSVersionBindReq udp 53 "\x13\x37\x01\x00\x00\x01\x00\x00\x00\x00\x00\x00\x07version\x04bind\x00\x00\x10\x00\x03"
+probe UdpEmpty udp * ""
+
+# match <service> <sonde|*> m/<regex>/[is] [p/<produit>/] [v/<version>/] [i/<info>/]
+match ssh NULL m/^SSH-([\d.]+)-OpenSSH_([\w.]+)/ p/OpenSSH/ v/$2/ i/protocol $1/
+match ssh NULL m/^SSH-([\d.]+)-dropbear_([\w.]+)/ p/Dropbear sshd/ v/$2/ i/protocol $1/
+match ssh NULL m/^SSH-([\d.]+)-([^\s]+)/ p/$2/ i/protocol $1/
+match ftp NULL m/^220[ -].*vsFTPd ([\w.]+)/ p/vsftpd/ v/$1/
+match ftp NULL m/^220[ -].*ProFTPD ([\w.]+)/ p/ProFTPD/ v/$1/
+match smtp NULL m/^220[ -]([\w.-]+) ESMTP Postfix/ p/Postfix smtpd/ i/hostname $1/
+match smtp NULL m/^220[ -]([\w.-]+) ESMTP Exim ([\w.]+)/ p/Exim smtpd/ v/$2/ i/hostname $1/
+match smtp NULL m/^220[ -]([\w.-]+) .*SMTP/i i/hostname $1/
+match ftp NULL m/^220[ -].*FTP/i
+match pop3 NULL m/^\+OK/
+match imap NULL m/^\* OK/
+match mysql NULL m/^.\x00\x00\x00\x0a([\w.-]+)\x00/s p/MySQL/ v/$1/
+match http GetRequest m|^HTTP/1\.[01] \d{3}.*?\r\nServer: Apache/([\w.]+)|s p/Apache httpd/ v/$1/
+match http GetRequest m|^HTTP/1\.[01] \d{3}.*?\r\nServer: nginx/([\w.]+)|s p/nginx/ v/$1/
+match http GetRequest m|^HTTP/1\.[01] \d{3}.*?\r\nServer: Microsoft-IIS/([\w.]+)|s p/Microsoft IIS httpd/ v/$1/
+match http GetRequest m|^HTTP/1\.[01] \d{3}.*?\r\nServer: ([^\r\n]+)|s p/$1/
+match http GetRequest m|^HTTP/1\.[01] \d{3}|
+match ssl TLSClientHello m|^\x16\x03[\x00-\x04]..\x02|s i/TLS handshake/
+match ssl TLSClientHello m|^\x15\x03[\x00-\x04]\x00\x02|s i/TLS alert/
+match domain DNSVersionBindReq m|^\x13\x37[\x80-\x87].{9}\x07version\x04bind\x00\x00\x10\x00\x03\xc0\x0c\x00\x10\x00\x03.{7}([\x20-\x7e]+)|s v/$1/
+match domain DNSVersionBindReq m|^\x13\x37[\x80-\x87]|s
+"#;
+
+impl ServiceSignature {
+
+    /// Signature sans modele ; la regex porte sur des octets (`\xHH`, classes ASCII)
+    pub fn new(service: &str, probe: &str, pattern: &str) -> Result<Self> {
+        Self::compile(service, probe, pattern, false, false)
+    }
+
+    /// Modele du produit (`$1`..`$9` : groupes captures)
+    pub fn with_product(mut self, product: &str) -> Self {
+        self.product = Some(product.into());
+        self
+    }
+
+    /// Modele de la version
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Modele des informations complementaires
+    pub fn with_info(mut self, info: &str) -> Self {
+        self.info = Some(info.into());
+        self
+    }
+
+    /// Vrai si la signature s'applique aux reponses de la sonde `probe`
+    pub fn applies_to(&self, probe: &str) -> bool {
+        self.probe == "*" || self.probe == probe
+    }
+
+    /// Service reconnu dans `response`, modeles developpes
+    pub fn match_response(&self, response: &[u8]) -> Option<ServiceMatch> {
+        let captures = self.pattern.captures(response)?;
+        let expand = |template: &Option<StringNoStd>| {
+            template.as_deref().and_then(|template| expand_template(template, &captures))
+        };
+        Some(ServiceMatch {
+            service: self.service.clone(),
+            product: expand(&self.product),
+            version: expand(&self.version),
+            info: expand(&self.info),
+        })
+    }
+
+    fn compile(
+        service: &str,
+        probe: &str,
+        pattern: &str,
+        case_insensitive: bool,
+        dot_all: bool,
+    ) -> Result<Self> {
+        let pattern = RegexBuilder::new(pattern)
+            .unicode(false)
+            .case_insensitive(case_insensitive)
+            .dot_matches_new_line(dot_all)
+            .build()
+            .map_err(|_| ParseError::InvalidFormat("invalid signature regex"))?;
+        Ok(Self {
+            service: service.into(),
+            probe: probe.into(),
+            pattern,
+            product: None,
+            version: None,
+            info: None,
+        })
+    }
+}
+
+impl ServiceDatabase {
+
+    /// Base vide
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Base integree : NULL (banniere spontanee), HTTP, TLS ClientHello, DNS version.bind
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_SERVICE_DATABASE).expect("builtin service database")
+    }
+
+    /// Charge une base depuis un fichier
+    pub fn load(path: &str) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse une base texte : une directive `probe` ou `match` par ligne,
+    /// lignes vides et commentaires `#` ignores
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut database = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |reason| ParseError::InvalidLine { line: index + 1, reason };
+            match split_word(line) {
+                Some(("probe", rest)) => database.add_probe(parse_probe(rest).map_err(invalid)?),
+                Some(("match", rest)) => database.add_signature(parse_signature(rest).map_err(invalid)?),
+                _ => return Err(invalid("expected probe or match")),
+            }
+        }
+        Ok(database)
+    }
+
+    /// Ajoute une sonde ; une sonde du meme nom est remplacee
+    pub fn add_probe(&mut self, probe: ServiceProbe) {
+        match self.probes.iter_mut().find(|existing| existing.name == probe.name) {
+            Some(existing) => *existing = probe,
+            None => self.probes.push(probe),
+        }
+    }
+
+    /// Ajoute une signature apres les existantes
+    pub fn add_signature(&mut self, signature: ServiceSignature) {
+        self.signatures.push(signature);
+    }
+
+    /// Integre une base utilisateur : ses sondes remplacent celles du meme nom,
+    /// ses signatures sont essayees avant les existantes
+    pub fn merge(&mut self, other: ServiceDatabase) {
+        for probe in other.probes {
+            self.add_probe(probe);
+        }
+        let mut signatures = other.signatures;
+        signatures.append(&mut self.signatures);
+        self.signatures = signatures;
+    }
+
+    /// Sonde par nom
+    pub fn probe(&self, name: &str) -> Option<&ServiceProbe> {
+        self.probes.iter().find(|probe| probe.name == name)
+    }
+
+    /// Sondes a essayer sur un port, dans l'ordre de la base
+    pub fn probes_for(&self, protocol: L4Protocol, port: u16) -> VecNoStd<&ServiceProbe> {
+        self.probes
+            .iter()
+            .filter(|probe| probe.protocol == protocol)
+            .filter(|probe| probe.ports.is_empty() || probe.ports.contains(&port))
+            .collect()
+    }
+
+    /// Premiere signature reconnaissant la reponse a la sonde `probe`
+    pub fn match_response(&self, probe: &str, response: &[u8]) -> Option<ServiceMatch> {
+        self.signatures
+            .iter()
+            .filter(|signature| signature.applies_to(probe))
+            .find_map(|signature| signature.match_response(response))
+    }
+}
+
+// probe <nom> <tcp|udp> <ports|*> "<payload>"
+fn parse_probe(line: &str) -> core::result::Result<ServiceProbe, &'static str> {
+    let (name, rest) = split_word(line).ok_or("missing probe name")?;
+    let (protocol, rest) = split_word(rest).ok_or("missing probe protocol")?;
+    let (ports, rest) = split_word(rest).ok_or("missing probe ports")?;
+    let protocol = match protocol {
+        "tcp" => L4Protocol::Tcp,
+        "udp" => L4Protocol::Udp,
+        _ => return Err("probe protocol must be tcp or udp"),
+    };
+    let ports = match ports {
+        "*" => VecNoStd::new(),
+        ports => parse_ports(ports).map_err(|_| "invalid probe ports")?,
+    };
+    let payload = rest
+        .trim()
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .ok_or("probe payload must be quoted")?;
+    Ok(ServiceProbe {
+        name: name.into(),
+        protocol,
+        ports,
+        payload: parse_escaped_bytes(payload).map_err(|_| "invalid probe payload escape")?,
+    })
+}
+
+// match <service> <sonde|*> m/<regex>/[is] [p/../] [v/../] [i/../]
+fn parse_signature(line: &str) -> core::result::Result<ServiceSignature, &'static str> {
+    let (service, rest) = split_word(line).ok_or("missing service name")?;
+    let (probe, rest) = split_word(rest).ok_or("missing probe name")?;
+    let ('m', pattern, rest) = delimited(rest).ok_or("missing m/<regex>/")? else {
+        return Err("missing m/<regex>/");
+    };
+    let flags_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+    let (flags, mut rest) = rest.split_at(flags_end);
+    if flags.chars().any(|flag| flag != 'i' && flag != 's') {
+        return Err("unknown regex flag");
+    }
+    let mut signature = ServiceSignature::compile(
+        service,
+        probe,
+        pattern,
+        flags.contains('i'),
+        flags.contains('s'),
+    ).map_err(|_| "invalid regex")?;
+
+    while !rest.trim().is_empty() {
+        let (key, value, next) = delimited(rest).ok_or("unterminated field")?;
+        match key {
+            'p' => signature.product = Some(value.into()),
+            'v' => signature.version = Some(value.into()),
+            'i' => signature.info = Some(value.into()),
+            _ => return Err("unknown field"),
+        }
+        rest = next;
+    }
+    Ok(signature)
+}
+
+// Premier mot et reste de la ligne
+fn split_word(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim_start();
+    if text.is_empty() {
+        return None;
+    }
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    Some((&text[..end], &text[end..]))
+}
+
+// Champ `<cle><delimiteur>contenu<delimiteur>` : (cle, contenu, reste)
+fn delimited(text: &str) -> Option<(char, &str, &str)> {
+    let text = text.trim_start();
+    let mut chars = text.chars();
+    let key = chars.next()?;
+    let delimiter = chars.next()?;
+    let start = key.len_utf8() + delimiter.len_utf8();
+    let end = start + text[start..].find(delimiter)?;
+    Some((key, &text[start..end], &text[end + delimiter.len_utf8()..]))
+}
+
+// Remplace `$1`..`$9` par les groupes captures ; None si le resultat est vide
+fn expand_template(template: &str, captures: &Captures) -> Option<StringNoStd> {
+    let mut result = StringNoStd::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek().and_then(|next| next.to_digit(10))) {
+            ('$', Some(group)) if group > 0 => {
+                chars.next();
+                if let Some(capture) = captures.get(group as usize) {
+                    result.push_str(&StringNoStd::from_utf8_lossy(capture.as_bytes()));
+                }
+            }
+            _ => result.push(c),
+        }
+    }
+    let result = result.trim();
+    (!result.is_empty()).then(|| result.into())
+}
+
+impl fmt::Display for ServiceMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.service)?;
+        if let Some(product) = &self.product {
+            write!(f, " {}", product)?;
+        }
+        if let Some(version) = &self.version {
+            write!(f, " {}", version)?;
+        }
+        if let Some(info) = &self.info {
+            write!(f, " ({})", info)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ServiceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let protocol = match self.protocol {
+            L4Protocol::Udp => "udp",
+            _ => "tcp",
+        };
+        write!(f, "{}/{} {}", self.port, protocol, self.state)?;
+        match &self.service {
+            Some(service) => write!(f, " {}", service),
+            None if self.state == PortState::Open || self.state == PortState::OpenFiltered => write!(f, " unknown"),
+            None => Ok(()),
+        }
+    }
+}
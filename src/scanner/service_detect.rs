@@ -0,0 +1,234 @@
+use crate::{
+    errors::errors::{Result, ParseError},
+    prelude::*,
+    sender::raw_socket::get_interface_index,
+    structs::{
+        ethernet::ETHERTYPE_IPV4,
+        l4_protocol::{L4Data, L4Protocol},
+        network_packet::NetworkPacket,
+        packet_builder::PacketBuilder,
+        scan::{PortState, UdpScanner},
+        service::{ServiceDatabase, ServiceDetector, ServiceProbe, ServiceReport},
+        socket::{RawSocketReceiver, RawSocketSender},
+        tcp_session::{BannerGrabber, Teardown},
+    },
+};
+use std::time::{Duration, Instant};
+
+impl ServiceDetector {
+
+    /// Constructeur : base integree, 1 retry, 2000 ms d'attente, 4096 octets par reponse
+    pub fn new(template: PacketBuilder) -> Self {
+        Self {
+            template,
+            database: ServiceDatabase::builtin(),
+            retries: 1,
+            timeout_ms: 2000,
+            max_bytes: 4096,
+        }
+    }
+
+    /// Remplace la base de sondes et de signatures
+    pub fn with_database(mut self, database: ServiceDatabase) -> Self {
+        self.database = database;
+        self
+    }
+
+    /// Renvois du SYN (TCP) ou du datagramme (UDP) sans reponse
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Attente d'une reponse, puis delai d'inactivite de la collecte (TCP)
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Taille maximale d'une reponse collectee
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Premier paquet emis par chaque sonde applicable : SYN (une connexion par sonde)
+    /// ou datagramme UDP
+    pub fn build_probes(&self, protocol: L4Protocol, port: u16) -> Result<VecNoStd<NetworkPacket>> {
+        self.probes(protocol, port)?
+            .into_iter()
+            .enumerate()
+            .map(|(index, probe)| match protocol {
+                L4Protocol::Udp => self.udp_scanner(probe, port).build_probe(port),
+                _ => self.grabber(index, probe).session(port).connect(),
+            })
+            .collect()
+    }
+
+    /// Detecte le service d'un port sur une interface (ouvre les sockets brutes)
+    pub fn detect(&self, interface: &str, protocol: L4Protocol, port: u16) -> Result<ServiceReport> {
+        let if_index = get_interface_index(interface)?;
+        let sender = RawSocketSender::new()?;
+        let mut receiver = RawSocketReceiver::with_protocol(ETHERTYPE_IPV4)?;
+        receiver.bind(interface)?;
+        self.detect_with(&sender, &receiver, if_index, protocol, port)
+    }
+
+    /// Detecte le service d'un port avec des sockets deja ouvertes
+    /// Les sondes sont essayees dans l'ordre de la base jusqu'a une signature reconnue ;
+    /// un port ferme ou filtre arrete la detection.
+    pub fn detect_with(
+        &self,
+        sender: &RawSocketSender,
+        receiver: &RawSocketReceiver,
+        if_index: i32,
+        protocol: L4Protocol,
+        port: u16,
+    ) -> Result<ServiceReport> {
+        match protocol {
+            L4Protocol::Tcp => self.detect_tcp(sender, receiver, if_index, port),
+            L4Protocol::Udp => self.detect_udp(sender, receiver, if_index, port),
+            L4Protocol::Icmp => Err(
+                ParseError::InvalidFormat("service detection needs tcp or udp")
+            ),
+        }
+    }
+
+    // Une connexion par sonde, fermee par RST une fois la reponse collectee
+    fn detect_tcp(
+        &self,
+        sender: &RawSocketSender,
+        receiver: &RawSocketReceiver,
+        if_index: i32,
+        port: u16,
+    ) -> Result<ServiceReport> {
+        let mut report = self.report(L4Protocol::Tcp, port, PortState::Filtered);
+        for (index, probe) in self.probes(L4Protocol::Tcp, port)?.into_iter().enumerate() {
+            let banner = self.grabber(index, probe).grab_with(sender, receiver, if_index, port)?;
+            report.state = banner.state;
+            if banner.state != PortState::Open {
+                break;
+            }
+            if self.record(&mut report, probe, banner.data) {
+                break;
+            }
+        }
+        Ok(report)
+    }
+
+    // Datagramme par sonde ; la reponse est classee comme pour un scan UDP
+    fn detect_udp(
+        &self,
+        sender: &RawSocketSender,
+        receiver: &RawSocketReceiver,
+        if_index: i32,
+        port: u16,
+    ) -> Result<ServiceReport> {
+        let mut report = self.report(L4Protocol::Udp, port, PortState::OpenFiltered);
+        for probe in self.probes(L4Protocol::Udp, port)? {
+            let scanner = self.udp_scanner(probe, port);
+            let Some((state, response)) = self.exchange_udp(&scanner, sender, receiver, if_index, port)? else {
+                continue;
+            };
+            report.state = state;
+            if state != PortState::Open || self.record(&mut report, probe, response) {
+                break;
+            }
+        }
+        Ok(report)
+    }
+
+    // Envoie la sonde jusqu'a `retries` fois et attend une reponse classee pour le port
+    fn exchange_udp(
+        &self,
+        scanner: &UdpScanner,
+        sender: &RawSocketSender,
+        receiver: &RawSocketReceiver,
+        if_index: i32,
+        port: u16,
+    ) -> Result<Option<(PortState, VecNoStd<u8>)>> {
+        let frame = scanner.build_probe(port)?.assemble_packet()?;
+        let mut reply = None;
+        for _ in 0..=self.retries {
+            sender.send(if_index, self.template.dst_mac, &frame)?;
+            let deadline = Instant::now() + Duration::from_millis(self.timeout_ms);
+            receiver.recv_until(deadline, |frame| {
+                if let Some((reply_port, state)) = scanner.classify_reply(&frame.record)
+                    && reply_port == port
+                {
+                    let mut response = match frame.record.packet.map(|packet| packet.l4_data) {
+                        Some(L4Data::Udp(udp)) => udp.payload.unwrap_or_default(),
+                        _ => VecNoStd::new(),
+                    };
+                    response.truncate(self.max_bytes);
+                    reply = Some((state, response));
+                    return Ok(false);
+                }
+                Ok(true)
+            })?;
+            if reply.is_some() {
+                break;
+            }
+        }
+        Ok(reply)
+    }
+
+    // Retient la reponse d'une sonde ; vrai si une signature la reconnait
+    fn record(&self, report: &mut ServiceReport, probe: &ServiceProbe, response: VecNoStd<u8>) -> bool {
+        if let Some(service) = self.database.match_response(&probe.name, &response) {
+            report.probe = Some(probe.name.clone());
+            report.service = Some(service);
+            report.response = response;
+            return true;
+        }
+        if report.response.is_empty() {
+            report.response = response;
+        }
+        false
+    }
+
+    fn probes(&self, protocol: L4Protocol, port: u16) -> Result<VecNoStd<&ServiceProbe>> {
+        let probes = self.database.probes_for(protocol, port);
+        if probes.is_empty() {
+            return Err(
+                ParseError::MissingRequiredField("service probe")
+            );
+        }
+        Ok(probes)
+    }
+
+    // Connexion d'une sonde TCP ; port source decale par sonde pour ne pas
+    // retomber sur la connexion precedente cote serveur
+    fn grabber(&self, index: usize, probe: &ServiceProbe) -> BannerGrabber {
+        let mut template = self.template.clone();
+        template.src_port = template.src_port.wrapping_add(index as u16);
+        let grabber = BannerGrabber::new(template)
+            .with_retries(self.retries)
+            .with_timeout_ms(self.timeout_ms)
+            .with_max_bytes(self.max_bytes)
+            .with_teardown(Teardown::Rst);
+        if probe.payload.is_empty() {
+            grabber
+        } else {
+            grabber.with_payload(probe.payload.clone())
+        }
+    }
+
+    fn udp_scanner(&self, probe: &ServiceProbe, port: u16) -> UdpScanner {
+        UdpScanner::new(self.template.clone())
+            .with_retries(self.retries)
+            .with_timeout_ms(self.timeout_ms)
+            .with_payload(port, probe.payload.clone())
+    }
+
+    fn report(&self, protocol: L4Protocol, port: u16, state: PortState) -> ServiceReport {
+        ServiceReport {
+            port,
+            protocol,
+            state,
+            probe: None,
+            service: None,
+            response: VecNoStd::new(),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{
+    errors::errors::Result,
+    prelude::*,
+    sender::raw_socket::get_interface_index,
+    structs::{
+        ethernet::ETHERTYPE_IPV4,
+        l4_protocol::{L4Data, L4Protocol},
+        network_packet::NetworkPacket,
+        packet_builder::PacketBuilder,
+        scan::{PortResult, PortState, SynScanner},
+        socket::{RawSocketReceiver, RawSocketSender},
+        tcp::TcpFlags,
+    },
+    utils::clock::{Clock, SystemClock},
+};
+use std::time::{Duration, Instant};
+
+impl SynScanner {
+
+    /// Constructeur : 1 retry et 1000 ms d'attente par defaut
+    pub fn new(template: PacketBuilder) -> Self {
+        let now = SystemClock.now_ns();
+        Self {
+            template,
+            retries: 1,
+            timeout_ms: 1000,
+            tcp_flags: TcpFlags::SYN,
+            secret: (now as u32) ^ ((now >> 32) as u32),
+        }
+    }
+
+    /// Nombre de renvois pour les ports sans reponse
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Attente des reponses apres chaque vague de sondes
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Drapeaux des sondes : FIN, vide (NULL), FIN|PSH|URG (Xmas) ou ACK
+    pub fn with_tcp_flags(mut self, tcp_flags: TcpFlags) -> Self {
+        self.tcp_flags = tcp_flags;
+        self
+    }
+
+    /// Etat d'un port reste sans reponse : un port ouvert ignore un segment
+    /// sans SYN ni ACK (`OpenFiltered`), sinon la sonde a ete filtree
+    pub fn unanswered_state(&self) -> PortState {
+        if self.tcp_flags.contains(TcpFlags::SYN) || self.tcp_flags.contains(TcpFlags::ACK) {
+            PortState::Filtered
+        } else {
+            PortState::OpenFiltered
+        }
+    }
+
+    /// Numero de sequence de la sonde d'un port
+    /// Derive de la destination et du secret : une reponse valide acquitte ce numero + 1.
+    pub fn probe_sequence(&self, port: u16) -> u32 {
+        let mut hash: u32 = 0x811C_9DC5 ^ self.secret;
+        let dst = self.template.dst_ip.octets;
+        let src_port = self.template.src_port.to_be_bytes();
+        let dst_port = port.to_be_bytes();
+        for byte in dst.iter().chain(src_port.iter()).chain(dst_port.iter()) {
+            hash ^= *byte as u32;
+            hash = hash.wrapping_mul(0x0100_0193);
+        }
+        hash
+    }
+
+    /// Construit la sonde d'un port a partir du template
+    /// Une sonde ACK porte aussi la sequence derivee en acquittement.
+    pub fn build_probe(&self, port: u16) -> Result<NetworkPacket> {
+        let mut builder = self.template.clone();
+        builder.dst_port = port;
+        builder.protocol = L4Protocol::Tcp;
+        builder.tcp_flags = self.tcp_flags;
+        builder.sequence_number = self.probe_sequence(port);
+        builder.ack_number = if self.tcp_flags.contains(TcpFlags::ACK) { self.probe_sequence(port) } else { 0 };
+        builder.payload = None;
+
+        builder.build_packet()
+    }
+
+    /// Associe une reponse a une sonde
+    /// Sonde SYN : SYN-ACK `Open`, RST `Closed`. Sonde FIN, NULL ou Xmas : RST `Closed`.
+    /// Le RST doit acquitter la sequence de la sonde plus son SYN et son FIN.
+    /// Sonde ACK : RST `Unfiltered`, reconnu a sa sequence egale a l'acquittement de la sonde.
+    pub fn classify_reply(&self, packet: &NetworkPacket) -> Option<(u16, PortState)> {
+        let ipv4 = packet.ipv4()?;
+        if ipv4.src_addr != self.template.dst_ip.octets
+            || ipv4.dst_addr != self.template.src_ip.octets
+        {
+            return None;
+        }
+        let L4Data::Tcp(tcp) = &packet.l4_data else {
+            return None;
+        };
+        if tcp.dst_port != self.template.src_port {
+            return None;
+        }
+
+        let sequence = self.probe_sequence(tcp.src_port);
+        let flags = tcp.tcp_flags();
+        if self.tcp_flags.contains(TcpFlags::ACK) {
+            return (flags.contains(TcpFlags::RST) && tcp.sequence_number == sequence)
+                .then_some((tcp.src_port, PortState::Unfiltered));
+        }
+        let consumed = [TcpFlags::SYN, TcpFlags::FIN]
+            .iter()
+            .filter(|flag| self.tcp_flags.contains(**flag))
+            .count() as u32;
+        if tcp.ack_nowledgment_number != sequence.wrapping_add(consumed) {
+            return None;
+        }
+
+        if flags.contains(TcpFlags::RST) {
+            Some((tcp.src_port, PortState::Closed))
+        } else if flags.contains(TcpFlags::SYN | TcpFlags::ACK) && self.tcp_flags.contains(TcpFlags::SYN) {
+            Some((tcp.src_port, PortState::Open))
+        } else {
+            None
+        }
+    }
+
+    /// Scanne les ports sur une interface (ouvre les sockets brutes)
+    pub fn scan(&self, interface: &str, ports: &[u16]) -> Result<VecNoStd<PortResult>> {
+        let if_index = get_interface_index(interface)?;
+        let sender = RawSocketSender::new()?;
+        let mut receiver = RawSocketReceiver::with_protocol(ETHERTYPE_IPV4)?;
+        receiver.bind(interface)?;
+        self.scan_with(&sender, &receiver, if_index, ports)
+    }
+
+    /// Scanne les ports avec des sockets deja ouvertes
+    /// Chaque vague envoie une sonde par port encore sans reponse puis attend
+    /// `timeout_ms` ; les ports muets apres `retries` renvois prennent `unanswered_state`.
+    pub fn scan_with(
+        &self,
+        sender: &RawSocketSender,
+        receiver: &RawSocketReceiver,
+        if_index: i32,
+        ports: &[u16],
+    ) -> Result<VecNoStd<PortResult>> {
+        let mut results: BTreeMap<u16, PortResult> = ports
+            .iter()
+            .map(|&port| (port, PortResult { port, state: self.unanswered_state(), attempts: 0 }))
+            .collect();
+        let mut pending: VecNoStd<u16> = results.keys().copied().collect();
+
+        for _ in 0..=self.retries {
+            if pending.is_empty() {
+                break;
+            }
+            for &port in &pending {
+                let frame = self.build_probe(port)?.assemble_packet()?;
+                sender.send(if_index, self.template.dst_mac, &frame)?;
+                if let Some(result) = results.get_mut(&port) {
+                    result.attempts += 1;
+                }
+            }
+
+            let deadline = Instant::now() + Duration::from_millis(self.timeout_ms);
+            receiver.recv_until(deadline, |frame| {
+                if let Some(packet) = &frame.record.packet
+                    && let Some((port, state)) = self.classify_reply(packet)
+                    && let Some(position) = pending.iter().position(|&p| p == port)
+                {
+                    pending.swap_remove(position);
+                    if let Some(result) = results.get_mut(&port) {
+                        result.state = state;
+                    }
+                }
+                Ok(!pending.is_empty())
+            })?;
+        }
+
+        Ok(results.into_values().collect())
+    }
+}
+
+impl fmt::Display for PortState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PortState::Open => write!(f, "open"),
+            PortState::Closed => write!(f, "closed"),
+            PortState::Filtered => write!(f, "filtered"),
+            PortState::OpenFiltered => write!(f, "open|filtered"),
+            PortState::Unfiltered => write!(f, "unfiltered"),
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{
+    errors::errors::Result,
+    prelude::*,
+    sender::raw_socket::get_interface_index,
+    structs::{
+        ethernet::ETHERTYPE_IPV4,
+        formats::PacketRecord,
+        icmp::ICMP_DEST_UNREACHABLE,
+        l4_protocol::{L4Data, L4Protocol},
+        network_packet::NetworkPacket,
+        packet_builder::PacketBuilder,
+        scan::{PortResult, PortState, UdpScanner},
+        socket::{RawSocketReceiver, RawSocketSender},
+    },
+};
+use std::time::{Duration, Instant};
+
+// Protocole UDP cite dans les erreurs ICMP
+const PROTOCOL_UDP: u8 = 17;
+
+// Codes ICMP destination unreachable
+const ICMP_PORT_UNREACHABLE: u8 = 3;
+// host/protocol unreachable, reseau/hote interdit, communication interdite
+const ICMP_FILTERED_CODES: [u8; 5] = [1, 2, 9, 10, 13];
+
+// Sonde NTP client (mode 3, version 4)
+const NTP_REQUEST: [u8; 48] = {
+    let mut request = [0u8; 48];
+    request[0] = 0x23;
+    request
+};
+
+// Sonde SNMPv1 GetRequest sysDescr.0, communaute "public"
+const SNMP_GET_SYSDESCR: [u8; 43] = [
+    0x30, 0x29, 0x02, 0x01, 0x00, 0x04, 0x06, b'p', b'u', b'b', b'l', b'i', b'c',
+    0xA0, 0x1C, 0x02, 0x04, 0x52, 0x53, 0x4E, 0x53, 0x02, 0x01, 0x00, 0x02, 0x01, 0x00,
+    0x30, 0x0E, 0x30, 0x0C, 0x06, 0x08, 0x2B, 0x06, 0x01, 0x02, 0x01, 0x01, 0x01, 0x00,
+    0x05, 0x00,
+];
+
+// Requete DNS standard : NS de la racine
+const DNS_QUERY: [u8; 17] = [
+    0x52, 0x53, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x02, 0x00, 0x01,
+];
+
+// Requete NetBIOS NBSTAT sur le nom "*"
+const NBSTAT_QUERY: [u8; 50] = [
+    0x52, 0x53, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x20, b'C', b'K', b'A', b'A', b'A', b'A', b'A', b'A', b'A', b'A', b'A', b'A',
+    b'A', b'A', b'A', b'A', b'A', b'A', b'A', b'A', b'A', b'A', b'A', b'A', b'A',
+    b'A', b'A', b'A', b'A', b'A', b'A', b'A', 0x00, 0x00, 0x21, 0x00, 0x01,
+];
+
+/// Payload integre adapte au service attendu sur le port
+/// Un port inconnu recoit un datagramme vide.
+pub fn udp_probe_payload(port: u16) -> VecNoStd<u8> {
+    match port {
+        53 => DNS_QUERY.to_vec(),
+        69 => b"\x00\x01rsns.txt\x00octet\x00".to_vec(),
+        123 => NTP_REQUEST.to_vec(),
+        137 => NBSTAT_QUERY.to_vec(),
+        161 => SNMP_GET_SYSDESCR.to_vec(),
+        1900 => b"M-SEARCH * HTTP/1.1\r\nHOST: 239.255.255.250:1900\r\nMAN: \"ssdp:discover\"\r\nMX: 1\r\nST: ssdp:all\r\n\r\n".to_vec(),
+        11211 => b"\x00\x01\x00\x00\x00\x01\x00\x00stats\r\n".to_vec(),
+        _ => VecNoStd::new(),
+    }
+}
+
+impl UdpScanner {
+
+    /// Constructeur : 1 retry, 1000 ms d'attente et 100 sondes/s par defaut
+    pub fn new(template: PacketBuilder) -> Self {
+        Self {
+            template,
+            retries: 1,
+            timeout_ms: 1000,
+            rate_limit: 100,
+            payloads: BTreeMap::new(),
+        }
+    }
+
+    /// Nombre de renvois pour les ports sans reponse
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Attente des reponses apres la derniere sonde de chaque vague
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Nombre maximal de sondes par seconde (0 : pas de limite)
+    pub fn with_rate_limit(mut self, probes_per_sec: u32) -> Self {
+        self.rate_limit = probes_per_sec;
+        self
+    }
+
+    /// Remplace la sonde integree d'un port
+    pub fn with_payload(mut self, port: u16, payload: VecNoStd<u8>) -> Self {
+        self.payloads.insert(port, payload);
+        self
+    }
+
+    /// Payload envoye a un port (utilisateur puis sondes integrees)
+    pub fn probe_payload(&self, port: u16) -> VecNoStd<u8> {
+        self.payloads
+            .get(&port)
+            .cloned()
+            .unwrap_or_else(|| udp_probe_payload(port))
+    }
+
+    /// Construit la sonde UDP d'un port a partir du template
+    pub fn build_probe(&self, port: u16) -> Result<NetworkPacket> {
+        let mut builder = self.template.clone();
+        builder.dst_port = port;
+        builder.protocol = L4Protocol::Udp;
+        builder.payload = Some(self.probe_payload(port));
+        builder.build_packet()
+    }
+
+    /// Associe une trame recue a une sonde
+    /// Reponse UDP du port : `Open` ; ICMP port unreachable citant la sonde : `Closed` ;
+    /// autre ICMP unreachable citant la sonde : `Filtered`.
+    pub fn classify_reply(&self, record: &PacketRecord) -> Option<(u16, PortState)> {
+        let packet = record.packet.as_ref()?;
+        let ipv4 = packet.ipv4()?;
+        if ipv4.dst_addr != self.template.src_ip.octets {
+            return None;
+        }
+        match &packet.l4_data {
+            L4Data::Udp(udp)
+                if ipv4.src_addr == self.template.dst_ip.octets
+                    && udp.dst_port == self.template.src_port =>
+            {
+                Some((udp.src_port, PortState::Open))
+            }
+            L4Data::Icmp(icmp) if icmp.icmp_type == ICMP_DEST_UNREACHABLE => {
+                let (quoted_ip, quoted_l4) = icmp.quoted_datagram()?;
+                if quoted_ip.protocol != PROTOCOL_UDP
+                    || quoted_ip.dst_addr != self.template.dst_ip.octets
+                    || quoted_l4.len() < 4
+                    || u16::from_be_bytes([quoted_l4[0], quoted_l4[1]]) != self.template.src_port
+                {
+                    return None;
+                }
+                let port = u16::from_be_bytes([quoted_l4[2], quoted_l4[3]]);
+                if icmp.code == ICMP_PORT_UNREACHABLE {
+                    Some((port, PortState::Closed))
+                } else if ICMP_FILTERED_CODES.contains(&icmp.code) {
+                    Some((port, PortState::Filtered))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Scanne les ports sur une interface (ouvre les sockets brutes)
+    pub fn scan(&self, interface: &str, ports: &[u16]) -> Result<VecNoStd<PortResult>> {
+        let if_index = get_interface_index(interface)?;
+        let sender = RawSocketSender::new()?;
+        let mut receiver = RawSocketReceiver::with_protocol(ETHERTYPE_IPV4)?;
+        receiver.bind(interface)?;
+        self.scan_with(&sender, &receiver, if_index, ports)
+    }
+
+    /// Scanne les ports avec des sockets deja ouvertes
+    /// Les sondes d'une vague sont espacees selon `rate_limit`, les reponses
+    /// etant lues entre deux envois ; les ports muets sont renvoyes `retries` fois.
+    pub fn scan_with(
+        &self,
+        sender: &RawSocketSender,
+        receiver: &RawSocketReceiver,
+        if_index: i32,
+        ports: &[u16],
+    ) -> Result<VecNoStd<PortResult>> {
+        let mut results: BTreeMap<u16, PortResult> = ports
+            .iter()
+            .map(|&port| (port, PortResult { port, state: PortState::OpenFiltered, attempts: 0 }))
+            .collect();
+        let mut pending: VecNoStd<u16> = results.keys().copied().collect();
+        let interval = match self.rate_limit {
+            0 => Duration::ZERO,
+            rate => Duration::from_secs(1) / rate,
+        };
+
+        for _ in 0..=self.retries {
+            if pending.is_empty() {
+                break;
+            }
+            for port in pending.clone() {
+                let frame = self.build_probe(port)?.assemble_packet()?;
+                sender.send(if_index, self.template.dst_mac, &frame)?;
+                if let Some(result) = results.get_mut(&port) {
+                    result.attempts += 1;
+                }
+                self.collect_replies(receiver, Instant::now() + interval, &mut pending, &mut results)?;
+            }
+            let deadline = Instant::now() + Duration::from_millis(self.timeout_ms);
+            self.collect_replies(receiver, deadline, &mut pending, &mut results)?;
+        }
+
+        Ok(results.into_values().collect())
+    }
+
+    // Lit les reponses jusqu'a `deadline` ou jusqu'a ce que tous les ports soient resolus
+    fn collect_replies(
+        &self,
+        receiver: &RawSocketReceiver,
+        deadline: Instant,
+        pending: &mut VecNoStd<u16>,
+        results: &mut BTreeMap<u16, PortResult>,
+    ) -> Result<()> {
+        if pending.is_empty() {
+            return Ok(());
+        }
+        receiver.recv_until(deadline, |frame| {
+            if let Some((port, state)) = self.classify_reply(&frame.record)
+                && let Some(position) = pending.iter().position(|&p| p == port)
+            {
+                pending.swap_remove(position);
+                if let Some(result) = results.get_mut(&port) {
+                    result.state = state;
+                }
+            }
+            Ok(!pending.is_empty())
+        })
+    }
+}
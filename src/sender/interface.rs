@@ -0,0 +1,168 @@
+use crate::errors::errors::{ParseError, Result};
+use crate::prelude::*;
+use crate::sender::{
+    neighbor::{best_route, read_route_table},
+    raw_socket::interface_ioctl,
+};
+use crate::structs::{
+    interface::{InterfaceAddress, NetworkInterface},
+    ipv4::Ipv4Addr,
+    neighbor::Route,
+};
+use core::ffi::CStr;
+
+/// Enumere les interfaces reseau (getifaddrs) triees par index
+/// Chaque interface porte sa MAC, ses adresses IPv4, son MTU et son etat.
+pub fn list_interfaces() -> Result<VecNoStd<NetworkInterface>> {
+    let mut head: *mut libc::ifaddrs = core::ptr::null_mut();
+    if unsafe { libc::getifaddrs(&mut head) } < 0 {
+        return Err(
+            ParseError::IoError(
+                "getifaddrs() failed".into()
+            )
+        );
+    }
+
+    let mut interfaces: VecNoStd<NetworkInterface> = VecNoStd::new();
+    let mut cursor = head;
+    while !cursor.is_null() {
+        let entry = unsafe { &*cursor };
+        cursor = entry.ifa_next;
+        if entry.ifa_name.is_null() {
+            continue;
+        }
+        let name = unsafe { CStr::from_ptr(entry.ifa_name) }.to_string_lossy().to_string();
+        let position = match interfaces.iter().position(|iface| iface.name == name) {
+            Some(position) => position,
+            None => {
+                interfaces.push(NetworkInterface {
+                    index: unsafe { libc::if_nametoindex(entry.ifa_name) } as i32,
+                    mtu: interface_mtu(&name).unwrap_or(0),
+                    name,
+                    mac: [0u8; 6],
+                    ipv4: VecNoStd::new(),
+                    is_up: entry.ifa_flags & libc::IFF_UP as u32 != 0,
+                    is_loopback: entry.ifa_flags & libc::IFF_LOOPBACK as u32 != 0,
+                });
+                interfaces.len() - 1
+            }
+        };
+        if entry.ifa_addr.is_null() {
+            continue;
+        }
+
+        let iface = &mut interfaces[position];
+        match unsafe { (*entry.ifa_addr).sa_family } as i32 {
+            libc::AF_PACKET => {
+                let link = unsafe {
+                    core::ptr::read_unaligned(entry.ifa_addr as *const libc::sockaddr_ll)
+                };
+                if link.sll_halen == 6 {
+                    iface.mac.copy_from_slice(&link.sll_addr[..6]);
+                }
+            }
+            libc::AF_INET => {
+                let addr = unsafe { sockaddr_ipv4(entry.ifa_addr) };
+                let netmask = if entry.ifa_netmask.is_null() {
+                    Ipv4Addr { octets: [255; 4] }
+                } else {
+                    unsafe { sockaddr_ipv4(entry.ifa_netmask) }
+                };
+                iface.ipv4.push(InterfaceAddress { addr, netmask });
+            }
+            _ => {}
+        }
+    }
+    unsafe { libc::freeifaddrs(head) };
+
+    interfaces.sort_by_key(|iface| iface.index);
+    Ok(interfaces)
+}
+
+/// Interface reseau par son nom
+pub fn interface_by_name(name: &str) -> Result<NetworkInterface> {
+    list_interfaces()?
+        .into_iter()
+        .find(|iface| iface.name == name)
+        .ok_or(ParseError::InvalidFormat("invalid interface name"))
+}
+
+/// Interface de sortie vers `dst` selon la table de routage du noyau
+/// Sans destination, l'interface de la route par defaut est retenue.
+pub fn select_interface(dst: Option<Ipv4Addr>) -> Result<NetworkInterface> {
+    let interfaces = list_interfaces()?;
+    pick_interface(&interfaces, &read_route_table(), dst)
+        .cloned()
+        .ok_or(ParseError::InvalidFormat("no usable network interface"))
+}
+
+/// Choix de l'interface parmi `interfaces` (actives uniquement) :
+/// boucle locale pour 127.0.0.0/8, meilleure route vers `dst` (ou route par
+/// defaut), sous-reseau connecte, puis premiere interface adressee hors boucle locale.
+pub fn pick_interface<'a>(
+    interfaces: &'a [NetworkInterface],
+    routes: &[Route],
+    dst: Option<Ipv4Addr>,
+) -> Option<&'a NetworkInterface> {
+    let up = |name: &str| interfaces.iter().find(|iface| iface.is_up && iface.name == name);
+    let target = dst.map(|ip| ip.octets).unwrap_or([0u8; 4]);
+
+    if target[0] == 127 {
+        return interfaces.iter().find(|iface| iface.is_up && iface.is_loopback);
+    }
+    if let Some(route) = best_route(routes, target, |route| up(&route.interface).is_some()) {
+        return up(&route.interface);
+    }
+    if let Some(dst) = dst
+        && let Some(iface) = interfaces.iter().find(|iface| iface.is_up && iface.contains(dst))
+    {
+        return Some(iface);
+    }
+    interfaces
+        .iter()
+        .find(|iface| iface.is_up && !iface.is_loopback && !iface.ipv4.is_empty())
+        .or_else(|| interfaces.iter().find(|iface| iface.is_up && iface.is_loopback))
+}
+
+impl NetworkInterface {
+
+    /// `ip` appartient a l'un des sous-reseaux de l'interface
+    pub fn contains(&self, ip: Ipv4Addr) -> bool {
+        self.ipv4.iter().any(|address| address.contains(ip))
+    }
+
+    /// Adresse source vers `dst` : celle du sous-reseau de `dst`, sinon la premiere
+    pub fn source_address(&self, dst: Option<Ipv4Addr>) -> Option<Ipv4Addr> {
+        dst.and_then(|dst| self.ipv4.iter().find(|address| address.contains(dst)))
+            .or_else(|| self.ipv4.first())
+            .map(|address| address.addr)
+    }
+}
+
+impl InterfaceAddress {
+
+    /// Longueur du prefixe (ex: 24 pour 255.255.255.0)
+    pub fn prefix_len(&self) -> u8 {
+        u32::from_be_bytes(self.netmask.octets).count_ones() as u8
+    }
+
+    /// `ip` appartient au sous-reseau de l'adresse
+    pub fn contains(&self, ip: Ipv4Addr) -> bool {
+        let mask = u32::from_be_bytes(self.netmask.octets);
+        u32::from_be_bytes(ip.octets) & mask == u32::from_be_bytes(self.addr.octets) & mask
+    }
+}
+
+// MTU de l'interface (ioctl SIOCGIFMTU)
+fn interface_mtu(name: &str) -> Result<u32> {
+    let ifr = interface_ioctl(name, libc::SIOCGIFMTU as _)?;
+    Ok(unsafe { ifr.ifr_ifru.ifru_mtu } as u32)
+}
+
+// Adresse d'un sockaddr_in
+unsafe fn sockaddr_ipv4(addr: *const libc::sockaddr) -> Ipv4Addr {
+    let addr = unsafe { core::ptr::read_unaligned(addr as *const libc::sockaddr_in) };
+    Ipv4Addr {
+        octets: addr.sin_addr.s_addr.to_ne_bytes()
+    }
+}
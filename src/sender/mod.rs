@@ -1,2 +1,4 @@
 pub mod raw_socket;
-
+pub mod raw_receiver;
+pub mod neighbor;
+pub mod interface;
\ No newline at end of file
@@ -0,0 +1,233 @@
+use crate::errors::errors::{ParseError, Result};
+use crate::parsing::my_parser::{parse_ipv4, parse_mac};
+use crate::prelude::*;
+use crate::sender::{interface::interface_by_name, raw_socket::get_interface_index};
+use crate::structs::{
+    arp::ETHERTYPE_ARP,
+    interface::NetworkInterface,
+    ipv4::Ipv4Addr,
+    neighbor::{NeighborResolver, Route},
+    scan::ArpScanner,
+    socket::{RawSocketReceiver, RawSocketSender},
+};
+
+// Tables du noyau
+const PROC_NET_ARP: &str = "/proc/net/arp";
+const PROC_NET_ROUTE: &str = "/proc/net/route";
+
+// Flags des tables : entree ARP complete, route active
+const ATF_COM: u32 = 0x02;
+const RTF_UP: u32 = 0x01;
+
+/// Lit les entrees completes de `/proc/net/arp` pour une interface
+pub fn parse_arp_table(contents: &str, interface: &str) -> BTreeMap<[u8; 4], [u8; 6]> {
+    let mut table = BTreeMap::new();
+    // IP address, HW type, Flags, HW address, Mask, Device
+    for line in contents.lines().skip(1) {
+        let fields: VecNoStd<&str> = line.split_whitespace().collect();
+        if fields.len() < 6 || fields[5] != interface {
+            continue;
+        }
+        let flags = u32::from_str_radix(fields[2].trim_start_matches("0x"), 16).unwrap_or(0);
+        if let (Ok(ip), Ok(mac)) = (parse_ipv4(fields[0]), parse_mac(fields[3]))
+            && flags & ATF_COM != 0
+            && mac != [0u8; 6]
+        {
+            table.insert(ip.octets, mac);
+        }
+    }
+    table
+}
+
+/// Lit les routes actives de `/proc/net/route`
+/// Les adresses y sont ecrites en hexadecimal dans l'ordre des octets de la machine.
+pub fn parse_route_table(contents: &str) -> VecNoStd<Route> {
+    let mut routes = VecNoStd::new();
+    // Iface, Destination, Gateway, Flags, RefCnt, Use, Metric, Mask, ...
+    for line in contents.lines().skip(1) {
+        let fields: VecNoStd<&str> = line.split_whitespace().collect();
+        if fields.len() < 8 {
+            continue;
+        }
+        let (Some(destination), Some(gateway), Some(mask)) = (
+            route_address(fields[1]),
+            route_address(fields[2]),
+            route_address(fields[7]),
+        ) else {
+            continue;
+        };
+        let flags = u32::from_str_radix(fields[3], 16).unwrap_or(0);
+        if flags & RTF_UP == 0 {
+            continue;
+        }
+        routes.push(Route {
+            interface: fields[0].to_string(),
+            destination,
+            gateway,
+            mask,
+            metric: fields[6].parse().unwrap_or(0),
+        });
+    }
+    routes
+}
+
+/// Route la plus specifique vers `dst` parmi celles retenues par `accept`
+/// (a longueur de prefixe egale, la metrique la plus faible)
+pub fn best_route<F>(routes: &[Route], dst: [u8; 4], mut accept: F) -> Option<&Route>
+where
+    F: FnMut(&Route) -> bool,
+{
+    let dst_bits = u32::from_be_bytes(dst);
+    routes
+        .iter()
+        .filter(|route| accept(route))
+        .filter(|route| {
+            let mask = u32::from_be_bytes(route.mask);
+            dst_bits & mask == u32::from_be_bytes(route.destination) & mask
+        })
+        .min_by_key(|route| (core::cmp::Reverse(u32::from_be_bytes(route.mask).count_ones()), route.metric))
+}
+
+/// Prochain saut vers `dst` par une interface : passerelle de la meilleure route,
+/// `dst` si la route est directe ou si aucune route de l'interface ne correspond.
+pub fn route_next_hop(routes: &[Route], interface: &str, dst: [u8; 4]) -> [u8; 4] {
+    best_route(routes, dst, |route| route.interface == interface)
+        .map(|route| if route.gateway == [0u8; 4] { dst } else { route.gateway })
+        .unwrap_or(dst)
+}
+
+/// Routes actives du noyau (vide si `/proc/net/route` est illisible)
+pub fn read_route_table() -> VecNoStd<Route> {
+    std::fs::read_to_string(PROC_NET_ROUTE)
+        .map(|contents| parse_route_table(&contents))
+        .unwrap_or_default()
+}
+
+impl NeighborResolver {
+
+    /// Constructeur : adresses de l'interface, 1 retry et 1000 ms d'attente par defaut
+    pub fn new(interface: &str) -> Result<Self> {
+        Ok(Self::from_interface(&interface_by_name(interface)?))
+    }
+
+    /// Constructeur depuis une interface deja enumeree
+    pub fn from_interface(interface: &NetworkInterface) -> Self {
+        Self {
+            interface: interface.name.clone(),
+            src_mac: interface.mac,
+            src_ip: interface.source_address(None).unwrap_or(Ipv4Addr { octets: [0u8; 4] }),
+            retries: 1,
+            timeout_ms: 1000,
+            cache: BTreeMap::new(),
+        }
+    }
+
+    /// Adresses annoncees dans les requetes ARP
+    pub fn with_source(mut self, src_mac: [u8; 6], src_ip: Ipv4Addr) -> Self {
+        self.src_mac = src_mac;
+        self.src_ip = src_ip;
+        self
+    }
+
+    /// Nombre de renvois de la requete ARP
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Attente de la reponse ARP
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Ajoute ou remplace une entree du cache
+    pub fn insert(&mut self, ip: Ipv4Addr, mac: [u8; 6]) {
+        self.cache.insert(ip.octets, mac);
+    }
+
+    /// Entree du cache pour une adresse
+    pub fn cached(&self, ip: Ipv4Addr) -> Option<[u8; 6]> {
+        self.cache.get(&ip.octets).copied()
+    }
+
+    /// Prochain saut vers une destination selon `/proc/net/route`
+    pub fn next_hop(&self, dst: Ipv4Addr) -> Ipv4Addr {
+        Ipv4Addr { octets: route_next_hop(&read_route_table(), &self.interface, dst.octets) }
+    }
+
+    /// Resolution sans emission : adresses speciales, cache puis `/proc/net/arp`
+    /// Boucle locale (ou interface sans MAC) : MAC nulle ; broadcast et multicast : MAC derivee.
+    pub fn lookup(&mut self, dst: Ipv4Addr) -> Option<[u8; 6]> {
+        if let Some(mac) = special_mac(dst, self.src_mac) {
+            return Some(mac);
+        }
+        let hop = self.next_hop(dst);
+        if let Some(mac) = self.cached(hop) {
+            return Some(mac);
+        }
+        let table = std::fs::read_to_string(PROC_NET_ARP)
+            .map(|contents| parse_arp_table(&contents, &self.interface))
+            .unwrap_or_default();
+        let mac = table.get(&hop.octets).copied()?;
+        self.insert(hop, mac);
+        Some(mac)
+    }
+
+    /// Resolution complete : `lookup` puis requete ARP vers le prochain saut
+    pub fn resolve(&mut self, dst: Ipv4Addr) -> Result<[u8; 6]> {
+        if let Some(mac) = self.lookup(dst) {
+            return Ok(mac);
+        }
+        let if_index = get_interface_index(&self.interface)?;
+        let sender = RawSocketSender::new()?;
+        let mut receiver = RawSocketReceiver::with_protocol(ETHERTYPE_ARP)?;
+        receiver.bind(&self.interface)?;
+        self.resolve_with(&sender, &receiver, if_index, dst)
+    }
+
+    /// Resolution complete avec des sockets deja ouvertes
+    pub fn resolve_with(
+        &mut self,
+        sender: &RawSocketSender,
+        receiver: &RawSocketReceiver,
+        if_index: i32,
+        dst: Ipv4Addr,
+    ) -> Result<[u8; 6]> {
+        if let Some(mac) = self.lookup(dst) {
+            return Ok(mac);
+        }
+        let hop = self.next_hop(dst);
+        let scanner = ArpScanner::new(self.src_mac, self.src_ip)
+            .with_retries(self.retries)
+            .with_timeout_ms(self.timeout_ms)
+            .with_rate_limit(0);
+        let host = scanner
+            .sweep_with(sender, receiver, if_index, &[hop])?
+            .into_iter()
+            .next()
+            .ok_or(ParseError::UnresolvedNeighbor(hop.octets))?;
+        self.insert(hop, host.mac);
+        Ok(host.mac)
+    }
+}
+
+// MAC des destinations qui ne passent pas par ARP
+fn special_mac(dst: Ipv4Addr, src_mac: [u8; 6]) -> Option<[u8; 6]> {
+    let [a, b, c, d] = dst.octets;
+    if a == 127 || src_mac == [0u8; 6] {
+        Some([0u8; 6])
+    } else if dst.octets == [255; 4] {
+        Some([0xFF; 6])
+    } else if (224..=239).contains(&a) {
+        // RFC 1112 : 01:00:5e + 23 bits de poids faible
+        Some([0x01, 0x00, 0x5E, b & 0x7F, c, d])
+    } else {
+        None
+    }
+}
+
+// Adresse hexadecimale de /proc/net/route (ordre des octets de la machine)
+fn route_address(hex: &str) -> Option<[u8; 4]> {
+    u32::from_str_radix(hex, 16).ok().map(|value| value.to_ne_bytes())
+}
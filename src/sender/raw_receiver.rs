@@ -0,0 +1,278 @@
+use crate::errors::errors::{ParseError, Result};
+use crate::formats::stream::ByteSink;
+use crate::packets::builder::packet_dissector::PacketDissector;
+use crate::prelude::*;
+use crate::sender::raw_socket::get_interface_index;
+use crate::structs::{
+    formats::PacketRecord,
+    pcap::LinkType,
+    socket::{CapturedFrame, RawSocketReceiver},
+    stream::StreamWriter,
+};
+use crate::utils::clock::{Clock, SystemClock, split_timestamp_ns};
+use core::mem;
+use std::time::{Duration, Instant};
+
+// Taille de capture par defaut (trame Ethernet maximale sans jumbo + marge)
+const DEFAULT_BUFFER_SIZE: usize = 65535;
+
+// Interface IP sans couche liaison (absent de libc)
+const ARPHRD_RAWIP: u16 = 519;
+
+// Reception de trames via un raw socket
+impl RawSocketReceiver {
+
+    // Constructor : ecoute tous les protocoles (ETH_P_ALL)
+    pub fn new() -> Result<Self> {
+        Self::with_protocol(libc::ETH_P_ALL as u16)
+    }
+
+    /// Constructeur filtrant sur un ethertype (ex: 0x0800 pour IPv4, 0x0806 pour ARP)
+    pub fn with_protocol(ethertype: u16) -> Result<Self> {
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_PACKET,
+                libc::SOCK_RAW,
+                ethertype.to_be() as i32)
+            };
+        if fd < 0 {
+            return Err(
+                ParseError::IoError(
+                    "socket() failed".into()
+                )
+            );
+        }
+        let receiver = Self {
+            fd,
+            protocol: ethertype,
+            if_index: None,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+        };
+        // Timestamps noyau en nanosecondes dans les messages de controle
+        receiver.set_int_option(libc::SO_TIMESTAMPNS, 1)?;
+        Ok(receiver)
+    }
+
+    /// Ouvre un socket de capture lie a une interface
+    pub fn open(interface: &str) -> Result<Self> {
+        let mut receiver = Self::new()?;
+        receiver.bind(interface)?;
+        Ok(receiver)
+    }
+
+    /// Lie le socket a une interface : seules ses trames sont recues
+    pub fn bind(&mut self, interface: &str) -> Result<()> {
+        let if_index = get_interface_index(interface)?;
+        let mut saddr: libc::sockaddr_ll = unsafe { mem::zeroed() };
+        saddr.sll_family = libc::AF_PACKET as u16;
+        saddr.sll_protocol = self.protocol.to_be();
+        saddr.sll_ifindex = if_index;
+        let ret = unsafe {
+            libc::bind(
+                self.fd,
+                &saddr as *const _ as *const libc::sockaddr,
+                mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+            )
+        };
+        if ret < 0 {
+            return Err(
+                ParseError::IoError(
+                    "bind() failed".into()
+                )
+            );
+        }
+        self.if_index = Some(if_index);
+        Ok(())
+    }
+
+    // Definit le timeout de lecture du socket (None : lecture bloquante)
+    pub fn set_read_timeout(&self, timeout_ms: Option<u64>) -> Result<()> {
+        let ms = timeout_ms.unwrap_or(0);
+        let tv = libc::timeval {
+            tv_sec: (ms / 1000) as libc::time_t,
+            tv_usec: ((ms % 1000) * 1000) as libc::suseconds_t,
+        };
+        let ret = unsafe {
+            libc::setsockopt(
+                self.fd,
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                &tv as *const _ as *const libc::c_void,
+                mem::size_of::<libc::timeval>() as libc::socklen_t,
+            )
+        };
+        if ret < 0 {
+            return Err(
+                ParseError::IoError(
+                    "setsockopt() failed".into()
+                )
+            );
+        }
+        Ok(())
+    }
+
+    /// Recoit une trame avec son timestamp noyau
+    /// Retourne `Ok(None)` si le timeout de lecture expire.
+    pub fn recv_frame(&self) -> Result<Option<CapturedFrame>> {
+        let mut buf = vec![0u8; self.buffer_size];
+        let mut saddr: libc::sockaddr_ll = unsafe { mem::zeroed() };
+        let mut iov = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        };
+        // u64 pour garantir l'alignement des cmsghdr
+        let mut control = [0u64; 16];
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        msg.msg_name = &mut saddr as *mut _ as *mut libc::c_void;
+        msg.msg_namelen = mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t;
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = mem::size_of_val(&control) as _;
+
+        // MSG_TRUNC : le noyau retourne la longueur reelle de la trame
+        let ret = unsafe {
+            libc::recvmsg(self.fd, &mut msg, libc::MSG_TRUNC)
+        };
+        if ret < 0 {
+            let err = std::io::Error::last_os_error();
+            return match err.kind() {
+                std::io::ErrorKind::WouldBlock
+                | std::io::ErrorKind::TimedOut
+                | std::io::ErrorKind::Interrupted => Ok(None),
+                _ => Err(err.into()),
+            };
+        }
+
+        let orig_len = ret as usize;
+        buf.truncate(orig_len.min(self.buffer_size));
+
+        let (ts_sec, ts_nsec) = kernel_timestamp(&msg)
+            .unwrap_or_else(|| split_timestamp_ns(SystemClock.now_ns()));
+        // Interfaces sans en-tete de liaison (tun, rawip) : trame IP brute
+        let link_type = match saddr.sll_hatype {
+            libc::ARPHRD_NONE | ARPHRD_RAWIP => LinkType::Raw,
+            _ => LinkType::Ethernet,
+        };
+        let packet = PacketDissector::new().dissect_link_frame(link_type, &buf).ok();
+
+        Ok(Some(CapturedFrame {
+            if_index: saddr.sll_ifindex,
+            pkt_type: saddr.sll_pkttype,
+            record: PacketRecord {
+                ts_sec: ts_sec as u32,
+                ts_usec: ts_nsec / 1000,
+                ts_nsec,
+                caplen: buf.len() as u32,
+                orig_len: orig_len as u32,
+                data: buf,
+                link_type,
+                packet,
+                comment: None,
+            },
+        }))
+    }
+
+    /// Capture des trames vers un writer en flux (pcap, pcapng, JSON)
+    /// S'arrete apres `max_frames` trames retenues par `filter` ou apres `duration_ms`.
+    /// Retourne le nombre de trames ecrites.
+    pub fn capture_into<S, F>(
+        &self,
+        stream: &mut StreamWriter<S>,
+        max_frames: usize,
+        duration_ms: u64,
+        mut filter: F,
+    ) -> Result<usize>
+    where
+        S: ByteSink,
+        F: FnMut(&CapturedFrame) -> bool,
+    {
+        let mut written = 0;
+        if max_frames == 0 {
+            return Ok(written);
+        }
+        let deadline = Instant::now() + Duration::from_millis(duration_ms);
+        self.recv_until(deadline, |frame| {
+            if filter(&frame) {
+                stream.write_record(&frame.record)?;
+                written += 1;
+            }
+            Ok(written < max_frames)
+        })?;
+        Ok(written)
+    }
+
+    /// Passe a `handle` chaque trame recue jusqu'a `deadline`
+    /// `handle` retourne faux pour arreter la lecture plus tot (reponses attendues recues).
+    pub fn recv_until<F>(&self, deadline: Instant, mut handle: F) -> Result<()>
+    where
+        F: FnMut(CapturedFrame) -> Result<bool>,
+    {
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(());
+            }
+            // Le timeout de lecture ne depasse jamais l'echeance
+            let remaining_ms = (deadline - now).as_millis().max(1) as u64;
+            self.set_read_timeout(Some(remaining_ms))?;
+            if let Some(frame) = self.recv_frame()?
+                && !handle(frame)?
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    // Definit une option entiere SOL_SOCKET
+    fn set_int_option(&self, option: libc::c_int, value: libc::c_int) -> Result<()> {
+        let ret = unsafe {
+            libc::setsockopt(
+                self.fd,
+                libc::SOL_SOCKET,
+                option,
+                &value as *const _ as *const libc::c_void,
+                mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        };
+        if ret < 0 {
+            return Err(
+                ParseError::IoError(
+                    "setsockopt() failed".into()
+                )
+            );
+        }
+        Ok(())
+    }
+}
+
+impl CapturedFrame {
+    /// Trame emise par la machine locale (copie sortante vue par le socket)
+    pub fn is_outgoing(&self) -> bool {
+        self.pkt_type == libc::PACKET_OUTGOING
+    }
+}
+
+// Implémentation de RawSocketReceiver
+impl Drop for RawSocketReceiver {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.fd) };
+    }
+}
+
+// Extrait le timestamp SCM_TIMESTAMPNS des messages de controle
+fn kernel_timestamp(msg: &libc::msghdr) -> Option<(u64, u32)> {
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(msg);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == libc::SOL_SOCKET && (*cmsg).cmsg_type == libc::SCM_TIMESTAMPNS {
+                let ts = core::ptr::read_unaligned(
+                    libc::CMSG_DATA(cmsg) as *const libc::timespec
+                );
+                return Some((ts.tv_sec as u64, ts.tv_nsec as u32));
+            }
+            cmsg = libc::CMSG_NXTHDR(msg, cmsg);
+        }
+    }
+    None
+}
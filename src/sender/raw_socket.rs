@@ -1,5 +1,5 @@
 use crate::errors::errors::Result;
-use crate::structs::socket::RawSocketSender;
+use crate::structs::{socket::RawSocketSender, ipv4::Ipv4Addr};
 use core::mem;
 
 // Envoie de paquets via un raw socket
@@ -127,3 +127,74 @@ pub fn get_interface_index(name: &str) -> Result<i32> {
 }
 
 
+
+/// Obtient l'adresse MAC de l'interface réseau par son nom
+pub fn get_interface_mac(name: &str) -> Result<[u8; 6]> {
+    let ifr = interface_ioctl(name, libc::SIOCGIFHWADDR as _)?;
+    let data = unsafe { ifr.ifr_ifru.ifru_hwaddr.sa_data };
+    let mut mac = [0u8; 6];
+    for (byte, value) in mac.iter_mut().zip(data.iter()) {
+        *byte = *value as u8;
+    }
+    Ok(
+        mac
+    )
+}
+
+/// Obtient l'adresse IPv4 principale de l'interface réseau par son nom
+pub fn get_interface_ipv4(name: &str) -> Result<Ipv4Addr> {
+    let ifr = interface_ioctl(name, libc::SIOCGIFADDR as _)?;
+    let addr = unsafe {
+        core::ptr::read_unaligned(
+            &ifr.ifr_ifru.ifru_addr as *const _ as *const libc::sockaddr_in
+        )
+    };
+    Ok(
+        Ipv4Addr {
+            octets: addr.sin_addr.s_addr.to_ne_bytes()
+        }
+    )
+}
+
+// Requete ioctl SIOCGIF* sur une interface (socket UDP, sans privileges)
+pub(crate) fn interface_ioctl(name: &str, request: libc::Ioctl) -> Result<libc::ifreq> {
+    let mut ifr: libc::ifreq = unsafe { mem::zeroed() };
+    if name.is_empty() || name.len() >= libc::IFNAMSIZ {
+        return Err(
+            crate::errors::errors::ParseError::InvalidFormat(
+                "invalid interface name"
+            )
+        );
+    }
+    for (dst, src) in ifr.ifr_name.iter_mut().zip(name.bytes()) {
+        *dst = src as libc::c_char;
+    }
+
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_INET,
+            libc::SOCK_DGRAM,
+            0)
+        };
+    if fd < 0 {
+        return Err(
+            crate::errors::errors::ParseError::IoError(
+                "socket() failed".into()
+        ));
+    }
+    let ret = unsafe {
+        libc::ioctl(fd, request, &mut ifr)
+    };
+    unsafe {
+        libc::close(fd)
+    };
+    if ret < 0 {
+        return Err(
+            crate::errors::errors::ParseError::IoError(
+                "ioctl() failed".into()
+        ));
+    }
+    Ok(
+        ifr
+    )
+}
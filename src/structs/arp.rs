@@ -0,0 +1,35 @@
+////////////////////////////////////////////////
+// ARP Packet Structure
+// https://tools.ietf.org/html/rfc826
+// Structure presente a paquet ARP Ethernet/IPv4.
+////////////////////////////////////////////////
+
+use crate::structs::ethernet::EthernetHeader;
+
+/// Ethertype ARP
+pub const ETHERTYPE_ARP: u16 = 0x0806;
+
+/// Operations ARP
+pub const ARP_REQUEST: u16 = 1;
+pub const ARP_REPLY: u16 = 2;
+
+/// Definition du paquet ARP (Ethernet/IPv4 : hlen 6, plen 4)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArpPacket {
+    pub hardware_type: u16,
+    pub protocol_type: u16,
+    pub hardware_len: u8,
+    pub protocol_len: u8,
+    pub operation: u16,
+    pub sender_mac: [u8; 6],
+    pub sender_ip: [u8; 4],
+    pub target_mac: [u8; 6],
+    pub target_ip: [u8; 4],
+}
+
+/// Trame ARP complete : header Ethernet (ethertype 0x0806) + paquet ARP
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArpFrame {
+    pub ethernet: EthernetHeader,
+    pub arp: ArpPacket,
+}
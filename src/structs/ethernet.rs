@@ -2,10 +2,35 @@
 /// https://notes.networklessons.com/ethernet-header
 ////////////////////////////////////////////////
 
+use alloc::vec::Vec as VecNoStd;
+
+/// Ethertype IPv4
+pub const ETHERTYPE_IPV4: u16 = 0x0800;
+
+/// TPID d'un tag 802.1Q (C-tag)
+pub const ETHERTYPE_VLAN: u16 = 0x8100;
+
+/// TPID d'un tag 802.1ad (S-tag, QinQ)
+pub const ETHERTYPE_QINQ: u16 = 0x88A8;
+
+/// TPID QinQ pre-standard encore utilise par certains equipements
+pub const ETHERTYPE_QINQ_LEGACY: u16 = 0x9100;
+
 /// Definition de l'header Ethernet
-#[derive(Debug, Clone, Copy)]
+/// Les tags VLAN s'intercalent entre les MAC et l'ethertype, du plus externe au plus interne.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EthernetHeader {
     pub dst_mac: [u8; 6],
     pub src_mac: [u8; 6],
+    pub vlan_tags: VecNoStd<VlanTag>,
     pub ethertype: u16,
 }
+
+/// Tag VLAN de 4 octets : TPID puis TCI (PCP sur 3 bits, DEI sur 1 bit, VID sur 12 bits)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VlanTag {
+    pub tpid: u16,
+    pub pcp: u8,
+    pub dei: bool,
+    pub vid: u16,
+}
@@ -1,5 +1,28 @@
+use crate::prelude::*;
+use crate::structs::network_packet::NetworkPacket;
+use crate::structs::pcap::LinkType;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum FormatType {
     Pcap,
+    PcapNg,
     Json,
-}
\ No newline at end of file
+}
+
+/// Enregistrement lu depuis un fichier de capture
+/// Contient le timestamp, les longueurs et la trame brute,
+/// ainsi que le paquet decode si la trame est Ethernet/IPv4/TCP|UDP
+/// et le commentaire associe (option opt_comment du PCAPNG).
+/// `link_type` est le type de lien de `data` (celui du fichier ou de l'interface source).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketRecord {
+    pub ts_sec: u32,
+    pub ts_usec: u32,
+    pub ts_nsec: u32,
+    pub caplen: u32,
+    pub orig_len: u32,
+    pub data: VecNoStd<u8>,
+    pub link_type: LinkType,
+    pub packet: Option<NetworkPacket>,
+    pub comment: Option<StringNoStd>,
+}
@@ -0,0 +1,68 @@
+////////////////////////////////////////////////
+// Fragmentation IPv4
+// https://www.rfc-editor.org/rfc/rfc791 (section 3.2)
+// https://www.rfc-editor.org/rfc/rfc1858 (fragments minuscules et chevauchants)
+////////////////////////////////////////////////
+
+use crate::prelude::*;
+use crate::utils::clock::Clock;
+use crate::structs::{
+    ethernet::EthernetHeader,
+    ip::Ipv4Header,
+};
+
+/// Fragment IPv4 dans sa trame Ethernet
+/// Hors `NetworkPacket` : seul le premier fragment porte l'header L4.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ipv4Fragment {
+    pub ethernet: EthernetHeader,
+    pub ipv4: Ipv4Header,
+    pub payload: VecNoStd<u8>,
+}
+
+/// Decoupage applique par le fragmenteur
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FragmentMode {
+    /// Fragments aussi grands que la MTU le permet
+    Normal,
+    /// Premier fragment de 8 octets : l'header TCP est coupe entre deux fragments
+    Tiny,
+    /// Chaque fragment reprend les `bytes` derniers octets du precedent (multiple de 8)
+    Overlap { bytes: u16 },
+}
+
+/// Fragmenteur IPv4
+/// `mtu` est la taille maximale d'un datagramme IP (header compris, hors Ethernet).
+#[derive(Debug, Clone)]
+pub struct Ipv4Fragmenter {
+    pub mtu: u16,
+    pub mode: FragmentMode,
+}
+
+/// Cle de regroupement des fragments (RFC 791) : source, destination, identification, protocole
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FragmentKey {
+    pub src_addr: [u8; 4],
+    pub dst_addr: [u8; 4],
+    pub identification: u16,
+    pub protocol: u8,
+}
+
+/// Datagramme en cours de reassemblage
+/// `ranges` liste les intervalles [debut, fin) recus, tries et fusionnes.
+#[derive(Debug, Clone)]
+pub struct FragmentBuffer {
+    pub first_seen_ns: u64,
+    pub first: Option<Ipv4Fragment>,
+    pub data: VecNoStd<u8>,
+    pub ranges: VecNoStd<(usize, usize)>,
+    pub total_len: Option<usize>,
+}
+
+/// Reassembleur IPv4
+/// Un datagramme incomplet est abandonne `timeout_ms` apres son premier fragment.
+pub struct Ipv4Reassembler {
+    pub timeout_ms: u64,
+    pub clock: Box<dyn Clock>,
+    pub buffers: BTreeMap<FragmentKey, FragmentBuffer>,
+}
@@ -0,0 +1,25 @@
+use alloc::vec::Vec;
+////////////////////////////////////////////////
+// ICMP Header Structure
+// https://tools.ietf.org/html/rfc792
+// Structure presente a header ICMP (IPv4).
+////////////////////////////////////////////////
+
+/// Types ICMP geres par le builder
+pub const ICMP_ECHO_REPLY: u8 = 0;
+pub const ICMP_DEST_UNREACHABLE: u8 = 3;
+pub const ICMP_ECHO_REQUEST: u8 = 8;
+pub const ICMP_TIME_EXCEEDED: u8 = 11;
+
+/// Definition de l'header ICMP
+/// `rest_of_header` contient identifier/sequence pour un echo,
+/// 0 (ou le MTU du saut suivant) pour un destination unreachable.
+/// Le payload contient les donnees echo ou le datagramme d'origine cite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcmpHeader {
+    pub icmp_type: u8,
+    pub code: u8,
+    pub checksum: u16,
+    pub rest_of_header: u32,
+    pub payload: Option<Vec<u8>>
+}
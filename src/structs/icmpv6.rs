@@ -0,0 +1,42 @@
+use alloc::vec::Vec;
+////////////////////////////////////////////////
+// ICMPv6 Header Structure
+// https://tools.ietf.org/html/rfc4443
+// https://tools.ietf.org/html/rfc4861 (Neighbor Discovery)
+// Structure presente a header ICMPv6.
+////////////////////////////////////////////////
+
+/// Numero de protocole ICMPv6 (next_header)
+pub const IPPROTO_ICMPV6: u8 = 58;
+
+/// Types ICMPv6 geres par le builder
+pub const ICMPV6_DEST_UNREACHABLE: u8 = 1;
+pub const ICMPV6_TIME_EXCEEDED: u8 = 3;
+pub const ICMPV6_ECHO_REQUEST: u8 = 128;
+pub const ICMPV6_ECHO_REPLY: u8 = 129;
+pub const ICMPV6_ROUTER_SOLICITATION: u8 = 133;
+pub const ICMPV6_ROUTER_ADVERTISEMENT: u8 = 134;
+pub const ICMPV6_NEIGHBOR_SOLICITATION: u8 = 135;
+pub const ICMPV6_NEIGHBOR_ADVERTISEMENT: u8 = 136;
+
+/// Drapeaux d'un Neighbor Advertisement (bits de poids fort de `rest_of_header`)
+pub const NA_FLAG_ROUTER: u32 = 0x8000_0000;
+pub const NA_FLAG_SOLICITED: u32 = 0x4000_0000;
+pub const NA_FLAG_OVERRIDE: u32 = 0x2000_0000;
+
+/// Options Neighbor Discovery d'adresse de lien
+pub const NDP_OPTION_SOURCE_LL_ADDR: u8 = 1;
+pub const NDP_OPTION_TARGET_LL_ADDR: u8 = 2;
+
+/// Definition de l'header ICMPv6
+/// `rest_of_header` contient identifier/sequence pour un echo,
+/// les drapeaux R/S/O pour un Neighbor Advertisement, 0 sinon.
+/// Le payload contient les donnees echo, ou l'adresse cible et les options ND.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Icmpv6Header {
+    pub icmp_type: u8,
+    pub code: u8,
+    pub checksum: u16,
+    pub rest_of_header: u32,
+    pub payload: Option<Vec<u8>>
+}
@@ -0,0 +1,27 @@
+//////////////////////////////////////////
+// interface.rs
+// Structures des interfaces reseau locales
+//////////////////////////////////////////
+
+use crate::prelude::*;
+use crate::structs::ipv4::Ipv4Addr;
+
+/// Adresse IPv4 d'une interface et son masque
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InterfaceAddress {
+    pub addr: Ipv4Addr,
+    pub netmask: Ipv4Addr,
+}
+
+/// Interface reseau locale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkInterface {
+    pub name: StringNoStd,
+    pub index: i32,
+    /// MAC nulle pour la boucle locale
+    pub mac: [u8; 6],
+    pub ipv4: VecNoStd<InterfaceAddress>,
+    pub mtu: u32,
+    pub is_up: bool,
+    pub is_loopback: bool,
+}
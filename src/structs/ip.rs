@@ -5,8 +5,13 @@
 
 use alloc::vec::Vec as VacNoStd;
 
+/// Bits du champ flags IPv4 (3 bits : reserve, DF, MF)
+pub const IPV4_FLAG_EVIL: u8 = 0b100;
+pub const IPV4_FLAG_DF: u8 = 0b010;
+pub const IPV4_FLAG_MF: u8 = 0b001;
+
 /// Definition de l'header IPV4
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Ipv4Header {
     pub version: u8,            
     pub ihl: u8,                
@@ -22,3 +27,79 @@ pub struct Ipv4Header {
     pub dst_addr: [u8; 4],      
     pub options: Option<VacNoStd<u8>>,
 }
+
+// https://www.rfc-editor.org/rfc/rfc791 (section 3.1, options)
+// Les options sont conservees brutes dans `Ipv4Header::options` ; `Ipv4Option`
+// en est la forme typee.
+
+/// Types d'options IPv4 (bit "copied", classe et numero compris)
+pub const IPV4_OPT_END: u8 = 0;
+pub const IPV4_OPT_NOP: u8 = 1;
+pub const IPV4_OPT_RECORD_ROUTE: u8 = 7;
+pub const IPV4_OPT_TIMESTAMP: u8 = 68;
+pub const IPV4_OPT_LOOSE_SOURCE_ROUTE: u8 = 131;
+pub const IPV4_OPT_STRICT_SOURCE_ROUTE: u8 = 137;
+pub const IPV4_OPT_ROUTER_ALERT: u8 = 148;
+
+/// Option IPv4 typee
+/// `pointer` est l'offset (depuis le debut de l'option, base 1) du prochain emplacement libre.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ipv4Option {
+    EndOfList,
+    NoOperation,
+    RecordRoute { pointer: u8, route: VacNoStd<[u8; 4]> },
+    /// `flag` 0 : horodatages seuls ; 1 : adresse + horodatage ; 3 : adresses pre-remplies
+    Timestamp { pointer: u8, overflow: u8, flag: u8, entries: VacNoStd<Ipv4TimestampEntry> },
+    LooseSourceRoute { pointer: u8, route: VacNoStd<[u8; 4]> },
+    StrictSourceRoute { pointer: u8, route: VacNoStd<[u8; 4]> },
+    RouterAlert(u16),
+    Unknown { option_type: u8, data: VacNoStd<u8> },
+}
+
+/// Entree d'une option Timestamp (adresse absente pour le flag 0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv4TimestampEntry {
+    pub address: Option<[u8; 4]>,
+    pub timestamp: u32,
+}
+
+// https://www.rfc-editor.org/rfc/rfc8200
+// Header IPv6 fixe (40 octets) suivi de la chaine d'extensions.
+
+/// Extensions IPv6 reconnues dans la chaine next_header
+pub const IPV6_HOP_BY_HOP: u8 = 0;
+pub const IPV6_ROUTING: u8 = 43;
+pub const IPV6_FRAGMENT: u8 = 44;
+pub const IPV6_AUTH: u8 = 51;
+pub const IPV6_DEST_OPTIONS: u8 = 60;
+
+/// Definition de l'header IPv6
+/// `next_header` designe la premiere extension, ou le protocole L4 sans extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ipv6Header {
+    pub version: u8,
+    pub traffic_class: u8,
+    pub flow_label: u32,
+    pub payload_length: u16,
+    pub next_header: u8,
+    pub hop_limit: u8,
+    pub src_addr: [u8; 16],
+    pub dst_addr: [u8; 16],
+    pub extension_headers: VacNoStd<Ipv6ExtensionHeader>,
+}
+
+/// Couche reseau d'un paquet : IPv4 (ethertype 0x0800) ou IPv6 (0x86DD)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpHeader {
+    V4(Ipv4Header),
+    V6(Ipv6Header),
+}
+
+/// Header d'extension IPv6
+/// `data` contient les octets qui suivent les champs next_header et longueur.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ipv6ExtensionHeader {
+    pub header_type: u8,
+    pub next_header: u8,
+    pub data: VacNoStd<u8>,
+}
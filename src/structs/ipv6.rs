@@ -0,0 +1,17 @@
+/////////////////////////////////////////////////////////
+/// ipv6.rs
+/// Struct pour parser une adresse IPv6
+/////////////////////////////////////////////////////////
+
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq
+)]
+
+/// Definition de la struct IPv6
+pub struct Ipv6Addr {
+    pub octets: [
+        u8;
+        16
+    ],
+}
@@ -1,8 +1,10 @@
 use serde::{Serialize, Deserialize};
 use crate::prelude::*;
+use crate::utils::clock::Clock;
+use crate::structs::formats::PacketRecord;
 
 // Enum pour remplacer serde_json::Value
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum JsonValue {
     U64(u64),
     Bool(bool),
@@ -13,41 +15,108 @@ pub enum JsonValue {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct JsonPacket {
     pub ethernet: JsonEthernet,
-    pub ipv4: JsonIpv4,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipv4: Option<JsonIpv4>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipv6: Option<JsonIpv6>,
     pub l4: JsonL4,
     pub metadata: JsonMetadata,
 }
 
 // Struct Ethernet
+// `vlan_tags` n'est serialise que pour une trame taguee
 #[derive(Serialize, Deserialize, Debug)]
 pub struct JsonEthernet {
     pub src_mac: StringNoStd,
     pub dst_mac: StringNoStd,
     pub ethertype: u16,
+    #[serde(default, skip_serializing_if = "VecNoStd::is_empty")]
+    pub vlan_tags: VecNoStd<JsonVlanTag>,
+}
+
+// Struct d'un tag VLAN (TPID 0x8100 par defaut)
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JsonVlanTag {
+    pub vid: u16,
+    #[serde(default)]
+    pub pcp: u8,
+    #[serde(default)]
+    pub dei: bool,
+    #[serde(default = "default_vlan_tpid")]
+    pub tpid: u16,
+}
+
+fn default_vlan_tpid() -> u16 {
+    0x8100
 }
 
 // Struct IPv4
+// Les champs absents d'un template JSON edite a la main prennent
+// une valeur par defaut (version 4, ihl deduit des options) ; longueur
+// totale et checksum absents ou nuls sont recalcules.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct JsonIpv4 {
     pub src_addr: StringNoStd,
     pub dst_addr: StringNoStd,
     pub protocol: u8,
+    #[serde(default)]
     pub total_length: u16,
+    #[serde(default)]
     pub header_checksum: u16,
     pub ttl: u8,
     pub flags: u8,
     pub fragment_offset: u16,
+    #[serde(default)]
+    pub version: u8,
+    #[serde(default)]
+    pub ihl: u8,
+    #[serde(default)]
+    pub dscp: u8,
+    #[serde(default)]
+    pub identification: u16,
+    #[serde(default)]
+    pub options: StringNoStd,
+}
+
+// Struct IPv6, absente pour un paquet IPv4 (et inversement pour JsonIpv4)
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JsonIpv6 {
+    pub src_addr: StringNoStd,
+    pub dst_addr: StringNoStd,
+    pub next_header: u8,
+    pub payload_length: u16,
+    pub hop_limit: u8,
+    #[serde(default)]
+    pub traffic_class: u8,
+    #[serde(default)]
+    pub flow_label: u32,
+    #[serde(default)]
+    pub extension_headers: VecNoStd<JsonIpv6Extension>,
+}
+
+// Struct d'un header d'extension IPv6
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JsonIpv6Extension {
+    pub header_type: u8,
+    pub next_header: u8,
+    #[serde(default)]
+    pub data: StringNoStd,
 }
 
 // Struct L4
+// Un checksum absent ou nul est recalcule
 #[derive(Serialize, Deserialize, Debug)]
 pub struct JsonL4 {
     pub protocol_type: StringNoStd,
     pub src_port: u16,
     pub dst_port: u16,
     pub payload_size: usize,
+    #[serde(default)]
     pub checksum: u16,
+    #[serde(deserialize_with = "crate::formats::json::deserialize_additional_fields")]
     pub additional_fields: BTreeMap<StringNoStd, JsonValue>,
+    #[serde(default)]
+    pub payload: StringNoStd,
 }
 
 // Struct Metadata
@@ -59,9 +128,19 @@ pub struct JsonMetadata {
 }
 
 // Serializer et Deserializer JSON
+// `array_open` : '[' ecrit dans le buffer, ']' pas encore
 pub struct JsonSerializer {
     pub include_raw_data: bool,
+    pub clock: Box<dyn Clock>,
+    pub buffer: VecNoStd<u8>,
+    pub packets_written: usize,
+    pub array_open: bool,
 }
 
 // Deserializer JSON
-pub struct JsonDeserializer;
+// Lit un paquet ou un tableau de paquets depuis data
+pub struct JsonDeserializer {
+    pub data: VecNoStd<u8>,
+    pub position: usize,
+    pub records: Option<VecNoStd<PacketRecord>>,
+}
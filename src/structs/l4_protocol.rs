@@ -1,11 +1,13 @@
 use crate::structs::{
     tcp::TcpHeader,
-    udp::UdpHeader
+    udp::UdpHeader,
+    icmp::IcmpHeader,
+    icmpv6::Icmpv6Header
 };
 
 /// Definition des donnees de couche 4
-/// Contient TCP ou UDP.
-#[derive(Debug, Clone)]
+/// Contient TCP, UDP, ICMP ou ICMPv6.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum L4Data {
     Tcp(
         TcpHeader
@@ -13,6 +15,12 @@ pub enum L4Data {
     Udp(
         UdpHeader
     ),
+    Icmp(
+        IcmpHeader
+    ),
+    Icmpv6(
+        Icmpv6Header
+    ),
 }
 
 
@@ -22,5 +30,6 @@ pub enum L4Data {
 pub enum L4Protocol {
     Tcp,
     Udp,
+    Icmp,
 } 
 
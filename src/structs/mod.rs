@@ -1,12 +1,26 @@
 pub mod ipv4;
+pub mod ipv6;
 pub mod tcp;
 pub mod udp;
+pub mod icmp;
+pub mod icmpv6;
+pub mod arp;
 pub mod ip;
+pub mod fragment;
 pub mod ethernet;
 pub mod l4_protocol;
 pub mod network_packet;
 pub mod packet_builder;
 pub mod formats;
 pub mod pcap;
+pub mod pcapng;
 pub mod json;
 pub mod socket;
+pub mod stream;
+pub mod scan;
+pub mod tcp_session;
+#[cfg(feature = "std")]
+pub mod service;
+pub mod os_fingerprint;
+pub mod neighbor;
+pub mod interface;
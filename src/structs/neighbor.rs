@@ -0,0 +1,34 @@
+//////////////////////////////////////////
+// neighbor.rs
+// Structures de resolution du prochain saut
+//////////////////////////////////////////
+
+use crate::prelude::*;
+use crate::structs::ipv4::Ipv4Addr;
+
+/// Route IPv4 de la table du noyau (`/proc/net/route`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub interface: StringNoStd,
+    pub destination: [u8; 4],
+    /// 0.0.0.0 pour une route directement connectee
+    pub gateway: [u8; 4],
+    pub mask: [u8; 4],
+    pub metric: u32,
+}
+
+/// Resolution de la MAC du prochain saut sur une interface
+/// Le prochain saut est la passerelle de la route la plus specifique, ou la
+/// destination elle-meme si elle est sur le lien. Les MAC sont cherchees dans
+/// `cache`, puis dans `/proc/net/arp`, puis par requete ARP (`src_mac`/`src_ip`).
+#[derive(Debug, Clone)]
+pub struct NeighborResolver {
+    pub interface: StringNoStd,
+    pub src_mac: [u8; 6],
+    pub src_ip: Ipv4Addr,
+    pub retries: u8,
+    pub timeout_ms: u64,
+    /// Rempli par la resolution (feature `std`)
+    #[cfg(feature = "std")]
+    pub(crate) cache: BTreeMap<[u8; 4], [u8; 6]>,
+}
@@ -4,13 +4,13 @@
 /////////////////////////////////////////////////
 
 use crate::structs::ethernet::EthernetHeader;
-use crate::structs::ip::Ipv4Header;
+use crate::structs::ip::IpHeader;
 use crate::structs::l4_protocol::L4Data;
 
 /// Definition d'un paquet reseau complet
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NetworkPacket {
     pub ethernet: EthernetHeader,
-    pub ipv4: Ipv4Header,
+    pub ip: IpHeader,
     pub l4_data: L4Data,
-}
\ No newline at end of file
+}
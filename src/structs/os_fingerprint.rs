@@ -0,0 +1,87 @@
+//////////////////////////////////////////
+// os_fingerprint.rs
+// Empreinte de systeme d'exploitation par la pile TCP/IP
+//////////////////////////////////////////
+
+use crate::prelude::*;
+use crate::structs::packet_builder::PacketBuilder;
+
+/// Comportement du champ identification IPv4 sur plusieurs paquets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpIdBehavior {
+    /// Toujours nul (Linux sur les paquets DF sans etat)
+    Zero,
+    /// Compteur global croissant (Windows)
+    Incremental,
+    /// Valeurs sans suite (BSD, macOS)
+    Random,
+    /// Un seul paquet observe
+    Unknown,
+}
+
+/// Caracteristiques TCP/IP d'un SYN ou d'un SYN-ACK
+/// `initial_ttl` : TTL d'origine deduit du TTL observe (32, 64, 128 ou 255) ;
+/// `options` : disposition des options TCP (`mss,sok,ts,nop,ws`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TcpFingerprint {
+    pub ttl: u8,
+    pub initial_ttl: u8,
+    pub window: u16,
+    pub mss: Option<u16>,
+    pub window_scale: Option<u8>,
+    pub options: StringNoStd,
+    pub df: bool,
+    pub ip_id: IpIdBehavior,
+}
+
+/// Valeur de fenetre attendue : fixe ou multiple du MSS annonce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowSignature {
+    Value(u16),
+    MssMultiple(u16),
+}
+
+/// Entree de la base d'empreintes ; `None` : critere indifferent (`*`)
+/// `window_scale: Some(None)` exige l'absence d'option window scale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsSignature {
+    pub name: StringNoStd,
+    pub initial_ttl: Option<u8>,
+    pub windows: Option<VecNoStd<WindowSignature>>,
+    pub window_scale: Option<Option<u8>>,
+    pub options: Option<StringNoStd>,
+    pub df: Option<bool>,
+    pub ip_id: Option<IpIdBehavior>,
+}
+
+/// Base d'empreintes, dans l'ordre de preference a score egal
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OsDatabase {
+    pub signatures: VecNoStd<OsSignature>,
+}
+
+/// Systeme candidat et confiance (0-100)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsMatch {
+    pub name: StringNoStd,
+    pub confidence: u8,
+}
+
+/// Resultat d'une identification : empreinte relevee et candidats par confiance decroissante
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsReport {
+    pub fingerprint: Option<TcpFingerprint>,
+    pub matches: VecNoStd<OsMatch>,
+}
+
+/// Identification active : SYN aux options choisies vers un port ouvert,
+/// empreinte des SYN-ACK recus
+/// `probes` (au moins 2) se regle par `with_probes` et se lit par `probes()`.
+#[derive(Debug, Clone)]
+pub struct OsProber {
+    pub template: PacketBuilder,
+    pub database: OsDatabase,
+    pub(crate) probes: u8,
+    pub retries: u8,
+    pub timeout_ms: u64,
+}
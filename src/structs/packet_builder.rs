@@ -5,13 +5,19 @@
 
 use crate::prelude::*;
 use crate::structs::{
+    ethernet::VlanTag,
+    ip::Ipv4Option,
     ipv4::Ipv4Addr,
     l4_protocol::L4Protocol,
+    tcp::{TcpFlags, TcpOption},
 };
 
 
 /// Structure pour construire un paquet complet
 /// Contient les informations pour construire le paquet
+/// `vlan_tags` est vide pour une trame non taguee.
+/// `ttl`, `dscp`, `ecn`, `identification` et `ip_options` alimentent l'header IPv4,
+/// `tcp_flags`, `sequence_number`, `ack_number`, `window`, `urgent_pointer` et `tcp_options` l'header TCP.
 #[derive(Debug, Clone)]
 pub struct PacketBuilder {
     pub src_ip: Ipv4Addr,
@@ -23,4 +29,16 @@ pub struct PacketBuilder {
     pub protocol: L4Protocol,
     pub ip_bitfield: u8,
     pub payload: Option<VecNoStd<u8>>,
+    pub vlan_tags: VecNoStd<VlanTag>,
+    pub ttl: u8,
+    pub dscp: u8,
+    pub ecn: u8,
+    pub identification: u16,
+    pub ip_options: VecNoStd<Ipv4Option>,
+    pub tcp_flags: TcpFlags,
+    pub sequence_number: u32,
+    pub ack_number: u32,
+    pub window: u16,
+    pub urgent_pointer: u16,
+    pub tcp_options: VecNoStd<TcpOption>,
 }
\ No newline at end of file
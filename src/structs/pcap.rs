@@ -1,12 +1,53 @@
 use crate::prelude::*;
+use crate::utils::clock::Clock;
+
+/// Ordre des octets d'un fichier PCAP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PcapByteOrder {
+    LittleEndian,
+    BigEndian,
+}
+
+/// Resolution des timestamps d'un fichier PCAP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PcapPrecision {
+    Micro,
+    Nano,
+}
+
+/// Type de lien (LINKTYPE_*) du fichier PCAP
+/// https://www.tcpdump.org/linktypes.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkType {
+    Ethernet,
+    Raw,
+    LinuxSll,
+    LinuxSll2,
+    Other(u32),
+}
+
+/// Options d'ecriture d'un fichier PCAP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcapOptions {
+    pub byte_order: PcapByteOrder,
+    pub precision: PcapPrecision,
+    pub snaplen: u32,
+    pub link_type: LinkType,
+}
 
 /// Struct constructeur fichiers PCAP
 pub struct PcapWriter {
     pub buffer: VecNoStd<u8>,
+    pub options: PcapOptions,
+    pub clock: Box<dyn Clock>,
 }  
 
 /// Read fichiers PCAP
 pub struct PcapReader {
     pub data: VecNoStd<u8>,
     pub position: usize,
-}
\ No newline at end of file
+    pub byte_order: PcapByteOrder,
+    pub precision: PcapPrecision,
+    pub snaplen: u32,
+    pub link_type: LinkType,
+}
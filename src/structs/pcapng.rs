@@ -0,0 +1,31 @@
+use crate::prelude::*;
+use crate::utils::clock::Clock;
+use crate::structs::pcap::{
+    PcapOptions,
+    PcapByteOrder,
+    LinkType
+};
+
+/// Interface decrite par un Interface Description Block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcapNgInterface {
+    pub link_type: LinkType,
+    pub snaplen: u32,
+    pub ts_resolution: u8,
+}
+
+/// Struct constructeur fichiers PCAPNG
+/// https://www.ietf.org/archive/id/draft-ietf-opsawg-pcapng-02.html
+pub struct PcapNgWriter {
+    pub buffer: VecNoStd<u8>,
+    pub options: PcapOptions,
+    pub clock: Box<dyn Clock>,
+}
+
+/// Read fichiers PCAPNG
+pub struct PcapNgReader {
+    pub data: VecNoStd<u8>,
+    pub position: usize,
+    pub byte_order: PcapByteOrder,
+    pub interfaces: VecNoStd<PcapNgInterface>,
+}
@@ -0,0 +1,104 @@
+//////////////////////////////////////////
+// scan.rs
+// Structures du moteur de scan de ports
+//////////////////////////////////////////
+
+use crate::prelude::*;
+use crate::structs::{
+    ipv4::Ipv4Addr,
+    ipv6::Ipv6Addr,
+    packet_builder::PacketBuilder,
+    tcp::TcpFlags,
+};
+
+/// Etat d'un port apres le scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortState {
+    /// SYN-ACK recu
+    Open,
+    /// RST recu
+    Closed,
+    /// Aucune reponse (scan SYN ou ACK) ou ICMP unreachable autre que port (UDP)
+    Filtered,
+    /// UDP, FIN, NULL ou Xmas : aucune reponse apres toutes les tentatives
+    OpenFiltered,
+    /// Scan ACK : RST recu, le port est joignable (ouvert ou ferme)
+    Unfiltered,
+}
+
+/// Resultat du scan d'un port
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortResult {
+    pub port: u16,
+    pub state: PortState,
+    /// Nombre de sondes envoyees pour ce port (jusqu'a `retries + 1` = 256)
+    pub attempts: u16,
+}
+
+/// Scanner TCP SYN
+/// `template` fournit les adresses, le port source et l'ip_bitfield des sondes,
+/// `secret` sert a deriver le numero de sequence de chaque sonde.
+/// `tcp_flags` remplace SYN pour les scans FIN, NULL, Xmas ou ACK.
+#[derive(Debug, Clone)]
+pub struct SynScanner {
+    pub template: PacketBuilder,
+    pub retries: u8,
+    pub timeout_ms: u64,
+    pub tcp_flags: TcpFlags,
+    /// Derive par `SynScanner::new` (feature `std`)
+    #[cfg(feature = "std")]
+    pub(crate) secret: u32,
+}
+
+/// Scanner UDP
+/// Une reponse UDP marque le port `Open`, un ICMP port unreachable `Closed`,
+/// les autres ICMP unreachable `Filtered` et l'absence de reponse `OpenFiltered`.
+/// `rate_limit` borne le nombre de sondes par seconde (0 : pas de limite) car la
+/// plupart des piles limitent l'emission des ICMP unreachable.
+#[derive(Debug, Clone)]
+pub struct UdpScanner {
+    pub template: PacketBuilder,
+    pub retries: u8,
+    pub timeout_ms: u64,
+    pub rate_limit: u32,
+    /// Payloads par port fournis par l'utilisateur (prioritaires sur les sondes integrees)
+    pub payloads: BTreeMap<u16, VecNoStd<u8>>,
+}
+
+/// Hote decouvert par un balayage ARP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArpHost {
+    pub ip: Ipv4Addr,
+    pub mac: [u8; 6],
+}
+
+/// Balayage ARP d'un sous-reseau
+/// `src_mac`/`src_ip` sont annonces dans les requetes et recoivent les reponses ;
+/// seuls les hotes ayant repondu figurent dans le resultat.
+#[derive(Debug, Clone)]
+pub struct ArpScanner {
+    pub src_mac: [u8; 6],
+    pub src_ip: Ipv4Addr,
+    pub retries: u8,
+    pub timeout_ms: u64,
+    pub rate_limit: u32,
+}
+
+/// Hote IPv6 decouvert par Neighbor Discovery ou ping multicast
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NdpHost {
+    pub ip: Ipv6Addr,
+    pub mac: [u8; 6],
+}
+
+/// Decouverte d'hotes IPv6
+/// Une cible unicast recoit un Neighbor Solicitation, une cible multicast
+/// (ex: ff02::1) un echo request auquel chaque membre du groupe repond.
+#[derive(Debug, Clone)]
+pub struct NdpScanner {
+    pub src_mac: [u8; 6],
+    pub src_ip: Ipv6Addr,
+    pub retries: u8,
+    pub timeout_ms: u64,
+    pub rate_limit: u32,
+}
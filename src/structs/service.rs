@@ -0,0 +1,76 @@
+//////////////////////////////////////////
+// service.rs
+// Detection de service et de version
+//////////////////////////////////////////
+
+use crate::prelude::*;
+use crate::structs::{
+    l4_protocol::L4Protocol,
+    packet_builder::PacketBuilder,
+    scan::PortState,
+};
+use regex::bytes::Regex;
+
+/// Sonde de service : payload envoye apres la connexion (TCP) ou en datagramme (UDP)
+/// `ports` vide : sonde essayee sur tous les ports. Un payload vide (sonde `NULL`)
+/// attend simplement la banniere que le serveur envoie de lui-meme.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceProbe {
+    pub name: StringNoStd,
+    pub protocol: L4Protocol,
+    pub ports: VecNoStd<u16>,
+    pub payload: VecNoStd<u8>,
+}
+
+/// Signature reconnaissant la reponse a une sonde (`probe`, `*` pour toutes)
+/// `product`, `version` et `info` sont des modeles : `$1`..`$9` y sont remplaces
+/// par les groupes captures par `pattern`.
+#[derive(Debug, Clone)]
+pub struct ServiceSignature {
+    pub service: StringNoStd,
+    pub probe: StringNoStd,
+    pub pattern: Regex,
+    pub product: Option<StringNoStd>,
+    pub version: Option<StringNoStd>,
+    pub info: Option<StringNoStd>,
+}
+
+/// Base de sondes et de signatures, dans l'ordre d'essai
+#[derive(Debug, Clone, Default)]
+pub struct ServiceDatabase {
+    pub probes: VecNoStd<ServiceProbe>,
+    pub signatures: VecNoStd<ServiceSignature>,
+}
+
+/// Service reconnu, modeles de la signature developpes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceMatch {
+    pub service: StringNoStd,
+    pub product: Option<StringNoStd>,
+    pub version: Option<StringNoStd>,
+    pub info: Option<StringNoStd>,
+}
+
+/// Resultat de la detection sur un port
+/// `probe` : sonde dont la reponse a ete reconnue ; `response` : cette reponse,
+/// ou la premiere reponse recue si aucune signature ne correspond.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceReport {
+    pub port: u16,
+    pub protocol: L4Protocol,
+    pub state: PortState,
+    pub probe: Option<StringNoStd>,
+    pub service: Option<ServiceMatch>,
+    pub response: VecNoStd<u8>,
+}
+
+/// Detection de service : essaie les sondes de la base applicables au port
+/// jusqu'a ce qu'une reponse corresponde a une signature.
+#[derive(Debug, Clone)]
+pub struct ServiceDetector {
+    pub template: PacketBuilder,
+    pub database: ServiceDatabase,
+    pub retries: u8,
+    pub timeout_ms: u64,
+    pub max_bytes: usize,
+}
@@ -1,3 +1,27 @@
+use crate::structs::formats::PacketRecord;
+
+#[cfg(feature = "std")]
 pub struct RawSocketSender {
     pub(crate) fd: libc::c_int,
-}
\ No newline at end of file
+}
+
+/// Socket brut de capture (AF_PACKET)
+/// `protocol` est l'ethertype ecoute (ETH_P_ALL par defaut),
+/// `buffer_size` la taille maximale capturee par trame.
+/// Descripteur libc : disponible avec la feature `std` seulement.
+#[cfg(feature = "std")]
+pub struct RawSocketReceiver {
+    pub(crate) fd: libc::c_int,
+    pub(crate) protocol: u16,
+    pub if_index: Option<i32>,
+    pub buffer_size: usize,
+}
+
+/// Trame capturee : enregistrement (timestamp noyau, longueurs, trame,
+/// paquet decode) et informations du `sockaddr_ll` de reception
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedFrame {
+    pub if_index: i32,
+    pub pkt_type: u8,
+    pub record: PacketRecord,
+}
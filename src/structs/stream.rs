@@ -0,0 +1,20 @@
+use crate::prelude::*;
+use crate::formats::{
+    format_factory::FormatWriter,
+    stream::ByteSink,
+};
+
+/// Writer de format en flux
+/// Chaque entete/paquet produit par `writer` est pousse immediatement
+/// dans `sink` : le fichier complet n'est jamais garde en memoire.
+pub struct StreamWriter<S: ByteSink> {
+    pub writer: Box<dyn FormatWriter>,
+    pub sink: S,
+    pub packets_written: usize,
+}
+
+/// Adaptateur `ByteSink` au-dessus d'un `std::io::Write` (fichier, socket...)
+#[cfg(feature = "std")]
+pub struct IoSink<W: std::io::Write> {
+    pub inner: W,
+}
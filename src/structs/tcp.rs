@@ -23,3 +23,46 @@ pub struct TcpHeader {
     pub payload: Option<Vec<u8>>
 }
 
+
+// https://www.iana.org/assignments/tcp-parameters
+// Types (kind) des options TCP reconnues.
+pub const TCP_OPT_END: u8 = 0;
+pub const TCP_OPT_NOP: u8 = 1;
+pub const TCP_OPT_MSS: u8 = 2;
+pub const TCP_OPT_WINDOW_SCALE: u8 = 3;
+pub const TCP_OPT_SACK_PERMITTED: u8 = 4;
+pub const TCP_OPT_SACK: u8 = 5;
+pub const TCP_OPT_TIMESTAMPS: u8 = 8;
+pub const TCP_OPT_FAST_OPEN: u8 = 34;
+
+/// Option TCP typee
+/// `Sack` contient les blocs (bord gauche, bord droit) ; `FastOpen` vide est une demande de cookie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TcpOption {
+    EndOfList,
+    NoOperation,
+    Mss(u16),
+    WindowScale(u8),
+    SackPermitted,
+    Sack(Vec<(u32, u32)>),
+    Timestamps { value: u32, echo_reply: u32 },
+    FastOpen(Vec<u8>),
+    Unknown { kind: u8, data: Vec<u8> },
+}
+
+/// Drapeaux TCP (9 bits, NS compris), a la maniere de bitflags
+/// `TcpHeader.flags` porte la valeur brute ; `TcpFlags` la manipule par nom.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TcpFlags(pub(crate) u16);
+
+impl TcpFlags {
+    pub const FIN: TcpFlags = TcpFlags(0x001);
+    pub const SYN: TcpFlags = TcpFlags(0x002);
+    pub const RST: TcpFlags = TcpFlags(0x004);
+    pub const PSH: TcpFlags = TcpFlags(0x008);
+    pub const ACK: TcpFlags = TcpFlags(0x010);
+    pub const URG: TcpFlags = TcpFlags(0x020);
+    pub const ECE: TcpFlags = TcpFlags(0x040);
+    pub const CWR: TcpFlags = TcpFlags(0x080);
+    pub const NS: TcpFlags = TcpFlags(0x100);
+}
@@ -0,0 +1,88 @@
+//////////////////////////////////////////
+// tcp_session.rs
+// Connexion TCP en espace utilisateur (cote client)
+//////////////////////////////////////////
+
+use crate::prelude::*;
+use crate::structs::{
+    network_packet::NetworkPacket,
+    packet_builder::PacketBuilder,
+    scan::PortState,
+};
+
+/// Etats de la connexion (RFC 793), cote client uniquement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcpState {
+    Closed,
+    SynSent,
+    Established,
+    /// FIN envoye, pas encore acquitte
+    FinWait1,
+    /// FIN acquitte, en attente du FIN distant
+    FinWait2,
+    /// FIN croises
+    Closing,
+    /// Fermeture complete des deux cotes
+    TimeWait,
+    /// FIN distant recu, notre cote reste ouvert
+    CloseWait,
+    /// FIN envoye apres celui du pair
+    LastAck,
+}
+
+/// Connexion TCP pilotee par des segments bruts
+/// `template` fournit adresses, MAC, ports et champs IP/TCP des segments emis.
+/// Les numeros de sequence suivent la RFC 793 : `iss`/`snd_nxt`/`snd_una` pour
+/// l'emission, `irs`/`rcv_nxt` pour la reception. Les segments arrives en avance
+/// attendent dans `out_of_order` (cle : offset depuis `irs`) jusqu'a combler le trou.
+#[derive(Debug, Clone)]
+pub struct TcpSession {
+    pub template: PacketBuilder,
+    pub state: TcpState,
+    pub iss: u32,
+    pub snd_una: u32,
+    pub snd_nxt: u32,
+    pub irs: u32,
+    pub rcv_nxt: u32,
+    /// Octets recus dans l'ordre
+    pub received: VecNoStd<u8>,
+    /// Connexion terminee par un RST du pair
+    pub reset: bool,
+    pub(crate) out_of_order: BTreeMap<u32, VecNoStd<u8>>,
+    /// Offset (depuis `irs`) du FIN distant, connu avant d'avoir recu tout ce qui le precede
+    pub(crate) peer_fin: Option<u32>,
+}
+
+/// Fermeture de la connexion apres la collecte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Teardown {
+    /// Echange de FIN
+    Fin,
+    /// RST immediat
+    Rst,
+}
+
+/// Recuperation de banniere par une connexion TCP en espace utilisateur
+/// `payload` est envoye apres la poignee de main (ex: requete HTTP) ;
+/// la collecte s'arrete sur FIN du pair, apres `max_bytes` octets ou apres
+/// `timeout_ms` sans nouveau segment.
+#[derive(Debug, Clone)]
+pub struct BannerGrabber {
+    pub template: PacketBuilder,
+    pub retries: u8,
+    pub timeout_ms: u64,
+    pub payload: Option<VecNoStd<u8>>,
+    pub max_bytes: usize,
+    pub teardown: Teardown,
+}
+
+/// Resultat d'une recuperation de banniere
+/// `state` : `Open` apres la poignee de main, `Closed` sur RST, `Filtered` sans reponse.
+/// `segments` retrace les segments emis et recus, dans l'ordre.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Banner {
+    pub port: u16,
+    pub state: PortState,
+    pub data: VecNoStd<u8>,
+    pub segments: VecNoStd<NetworkPacket>,
+}
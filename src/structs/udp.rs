@@ -8,7 +8,7 @@ use alloc::vec::Vec;
 
 /// Definition de l'header UDP
 /// Contient les infos du header.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UdpHeader {
     pub src_port: u16,
     pub dst_port: u16,
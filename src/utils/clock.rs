@@ -0,0 +1,60 @@
+/////////////////////////////////////////////////////////////
+// clock.rs
+// Source de temps pour horodater les paquets ecrits
+// (PCAP, PCAPNG, metadonnees JSON).
+// Sous la feature std l'horloge systeme est utilisee, en no_std
+// l'appelant fournit sa propre implementation de Clock (RTC, compteur...).
+/////////////////////////////////////////////////////////////
+
+use crate::prelude::*;
+
+/// Trait d'horloge
+pub trait Clock {
+    /// Temps ecoule depuis l'epoch Unix, en nanosecondes
+    fn now_ns(&self) -> u64;
+}
+
+/// Horloge systeme
+#[cfg(feature = "std")]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now_ns(&self) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0)
+    }
+}
+
+/// Horloge figee, utile pour les tests ou en no_std sans source de temps
+pub struct FixedClock {
+    pub ns: u64,
+}
+
+impl Clock for FixedClock {
+    fn now_ns(&self) -> u64 {
+        self.ns
+    }
+}
+
+/// Horloge par defaut : systeme sous std, epoch sinon
+pub fn default_clock() -> Box<dyn Clock> {
+    #[cfg(feature = "std")]
+    {
+        Box::new(SystemClock)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        Box::new(FixedClock { ns: 0 })
+    }
+}
+
+/// Decoupe un timestamp en nanosecondes en (secondes, nanosecondes)
+pub fn split_timestamp_ns(ns: u64) -> (u64, u32) {
+    (
+        ns / 1_000_000_000,
+        (ns % 1_000_000_000) as u32
+    )
+}
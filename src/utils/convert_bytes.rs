@@ -69,4 +69,30 @@ pub fn convert_n_to_bytes <T: Into<u64>>(value: T, size: usize) -> Result<VecNoS
         ) as u8;
     }
     Ok(bytes)
-}
\ No newline at end of file
+}
+/// Convertit un slice d’octets en Big Endian (1, 2, 4 ou 8 octets) en nombre.
+/// Operation inverse de convert_n_to_bytes.
+pub fn convert_bytes_to_n(bytes: &[u8]) -> Result<u64> {
+    let size = bytes.len();
+    if size != 1
+    && size != 2
+    && size != 4
+    && size != 8
+    {
+        return Err(
+            InvalidLengthBytes {
+                size
+            }
+        );
+    }
+
+    let mut value: u64 = 0;
+    for &b in bytes {
+        value = (
+            value << 8
+        ) | (
+            b as u64
+        );
+    }
+    Ok(value)
+}
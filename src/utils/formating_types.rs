@@ -1,4 +1,5 @@
 use crate::prelude::StringNoStd;
+use crate::utils::clock::default_clock;
 use alloc::format;
 
 // Formate une adresse MAC en chaîne hexadécimale MAC
@@ -12,6 +13,33 @@ pub fn format_ip(ip: &[u8; 4]) -> StringNoStd {
     format!("{}.{}.{}.{}", ip[0], ip[1], ip[2], ip[3])
 }
 
+// Formate une adresse IPv6 (RFC 5952) : hexadecimal minuscule,
+// la plus longue suite d'au moins deux groupes nuls est remplacee par "::"
+pub fn format_ipv6(ip: &[u8; 16]) -> StringNoStd {
+    let groups: [u16; 8] = core::array::from_fn(|i| u16::from_be_bytes([ip[2 * i], ip[2 * i + 1]]));
+
+    let (mut best_start, mut best_len) = (0, 0);
+    let mut i = 0;
+    while i < 8 {
+        let start = i;
+        while i < 8 && groups[i] == 0 {
+            i += 1;
+        }
+        if i - start > best_len {
+            (best_start, best_len) = (start, i - start);
+        }
+        i += 1;
+    }
+
+    let join = |groups: &[u16]| {
+        groups.iter().map(|g| format!("{:x}", g)).collect::<alloc::vec::Vec<_>>().join(":")
+    };
+    if best_len < 2 {
+        return join(&groups);
+    }
+    format!("{}::{}", join(&groups[..best_start]), join(&groups[best_start + best_len..]))
+}
+
 // Formate un tableau d'octets en une chaîne de représentation hexadécimale
 pub fn format_bytes(bytes: &[u8]) -> StringNoStd {
     let mut result = StringNoStd::new();
@@ -24,7 +52,7 @@ pub fn format_bytes(bytes: &[u8]) -> StringNoStd {
     result
 }
 
-// Obtient le timestamp actuel en millisecondes
+// Obtient le timestamp actuel en millisecondes (horloge par defaut)
 pub fn get_timestamp_ms() -> u64 {
-    0
+    default_clock().now_ns() / 1_000_000
 }
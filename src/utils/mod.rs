@@ -4,4 +4,5 @@ pub mod push_bytes;
 pub mod convert_string;
 pub mod format_mac;
 pub mod payload_size;
-pub mod formating_types;
\ No newline at end of file
+pub mod formating_types;
+pub mod clock;
\ No newline at end of file
@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        packets::{
+            arp::{pack_arp, unpack_arp},
+            builder::arp_builder::ArpBuilder,
+        },
+        parsing::my_parser::{parse_cidr, parse_ipv4},
+        sender::raw_socket::{get_interface_index, get_interface_ipv4, get_interface_mac},
+        structs::{
+            arp::{ArpFrame, ARP_REPLY, ARP_REQUEST, ETHERTYPE_ARP},
+            ipv4::Ipv4Addr,
+            scan::{ArpHost, ArpScanner},
+            socket::{RawSocketReceiver, RawSocketSender},
+        },
+    };
+    use std::time::{Duration, Instant};
+
+    const LOCAL_MAC: [u8; 6] = [0x02, 0x00, 0x00, 0x00, 0x00, 0x01];
+
+    fn builder(ip: &str) -> ArpBuilder {
+        ArpBuilder::new(LOCAL_MAC, parse_ipv4(ip).unwrap())
+    }
+
+    // Hotes simules sur lo : chaque hote de `hosts` repond aux requetes qui le visent
+    fn spawn_responder(hosts: Vec<(&str, [u8; 6])>, requests: usize) -> std::thread::JoinHandle<()> {
+        let hosts: Vec<_> = hosts.into_iter().map(|(ip, mac)| (parse_ipv4(ip).unwrap(), mac)).collect();
+        let mut receiver = RawSocketReceiver::with_protocol(ETHERTYPE_ARP).unwrap();
+        receiver.bind("lo").unwrap();
+        receiver.set_read_timeout(Some(100)).unwrap();
+        std::thread::spawn(move || {
+            let sender = RawSocketSender::new().unwrap();
+            let if_index = get_interface_index("lo").unwrap();
+            let deadline = Instant::now() + Duration::from_secs(3);
+            let mut seen = 0;
+            while seen < requests && Instant::now() < deadline {
+                let Some(frame) = receiver.recv_frame().unwrap() else { continue };
+                let Ok(request) = ArpFrame::parse(&frame.record.data) else { continue };
+                if frame.is_outgoing() || request.arp.operation != ARP_REQUEST {
+                    continue;
+                }
+                seen += 1;
+                let Some(&(ip, mac)) = hosts.iter().find(|(ip, _)| ip.octets == request.arp.target_ip) else {
+                    continue;
+                };
+                let reply = ArpBuilder::new(mac, ip).build_reply(
+                    request.arp.sender_mac,
+                    Ipv4Addr { octets: request.arp.sender_ip },
+                );
+                sender.send(if_index, reply.ethernet.dst_mac, &reply.assemble().unwrap()).unwrap();
+            }
+        })
+    }
+
+    ///////////////////////////////////////////
+    ///          ARP Packet Tests           ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_arp_request_bytes() {
+        let frame = builder("10.0.0.1").build_request(parse_ipv4("10.0.0.7").unwrap());
+        let bytes = frame.assemble().unwrap();
+        assert_eq!(bytes.len(), 42);
+        assert_eq!(&bytes[0..6], &[0xFF; 6]);
+        assert_eq!(&bytes[12..14], &[0x08, 0x06]);
+        assert_eq!(&bytes[14..22], &[0x00, 0x01, 0x08, 0x00, 0x06, 0x04, 0x00, 0x01]);
+        assert_eq!(&bytes[22..28], &LOCAL_MAC);
+        assert_eq!(&bytes[28..32], &[10, 0, 0, 1]);
+        assert_eq!(&bytes[32..38], &[0; 6]);
+        assert_eq!(&bytes[38..42], &[10, 0, 0, 7]);
+    }
+
+    #[test]
+    fn test_arp_reply_is_unicast() {
+        let requester = [0x02, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE];
+        let frame = builder("10.0.0.7").build_reply(requester, parse_ipv4("10.0.0.1").unwrap());
+        assert_eq!(frame.ethernet.dst_mac, requester);
+        assert_eq!(frame.arp.operation, ARP_REPLY);
+        assert_eq!(frame.arp.target_mac, requester);
+        assert_eq!(frame.arp.sender_ip, [10, 0, 0, 7]);
+    }
+
+    #[test]
+    fn test_arp_gratuitous_announces_itself() {
+        let frame = builder("10.0.0.5").build_gratuitous();
+        assert_eq!(frame.ethernet.dst_mac, [0xFF; 6]);
+        assert_eq!(frame.arp.operation, ARP_REQUEST);
+        assert_eq!(frame.arp.sender_ip, frame.arp.target_ip);
+    }
+
+    #[test]
+    fn test_arp_frame_round_trip_with_padding() {
+        let frame = builder("10.0.0.1").build_request(parse_ipv4("10.0.0.2").unwrap());
+        let mut bytes = frame.assemble().unwrap();
+        bytes.resize(60, 0);
+        assert_eq!(ArpFrame::parse(&bytes).unwrap(), frame);
+        assert_eq!(unpack_arp(&pack_arp(&frame.arp).unwrap()).unwrap(), frame.arp);
+    }
+
+    #[test]
+    fn test_arp_parse_errors() {
+        let frame = builder("10.0.0.1").build_request(parse_ipv4("10.0.0.2").unwrap());
+        let bytes = frame.assemble().unwrap();
+        assert_eq!(
+            unpack_arp(&bytes[14..30]),
+            Err(ParseError::TruncatedPacket { layer: "ARP", needed: 28, available: 16 })
+        );
+
+        let mut ipv4 = bytes.clone();
+        ipv4[13] = 0x00;
+        assert_eq!(ArpFrame::parse(&ipv4), Err(ParseError::UnsupportedEtherType(0x0800)));
+
+        let mut token_ring = bytes;
+        token_ring[15] = 6;
+        assert!(matches!(ArpFrame::parse(&token_ring), Err(ParseError::InvalidFormat(_))));
+    }
+
+    ///////////////////////////////////////////
+    ///         CIDR Parsing Tests          ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_parse_cidr_hosts() {
+        let hosts = parse_cidr("192.168.1.77/29").unwrap();
+        let octets: Vec<_> = hosts.iter().map(|ip| ip.octets[3]).collect();
+        assert_eq!(octets, vec![73, 74, 75, 76, 77, 78]);
+        assert_eq!(parse_cidr("10.0.0.0/24").unwrap().len(), 254);
+        assert_eq!(parse_cidr("10.0.0.0/31").unwrap().len(), 2);
+        assert_eq!(parse_cidr("10.0.0.9").unwrap(), vec![parse_ipv4("10.0.0.9").unwrap()]);
+    }
+
+    #[test]
+    fn test_parse_cidr_invalid() {
+        assert!(matches!(parse_cidr("10.0.0.0/8"), Err(ParseError::InvalidFormat(_))));
+        assert!(matches!(parse_cidr("10.0.0.0/33"), Err(ParseError::InvalidFormat(_))));
+        assert!(matches!(parse_cidr("10.0.0.0/x"), Err(ParseError::InvalidFormat(_))));
+        assert!(parse_cidr("10.0.0/24").is_err());
+    }
+
+    ///////////////////////////////////////////
+    ///          ARP Sweep Tests            ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_classify_reply_filters_on_local_ip() {
+        let scanner = ArpScanner::new(LOCAL_MAC, parse_ipv4("10.0.0.1").unwrap());
+        let host_mac = [0x02, 0x11, 0x22, 0x33, 0x44, 0x55];
+        let reply = ArpBuilder::new(host_mac, parse_ipv4("10.0.0.9").unwrap())
+            .build_reply(LOCAL_MAC, parse_ipv4("10.0.0.1").unwrap());
+        assert_eq!(
+            scanner.classify_reply(&reply.assemble().unwrap()),
+            Some(ArpHost { ip: parse_ipv4("10.0.0.9").unwrap(), mac: host_mac })
+        );
+
+        let other = ArpBuilder::new(host_mac, parse_ipv4("10.0.0.9").unwrap())
+            .build_reply(LOCAL_MAC, parse_ipv4("10.0.0.2").unwrap());
+        assert_eq!(scanner.classify_reply(&other.assemble().unwrap()), None);
+        let request = scanner.build_probe(parse_ipv4("10.0.0.9").unwrap());
+        assert_eq!(scanner.classify_reply(&request.assemble().unwrap()), None);
+    }
+
+    #[test]
+    fn test_interface_addresses_loopback() {
+        assert_eq!(get_interface_ipv4("lo").unwrap(), parse_ipv4("127.0.0.1").unwrap());
+        assert_eq!(get_interface_mac("lo").unwrap(), [0; 6]);
+        assert!(get_interface_mac("no_such_if0").is_err());
+    }
+
+    #[test]
+    fn test_arp_sweep_loopback() {
+        if RawSocketSender::new().is_err() {
+            eprintln!("raw socket indisponible (root requis), test ignore");
+            return;
+        }
+        let mac_a = [0x02, 0x00, 0x5E, 0x00, 0x00, 0x0A];
+        let mac_b = [0x02, 0x00, 0x5E, 0x00, 0x00, 0x0C];
+        let responder = spawn_responder(vec![("198.51.100.10", mac_a), ("198.51.100.12", mac_b)], 6);
+
+        let scanner = ArpScanner::new(LOCAL_MAC, parse_ipv4("198.51.100.1").unwrap())
+            .with_timeout_ms(300)
+            .with_retries(0)
+            .with_rate_limit(50);
+        let targets = parse_cidr("198.51.100.9/29").unwrap();
+        let hosts = scanner.sweep("lo", &targets).unwrap();
+        responder.join().unwrap();
+
+        assert_eq!(hosts, vec![
+            ArpHost { ip: parse_ipv4("198.51.100.10").unwrap(), mac: mac_a },
+            ArpHost { ip: parse_ipv4("198.51.100.12").unwrap(), mac: mac_b },
+        ]);
+    }
+}
@@ -2,7 +2,7 @@
 mod tests {
     use projet_rsns_morissetlarresacha::{
         errors::errors::ParseError,
-        utils::convert_bytes::convert_n_to_bytes
+        utils::convert_bytes::{convert_n_to_bytes, convert_bytes_to_n}
     };
 
     ///////////////////////////////////////////
@@ -112,4 +112,17 @@ mod tests {
         let result = convert_n_to_bytes(value, 2);
         assert_eq!(result, Ok(vec![0x00, 0x01]));
     }
+
+    #[test]
+    fn test_convert_bytes_to_n_round_trip() {
+        let bytes = convert_n_to_bytes(0xDEADBEEFu32, 4).unwrap();
+        assert_eq!(convert_bytes_to_n(&bytes), Ok(0xDEADBEEF));
+        assert_eq!(convert_bytes_to_n(&[0x12, 0x34]), Ok(0x1234));
+    }
+
+    #[test]
+    fn test_convert_bytes_to_n_invalid_size() {
+        let result = convert_bytes_to_n(&[0x01, 0x02, 0x03]);
+        assert_eq!(result, Err(ParseError::InvalidLengthBytes { size: 3 }));
+    }
 }
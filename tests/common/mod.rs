@@ -0,0 +1,112 @@
+// Aides partagees par les suites de tests (`mod common;` en tete de fichier)
+// Chaque suite n'en utilise qu'une partie
+#![allow(dead_code)]
+
+use projet_rsns_morissetlarresacha::{
+    packets::l4::tcp::pack_tcp,
+    parsing::my_parser::parse_ipv4,
+    sender::raw_socket::get_interface_index,
+    structs::{
+        l4_protocol::L4Data,
+        network_packet::NetworkPacket,
+        packet_builder::PacketBuilder,
+        socket::{RawSocketReceiver, RawSocketSender},
+        tcp::TcpHeader,
+    },
+    utils::checksum::internet_checksum,
+};
+use std::{
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+/// En-tete TCP d'un paquet, panique pour un autre protocole
+pub fn tcp(packet: &NetworkPacket) -> &TcpHeader {
+    let L4Data::Tcp(tcp) = &packet.l4_data else { panic!("expected TCP") };
+    tcp
+}
+
+/// Checksum verifie sur pseudo-header IPv4 + segment emballe
+pub fn tcp_checksum_ok(packet: &NetworkPacket) -> bool {
+    let segment = pack_tcp(tcp(packet)).unwrap();
+    let ipv4 = packet.ipv4().unwrap();
+    let mut data = Vec::new();
+    data.extend_from_slice(&ipv4.src_addr);
+    data.extend_from_slice(&ipv4.dst_addr);
+    data.extend_from_slice(&[0, 6]);
+    data.extend_from_slice(&(segment.len() as u16).to_be_bytes());
+    data.extend_from_slice(&segment);
+    internet_checksum(&data) == 0
+}
+
+/// Gabarit des scanners : MAC nulles (trames injectees sur lo)
+pub fn template(src: &str, dst: &str, protocol: &str, src_port: u16, dst_port: u16) -> PacketBuilder {
+    PacketBuilder::from_cli_args(
+        Some(src),
+        Some(dst),
+        Some([0; 6]),
+        Some([0; 6]),
+        Some(src_port),
+        Some(dst_port),
+        Some(protocol),
+        None,
+        None,
+    ).unwrap()
+}
+
+/// Gabarit de reponse a `packet` : adresses et ports TCP/UDP inverses
+pub fn reply_to(packet: &NetworkPacket) -> PacketBuilder {
+    let (protocol, src_port, dst_port) = match &packet.l4_data {
+        L4Data::Tcp(tcp) => ("tcp", tcp.dst_port, tcp.src_port),
+        L4Data::Udp(udp) => ("udp", udp.dst_port, udp.src_port),
+        _ => panic!("expected TCP or UDP"),
+    };
+    let ipv4 = packet.ipv4().expect("expected IPv4");
+    let mut reply = template("0.0.0.0", "0.0.0.0", protocol, src_port, dst_port);
+    reply.src_ip.octets = ipv4.dst_addr;
+    reply.dst_ip.octets = ipv4.src_addr;
+    reply
+}
+
+/// Vrai si les sockets brutes sont refusees : le test appelant est ignore
+pub fn raw_socket_unavailable() -> bool {
+    let unavailable = RawSocketSender::new().is_err();
+    if unavailable {
+        eprintln!("raw socket indisponible (root requis), test ignore");
+    }
+    unavailable
+}
+
+/// Hote simule sur lo pour `target` (le noyau ignore les trames injectees vers une
+/// adresse non locale). `respond` recoit chaque paquet entrant qui lui est adresse,
+/// ajoute les trames a injecter et rend faux pour s'arreter apres leur envoi.
+/// La socket est ouverte avant le retour : aucune sonde n'est manquee.
+pub fn spawn_responder<F>(target: &str, timeout: Duration, mut respond: F) -> JoinHandle<()>
+where
+    F: FnMut(&NetworkPacket, &mut Vec<Vec<u8>>) -> bool + Send + 'static,
+{
+    let target = parse_ipv4(target).unwrap();
+    let mut receiver = RawSocketReceiver::with_protocol(0x0800).unwrap();
+    receiver.bind("lo").unwrap();
+    receiver.set_read_timeout(Some(100)).unwrap();
+    std::thread::spawn(move || {
+        let sender = RawSocketSender::new().unwrap();
+        let if_index = get_interface_index("lo").unwrap();
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            let Some(frame) = receiver.recv_frame().unwrap() else { continue };
+            let Some(packet) = &frame.record.packet else { continue };
+            if frame.is_outgoing() || packet.ipv4().is_none_or(|ipv4| ipv4.dst_addr != target.octets) {
+                continue;
+            }
+            let mut replies = Vec::new();
+            let more = respond(packet, &mut replies);
+            for reply in replies {
+                sender.send(if_index, [0; 6], &reply).unwrap();
+            }
+            if !more {
+                return;
+            }
+        }
+    })
+}
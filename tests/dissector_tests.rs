@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        structs::{
+            packet_builder::PacketBuilder,
+            network_packet::NetworkPacket,
+            l4_protocol::L4Data,
+        },
+        packets::{
+            ip::unpack_ipv4,
+            l4::{tcp::unpack_tcp, udp::unpack_udp},
+        },
+        errors::errors::ParseError,
+    };
+
+    fn build(protocol: &str, payload: Option<Vec<u8>>) -> NetworkPacket {
+        PacketBuilder::from_cli_args(
+            Some("192.168.1.10"),
+            Some("192.168.1.20"),
+            Some([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]),
+            Some([0x11, 0x22, 0x33, 0x44, 0x55, 0x66]),
+            Some(40000),
+            Some(443),
+            Some(protocol),
+            Some(0x40),
+            payload,
+        ).unwrap().build_packet().unwrap()
+    }
+
+    ///////////////////////////////////////////
+    ///      Round-trip Tests               ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_dissect_tcp_round_trip() {
+        let packet = build("tcp", Some(b"GET / HTTP/1.0\r\n\r\n".to_vec()));
+        let bytes = packet.assemble_packet().unwrap();
+        let parsed = NetworkPacket::parse(&bytes).unwrap();
+        assert_eq!(parsed, packet);
+    }
+
+    #[test]
+    fn test_dissect_udp_round_trip() {
+        let packet = build("udp", Some(b"dns".to_vec()));
+        let bytes = packet.assemble_packet().unwrap();
+        let parsed = NetworkPacket::parse(&bytes).unwrap();
+        assert_eq!(parsed, packet);
+    }
+
+    #[test]
+    fn test_dissect_without_payload() {
+        let packet = build("udp", None);
+        let bytes = packet.assemble_packet().unwrap();
+        let parsed = NetworkPacket::parse(&bytes).unwrap();
+        match parsed.l4_data {
+            L4Data::Udp(udp) => assert_eq!(udp.payload, None),
+            _ => panic!("Expected UDP data"),
+        }
+    }
+
+    #[test]
+    fn test_dissect_ignores_ethernet_padding() {
+        let packet = build("tcp", None);
+        let mut bytes = packet.assemble_packet().unwrap();
+        bytes.resize(60, 0);
+        let parsed = NetworkPacket::parse(&bytes).unwrap();
+        assert_eq!(parsed, packet);
+    }
+
+    #[test]
+    fn test_dissect_ipv4_and_tcp_options() {
+        let mut packet = build("tcp", Some(b"x".to_vec()));
+        let ipv4 = packet.ipv4_mut().unwrap();
+        ipv4.ihl = 6;
+        ipv4.options = Some(vec![0x94, 0x04, 0x00, 0x00]);
+        ipv4.total_length += 8;
+        if let L4Data::Tcp(tcp) = &mut packet.l4_data {
+            tcp.data_offset = 6;
+            tcp.options = Some(vec![0x02, 0x04, 0x05, 0xB4]);
+        }
+        let bytes = packet.assemble_packet().unwrap();
+        let parsed = NetworkPacket::parse(&bytes).unwrap();
+        assert_eq!(parsed, packet);
+    }
+
+    ///////////////////////////////////////////
+    ///      Error Tests                    ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_dissect_truncated_ethernet() {
+        let result = NetworkPacket::parse(&[0u8; 10]);
+        assert_eq!(
+            result,
+            Err(ParseError::TruncatedPacket { layer: "Ethernet", needed: 14, available: 10 })
+        );
+    }
+
+    #[test]
+    fn test_dissect_unsupported_ethertype() {
+        let mut bytes = build("tcp", None).assemble_packet().unwrap();
+        bytes[12] = 0x08;
+        bytes[13] = 0x06;
+        assert_eq!(NetworkPacket::parse(&bytes), Err(ParseError::UnsupportedEtherType(0x0806)));
+    }
+
+    #[test]
+    fn test_dissect_truncated_ipv4_total_length() {
+        let bytes = build("tcp", Some(b"payload".to_vec())).assemble_packet().unwrap();
+        let result = NetworkPacket::parse(&bytes[..bytes.len() - 3]);
+        assert!(matches!(result, Err(ParseError::TruncatedPacket { layer: "IPv4", .. })));
+    }
+
+    #[test]
+    fn test_unpack_ipv4_invalid_version_and_ihl() {
+        let mut header = [0u8; 20];
+        header[0] = 0x65;
+        assert_eq!(unpack_ipv4(&header).unwrap_err(), ParseError::InvalidVersion(6));
+        header[0] = 0x44;
+        assert_eq!(
+            unpack_ipv4(&header).unwrap_err(),
+            ParseError::InvalidHeaderLength { layer: "IPv4", length: 16 }
+        );
+    }
+
+    #[test]
+    fn test_unpack_tcp_bad_data_offset() {
+        let mut segment = [0u8; 20];
+        segment[12] = 0x40;
+        assert!(matches!(unpack_tcp(&segment), Err(ParseError::InvalidHeaderLength { layer: "TCP", .. })));
+        segment[12] = 0x80;
+        assert!(matches!(unpack_tcp(&segment), Err(ParseError::TruncatedPacket { layer: "TCP", needed: 32, .. })));
+    }
+
+    #[test]
+    fn test_unpack_udp_length_mismatch() {
+        let datagram = [0x00, 0x35, 0x00, 0x35, 0x00, 0x20, 0x00, 0x00, 0x01];
+        assert_eq!(
+            unpack_udp(&datagram),
+            Err(ParseError::LengthMismatch { layer: "UDP", declared: 32, actual: 9 })
+        );
+    }
+
+    #[test]
+    fn test_dissect_unsupported_protocol() {
+        let mut packet = build("udp", None);
+        packet.ipv4_mut().unwrap().protocol = 47;
+        let bytes = packet.assemble_packet().unwrap();
+        assert_eq!(NetworkPacket::parse(&bytes), Err(ParseError::UnsupportedProtocol(47)));
+    }
+}
@@ -14,6 +14,7 @@ mod tests {
             src_mac: [
                 0x11, 0x22, 0x33, 0x44, 0x55, 0x66
             ],
+            vlan_tags: Vec::new(),
             ethertype: 0x0800,
         };
 
@@ -75,6 +76,7 @@ mod tests {
             src_mac: [
                 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B
             ],
+            vlan_tags: Vec::new(),
             ethertype: 0x86DD,
         };
 
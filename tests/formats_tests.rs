@@ -1,18 +1,21 @@
 #[cfg(test)]
 mod tests {
     use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
         formats::format_factory::FormatFactory,
         structs::{
             network_packet::NetworkPacket,
             ethernet::EthernetHeader,
-            ip::Ipv4Header,
+            ip::{IpHeader, Ipv4Header},
             tcp::TcpHeader,
             udp::UdpHeader,
             l4_protocol::L4Data,
             formats::FormatType,
+            packet_builder::PacketBuilder,
             json::JsonSerializer,
-            pcap::{PcapWriter, PcapReader},
+            pcap::{PcapWriter, PcapReader, PcapOptions, PcapByteOrder, PcapPrecision, LinkType},
         },
+        utils::clock::FixedClock,
     };
 
     ///////////////////////////////////////////
@@ -24,9 +27,10 @@ mod tests {
             ethernet: EthernetHeader {
                 src_mac: [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF],
                 dst_mac: [0x11, 0x22, 0x33, 0x44, 0x55, 0x66],
+                vlan_tags: Vec::new(),
                 ethertype: 0x0800,
             },
-            ipv4: Ipv4Header {
+            ip: IpHeader::V4(Ipv4Header {
                 version: 4,
                 ihl: 5,
                 dscp: 0,
@@ -40,7 +44,7 @@ mod tests {
                 src_addr: [192, 168, 1, 1],
                 dst_addr: [192, 168, 1, 2],
                 options: None,
-            },
+            }),
             l4_data: L4Data::Tcp(TcpHeader {
                 src_port: 8080,
                 dst_port: 443,
@@ -63,9 +67,10 @@ mod tests {
             ethernet: EthernetHeader {
                 src_mac: [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
                 dst_mac: [0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB],
+                vlan_tags: Vec::new(),
                 ethertype: 0x0800,
             },
-            ipv4: Ipv4Header {
+            ip: IpHeader::V4(Ipv4Header {
                 version: 4,
                 ihl: 5,
                 dscp: 0,
@@ -79,7 +84,7 @@ mod tests {
                 src_addr: [10, 0, 0, 1],
                 dst_addr: [10, 0, 0, 2],
                 options: None,
-            },
+            }),
             l4_data: L4Data::Udp(UdpHeader {
                 src_port: 53,
                 dst_port: 53,
@@ -222,6 +227,241 @@ mod tests {
         assert!(!reader.has_more_packets());
     }
 
+    #[test]
+    fn test_pcap_reader_records() {
+        let packet = create_test_udp_packet();
+        let mut writer = PcapWriter::new();
+        writer.write_global_header().unwrap();
+        writer.write_packet(&packet).unwrap();
+        let data = writer.into_data();
+
+        let mut reader = PcapReader::new(data);
+        let record = reader.read_next_record().unwrap().unwrap();
+
+        assert_eq!(record.caplen as usize, packet.get_packet_size());
+        assert_eq!(record.orig_len, record.caplen);
+        assert_eq!(record.data, packet.assemble_packet().unwrap());
+        assert!(reader.read_next_record().unwrap().is_none());
+    }
+
+    fn build_valid_tcp_packet() -> NetworkPacket {
+        PacketBuilder::from_cli_args(
+            Some("10.0.0.1"),
+            Some("10.0.0.2"),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            None,
+            Some(1234),
+            Some(80),
+            Some("tcp"),
+            None,
+            Some(b"hello".to_vec()),
+        ).unwrap().build_packet().unwrap()
+    }
+
+    fn write_with_options(packet: &NetworkPacket, options: PcapOptions) -> Vec<u8> {
+        let mut writer = PcapWriter::with_options(options);
+        writer.write_global_header().unwrap();
+        writer.write_packet(packet).unwrap();
+        writer.into_data()
+    }
+
+    #[test]
+    fn test_pcap_reader_record_decodes_packet() {
+        let packet = build_valid_tcp_packet();
+        let factory = FormatFactory::new();
+        let data = factory.write_packet(&packet, FormatType::Pcap).unwrap();
+
+        let mut reader = factory.create_reader(FormatType::Pcap, data);
+        let record = reader.read_next_record().unwrap().unwrap();
+
+        assert_eq!(record.packet, Some(packet));
+        assert!(!reader.has_more_packets());
+    }
+
+    #[test]
+    fn test_pcap_big_endian_nanosecond() {
+        let packet = build_valid_tcp_packet();
+        let data = write_with_options(&packet, PcapOptions {
+            byte_order: PcapByteOrder::BigEndian,
+            precision: PcapPrecision::Nano,
+            ..PcapOptions::default()
+        });
+        assert_eq!(data[0..4], [0xA1, 0xB2, 0x3C, 0x4D]);
+
+        let mut reader = PcapReader::new(data);
+        reader.read_global_header().unwrap();
+        assert_eq!(reader.byte_order, PcapByteOrder::BigEndian);
+        assert_eq!(reader.precision, PcapPrecision::Nano);
+        assert_eq!(reader.snaplen, 65535);
+
+        let record = reader.read_next_record().unwrap().unwrap();
+        assert_eq!(record.packet, Some(packet));
+    }
+
+    #[test]
+    fn test_pcap_reader_swapped_magic_header() {
+        let mut data = vec![
+            0xA1, 0xB2, 0xC3, 0xD4, 0x00, 0x02, 0x00, 0x04,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x65,
+        ];
+        data.extend_from_slice(&[0, 0, 0, 5, 0, 0, 0, 7, 0, 0, 0, 2, 0, 0, 0, 2, 0x45, 0x00]);
+
+        let mut reader = PcapReader::new(data);
+        let record = reader.read_next_record().unwrap().unwrap();
+        assert_eq!(reader.link_type, LinkType::Raw);
+        assert_eq!(reader.snaplen, 256);
+        assert_eq!(record.ts_sec, 5);
+        assert_eq!(record.ts_usec, 7);
+        assert_eq!(record.ts_nsec, 7000);
+        assert_eq!(record.data, vec![0x45, 0x00]);
+        assert!(record.packet.is_none());
+    }
+
+    #[test]
+    fn test_pcap_reader_timestamp_fraction_out_of_range() {
+        let packet = build_valid_tcp_packet();
+        for (precision, ts_frac) in [(PcapPrecision::Micro, 1_000_000u32), (PcapPrecision::Nano, u32::MAX)] {
+            let mut data = write_with_options(&packet, PcapOptions {
+                precision,
+                ..PcapOptions::default()
+            });
+            data[28..32].copy_from_slice(&ts_frac.to_le_bytes());
+            let mut reader = PcapReader::new(data);
+            assert!(reader.read_next_record().is_err());
+        }
+    }
+
+    #[test]
+    fn test_pcap_raw_and_cooked_link_types() {
+        let packet = build_valid_tcp_packet();
+        for link_type in [LinkType::Raw, LinkType::LinuxSll, LinkType::LinuxSll2] {
+            let data = write_with_options(&packet, PcapOptions {
+                link_type,
+                ..PcapOptions::default()
+            });
+            let mut reader = PcapReader::new(data);
+            let record = reader.read_next_record().unwrap().unwrap();
+            let decoded = record.packet.unwrap();
+
+            assert_eq!(reader.link_type, link_type);
+            assert_eq!(decoded.ip, packet.ip);
+            assert_eq!(decoded.l4_data, packet.l4_data);
+            if link_type != LinkType::Raw {
+                assert_eq!(decoded.ethernet.src_mac, packet.ethernet.src_mac);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pcap_record_converted_from_its_link_type() {
+        let packet = build_valid_tcp_packet();
+        for source in [LinkType::Raw, LinkType::LinuxSll, LinkType::LinuxSll2] {
+            let data = write_with_options(&packet, PcapOptions {
+                link_type: source,
+                ..PcapOptions::default()
+            });
+            let record = PcapReader::new(data).read_next_record().unwrap().unwrap();
+            assert_eq!(record.link_type, source);
+
+            for target in [LinkType::Ethernet, LinkType::Raw, LinkType::LinuxSll, LinkType::LinuxSll2] {
+                let mut writer = PcapWriter::with_options(PcapOptions {
+                    link_type: target,
+                    ..PcapOptions::default()
+                });
+                writer.write_global_header().unwrap();
+                writer.write_record(&record).unwrap();
+
+                let decoded = PcapReader::new(writer.into_data()).read_next_record().unwrap().unwrap();
+                let decoded = decoded.packet.unwrap();
+                assert_eq!(decoded.ip, packet.ip);
+                assert_eq!(decoded.l4_data, packet.l4_data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pcap_write_short_record() {
+        let mut reader = PcapReader::new(write_with_options(&build_valid_tcp_packet(), PcapOptions::default()));
+        let mut record = reader.read_next_record().unwrap().unwrap();
+        record.data.truncate(10);
+
+        let mut writer = PcapWriter::new();
+        writer.write_global_header().unwrap();
+        assert_eq!(
+            writer.write_record(&record),
+            Err(ParseError::TruncatedPacket { layer: "Ethernet", needed: 14, available: 10 })
+        );
+        record.link_type = LinkType::Raw;
+        record.data.clear();
+        assert!(writer.write_record(&record).is_err());
+        assert_eq!(writer.get_data().len(), 24);
+    }
+
+    #[test]
+    fn test_pcap_snaplen_truncates_capture() {
+        let packet = build_valid_tcp_packet();
+        let data = write_with_options(&packet, PcapOptions {
+            snaplen: 40,
+            ..PcapOptions::default()
+        });
+        let mut reader = PcapReader::new(data);
+        let record = reader.read_next_record().unwrap().unwrap();
+
+        assert_eq!(record.caplen, 40);
+        assert_eq!(record.orig_len as usize, packet.get_packet_size());
+        assert!(record.packet.is_none());
+    }
+
+    #[test]
+    fn test_pcap_reader_invalid_magic() {
+        let mut reader = PcapReader::new(vec![0u8; 24]);
+        assert!(reader.read_global_header().is_err());
+    }
+
+    #[test]
+    fn test_pcap_timestamp_from_clock() {
+        let packet = build_valid_tcp_packet();
+        let clock_ns = 1_700_000_000_123_456_789;
+        for (precision, expected_usec, expected_nsec) in [
+            (PcapPrecision::Micro, 123_456, 123_456_000),
+            (PcapPrecision::Nano, 123_456, 123_456_789),
+        ] {
+            let mut writer = PcapWriter::with_options(PcapOptions {
+                precision,
+                ..PcapOptions::default()
+            }).with_clock(Box::new(FixedClock { ns: clock_ns }));
+            writer.write_global_header().unwrap();
+            writer.write_packet(&packet).unwrap();
+
+            let mut reader = PcapReader::new(writer.into_data());
+            let record = reader.read_next_record().unwrap().unwrap();
+            assert_eq!(record.ts_sec, 1_700_000_000);
+            assert_eq!(record.ts_usec, expected_usec);
+            assert_eq!(record.ts_nsec, expected_nsec);
+        }
+    }
+
+    #[test]
+    fn test_pcap_default_clock_is_not_epoch() {
+        let mut writer = PcapWriter::new();
+        writer.write_global_header().unwrap();
+        writer.write_packet(&build_valid_tcp_packet()).unwrap();
+
+        let mut reader = PcapReader::new(writer.into_data());
+        let record = reader.read_next_record().unwrap().unwrap();
+        assert!(record.ts_sec > 1_600_000_000);
+        assert!(record.ts_usec < 1_000_000);
+    }
+
+    #[test]
+    fn test_json_timestamp_from_clock() {
+        let serializer = JsonSerializer::without_raw_data()
+            .with_clock(Box::new(FixedClock { ns: 1_700_000_000_123_456_789 }));
+        let json_str = serializer.serialize_packet(&create_test_tcp_packet()).unwrap();
+        assert!(json_str.contains("\"timestamp\":1700000000123"));
+    }
+
     ///////////////////////////////////////////
     ///      Format Factory Tests           ///
     ///////////////////////////////////////////
@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        packets::ip::{ipv4_header_checksum, pack_ipv4},
+        structs::{
+            fragment::{FragmentMode, Ipv4Fragment, Ipv4Fragmenter, Ipv4Reassembler},
+            ip::{IPV4_FLAG_DF, IPV4_FLAG_MF},
+            l4_protocol::L4Data,
+            network_packet::NetworkPacket,
+            packet_builder::PacketBuilder,
+        },
+        utils::{checksum::internet_checksum, clock::FixedClock},
+    };
+
+    fn packet(protocol: &str, payload_len: usize) -> NetworkPacket {
+        PacketBuilder::from_cli_args(
+            Some("10.0.0.1"),
+            Some("10.0.0.2"),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            Some(40000),
+            Some(53),
+            Some(protocol),
+            None,
+            Some((0..payload_len).map(|i| i as u8).collect()),
+        ).unwrap()
+            .build_packet()
+            .unwrap()
+    }
+
+    fn header_checksum_ok(fragment: &Ipv4Fragment) -> bool {
+        let bytes = pack_ipv4(&fragment.ipv4, &[]).unwrap();
+        internet_checksum(&bytes) == 0
+    }
+
+    // Reassemble des fragments dans l'ordre donne, le dernier complete le datagramme
+    fn reassemble(fragments: Vec<Ipv4Fragment>) -> NetworkPacket {
+        let mut reassembler = Ipv4Reassembler::new();
+        let count = fragments.len();
+        let mut result = None;
+        for (i, fragment) in fragments.into_iter().enumerate() {
+            result = reassembler.push_at(fragment, 0).unwrap();
+            assert_eq!(result.is_some(), i + 1 == count);
+        }
+        assert_eq!(reassembler.pending(), 0);
+        result.unwrap()
+    }
+
+    ///////////////////////////////////////////
+    ///        Fragmenter Tests             ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_fragment_offsets_and_flags() {
+        // UDP : 8 + 100 octets, MTU 68 -> 48 octets de donnees par fragment
+        let packet = packet("udp", 100);
+        let fragments = Ipv4Fragmenter::new(68).fragment(&packet).unwrap();
+        let layout: Vec<_> = fragments
+            .iter()
+            .map(|f| (f.ipv4.fragment_offset, f.payload.len(), f.ipv4.flags & IPV4_FLAG_MF, f.ipv4.total_length))
+            .collect();
+        assert_eq!(layout, vec![(0, 48, 1, 68), (6, 48, 1, 68), (12, 12, 0, 32)]);
+        for fragment in &fragments {
+            assert!(header_checksum_ok(fragment));
+            assert_eq!(fragment.key(), fragments[0].key());
+            assert_eq!(Ipv4Fragment::parse(&fragment.assemble().unwrap()).unwrap(), *fragment);
+        }
+    }
+
+    #[test]
+    fn test_fragment_fits_mtu_and_df() {
+        let packet = packet("tcp", 10);
+        let fragments = Ipv4Fragmenter::new(1500).fragment(&packet).unwrap();
+        assert_eq!(fragments.len(), 1);
+        assert!(!fragments[0].ipv4.is_fragment());
+        assert_eq!(fragments[0].assemble().unwrap(), packet.assemble_packet().unwrap());
+
+        let mut df = self::packet("udp", 100);
+        df.ipv4_mut().unwrap().flags |= IPV4_FLAG_DF;
+        assert!(Ipv4Fragmenter::new(68).fragment(&df).is_err());
+        // MTU ne laissant pas 8 octets de donnees
+        assert!(Ipv4Fragmenter::new(27).fragment(&self::packet("udp", 100)).is_err());
+    }
+
+    #[test]
+    fn test_tiny_fragment_splits_tcp_header() {
+        let packet = packet("tcp", 40);
+        let fragments = Ipv4Fragmenter::new(1500).with_mode(FragmentMode::Tiny).fragment(&packet).unwrap();
+        assert_eq!(fragments.len(), 2);
+        // Ports et numero de sequence seuls dans le premier fragment, drapeaux TCP dans le second
+        assert_eq!(fragments[0].payload.len(), 8);
+        assert_eq!(fragments[1].ipv4.fragment_offset, 1);
+        assert_eq!(fragments[1].payload.len(), 20 + 40 - 8);
+        assert_eq!(reassemble(fragments), packet);
+    }
+
+    #[test]
+    fn test_dissector_rejects_fragments_without_l4_header() {
+        // Premier fragment UDP : en-tete present mais datagramme incomplet
+        let packet = packet("udp", 100);
+        let fragments = Ipv4Fragmenter::new(68).fragment(&packet).unwrap();
+        for fragment in &fragments {
+            let frame = fragment.assemble().unwrap();
+            assert_eq!(NetworkPacket::parse(&frame), Err(ParseError::Fragmented));
+        }
+        // Premier fragment TCP de 8 octets : en-tete coupe
+        let tiny = Ipv4Fragmenter::new(1500).with_mode(FragmentMode::Tiny).fragment(&self::packet("tcp", 40)).unwrap();
+        assert_eq!(NetworkPacket::parse(&tiny[0].assemble().unwrap()), Err(ParseError::Fragmented));
+        // Les trames se decodent en fragments puis se reassemblent
+        let parsed = fragments
+            .iter()
+            .map(|fragment| Ipv4Fragment::parse(&fragment.assemble().unwrap()).unwrap())
+            .collect();
+        assert_eq!(reassemble(parsed), packet);
+    }
+
+    #[test]
+    fn test_overlapping_fragments() {
+        let packet = packet("udp", 100);
+        let fragments = Ipv4Fragmenter::new(68)
+            .with_mode(FragmentMode::Overlap { bytes: 16 })
+            .fragment(&packet)
+            .unwrap();
+        let offsets: Vec<_> = fragments.iter().map(|f| (f.byte_offset(), f.payload.len())).collect();
+        assert_eq!(offsets, vec![(0, 48), (32, 48), (64, 44)]);
+        assert_eq!(reassemble(fragments.clone()), packet);
+
+        assert!(Ipv4Fragmenter::new(68).with_mode(FragmentMode::Overlap { bytes: 12 }).fragment(&packet).is_err());
+        assert!(Ipv4Fragmenter::new(68).with_mode(FragmentMode::Overlap { bytes: 48 }).fragment(&packet).is_err());
+    }
+
+    #[test]
+    fn test_fragment_copies_flagged_options() {
+        let mut packet = packet("udp", 64);
+        // Loose Source Route (copiee), Record Route (non copiee), fin d'options
+        let ipv4 = packet.ipv4_mut().unwrap();
+        ipv4.options = Some(vec![
+            0x83, 7, 4, 10, 0, 0, 9,
+            0x07, 7, 4, 0, 0, 0, 0,
+            0x00, 0x00,
+        ]);
+        ipv4.ihl = 9;
+        ipv4.total_length += 16;
+        ipv4.header_checksum = ipv4_header_checksum(ipv4).unwrap();
+
+        let fragments = Ipv4Fragmenter::new(76).fragment(&packet).unwrap();
+        assert_eq!(fragments[0].ipv4.ihl, 9);
+        assert_eq!(fragments[0].payload.len(), 40);
+        assert_eq!(fragments[1].ipv4.ihl, 7);
+        assert_eq!(fragments[1].ipv4.options, Some(vec![0x83, 7, 4, 10, 0, 0, 9, 0]));
+        assert!(fragments.iter().all(header_checksum_ok));
+        assert_eq!(reassemble(fragments), packet);
+    }
+
+    ///////////////////////////////////////////
+    ///        Reassembler Tests            ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_reassemble_out_of_order() {
+        let packet = packet("icmp", 300);
+        let mut fragments = Ipv4Fragmenter::new(100).fragment(&packet).unwrap();
+        assert_eq!(fragments.len(), 4);
+        fragments.reverse();
+        fragments.swap(1, 2);
+        let reassembled = reassemble(fragments);
+        assert_eq!(reassembled, packet);
+        assert!(matches!(reassembled.l4_data, L4Data::Icmp(_)));
+    }
+
+    #[test]
+    fn test_reassemble_groups_by_key() {
+        let first = packet("udp", 100);
+        let mut second = packet("udp", 100);
+        second.ipv4_mut().unwrap().identification = 7;
+        let mut reassembler = Ipv4Reassembler::new();
+        let a = Ipv4Fragmenter::new(68).fragment(&first).unwrap();
+        let b = Ipv4Fragmenter::new(68).fragment(&second).unwrap();
+        for i in 0..2 {
+            assert_eq!(reassembler.push_at(a[i].clone(), 0).unwrap(), None);
+            assert_eq!(reassembler.push_at(b[i].clone(), 0).unwrap(), None);
+        }
+        assert_eq!(reassembler.pending(), 2);
+        assert_eq!(reassembler.push_at(b[2].clone(), 0).unwrap().unwrap().ipv4().unwrap().identification, 7);
+        assert_eq!(reassembler.push_at(a[2].clone(), 0).unwrap(), Some(first));
+
+        // Un datagramme non fragmente traverse le reassembleur
+        let whole = packet("tcp", 4);
+        let fragment = Ipv4Fragmenter::new(1500).fragment(&whole).unwrap().remove(0);
+        assert_eq!(reassembler.push_at(fragment, 0).unwrap(), Some(whole));
+    }
+
+    #[test]
+    fn test_reassemble_overlap_keeps_first_data() {
+        let packet = packet("udp", 100);
+        let mut fragments = Ipv4Fragmenter::new(68)
+            .with_mode(FragmentMode::Overlap { bytes: 8 })
+            .fragment(&packet)
+            .unwrap();
+        // Le second fragment tente de reecrire les 8 octets deja recus
+        for byte in &mut fragments[1].payload[..8] {
+            *byte = 0xEE;
+        }
+        assert_eq!(reassemble(fragments), packet);
+    }
+
+    #[test]
+    fn test_reassembly_timeout() {
+        let fragments = Ipv4Fragmenter::new(68).fragment(&packet("udp", 100)).unwrap();
+        let mut reassembler = Ipv4Reassembler::new()
+            .with_timeout_ms(1000)
+            .with_clock(Box::new(FixedClock { ns: 5_000_000_000 }));
+        assert_eq!(reassembler.push(fragments[0].clone()).unwrap(), None);
+        assert_eq!(reassembler.push_at(fragments[1].clone(), 5_900_000_000).unwrap(), None);
+        // Plus d'une seconde apres le premier fragment : le datagramme est abandonne
+        let expired = reassembler.expire(6_000_000_001);
+        assert_eq!(expired, vec![fragments[0].key()]);
+        assert_eq!(reassembler.push_at(fragments[2].clone(), 6_000_000_002).unwrap(), None);
+        assert_eq!(reassembler.pending(), 1);
+    }
+
+    #[test]
+    fn test_reassembly_rejects_oversized_fragment() {
+        let mut fragment = Ipv4Fragmenter::new(68).fragment(&packet("udp", 100)).unwrap().remove(1);
+        fragment.ipv4.fragment_offset = 0x1FFF;
+        assert!(Ipv4Reassembler::new().push_at(fragment, 0).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        formats::format_factory::FormatFactory,
+        packets::{
+            builder::icmp_builder::IcmpBuilder,
+            l4::icmp::{pack_icmp, unpack_icmp},
+        },
+        structs::{
+            formats::FormatType,
+            icmp::{ICMP_DEST_UNREACHABLE, ICMP_ECHO_REPLY, ICMP_ECHO_REQUEST, ICMP_TIME_EXCEEDED},
+            json::{JsonDeserializer, JsonSerializer},
+            l4_protocol::{L4Data, L4Protocol},
+            network_packet::NetworkPacket,
+            packet_builder::PacketBuilder,
+        },
+        utils::checksum::internet_checksum,
+    };
+
+    fn build(protocol: &str, payload: Option<Vec<u8>>) -> NetworkPacket {
+        PacketBuilder::from_cli_args(
+            Some("10.1.1.1"),
+            Some("10.1.1.2"),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            Some(4242),
+            Some(53),
+            Some(protocol),
+            None,
+            payload,
+        ).unwrap().build_packet().unwrap()
+    }
+
+    ///////////////////////////////////////////
+    ///         ICMP Builder Tests          ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_echo_request_known_checksum() {
+        let echo = IcmpBuilder::new().build_echo_request(1, 1, None).unwrap();
+        assert_eq!(echo.icmp_type, ICMP_ECHO_REQUEST);
+        assert_eq!(echo.checksum, 0xF7FD);
+        assert_eq!(pack_icmp(&echo).unwrap(), vec![0x08, 0x00, 0xF7, 0xFD, 0x00, 0x01, 0x00, 0x01]);
+    }
+
+    #[test]
+    fn test_echo_reply_fields_and_checksum() {
+        let reply = IcmpBuilder::new().build_echo_reply(0x1234, 7, Some(b"ping!".to_vec())).unwrap();
+        assert_eq!(reply.icmp_type, ICMP_ECHO_REPLY);
+        assert_eq!(reply.identifier(), 0x1234);
+        assert_eq!(reply.sequence(), 7);
+        assert_eq!(internet_checksum(&pack_icmp(&reply).unwrap()), 0);
+    }
+
+    #[test]
+    fn test_destination_unreachable_quotes_original() {
+        let original = build("udp", Some(b"a long payload not quoted".to_vec()));
+        let error = IcmpBuilder::new().build_destination_unreachable(3, &original).unwrap();
+        assert_eq!(error.icmp_type, ICMP_DEST_UNREACHABLE);
+        assert_eq!(error.code, 3);
+        assert_eq!(error.payload.as_ref().unwrap().len(), 20 + 8);
+        assert_eq!(internet_checksum(&pack_icmp(&error).unwrap()), 0);
+
+        let (quoted_ip, quoted_l4) = error.quoted_datagram().unwrap();
+        assert_eq!(quoted_ip, *original.ipv4().unwrap());
+        assert_eq!(&quoted_l4[0..2], &4242u16.to_be_bytes());
+        assert_eq!(&quoted_l4[2..4], &53u16.to_be_bytes());
+    }
+
+    #[test]
+    fn test_time_exceeded_quotes_ip_options() {
+        let mut original = build("tcp", None);
+        let ipv4 = original.ipv4_mut().unwrap();
+        ipv4.ihl = 6;
+        ipv4.options = Some(vec![0x94, 0x04, 0x00, 0x00]);
+        ipv4.total_length += 4;
+        let error = IcmpBuilder::new().build_time_exceeded(0, &original).unwrap();
+        assert_eq!(error.icmp_type, ICMP_TIME_EXCEEDED);
+        assert_eq!(error.payload.as_ref().unwrap().len(), 24 + 8);
+
+        let (quoted_ip, quoted_l4) = error.quoted_datagram().unwrap();
+        assert_eq!(quoted_ip.options, original.ipv4().unwrap().options);
+        assert_eq!(quoted_l4.len(), 8);
+    }
+
+    #[test]
+    fn test_echo_has_no_quoted_datagram() {
+        let echo = IcmpBuilder::new().build_echo_request(1, 1, Some(vec![0x45; 28])).unwrap();
+        assert!(echo.quoted_datagram().is_none());
+    }
+
+    ///////////////////////////////////////////
+    ///       ICMP Pack/Unpack Tests        ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_unpack_icmp_round_trip() {
+        let echo = IcmpBuilder::new().build_echo_request(99, 3, Some(b"data".to_vec())).unwrap();
+        assert_eq!(unpack_icmp(&pack_icmp(&echo).unwrap()).unwrap(), echo);
+    }
+
+    #[test]
+    fn test_unpack_icmp_truncated() {
+        assert!(matches!(
+            unpack_icmp(&[0x08, 0x00, 0x00]),
+            Err(ParseError::TruncatedPacket { layer: "ICMP", needed: 8, available: 3 })
+        ));
+    }
+
+    ///////////////////////////////////////////
+    ///        ICMP Packet Tests            ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_factory_builds_echo_request() {
+        let packet = build("icmp", Some(b"abcd".to_vec()));
+        assert_eq!(packet.ipv4().unwrap().protocol, 1);
+        assert_eq!(packet.ipv4().unwrap().total_length, 20 + 8 + 4);
+        assert_eq!(packet.get_packet_size(), 14 + 20 + 8 + 4);
+        let L4Data::Icmp(icmp) = &packet.l4_data else { panic!("ICMP attendu") };
+        assert_eq!(icmp.icmp_type, ICMP_ECHO_REQUEST);
+        assert_eq!(icmp.identifier(), 4242);
+    }
+
+    #[test]
+    fn test_cli_protocol_icmp() {
+        let builder = PacketBuilder::from_cli_args(
+            None, None, None, None, None, None, Some("icmp"), None, None,
+        ).unwrap();
+        assert_eq!(builder.protocol, L4Protocol::Icmp);
+    }
+
+    #[test]
+    fn test_dissect_icmp_round_trip() {
+        let original = build("udp", None);
+        let mut packet = build("icmp", None);
+        packet.l4_data = L4Data::Icmp(
+            IcmpBuilder::new().build_destination_unreachable(13, &original).unwrap()
+        );
+        packet.ipv4_mut().unwrap().total_length = 20 + 8 + 28;
+        let bytes = packet.assemble_packet().unwrap();
+        assert_eq!(NetworkPacket::parse(&bytes).unwrap(), packet);
+    }
+
+    #[test]
+    fn test_json_round_trip_icmp() {
+        let packet = build("icmp", Some(b"\x00\xffecho".to_vec()));
+        let json_str = JsonSerializer::new().serialize_packet(&packet).unwrap();
+        assert!(json_str.contains("\"protocol_type\":\"ICMP\""));
+        let parsed = JsonDeserializer::new().deserialize_network_packet(&json_str).unwrap();
+        assert_eq!(parsed, packet);
+    }
+
+    #[test]
+    fn test_pcap_round_trip_icmp() {
+        let packet = build("icmp", Some(b"pcap".to_vec()));
+        let factory = FormatFactory::new();
+        let data = factory.write_packet(&packet, FormatType::Pcap).unwrap();
+        let mut reader = factory.create_reader(FormatType::Pcap, data);
+        let record = reader.read_next_record().unwrap().unwrap();
+        assert_eq!(record.packet, Some(packet));
+    }
+}
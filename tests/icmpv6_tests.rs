@@ -0,0 +1,250 @@
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        packets::{
+            builder::{icmpv6_builder::Icmpv6Builder, ipv6_builder::Ipv6Builder},
+            ipv6::{link_local_from_mac, multicast_mac, solicited_node_multicast, ALL_NODES_MULTICAST, ALL_ROUTERS_MULTICAST},
+            l4::icmpv6::{pack_icmpv6, unpack_icmpv6},
+        },
+        parsing::my_parser::parse_ipv6,
+        sender::raw_socket::get_interface_index,
+        structs::{
+            formats::PacketRecord,
+            pcap::LinkType,
+            icmpv6::{
+                Icmpv6Header, ICMPV6_ECHO_REQUEST, ICMPV6_NEIGHBOR_ADVERTISEMENT, ICMPV6_NEIGHBOR_SOLICITATION,
+                ICMPV6_ROUTER_SOLICITATION, NA_FLAG_OVERRIDE, NA_FLAG_SOLICITED, NDP_OPTION_SOURCE_LL_ADDR,
+                NDP_OPTION_TARGET_LL_ADDR,
+            },
+            ipv6::Ipv6Addr,
+            json::{JsonDeserializer, JsonSerializer},
+            l4_protocol::L4Data,
+            network_packet::NetworkPacket,
+            scan::{NdpHost, NdpScanner},
+            socket::{RawSocketReceiver, RawSocketSender},
+        },
+    };
+    use std::time::{Duration, Instant};
+
+    const LOCAL_MAC: [u8; 6] = [0x02, 0x00, 0x00, 0x00, 0x00, 0x01];
+
+    fn ip(text: &str) -> Ipv6Addr {
+        parse_ipv6(text).unwrap()
+    }
+
+    // Le checksum d'un message valide, pseudo-header compris, s'annule
+    fn checksum_ok(src: &str, dst: &str, message: &Icmpv6Header) -> bool {
+        Ipv6Builder::new(ip(src), ip(dst)).pseudo_header_checksum(58, &pack_icmpv6(message).unwrap()) == 0
+    }
+
+    fn record(packet: &NetworkPacket) -> PacketRecord {
+        let data = packet.assemble_packet().unwrap();
+        PacketRecord {
+            ts_sec: 0,
+            ts_usec: 0,
+            ts_nsec: 0,
+            caplen: data.len() as u32,
+            orig_len: data.len() as u32,
+            data,
+            link_type: LinkType::Ethernet,
+            packet: Some(packet.clone()),
+            comment: None,
+        }
+    }
+
+    // Voisins simules sur lo : chaque hote de `hosts` repond aux NS qui le visent
+    fn spawn_responder(hosts: Vec<(&str, [u8; 6])>, solicitations: usize) -> std::thread::JoinHandle<()> {
+        let hosts: Vec<_> = hosts.into_iter().map(|(addr, mac)| (ip(addr), mac)).collect();
+        let mut receiver = RawSocketReceiver::with_protocol(0x86DD).unwrap();
+        receiver.bind("lo").unwrap();
+        receiver.set_read_timeout(Some(100)).unwrap();
+        std::thread::spawn(move || {
+            let sender = RawSocketSender::new().unwrap();
+            let if_index = get_interface_index("lo").unwrap();
+            let deadline = Instant::now() + Duration::from_secs(3);
+            let mut seen = 0;
+            while seen < solicitations && Instant::now() < deadline {
+                let Some(frame) = receiver.recv_frame().unwrap() else { continue };
+                let Some(packet) = frame.record.packet.as_ref() else { continue };
+                let L4Data::Icmpv6(ns) = &packet.l4_data else { continue };
+                if frame.is_outgoing() || ns.icmp_type != ICMPV6_NEIGHBOR_SOLICITATION {
+                    continue;
+                }
+                seen += 1;
+                let target = ns.target_address().unwrap();
+                let Some(&(addr, mac)) = hosts.iter().find(|(addr, _)| addr.octets == target) else {
+                    continue;
+                };
+                let solicitor = Ipv6Addr { octets: packet.ipv6().unwrap().src_addr };
+                let builder = Icmpv6Builder::new(addr, solicitor);
+                let na = builder
+                    .build_neighbor_advertisement(addr, mac, NA_FLAG_SOLICITED | NA_FLAG_OVERRIDE)
+                    .unwrap();
+                let reply = builder.build_packet(mac, packet.ethernet.src_mac, na).unwrap();
+                sender.send(if_index, reply.ethernet.dst_mac, &reply.assemble_packet().unwrap()).unwrap();
+            }
+        })
+    }
+
+    ///////////////////////////////////////////
+    ///       IPv6 Multicast Tests          ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_solicited_node_and_multicast_mac() {
+        let group = solicited_node_multicast(&ip("2001:db8::aabb:ccdd"));
+        assert_eq!(group, ip("ff02::1:ffbb:ccdd"));
+        assert_eq!(multicast_mac(&group), [0x33, 0x33, 0xFF, 0xBB, 0xCC, 0xDD]);
+        assert_eq!(multicast_mac(&ALL_NODES_MULTICAST), [0x33, 0x33, 0, 0, 0, 1]);
+        assert_eq!(ALL_ROUTERS_MULTICAST, ip("ff02::2"));
+    }
+
+    #[test]
+    fn test_link_local_eui64() {
+        let link_local = link_local_from_mac(&[0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E]);
+        assert_eq!(link_local, ip("fe80::21a:2bff:fe3c:4d5e"));
+    }
+
+    ///////////////////////////////////////////
+    ///        ICMPv6 Builder Tests         ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_echo_request_checksum_and_roundtrip() {
+        let builder = Icmpv6Builder::new(ip("2001:db8::1"), ip("2001:db8::2"));
+        let echo = builder.build_echo_request(0x1234, 7, Some(b"ping6".to_vec())).unwrap();
+        assert_eq!(echo.icmp_type, ICMPV6_ECHO_REQUEST);
+        assert_eq!((echo.identifier(), echo.sequence()), (0x1234, 7));
+        assert!(echo.is_echo());
+        assert!(checksum_ok("2001:db8::1", "2001:db8::2", &echo));
+        // Le pseudo-header change avec les adresses
+        assert!(!checksum_ok("2001:db8::1", "2001:db8::3", &echo));
+
+        let bytes = pack_icmpv6(&echo).unwrap();
+        assert_eq!(bytes[..2], [128, 0]);
+        assert_eq!(unpack_icmpv6(&bytes).unwrap(), echo);
+        assert!(matches!(
+            unpack_icmpv6(&bytes[..6]),
+            Err(ParseError::TruncatedPacket { layer: "ICMPv6", needed: 8, available: 6 })
+        ));
+    }
+
+    #[test]
+    fn test_neighbor_solicitation_layout() {
+        let target = ip("2001:db8::20");
+        let group = solicited_node_multicast(&target);
+        let builder = Icmpv6Builder::new(ip("2001:db8::10"), group);
+        let ns = builder.build_neighbor_solicitation(target, Some(LOCAL_MAC)).unwrap();
+        assert_eq!(ns.icmp_type, ICMPV6_NEIGHBOR_SOLICITATION);
+        assert_eq!(ns.rest_of_header, 0);
+        assert_eq!(ns.target_address(), Some(target.octets));
+        assert_eq!(ns.link_layer_option(NDP_OPTION_SOURCE_LL_ADDR), Some(LOCAL_MAC));
+        assert_eq!(ns.link_layer_option(NDP_OPTION_TARGET_LL_ADDR), None);
+        assert_eq!(ns.payload.as_ref().unwrap().len(), 24);
+        assert!(checksum_ok("2001:db8::10", "ff02::1:ff00:20", &ns));
+
+        // Sans MAC source (DAD) : pas d'option
+        let dad = builder.build_neighbor_solicitation(target, None).unwrap();
+        assert_eq!(dad.payload.as_ref().unwrap().len(), 16);
+
+        let packet = builder.build_packet(LOCAL_MAC, multicast_mac(&group), ns).unwrap();
+        let ipv6 = packet.ipv6().unwrap();
+        assert_eq!(ipv6.hop_limit, 255);
+        assert_eq!(ipv6.next_header, 58);
+        assert_eq!(packet.ethernet.dst_mac, [0x33, 0x33, 0xFF, 0x00, 0x00, 0x20]);
+        assert_eq!(NetworkPacket::parse(&packet.assemble_packet().unwrap()).unwrap(), packet);
+    }
+
+    #[test]
+    fn test_neighbor_advertisement_flags() {
+        let mac = [0x02, 0x00, 0x5E, 0x00, 0x00, 0x20];
+        let builder = Icmpv6Builder::new(ip("2001:db8::20"), ip("2001:db8::10"));
+        let na = builder
+            .build_neighbor_advertisement(ip("2001:db8::20"), mac, NA_FLAG_SOLICITED | NA_FLAG_OVERRIDE | 0xFF)
+            .unwrap();
+        assert_eq!(na.icmp_type, ICMPV6_NEIGHBOR_ADVERTISEMENT);
+        // Seuls les bits R/S/O sont conserves
+        assert_eq!(na.rest_of_header, 0x6000_0000);
+        assert_eq!(na.link_layer_option(NDP_OPTION_TARGET_LL_ADDR), Some(mac));
+        assert!(checksum_ok("2001:db8::20", "2001:db8::10", &na));
+    }
+
+    #[test]
+    fn test_router_solicitation() {
+        let builder = Icmpv6Builder::new(ip("fe80::1"), ALL_ROUTERS_MULTICAST);
+        let rs = builder.build_router_solicitation(Some(LOCAL_MAC)).unwrap();
+        assert_eq!(rs.icmp_type, ICMPV6_ROUTER_SOLICITATION);
+        assert_eq!(rs.link_layer_option(NDP_OPTION_SOURCE_LL_ADDR), Some(LOCAL_MAC));
+        assert!(checksum_ok("fe80::1", "ff02::2", &rs));
+        assert_eq!(builder.build_router_solicitation(None).unwrap().payload, None);
+    }
+
+    #[test]
+    fn test_icmpv6_json_roundtrip() {
+        let builder = Icmpv6Builder::new(ip("fe80::1"), ALL_NODES_MULTICAST);
+        let echo = builder.build_echo_request(1, 2, Some(vec![0xAB; 4])).unwrap();
+        let packet = builder.build_packet(LOCAL_MAC, multicast_mac(&ALL_NODES_MULTICAST), echo).unwrap();
+        let json = JsonSerializer::new().serialize_packet(&packet).unwrap();
+        assert!(json.contains("\"protocol_type\":\"ICMPv6\""));
+        assert_eq!(JsonDeserializer::new().deserialize_network_packet(&json).unwrap(), packet);
+    }
+
+    ///////////////////////////////////////////
+    ///          NDP Scanner Tests          ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_ndp_probe_kinds() {
+        let scanner = NdpScanner::new(LOCAL_MAC, ip("fe80::1"));
+        let ns = scanner.build_probe(ip("fe80::abcd")).unwrap();
+        let L4Data::Icmpv6(message) = &ns.l4_data else { panic!("ICMPv6 attendu") };
+        assert_eq!(message.icmp_type, ICMPV6_NEIGHBOR_SOLICITATION);
+        assert_eq!(ns.ipv6().unwrap().dst_addr, ip("ff02::1:ff00:abcd").octets);
+
+        let ping = scanner.build_probe(ALL_NODES_MULTICAST).unwrap();
+        let L4Data::Icmpv6(message) = &ping.l4_data else { panic!("ICMPv6 attendu") };
+        assert_eq!(message.icmp_type, ICMPV6_ECHO_REQUEST);
+        assert_eq!(ping.ethernet.dst_mac, [0x33, 0x33, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_ndp_classify_replies() {
+        let scanner = NdpScanner::new(LOCAL_MAC, ip("fe80::1"));
+        let mac = [0x02, 0x00, 0x5E, 0x00, 0x00, 0x09];
+
+        let builder = Icmpv6Builder::new(ip("fe80::9"), ip("fe80::1"));
+        let na = builder.build_neighbor_advertisement(ip("fe80::9"), mac, NA_FLAG_SOLICITED).unwrap();
+        let reply = builder.build_packet(mac, LOCAL_MAC, na).unwrap();
+        assert_eq!(scanner.classify_reply(&record(&reply)), Some(NdpHost { ip: ip("fe80::9"), mac }));
+
+        let echo = builder.build_echo_reply(0x5253, 1, None).unwrap();
+        let reply = builder.build_packet(mac, LOCAL_MAC, echo).unwrap();
+        assert_eq!(scanner.classify_reply(&record(&reply)), Some(NdpHost { ip: ip("fe80::9"), mac }));
+
+        // Echo d'un autre outil et reponse adressee a une autre machine
+        let echo = builder.build_echo_reply(1, 1, None).unwrap();
+        assert_eq!(scanner.classify_reply(&record(&builder.build_packet(mac, LOCAL_MAC, echo).unwrap())), None);
+        let other = Icmpv6Builder::new(ip("fe80::9"), ip("fe80::2"));
+        let na = other.build_neighbor_advertisement(ip("fe80::9"), mac, 0).unwrap();
+        assert_eq!(scanner.classify_reply(&record(&other.build_packet(mac, LOCAL_MAC, na).unwrap())), None);
+    }
+
+    #[test]
+    fn test_ndp_discover_loopback() {
+        if RawSocketSender::new().is_err() {
+            eprintln!("raw socket indisponible (root requis), test ignore");
+            return;
+        }
+        let mac_a = [0x02, 0x00, 0x5E, 0x00, 0x00, 0x0A];
+        let responder = spawn_responder(vec![("2001:db8:64::a", mac_a)], 2);
+
+        let scanner = NdpScanner::new(LOCAL_MAC, ip("2001:db8:64::1"))
+            .with_timeout_ms(300)
+            .with_retries(0);
+        let hosts = scanner.discover("lo", &[ip("2001:db8:64::a"), ip("2001:db8:64::b")]).unwrap();
+        responder.join().unwrap();
+
+        assert_eq!(hosts, vec![NdpHost { ip: ip("2001:db8:64::a"), mac: mac_a }]);
+    }
+}
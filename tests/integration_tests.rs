@@ -32,9 +32,9 @@ mod tests {
 
         assert_eq!(network_packet.ethernet.src_mac, [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
         assert_eq!(network_packet.ethernet.dst_mac, [0x11, 0x22, 0x33, 0x44, 0x55, 0x66]);
-        assert_eq!(network_packet.ipv4.src_addr, [192, 168, 1, 100]);
-        assert_eq!(network_packet.ipv4.dst_addr, [192, 168, 1, 200]);
-        assert_eq!(network_packet.ipv4.protocol, 6); // TCP
+        assert_eq!(network_packet.ipv4().unwrap().src_addr, [192, 168, 1, 100]);
+        assert_eq!(network_packet.ipv4().unwrap().dst_addr, [192, 168, 1, 200]);
+        assert_eq!(network_packet.ipv4().unwrap().protocol, 6); // TCP
 
         match &network_packet.l4_data {
             L4Data::Tcp(tcp) => {
@@ -78,7 +78,7 @@ mod tests {
 
         let network_packet = builder.build_packet().unwrap();
 
-        assert_eq!(network_packet.ipv4.protocol, 17);
+        assert_eq!(network_packet.ipv4().unwrap().protocol, 17);
         match &network_packet.l4_data {
             L4Data::Udp(udp) => {
                 assert_eq!(udp.src_port, 53);
@@ -173,8 +173,8 @@ mod tests {
 
             let network_packet = builder.build_packet().unwrap();
             
-            assert_eq!(network_packet.ipv4.flags, (bitfield >> 5) & 0x07);
-            assert_eq!(network_packet.ipv4.fragment_offset, ((bitfield & 0x1F) as u16) << 8);
+            assert_eq!(network_packet.ipv4().unwrap().flags, (bitfield >> 5) & 0x07);
+            assert_eq!(network_packet.ipv4().unwrap().fragment_offset, ((bitfield & 0x1F) as u16) << 8);
         }
     }
 
@@ -194,7 +194,7 @@ mod tests {
 
         let network_packet = builder.build_packet().unwrap();
 
-        assert!(network_packet.ipv4.header_checksum > 0);
+        assert!(network_packet.ipv4().unwrap().header_checksum > 0);
         
         match &network_packet.l4_data {
             L4Data::Tcp(tcp) => {
@@ -222,9 +222,9 @@ mod tests {
 
         assert_eq!(network_packet.ethernet.src_mac, [0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
         assert_eq!(network_packet.ethernet.dst_mac, [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
-        assert_eq!(network_packet.ipv4.src_addr, [192, 168, 1, 100]);
-        assert_eq!(network_packet.ipv4.dst_addr, [192, 168, 1, 1]);  
-        assert_eq!(network_packet.ipv4.protocol, 6);
+        assert_eq!(network_packet.ipv4().unwrap().src_addr, [192, 168, 1, 100]);
+        assert_eq!(network_packet.ipv4().unwrap().dst_addr, [192, 168, 1, 1]);  
+        assert_eq!(network_packet.ipv4().unwrap().protocol, 6);
 
         match &network_packet.l4_data {
             L4Data::Tcp(tcp) => {
@@ -0,0 +1,137 @@
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        parsing::my_parser::parse_ipv4,
+        sender::{
+            interface::{interface_by_name, list_interfaces, pick_interface, select_interface},
+            raw_socket::get_interface_index,
+        },
+        structs::{
+            interface::{InterfaceAddress, NetworkInterface},
+            ipv4::Ipv4Addr,
+            neighbor::{NeighborResolver, Route},
+        },
+    };
+
+    fn ip(s: &str) -> Ipv4Addr {
+        parse_ipv4(s).unwrap()
+    }
+
+    fn iface(name: &str, index: i32, addr: Option<(&str, &str)>, is_up: bool) -> NetworkInterface {
+        NetworkInterface {
+            name: name.to_string(),
+            index,
+            mac: if name == "lo" { [0; 6] } else { [0x02, 0, 0, 0, 0, index as u8] },
+            ipv4: addr
+                .map(|(addr, netmask)| vec![InterfaceAddress { addr: ip(addr), netmask: ip(netmask) }])
+                .unwrap_or_default(),
+            mtu: 1500,
+            is_up,
+            is_loopback: name == "lo",
+        }
+    }
+
+    fn route(interface: &str, destination: [u8; 4], gateway: [u8; 4], mask: [u8; 4], metric: u32) -> Route {
+        Route { interface: interface.to_string(), destination, gateway, mask, metric }
+    }
+
+    fn sample() -> Vec<NetworkInterface> {
+        vec![
+            iface("lo", 1, Some(("127.0.0.1", "255.0.0.0")), true),
+            iface("eth0", 2, Some(("192.168.1.10", "255.255.255.0")), true),
+            iface("wlan0", 3, Some(("10.0.0.5", "255.255.0.0")), true),
+            iface("eth1", 4, Some(("172.16.0.2", "255.255.255.0")), false),
+        ]
+    }
+
+    ///////////////////////////////////////////
+    ///      Interface Discovery Tests      ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_list_interfaces_loopback() {
+        let interfaces = list_interfaces().unwrap();
+        let lo = interfaces.iter().find(|iface| iface.name == "lo").unwrap();
+        assert!(lo.is_loopback && lo.is_up);
+        assert_eq!(lo.index, get_interface_index("lo").unwrap());
+        assert_eq!(lo.mac, [0; 6]);
+        assert!(lo.mtu > 0);
+        assert!(lo.ipv4.contains(&InterfaceAddress { addr: ip("127.0.0.1"), netmask: ip("255.0.0.0") }));
+        assert!(interfaces.windows(2).all(|pair| pair[0].index < pair[1].index));
+    }
+
+    #[test]
+    fn test_interface_by_name() {
+        assert_eq!(interface_by_name("lo").unwrap().name, "lo");
+        assert_eq!(
+            interface_by_name("no_such_if0"),
+            Err(ParseError::InvalidFormat("invalid interface name"))
+        );
+    }
+
+    #[test]
+    fn test_select_interface_loopback_destination() {
+        assert_eq!(select_interface(Some(ip("127.0.0.53"))).unwrap().name, "lo");
+    }
+
+    ///////////////////////////////////////////
+    ///      Interface Selection Tests      ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_pick_interface_by_route() {
+        let interfaces = sample();
+        let routes = vec![
+            route("eth0", [0; 4], [192, 168, 1, 1], [0; 4], 100),
+            route("wlan0", [0; 4], [10, 0, 0, 1], [0; 4], 600),
+            route("eth0", [192, 168, 1, 0], [0; 4], [255, 255, 255, 0], 0),
+            route("wlan0", [10, 0, 0, 0], [0; 4], [255, 255, 0, 0], 0),
+            route("eth1", [172, 16, 0, 0], [0; 4], [255, 255, 0, 0], 0),
+        ];
+        let pick = |dst: Option<&str>| pick_interface(&interfaces, &routes, dst.map(ip)).map(|iface| iface.name.as_str());
+
+        assert_eq!(pick(Some("8.8.8.8")), Some("eth0"));
+        assert_eq!(pick(Some("10.0.3.4")), Some("wlan0"));
+        assert_eq!(pick(Some("127.0.0.1")), Some("lo"));
+        assert_eq!(pick(None), Some("eth0"));
+        // Route d'une interface inactive ignoree : route par defaut
+        assert_eq!(pick(Some("172.16.0.9")), Some("eth0"));
+    }
+
+    #[test]
+    fn test_pick_interface_without_routes() {
+        let interfaces = sample();
+        let pick = |dst: Option<&str>| pick_interface(&interfaces, &[], dst.map(ip)).map(|iface| iface.name.as_str());
+        assert_eq!(pick(Some("10.0.200.1")), Some("wlan0"));
+        assert_eq!(pick(Some("8.8.8.8")), Some("eth0"));
+
+        let only_lo = vec![iface("lo", 1, Some(("127.0.0.1", "255.0.0.0")), true)];
+        assert_eq!(pick_interface(&only_lo, &[], Some(ip("8.8.8.8"))).unwrap().name, "lo");
+        assert!(pick_interface(&[], &[], None).is_none());
+    }
+
+    #[test]
+    fn test_interface_addresses() {
+        let address = InterfaceAddress { addr: ip("192.168.1.10"), netmask: ip("255.255.255.0") };
+        assert_eq!(address.prefix_len(), 24);
+        assert!(address.contains(ip("192.168.1.200")));
+        assert!(!address.contains(ip("192.168.2.1")));
+
+        let mut eth0 = iface("eth0", 2, Some(("192.168.1.10", "255.255.255.0")), true);
+        eth0.ipv4.push(InterfaceAddress { addr: ip("10.9.0.1"), netmask: ip("255.255.0.0") });
+        assert_eq!(eth0.source_address(Some(ip("10.9.8.7"))), Some(ip("10.9.0.1")));
+        assert_eq!(eth0.source_address(Some(ip("8.8.8.8"))), Some(ip("192.168.1.10")));
+        assert_eq!(eth0.source_address(None), Some(ip("192.168.1.10")));
+        assert_eq!(iface("eth2", 5, None, true).source_address(None), None);
+    }
+
+    #[test]
+    fn test_neighbor_resolver_from_interface() {
+        let eth0 = iface("eth0", 2, Some(("192.168.1.10", "255.255.255.0")), true);
+        let resolver = NeighborResolver::from_interface(&eth0);
+        assert_eq!(resolver.interface, "eth0");
+        assert_eq!(resolver.src_mac, eth0.mac);
+        assert_eq!(resolver.src_ip, ip("192.168.1.10"));
+    }
+}
@@ -0,0 +1,209 @@
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        packets::{
+            builder::{ipv4_builder::Ipv4Builder, packet_dissector::PacketDissector},
+            ip::{ipv4_header_checksum, pack_ipv4, pack_ipv4_options, unpack_ipv4_options},
+        },
+        parsing::my_parser::{parse_ipv4, parse_ipv4_options},
+        structs::{
+            ip::{Ipv4Option, Ipv4TimestampEntry},
+            json::{JsonDeserializer, JsonSerializer},
+            l4_protocol::L4Data,
+            network_packet::NetworkPacket,
+            packet_builder::PacketBuilder,
+            udp::UdpHeader,
+        },
+        utils::checksum::internet_checksum,
+    };
+
+    fn builder(protocol: &str) -> PacketBuilder {
+        PacketBuilder::from_cli_args(
+            Some("10.0.0.1"),
+            Some("10.0.0.2"),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            Some(40000),
+            Some(53),
+            Some(protocol),
+            None,
+            Some(b"options".to_vec()),
+        ).unwrap()
+    }
+
+    fn header_checksum_ok(packet: &NetworkPacket) -> bool {
+        internet_checksum(&pack_ipv4(packet.ipv4().unwrap(), &[]).unwrap()) == 0
+    }
+
+    ///////////////////////////////////////////
+    ///        IPv4 Options Tests           ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_pack_record_route_and_router_alert() {
+        let bytes = pack_ipv4_options(&[Ipv4Option::record_route(2), Ipv4Option::router_alert()]).unwrap();
+        // RR : type 7, longueur 3 + 2 * 4, pointeur 4 ; RA : 0x94 0x04 0x0000 ; padding a 16 octets
+        assert_eq!(bytes, vec![
+            0x07, 11, 4, 0, 0, 0, 0, 0, 0, 0, 0,
+            0x94, 4, 0, 0,
+            0x00,
+        ]);
+        assert_eq!(pack_ipv4_options(&[]).unwrap(), Vec::<u8>::new());
+        assert_eq!(pack_ipv4_options(&[Ipv4Option::NoOperation]).unwrap(), vec![1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_options_roundtrip() {
+        let hop = parse_ipv4("10.0.0.9").unwrap().octets;
+        let options = vec![
+            Ipv4Option::NoOperation,
+            Ipv4Option::loose_source_route(vec![hop]),
+            Ipv4Option::Timestamp {
+                pointer: 13,
+                overflow: 2,
+                flag: 1,
+                entries: vec![
+                    Ipv4TimestampEntry { address: Some(hop), timestamp: 0x01020304 },
+                    Ipv4TimestampEntry { address: Some([0; 4]), timestamp: 0 },
+                ],
+            },
+            Ipv4Option::RouterAlert(0),
+        ];
+        let bytes = pack_ipv4_options(&options).unwrap();
+        assert!(bytes.len().is_multiple_of(4));
+        assert_eq!(unpack_ipv4_options(&bytes).unwrap(), options);
+
+        for option in [Ipv4Option::timestamp(3), Ipv4Option::strict_source_route(vec![hop, hop]), Ipv4Option::record_route(9)] {
+            let bytes = pack_ipv4_options(core::slice::from_ref(&option)).unwrap();
+            assert_eq!(unpack_ipv4_options(&bytes).unwrap(), vec![option]);
+        }
+        // Option inconnue conservee telle quelle
+        let unknown = unpack_ipv4_options(&[0x82, 4, 0xAB, 0xCD]).unwrap();
+        assert_eq!(unknown, vec![Ipv4Option::Unknown { option_type: 0x82, data: vec![0xAB, 0xCD] }]);
+    }
+
+    #[test]
+    fn test_options_size_limits() {
+        // 39 octets pour 9 emplacements : la limite de 40 octets est atteinte avec le padding, RA la depasse
+        assert_eq!(pack_ipv4_options(&[Ipv4Option::record_route(9)]).unwrap().len(), 40);
+        assert!(matches!(
+            pack_ipv4_options(&[Ipv4Option::record_route(9), Ipv4Option::router_alert()]),
+            Err(ParseError::InvalidHeaderLength { layer: "IPv4 options", length: 44 })
+        ));
+        assert!(pack_ipv4_options(&[Ipv4Option::record_route(10)]).is_err());
+    }
+
+    #[test]
+    fn test_unpack_options_errors() {
+        assert!(matches!(unpack_ipv4_options(&[0x07]), Err(ParseError::TruncatedPacket { layer: "IPv4 option", .. })));
+        assert!(matches!(unpack_ipv4_options(&[0x07, 11, 4, 0]), Err(ParseError::TruncatedPacket { layer: "IPv4 option", .. })));
+        assert!(matches!(unpack_ipv4_options(&[0x07, 1, 0, 0]), Err(ParseError::InvalidHeaderLength { length: 1, .. })));
+        // Route dont la taille n'est pas un multiple de 4
+        assert!(unpack_ipv4_options(&[0x83, 5, 4, 1, 2, 0, 0, 0]).is_err());
+        // Tout ce qui suit End of List est ignore
+        assert_eq!(unpack_ipv4_options(&[0x01, 0x00, 0xFF, 0xFF]).unwrap(), vec![Ipv4Option::NoOperation]);
+    }
+
+    ///////////////////////////////////////////
+    ///        IPv4 Builder Tests           ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_builder_header_fields() {
+        let packet = builder("udp")
+            .with_ttl(3)
+            .with_dscp(46)
+            .with_ecn(1)
+            .with_identification(0xBEEF)
+            .with_ip_options(vec![Ipv4Option::record_route(4)])
+            .build_packet()
+            .unwrap();
+        let ipv4 = packet.ipv4().unwrap();
+        assert_eq!(ipv4.ttl, 3);
+        // TOS = DSCP << 2 | ECN
+        assert_eq!(ipv4.dscp, (46 << 2) | 1);
+        assert_eq!(ipv4.identification, 0xBEEF);
+        // 3 + 16 octets d'option, completes a 20 : ihl 10
+        assert_eq!(ipv4.ihl, 10);
+        assert_eq!(ipv4.total_length, 40 + 8 + 7);
+
+        let bytes = packet.assemble_packet().unwrap();
+        assert_eq!(bytes.len(), packet.get_packet_size());
+        assert_eq!(bytes[14..16], [0x4A, 0xB9]);
+        assert_eq!(bytes[34], 0x07);
+        assert!(header_checksum_ok(&packet));
+        assert_eq!(ipv4.typed_options().unwrap(), vec![Ipv4Option::record_route(4)]);
+    }
+
+    #[test]
+    fn test_checksum_covers_options() {
+        for protocol in ["tcp", "udp", "icmp"] {
+            let packet = builder(protocol)
+                .with_ip_options(vec![Ipv4Option::router_alert(), Ipv4Option::timestamp(2)])
+                .build_packet()
+                .unwrap();
+            assert!(header_checksum_ok(&packet), "{}", protocol);
+            assert_eq!(packet.ipv4().unwrap().header_checksum, ipv4_header_checksum(packet.ipv4().unwrap()).unwrap());
+            assert_eq!(packet.assemble_packet().unwrap().len(), packet.get_packet_size());
+        }
+
+        // Une option modifiee change le checksum meme hors des 20 premiers octets
+        let mut packet = builder("udp").with_ip_options(vec![Ipv4Option::record_route(1)]).build_packet().unwrap();
+        packet.ipv4_mut().unwrap().options.as_mut().unwrap()[3] = 10;
+        assert!(!header_checksum_ok(&packet));
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_fields() {
+        assert!(builder("udp").with_dscp(64).build_packet().is_err());
+        assert!(builder("udp").with_ecn(4).build_packet().is_err());
+        assert!(builder("udp").with_ip_options(vec![Ipv4Option::record_route(12)]).build_packet().is_err());
+
+        let udp = L4Data::Udp(UdpHeader { src_port: 1, dst_port: 2, length: 8, checksum: 0, payload: None });
+        let header = Ipv4Builder::new(parse_ipv4("10.0.0.1").unwrap(), parse_ipv4("10.0.0.2").unwrap(), 0)
+            .with_ttl(1)
+            .build_ipv4_header(&udp)
+            .unwrap();
+        assert_eq!((header.ttl, header.ihl, header.total_length), (1, 5, 28));
+    }
+
+    #[test]
+    fn test_options_dissect_and_json_roundtrip() {
+        let packet = builder("tcp")
+            .with_identification(7)
+            .with_ip_options(vec![Ipv4Option::strict_source_route(vec![[192, 0, 2, 1]]), Ipv4Option::NoOperation])
+            .build_packet()
+            .unwrap();
+        let bytes = packet.assemble_packet().unwrap();
+        assert_eq!(PacketDissector::new().dissect_packet(&bytes).unwrap(), packet);
+
+        let json = JsonSerializer::new().serialize_packet(&packet).unwrap();
+        assert!(json.contains("\"options\":\"89 07 04 C0 00 02 01 01\""));
+        assert_eq!(JsonDeserializer::new().deserialize_network_packet(&json).unwrap(), packet);
+    }
+
+    ///////////////////////////////////////////
+    ///        Options Parsing Tests        ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_parse_ipv4_options() {
+        assert_eq!(parse_ipv4_options("rr").unwrap(), vec![Ipv4Option::record_route(9)]);
+        assert_eq!(
+            parse_ipv4_options("rr:3, ts:2,ra,nop").unwrap(),
+            vec![Ipv4Option::record_route(3), Ipv4Option::timestamp(2), Ipv4Option::router_alert(), Ipv4Option::NoOperation]
+        );
+        assert_eq!(
+            parse_ipv4_options("lsrr:10.0.0.1/10.0.0.2,ssrr:192.0.2.1").unwrap(),
+            vec![
+                Ipv4Option::loose_source_route(vec![[10, 0, 0, 1], [10, 0, 0, 2]]),
+                Ipv4Option::strict_source_route(vec![[192, 0, 2, 1]]),
+            ]
+        );
+
+        for options in ["", "rr:0", "rr:10", "ts:x", "lsrr", "ssrr:10.0.0", "ra:1", "sec", "rr,"] {
+            assert!(parse_ipv4_options(options).is_err(), "{}", options);
+        }
+    }
+}
@@ -0,0 +1,218 @@
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        formats::format_factory::FormatFactory,
+        packets::{
+            builder::{ipv6_builder::Ipv6Builder, packet_dissector::PacketDissector},
+            ipv6::{pack_ipv6, unpack_ipv6},
+            l4::{tcp::pack_tcp, udp::pack_udp, icmpv6::pack_icmpv6},
+        },
+        parsing::my_parser::parse_ipv6,
+        structs::{
+            formats::FormatType,
+            icmp::IcmpHeader,
+            ip::{Ipv6ExtensionHeader, IPV6_DEST_OPTIONS, IPV6_FRAGMENT, IPV6_HOP_BY_HOP},
+            json::{JsonDeserializer, JsonSerializer},
+            l4_protocol::L4Data,
+            network_packet::NetworkPacket,
+            packet_builder::PacketBuilder,
+            pcap::{LinkType, PcapReader},
+        },
+        utils::formating_types::format_ipv6,
+    };
+
+    fn build(protocol: &str, extension_headers: Vec<Ipv6ExtensionHeader>) -> NetworkPacket {
+        PacketBuilder::from_cli_args(
+            None,
+            None,
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            Some(4242),
+            Some(443),
+            Some(protocol),
+            None,
+            Some(b"dual-stack".to_vec()),
+        ).unwrap()
+            .build_ipv6_packet(
+                parse_ipv6("2001:db8::10").unwrap(),
+                parse_ipv6("2001:db8::20").unwrap(),
+                extension_headers,
+            ).unwrap()
+    }
+
+    fn checksum_builder() -> Ipv6Builder {
+        Ipv6Builder::new(parse_ipv6("2001:db8::10").unwrap(), parse_ipv6("2001:db8::20").unwrap())
+    }
+
+    ///////////////////////////////////////////
+    ///         IPv6 Address Tests          ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_parse_ipv6_full_and_compressed() {
+        let full = parse_ipv6("2001:0db8:0000:0000:0000:0000:0000:0001").unwrap();
+        assert_eq!(parse_ipv6("2001:db8::1").unwrap(), full);
+        assert_eq!(full.octets[..4], [0x20, 0x01, 0x0D, 0xB8]);
+        assert_eq!(full.octets[15], 1);
+        assert_eq!(parse_ipv6("::").unwrap().octets, [0u8; 16]);
+        assert_eq!(parse_ipv6("::1").unwrap().octets[15], 1);
+        assert_eq!(parse_ipv6("fe80::").unwrap().octets[..2], [0xFE, 0x80]);
+    }
+
+    #[test]
+    fn test_parse_ipv6_embedded_ipv4() {
+        let mapped = parse_ipv6("::ffff:192.0.2.1").unwrap();
+        assert_eq!(mapped.octets[10..], [0xFF, 0xFF, 192, 0, 2, 1]);
+    }
+
+    #[test]
+    fn test_parse_ipv6_invalid() {
+        for ip in ["", "1::2::3", "2001:db8:::1", "12345::", "1:2:3:4:5:6:7", "1:2:3:4:5:6:7:8:9", "1:2:3:4:5:6:7::8", "g::1", ":1:2:3:4:5:6:7", "::1.2.3"] {
+            assert_eq!(parse_ipv6(ip), Err(ParseError::InvalidIpv6), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn test_format_ipv6_rfc5952() {
+        assert_eq!(format_ipv6(&parse_ipv6("2001:0db8:0:0:0:0:0:1").unwrap().octets), "2001:db8::1");
+        assert_eq!(format_ipv6(&parse_ipv6("::").unwrap().octets), "::");
+        // Un seul groupe nul n'est pas compresse, la suite la plus longue l'emporte
+        assert_eq!(format_ipv6(&parse_ipv6("2001:db8:0:1:1:1:1:1").unwrap().octets), "2001:db8:0:1:1:1:1:1");
+        assert_eq!(format_ipv6(&parse_ipv6("1:0:0:2:0:0:0:3").unwrap().octets), "1:0:0:2::3");
+    }
+
+    ///////////////////////////////////////////
+    ///       IPv6 Header Tests             ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_ipv6_header_layout() {
+        let packet = build("udp", Vec::new());
+        let ipv6 = packet.ipv6().unwrap();
+        assert!(packet.ipv4().is_none());
+        assert_eq!(packet.ethernet.ethertype, 0x86DD);
+        assert_eq!(ipv6.next_header, 17);
+        assert_eq!(ipv6.hop_limit, 64);
+        assert_eq!(ipv6.payload_length, 8 + 10);
+
+        let bytes = packet.assemble_packet().unwrap();
+        assert_eq!(bytes.len(), packet.get_packet_size());
+        assert_eq!(bytes[14..22], [0x60, 0x00, 0x00, 0x00, 0x00, 18, 17, 64]);
+        assert_eq!(bytes[22..38], parse_ipv6("2001:db8::10").unwrap().octets);
+        assert_eq!(bytes[38..54], parse_ipv6("2001:db8::20").unwrap().octets);
+    }
+
+    #[test]
+    fn test_ipv6_extension_chain_roundtrip() {
+        let packet = build("tcp", vec![
+            Ipv6ExtensionHeader::options(IPV6_HOP_BY_HOP, &[]),
+            Ipv6ExtensionHeader::fragment(0, false, 0xCAFE),
+            Ipv6ExtensionHeader::options(IPV6_DEST_OPTIONS, &[0x1E, 0x02, 0xAA, 0xBB]),
+        ]);
+        let ipv6 = packet.ipv6().unwrap();
+        assert_eq!(ipv6.next_header, IPV6_HOP_BY_HOP);
+        assert_eq!(ipv6.extension_headers[0].next_header, IPV6_FRAGMENT);
+        assert_eq!(ipv6.extension_headers[1].next_header, IPV6_DEST_OPTIONS);
+        assert_eq!(ipv6.upper_layer_protocol(), 6);
+        assert_eq!(ipv6.extensions_len(), 24);
+
+        let bytes = packet.assemble_packet().unwrap();
+        let (decoded, l4) = unpack_ipv6(&bytes[14..]).unwrap();
+        assert_eq!(&decoded, ipv6);
+        let L4Data::Tcp(tcp) = &packet.l4_data else { panic!("expected TCP") };
+        assert_eq!(l4, pack_tcp(tcp).unwrap());
+        assert_eq!(PacketDissector::new().dissect_packet(&bytes).unwrap(), packet);
+    }
+
+    #[test]
+    fn test_ipv6_invalid_extension_length() {
+        let mut packet = build("udp", Vec::new());
+        let ipv6 = packet.ipv6_mut().unwrap();
+        ipv6.extension_headers.push(Ipv6ExtensionHeader {
+            header_type: IPV6_DEST_OPTIONS,
+            next_header: 17,
+            data: vec![0; 3],
+        });
+        assert!(matches!(
+            pack_ipv6(ipv6, &[]),
+            Err(ParseError::InvalidHeaderLength { layer: "IPv6 extension", length: 5 })
+        ));
+    }
+
+    #[test]
+    fn test_unpack_ipv6_errors() {
+        assert!(matches!(unpack_ipv6(&[0x60; 20]), Err(ParseError::TruncatedPacket { layer: "IPv6", .. })));
+        let mut bytes = build("udp", Vec::new()).assemble_packet().unwrap()[14..].to_vec();
+        bytes[0] = 0x40;
+        assert_eq!(unpack_ipv6(&bytes), Err(ParseError::InvalidVersion(4)));
+        bytes[0] = 0x60;
+        // payload_length au-dela des octets recus
+        bytes[5] += 1;
+        assert!(matches!(unpack_ipv6(&bytes), Err(ParseError::TruncatedPacket { layer: "IPv6", .. })));
+    }
+
+    ///////////////////////////////////////////
+    ///      Pseudo-header Checksum Tests   ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_tcp_udp_pseudo_header_checksum() {
+        let builder = checksum_builder();
+        let tcp = build("tcp", Vec::new());
+        let L4Data::Tcp(header) = &tcp.l4_data else { panic!("expected TCP") };
+        assert_eq!(builder.pseudo_header_checksum(6, &pack_tcp(header).unwrap()), 0);
+
+        let udp = build("udp", vec![Ipv6ExtensionHeader::options(IPV6_DEST_OPTIONS, &[])]);
+        let L4Data::Udp(header) = &udp.l4_data else { panic!("expected UDP") };
+        assert_ne!(header.checksum, 0);
+        assert_eq!(builder.pseudo_header_checksum(17, &pack_udp(header).unwrap()), 0);
+    }
+
+    #[test]
+    fn test_ipv6_icmp_becomes_icmpv6_echo() {
+        let packet = build("icmp", Vec::new());
+        assert_eq!(packet.ipv6().unwrap().next_header, 58);
+        let L4Data::Icmpv6(echo) = &packet.l4_data else { panic!("expected ICMPv6") };
+        assert_eq!(echo.icmp_type, 128);
+        assert_eq!(echo.identifier(), 4242);
+        assert_eq!(checksum_builder().pseudo_header_checksum(58, &pack_icmpv6(echo).unwrap()), 0);
+
+        // ICMPv4 n'est pas transporte sur IPv6
+        let l4_data = L4Data::Icmp(IcmpHeader { icmp_type: 8, code: 0, checksum: 0, rest_of_header: 0, payload: None });
+        assert!(checksum_builder().build_ipv6_header(&l4_data, Vec::new()).is_err());
+    }
+
+    ///////////////////////////////////////////
+    ///         IPv6 Formats Tests          ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_ipv6_json_roundtrip() {
+        let packet = build("tcp", vec![Ipv6ExtensionHeader::fragment(0, true, 7)]);
+        let json = JsonSerializer::new().serialize_packet(&packet).unwrap();
+        assert!(json.contains("\"dst_addr\":\"2001:db8::20\""));
+        assert!(json.contains("\"extension_headers\":[{\"header_type\":44"));
+        let decoded = JsonDeserializer::new().deserialize_network_packet(&json).unwrap();
+        assert_eq!(decoded, packet);
+
+        // Un paquet IPv4 n'a pas de bloc ipv6
+        let ipv4 = PacketBuilder::from_cli_args(None, None, None, None, None, None, None, None, None).unwrap();
+        let json = JsonSerializer::new().serialize_packet(&ipv4.build_packet().unwrap()).unwrap();
+        assert!(!json.contains("ipv6"));
+    }
+
+    #[test]
+    fn test_ipv6_pcap_roundtrip() {
+        let packet = build("udp", Vec::new());
+        let data = FormatFactory::new().write_packet(&packet, FormatType::Pcap).unwrap();
+        let mut reader = PcapReader::new(data);
+        let record = reader.read_next_record().unwrap().unwrap();
+        assert_eq!(record.packet, Some(packet.clone()));
+
+        // Lien IP brut : la version du premier octet donne l'ethertype
+        let raw = PacketDissector::new().dissect_link_frame(LinkType::Raw, &record.data[14..]).unwrap();
+        assert_eq!(raw.ip, packet.ip);
+        assert_eq!(raw.ethernet.ethertype, 0x86DD);
+    }
+}
@@ -0,0 +1,231 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        formats::format_factory::FormatFactory,
+        structs::{
+            packet_builder::PacketBuilder,
+            network_packet::NetworkPacket,
+            l4_protocol::L4Data,
+            formats::FormatType,
+            json::{JsonSerializer, JsonDeserializer},
+        },
+        utils::{checksum::internet_checksum, clock::FixedClock},
+    };
+    use crate::common::tcp_checksum_ok;
+
+    // Template TCP edite a la main : longueurs et checksums omis
+    fn tcp_template(ipv4_fields: &str, tcp_fields: &str) -> String {
+        format!(r#"{{
+            "ethernet": {{"src_mac": "aa:bb:cc:dd:ee:ff", "dst_mac": "11:22:33:44:55:66", "ethertype": 2048}},
+            "ipv4": {{"src_addr": "10.0.0.1", "dst_addr": "10.0.0.2", "protocol": 6,
+                     "ttl": 64, "flags": 2, "fragment_offset": 0{}}},
+            "l4": {{"protocol_type": "tcp", "src_port": 40000, "dst_port": 80, "payload_size": 2,
+                   "additional_fields": {{"sequence_number": {{"U64": 1}}, "ack_number": {{"U64": 0}},
+                   "reserved": {{"U64": 0}}, "flags": {{"U64": 2}}, "urgent_pointer": {{"U64": 0}}{}}},
+                   "payload": "68 69"}},
+            "metadata": {{"packet_size": 0, "timestamp": 0, "raw_data": ""}}
+        }}"#, ipv4_fields, tcp_fields)
+    }
+
+    fn build_packet(protocol: &str, payload: &[u8]) -> NetworkPacket {
+        PacketBuilder::from_cli_args(
+            Some("172.16.0.1"),
+            Some("172.16.0.99"),
+            Some([0xDE, 0xAD, 0xBE, 0xEF, 0x00, 0x01]),
+            Some([0xDE, 0xAD, 0xBE, 0xEF, 0x00, 0x02]),
+            Some(31337),
+            Some(8080),
+            Some(protocol),
+            Some(0x20),
+            Some(payload.to_vec()),
+        ).unwrap().build_packet().unwrap()
+    }
+
+    ///////////////////////////////////////////
+    ///      JSON Round-trip Tests          ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_json_round_trip_tcp_with_options() {
+        let mut packet = build_packet("tcp", b"\x00\x01binary\xff");
+        let ipv4 = packet.ipv4_mut().unwrap();
+        ipv4.dscp = 0xB8;
+        ipv4.identification = 0xBEEF;
+        ipv4.ihl = 6;
+        ipv4.options = Some(vec![0x94, 0x04, 0x00, 0x00]);
+        if let L4Data::Tcp(tcp) = &mut packet.l4_data {
+            tcp.data_offset = 7;
+            tcp.options = Some(vec![0x02, 0x04, 0x05, 0xB4, 0x01, 0x03, 0x03, 0x07]);
+            tcp.urgent_pointer = 12;
+            tcp.reserved = 0x01;
+        }
+
+        let json_str = JsonSerializer::new().serialize_packet(&packet).unwrap();
+        let parsed = JsonDeserializer::new().deserialize_network_packet(&json_str).unwrap();
+        assert_eq!(parsed, packet);
+    }
+
+    #[test]
+    fn test_json_round_trip_udp() {
+        let packet = build_packet("udp", b"query");
+        let json_str = JsonSerializer::without_raw_data().serialize_packet(&packet).unwrap();
+        let parsed = JsonDeserializer::new().deserialize_network_packet(&json_str).unwrap();
+        assert_eq!(parsed, packet);
+    }
+
+    #[test]
+    fn test_json_round_trip_multiple_packets() {
+        let packets = vec![build_packet("tcp", b"a"), build_packet("udp", b"b")];
+        let json_str = JsonSerializer::without_raw_data().serialize_packets(&packets).unwrap();
+        let parsed = JsonDeserializer::new().deserialize_network_packets(&json_str).unwrap();
+        assert_eq!(parsed, packets);
+    }
+
+    #[test]
+    fn test_json_format_reader_records() {
+        let packets = vec![build_packet("tcp", b"a"), build_packet("udp", b"b")];
+        let json = JsonSerializer::without_raw_data()
+            .with_clock(Box::new(FixedClock { ns: 1_700_000_000_250_000_000 }))
+            .serialize_packets(&packets)
+            .unwrap();
+
+        let factory = FormatFactory::new();
+        let mut reader = factory.create_reader(FormatType::Json, json.into_bytes());
+        assert!(reader.has_more_packets());
+
+        let first = reader.read_next_record().unwrap().unwrap();
+        assert_eq!(first.ts_sec, 1_700_000_000);
+        assert_eq!(first.ts_usec, 250_000);
+        assert_eq!(first.data, packets[0].assemble_packet().unwrap());
+        assert_eq!(first.packet.as_ref(), Some(&packets[0]));
+
+        let second = reader.read_next_packet().unwrap().unwrap();
+        assert_eq!(second, packets[1].assemble_packet().unwrap());
+        assert!(reader.read_next_record().unwrap().is_none());
+        assert!(!reader.has_more_packets());
+    }
+
+    #[test]
+    fn test_json_hand_written_template() {
+        let template = r#"{
+            "ethernet": {"src_mac": "aa:bb:cc:dd:ee:ff", "dst_mac": "11:22:33:44:55:66", "ethertype": 2048},
+            "ipv4": {"src_addr": "10.0.0.1", "dst_addr": "10.0.0.2", "protocol": 17,
+                     "total_length": 31, "header_checksum": 0, "ttl": 32, "flags": 2, "fragment_offset": 0},
+            "l4": {"protocol_type": "udp", "src_port": 1000, "dst_port": 53, "payload_size": 3,
+                   "checksum": 0, "additional_fields": {}, "payload": "01 02 03"},
+            "metadata": {"packet_size": 0, "timestamp": 0, "raw_data": ""}
+        }"#;
+
+        let packet = JsonDeserializer::new().deserialize_network_packet(template).unwrap();
+        assert_eq!(packet.ipv4().unwrap().version, 4);
+        assert_eq!(packet.ipv4().unwrap().ihl, 5);
+        assert_eq!(packet.ipv4().unwrap().ttl, 32);
+        match packet.l4_data {
+            L4Data::Udp(udp) => {
+                assert_eq!(udp.length, 11);
+                assert_eq!(udp.payload, Some(vec![1, 2, 3]));
+            }
+            _ => panic!("Expected UDP data"),
+        }
+    }
+
+    #[test]
+    fn test_json_template_lengths_and_checksums_recomputed() {
+        let template = tcp_template("", r#", "window": {"U64": 1024}"#);
+        let packet = JsonDeserializer::new().deserialize_network_packet(&template).unwrap();
+        assert_eq!(packet.ipv4().unwrap().total_length, 20 + 20 + 2);
+        assert_ne!(packet.ipv4().unwrap().header_checksum, 0);
+        let bytes = packet.assemble_packet().unwrap();
+        assert_eq!(internet_checksum(&bytes[14..34]), 0);
+        assert!(tcp_checksum_ok(&packet));
+        assert_eq!(NetworkPacket::parse(&bytes).unwrap(), packet);
+    }
+
+    #[test]
+    fn test_json_template_invalid_header_lengths() {
+        let deserializer = JsonDeserializer::new();
+        let window = r#", "window": {"U64": 1024}"#;
+        // ihl hors de 5..=15 ou different de 5 + options / 4
+        for ihl in [2, 6, 200] {
+            let template = tcp_template(&format!(r#", "ihl": {}"#, ihl), window);
+            assert_eq!(
+                deserializer.deserialize_network_packet(&template),
+                Err(ParseError::InvalidHeaderLength { layer: "IPv4", length: ihl * 4 })
+            );
+        }
+        let template = tcp_template("", &format!(r#"{}, "data_offset": {{"U64": 4}}"#, window));
+        assert_eq!(
+            deserializer.deserialize_network_packet(&template),
+            Err(ParseError::InvalidHeaderLength { layer: "TCP", length: 16 })
+        );
+        // Options sur 6 mots : ihl 11
+        let template = tcp_template(r#", "ihl": 11, "options": "01 01 01 01 01 01 01 01 01 01 01 01 01 01 01 01 01 01 01 01 01 01 01 01""#, window);
+        assert_eq!(deserializer.deserialize_network_packet(&template).unwrap().ipv4().unwrap().ihl, 11);
+    }
+
+    #[test]
+    fn test_json_template_missing_or_invalid_fields() {
+        let deserializer = JsonDeserializer::new();
+        assert_eq!(
+            deserializer.deserialize_network_packet(&tcp_template("", "")),
+            Err(ParseError::MissingRequiredField("window"))
+        );
+        assert_eq!(
+            deserializer.deserialize_network_packet(&tcp_template("", r#", "window": {"U64": 70000}"#)),
+            Err(ParseError::ValueTooLarge { value: 70000, size: 2 })
+        );
+        assert!(matches!(
+            deserializer.deserialize_network_packet(&tcp_template("", r#", "window": {"String": "big"}"#)),
+            Err(ParseError::JsonError(_))
+        ));
+    }
+
+    #[test]
+    fn test_json_unknown_protocol_type() {
+        let packet = build_packet("udp", b"x");
+        let json_str = JsonSerializer::new().serialize_packet(&packet).unwrap();
+        let json_str = json_str.replace("\"UDP\"", "\"SCTP\"");
+        assert!(JsonDeserializer::new().deserialize_network_packet(&json_str).is_err());
+    }
+
+    ///////////////////////////////////////////
+    ///      JSON Buffer Size Tests         ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_json_jumbo_payload_with_raw_data() {
+        let payload = vec![0xA5u8; 9000];
+        let packet = build_packet("udp", &payload);
+        let json_str = JsonSerializer::new().serialize_packet(&packet).unwrap();
+
+        assert!(json_str.len() > 9000 * 3);
+        let parsed = JsonDeserializer::new().deserialize_network_packet(&json_str).unwrap();
+        assert_eq!(parsed, packet);
+    }
+
+    #[test]
+    fn test_json_many_packets() {
+        let packets: Vec<NetworkPacket> = (0..200)
+            .map(|i| build_packet(if i % 2 == 0 { "tcp" } else { "udp" }, b"run"))
+            .collect();
+        let json_str = JsonSerializer::new().serialize_packets(&packets).unwrap();
+        let parsed = JsonDeserializer::new().deserialize_network_packets(&json_str).unwrap();
+        assert_eq!(parsed, packets);
+    }
+
+    #[test]
+    fn test_json_serialize_packets_to_writer() {
+        let packets = vec![build_packet("tcp", b"a"), build_packet("udp", &[0u8; 5000])];
+        let serializer = JsonSerializer::without_raw_data()
+            .with_clock(Box::new(FixedClock { ns: 0 }));
+
+        let mut out: Vec<u8> = Vec::new();
+        serializer.serialize_packets_to_writer(&packets, &mut out).unwrap();
+
+        let expected = serializer.serialize_packets(&packets).unwrap();
+        assert_eq!(out, expected.into_bytes());
+    }
+}
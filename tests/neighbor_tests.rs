@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        packets::builder::arp_builder::ArpBuilder,
+        parsing::my_parser::parse_ipv4,
+        sender::{
+            neighbor::{parse_arp_table, parse_route_table, route_next_hop},
+            raw_socket::get_interface_index,
+        },
+        structs::{
+            arp::{ArpFrame, ARP_REQUEST, ETHERTYPE_ARP},
+            ipv4::Ipv4Addr,
+            neighbor::NeighborResolver,
+            socket::{RawSocketReceiver, RawSocketSender},
+        },
+    };
+    use std::time::{Duration, Instant};
+
+    const LOCAL_MAC: [u8; 6] = [0x02, 0x00, 0x00, 0x00, 0x00, 0x01];
+
+    const ARP_TABLE: &str = "\
+IP address       HW type     Flags       HW address            Mask     Device
+192.168.1.1      0x1         0x2         aa:bb:cc:dd:ee:01     *        eth0
+192.168.1.7      0x1         0x0         00:00:00:00:00:00     *        eth0
+10.0.0.1         0x1         0x2         aa:bb:cc:dd:ee:02     *        wlan0
+";
+
+    // Adresses en ordre hote (x86 : little-endian)
+    fn route_line(iface: &str, dst: [u8; 4], gw: [u8; 4], mask: [u8; 4], metric: u32) -> String {
+        format!(
+            "{}\t{:08X}\t{:08X}\t0003\t0\t0\t{}\t{:08X}\t0\t0\t0\n",
+            iface,
+            u32::from_ne_bytes(dst),
+            u32::from_ne_bytes(gw),
+            metric,
+            u32::from_ne_bytes(mask),
+        )
+    }
+
+    fn ip(s: &str) -> Ipv4Addr {
+        parse_ipv4(s).unwrap()
+    }
+
+    // Passerelle simulee sur lo : repond aux requetes ARP visant `gateway`
+    fn spawn_gateway(gateway: &str, mac: [u8; 6]) -> std::thread::JoinHandle<usize> {
+        let gateway = ip(gateway);
+        let mut receiver = RawSocketReceiver::with_protocol(ETHERTYPE_ARP).unwrap();
+        receiver.bind("lo").unwrap();
+        receiver.set_read_timeout(Some(100)).unwrap();
+        std::thread::spawn(move || {
+            let sender = RawSocketSender::new().unwrap();
+            let if_index = get_interface_index("lo").unwrap();
+            let deadline = Instant::now() + Duration::from_millis(1500);
+            let mut answered = 0;
+            while Instant::now() < deadline {
+                let Some(frame) = receiver.recv_frame().unwrap() else { continue };
+                let Ok(request) = ArpFrame::parse(&frame.record.data) else { continue };
+                if frame.is_outgoing()
+                    || request.arp.operation != ARP_REQUEST
+                    || request.arp.target_ip != gateway.octets
+                {
+                    continue;
+                }
+                let reply = ArpBuilder::new(mac, gateway)
+                    .build_reply(request.arp.sender_mac, Ipv4Addr { octets: request.arp.sender_ip });
+                sender.send(if_index, reply.ethernet.dst_mac, &reply.assemble().unwrap()).unwrap();
+                answered += 1;
+            }
+            answered
+        })
+    }
+
+    ///////////////////////////////////////////
+    ///        Kernel Tables Tests          ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_parse_arp_table_complete_entries() {
+        let table = parse_arp_table(ARP_TABLE, "eth0");
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.get(&[192, 168, 1, 1]), Some(&[0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0x01]));
+        assert_eq!(parse_arp_table(ARP_TABLE, "wlan0").len(), 1);
+        assert!(parse_arp_table("", "eth0").is_empty());
+    }
+
+    #[test]
+    fn test_route_next_hop() {
+        let mut contents = String::from("Iface\tDestination\tGateway\tFlags\tRefCnt\tUse\tMetric\tMask\tMTU\tWindow\tIRTT\n");
+        contents += &route_line("eth0", [0; 4], [192, 168, 1, 1], [0; 4], 100);
+        contents += &route_line("eth0", [192, 168, 1, 0], [0; 4], [255, 255, 255, 0], 0);
+        contents += &route_line("eth0", [10, 8, 0, 0], [192, 168, 1, 254], [255, 255, 0, 0], 0);
+        contents += &route_line("wlan0", [0; 4], [10, 0, 0, 1], [0; 4], 600);
+        let routes = parse_route_table(&contents);
+        assert_eq!(routes.len(), 4);
+        assert_eq!(routes[0].gateway, [192, 168, 1, 1]);
+        assert_eq!(routes[0].metric, 100);
+
+        // Hors sous-reseau : passerelle par defaut de l'interface
+        assert_eq!(route_next_hop(&routes, "eth0", [8, 8, 8, 8]), [192, 168, 1, 1]);
+        // Sur le lien : la destination elle-meme
+        assert_eq!(route_next_hop(&routes, "eth0", [192, 168, 1, 42]), [192, 168, 1, 42]);
+        // Route la plus specifique
+        assert_eq!(route_next_hop(&routes, "eth0", [10, 8, 3, 4]), [192, 168, 1, 254]);
+        assert_eq!(route_next_hop(&routes, "wlan0", [8, 8, 8, 8]), [10, 0, 0, 1]);
+        // Aucune route pour l'interface
+        assert_eq!(route_next_hop(&routes, "eth1", [8, 8, 8, 8]), [8, 8, 8, 8]);
+    }
+
+    ///////////////////////////////////////////
+    ///        Neighbor Lookup Tests        ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_lookup_special_addresses() {
+        let mut loopback = NeighborResolver::new("lo").unwrap();
+        assert_eq!(loopback.lookup(ip("127.0.0.1")), Some([0; 6]));
+        assert_eq!(loopback.lookup(ip("198.51.100.1")), Some([0; 6]));
+
+        let mut resolver = loopback.with_source(LOCAL_MAC, ip("198.51.100.1"));
+        assert_eq!(resolver.lookup(ip("224.0.0.251")), Some([0x01, 0x00, 0x5E, 0x00, 0x00, 0xFB]));
+        assert_eq!(resolver.lookup(ip("239.255.255.250")), Some([0x01, 0x00, 0x5E, 0x7F, 0xFF, 0xFA]));
+        assert_eq!(resolver.lookup(ip("255.255.255.255")), Some([0xFF; 6]));
+        assert_eq!(resolver.lookup(ip("198.51.100.50")), None);
+    }
+
+    #[test]
+    fn test_lookup_uses_cache() {
+        let mut resolver = NeighborResolver::new("lo").unwrap()
+            .with_source(LOCAL_MAC, ip("198.51.100.1"));
+        resolver.insert(ip("198.51.100.50"), [0x02, 0, 0, 0, 0, 0x50]);
+        assert_eq!(resolver.lookup(ip("198.51.100.50")), Some([0x02, 0, 0, 0, 0, 0x50]));
+        assert_eq!(resolver.cached(ip("198.51.100.51")), None);
+    }
+
+    #[test]
+    fn test_resolve_active_arp_then_cache() {
+        if RawSocketSender::new().is_err() {
+            eprintln!("raw socket indisponible (root requis), test ignore");
+            return;
+        }
+        let gateway_mac = [0x02, 0x00, 0x5E, 0x00, 0x00, 0xFE];
+        let responder = spawn_gateway("198.51.100.254", gateway_mac);
+
+        let sender = RawSocketSender::new().unwrap();
+        let mut receiver = RawSocketReceiver::with_protocol(ETHERTYPE_ARP).unwrap();
+        receiver.bind("lo").unwrap();
+        let if_index = get_interface_index("lo").unwrap();
+        let mut resolver = NeighborResolver::new("lo").unwrap()
+            .with_source(LOCAL_MAC, ip("198.51.100.1"))
+            .with_timeout_ms(300);
+
+        let mac = resolver.resolve_with(&sender, &receiver, if_index, ip("198.51.100.254")).unwrap();
+        assert_eq!(mac, gateway_mac);
+        assert_eq!(resolver.cached(ip("198.51.100.254")), Some(gateway_mac));
+        // Deuxieme resolution servie par le cache : aucune nouvelle requete
+        assert_eq!(resolver.resolve_with(&sender, &receiver, if_index, ip("198.51.100.254")).unwrap(), gateway_mac);
+        assert_eq!(responder.join().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_resolve_unanswered() {
+        if RawSocketSender::new().is_err() {
+            eprintln!("raw socket indisponible (root requis), test ignore");
+            return;
+        }
+        let sender = RawSocketSender::new().unwrap();
+        let mut receiver = RawSocketReceiver::with_protocol(ETHERTYPE_ARP).unwrap();
+        receiver.bind("lo").unwrap();
+        let if_index = get_interface_index("lo").unwrap();
+        let mut resolver = NeighborResolver::new("lo").unwrap()
+            .with_source(LOCAL_MAC, ip("203.0.113.1"))
+            .with_retries(0)
+            .with_timeout_ms(100);
+        assert_eq!(
+            resolver.resolve_with(&sender, &receiver, if_index, ip("203.0.113.9")),
+            Err(ParseError::UnresolvedNeighbor([203, 0, 113, 9]))
+        );
+    }
+}
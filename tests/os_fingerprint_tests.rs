@@ -0,0 +1,342 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        packets::os_fingerprint::{initial_ttl, options_layout},
+        parsing::my_parser::parse_ipv6,
+        structs::{
+            l4_protocol::{L4Data, L4Protocol},
+            network_packet::NetworkPacket,
+            os_fingerprint::{IpIdBehavior, OsDatabase, OsMatch, OsProber, TcpFingerprint},
+            packet_builder::PacketBuilder,
+            tcp::{TcpFlags, TcpOption},
+        },
+    };
+    use crate::common::{raw_socket_unavailable, reply_to, spawn_responder, tcp};
+    use std::{thread::JoinHandle, time::Duration};
+
+    fn template(src: &str, dst: &str) -> PacketBuilder {
+        crate::common::template(src, dst, "tcp", 42000, 22)
+    }
+
+    fn windows_options() -> Vec<TcpOption> {
+        vec![
+            TcpOption::Mss(1460),
+            TcpOption::NoOperation,
+            TcpOption::WindowScale(8),
+            TcpOption::NoOperation,
+            TcpOption::NoOperation,
+            TcpOption::SackPermitted,
+        ]
+    }
+
+    // SYN-ACK d'une pile Windows 10 : TTL 128, fenetre 64240, DF
+    fn windows_stack(reply: PacketBuilder, ttl: u8, identification: u16) -> PacketBuilder {
+        let mut reply = reply
+            .with_tcp_flags(TcpFlags::SYN | TcpFlags::ACK)
+            .with_ttl(ttl)
+            .with_window(64240)
+            .with_identification(identification)
+            .with_tcp_options(windows_options());
+        reply.ip_bitfield = 0x40;
+        reply
+    }
+
+    fn windows_reply(src: &str, dst: &str, ttl: u8, identification: u16) -> NetworkPacket {
+        windows_stack(template(src, dst), ttl, identification).build_packet().unwrap()
+    }
+
+    // SYN-ACK d'une pile Linux : TTL 64, fenetre 65160 (mss*45), identification nulle, DF
+    fn linux_syn_ack(src: &str, ttl: u8) -> NetworkPacket {
+        let mut reply = template(src, "10.0.0.1")
+            .with_tcp_flags(TcpFlags::SYN | TcpFlags::ACK)
+            .with_ttl(ttl)
+            .with_window(65160)
+            .with_tcp_options(vec![
+                TcpOption::Mss(1448),
+                TcpOption::SackPermitted,
+                TcpOption::Timestamps { value: 1, echo_reply: 1 },
+                TcpOption::NoOperation,
+                TcpOption::WindowScale(7),
+            ]);
+        reply.ip_bitfield = 0x40;
+        reply.build_packet().unwrap()
+    }
+
+    // Hote Windows simule : SYN-ACK a chaque SYN, identification IP croissante ;
+    // s'arrete apres `syns` SYN
+    fn spawn_windows_host(target: &str, syns: usize) -> JoinHandle<()> {
+        let mut answered = 0;
+        spawn_responder(target, Duration::from_secs(5), move |segment, replies| {
+            let L4Data::Tcp(client) = &segment.l4_data else { return true };
+            if client.tcp_flags() != TcpFlags::SYN {
+                return true;
+            }
+            let reply = windows_stack(reply_to(segment), 128, 2000 + answered as u16)
+                .with_sequence(7000)
+                .with_ack(client.sequence_number.wrapping_add(1));
+            replies.push(reply.build_packet().unwrap().assemble_packet().unwrap());
+            answered += 1;
+            answered < syns
+        })
+    }
+
+    ///////////////////////////////////////////
+    ///         Fingerprint Tests           ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_initial_ttl_and_layout() {
+        assert_eq!(initial_ttl(30), 32);
+        assert_eq!(initial_ttl(61), 64);
+        assert_eq!(initial_ttl(64), 64);
+        assert_eq!(initial_ttl(65), 128);
+        assert_eq!(initial_ttl(117), 128);
+        assert_eq!(initial_ttl(250), 255);
+
+        assert_eq!(options_layout(&windows_options()), "mss,nop,ws,nop,nop,sok");
+        assert_eq!(
+            options_layout(&[TcpOption::Sack(vec![(1, 2)]), TcpOption::FastOpen(vec![]), TcpOption::EndOfList]),
+            "sack,tfo,eol"
+        );
+        assert_eq!(options_layout(&[TcpOption::Unknown { kind: 30, data: vec![] }]), "?30");
+        assert_eq!(options_layout(&[]), "");
+    }
+
+    #[test]
+    fn test_ip_id_behavior() {
+        assert_eq!(IpIdBehavior::from_ids(&[]), IpIdBehavior::Unknown);
+        assert_eq!(IpIdBehavior::from_ids(&[0]), IpIdBehavior::Zero);
+        assert_eq!(IpIdBehavior::from_ids(&[0, 0, 0]), IpIdBehavior::Zero);
+        assert_eq!(IpIdBehavior::from_ids(&[4321]), IpIdBehavior::Unknown);
+        assert_eq!(IpIdBehavior::from_ids(&[100, 101, 140]), IpIdBehavior::Incremental);
+        // Passage de 65535 a 0
+        assert_eq!(IpIdBehavior::from_ids(&[65534, 3]), IpIdBehavior::Incremental);
+        assert_eq!(IpIdBehavior::from_ids(&[100, 100]), IpIdBehavior::Random);
+        assert_eq!(IpIdBehavior::from_ids(&[100, 31000, 7]), IpIdBehavior::Random);
+    }
+
+    #[test]
+    fn test_fingerprint_from_packets() {
+        let linux = TcpFingerprint::from_packet(&linux_syn_ack("10.0.0.2", 61)).unwrap();
+        assert_eq!(linux, TcpFingerprint {
+            ttl: 61,
+            initial_ttl: 64,
+            window: 65160,
+            mss: Some(1448),
+            window_scale: Some(7),
+            options: "mss,sok,ts,nop,ws".into(),
+            df: true,
+            ip_id: IpIdBehavior::Zero,
+        });
+        assert_eq!(linux.hops(), 3);
+
+        let packets: Vec<NetworkPacket> = [1200, 1201, 1203]
+            .into_iter()
+            .map(|id| windows_reply("10.0.0.3", "10.0.0.1", 120, id))
+            .collect();
+        let windows = TcpFingerprint::from_packets(&packets).unwrap();
+        assert_eq!((windows.initial_ttl, windows.hops()), (128, 8));
+        assert_eq!(windows.ip_id, IpIdBehavior::Incremental);
+        assert_eq!(windows.to_string(), "ttl=128 window=64240 ws=8 options=mss,nop,ws,nop,nop,sok df=1 ipid=incr");
+        assert_eq!(TcpFingerprint::from_packet(&packets[0]).unwrap().ip_id, IpIdBehavior::Unknown);
+
+        // Ni SYN ni TCP : pas d'empreinte
+        let ack = template("10.0.0.3", "10.0.0.1").with_tcp_flags(TcpFlags::ACK).build_packet().unwrap();
+        assert_eq!(TcpFingerprint::from_packet(&ack), None);
+        let mut udp = template("10.0.0.3", "10.0.0.1");
+        udp.protocol = L4Protocol::Udp;
+        assert_eq!(TcpFingerprint::from_packet(&udp.build_packet().unwrap()), None);
+        assert_eq!(TcpFingerprint::from_packets(&[]), None);
+    }
+
+    ///////////////////////////////////////////
+    ///          OS Database Tests          ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_builtin_identify() {
+        let database = OsDatabase::builtin();
+        assert_eq!(database.signatures.len(), 11);
+
+        let linux = database.report(TcpFingerprint::from_packet(&linux_syn_ack("10.0.0.2", 57)));
+        assert_eq!(linux.best(), Some(&OsMatch { name: "Linux 3.x-6.x".into(), confidence: 100 }));
+        assert_eq!(linux.best().unwrap().to_string(), "Linux 3.x-6.x (100%)");
+
+        // Un seul paquet : identification IP non jugee, la confiance reste entiere
+        let packet = windows_reply("10.0.0.3", "10.0.0.1", 128, 4321);
+        let windows = database.report(TcpFingerprint::from_packet(&packet));
+        assert_eq!(windows.best(), Some(&OsMatch { name: "Windows 10/11".into(), confidence: 100 }));
+        // Meme disposition d'options, fenetre differente
+        assert_eq!(windows.matches[1], OsMatch { name: "Windows 7/8, Server 2008-2012".into(), confidence: 72 });
+        assert!(windows.matches.windows(2).all(|pair| pair[0].confidence >= pair[1].confidence));
+        assert!(windows.matches.iter().all(|candidate| candidate.confidence > 0));
+
+        assert_eq!(database.report(None).matches, vec![]);
+    }
+
+    #[test]
+    fn test_user_database() {
+        let user = OsDatabase::parse(concat!(
+            "# equipements maison\n",
+            "\n",
+            "os \"Embedded RTOS\" ttl=64 window=mss*2,2048 ws=none options=mss df=0 ipid=incr\n",
+            "os \"Anything at 255\" ttl=255 window=* options=*\n",
+        )).unwrap();
+        assert_eq!(user.signatures.len(), 2);
+        assert_eq!(user.signatures[1].windows, None);
+
+        let mut reply = template("10.0.0.4", "10.0.0.1")
+            .with_tcp_flags(TcpFlags::SYN | TcpFlags::ACK)
+            .with_ttl(60)
+            .with_window(2920)
+            .with_identification(77)
+            .with_tcp_options(vec![TcpOption::Mss(1460)]);
+        let fingerprint = TcpFingerprint::from_packet(&reply.build_packet().unwrap()).unwrap();
+        assert_eq!(user.identify(&fingerprint)[0], OsMatch { name: "Embedded RTOS".into(), confidence: 100 });
+
+        // Signature vague : un critere indifferent n'apporte rien
+        reply = reply.with_ttl(250);
+        let fingerprint = TcpFingerprint::from_packet(&reply.build_packet().unwrap()).unwrap();
+        assert_eq!(user.identify(&fingerprint), vec![
+            OsMatch { name: "Embedded RTOS".into(), confidence: 77 },
+            OsMatch { name: "Anything at 255".into(), confidence: 22 },
+        ]);
+
+        // Base utilisateur prioritaire a egalite de confiance
+        let mut database = OsDatabase::builtin();
+        database.merge(OsDatabase::parse("os \"Custom Linux\" ttl=64 window=65160 ws=7 options=mss,sok,ts,nop,ws df=1 ipid=zero").unwrap());
+        assert_eq!(database.signatures.len(), 12);
+        let linux = database.report(TcpFingerprint::from_packet(&linux_syn_ack("10.0.0.2", 64)));
+        assert_eq!(linux.matches[0].name, "Custom Linux");
+        assert_eq!(linux.matches[1].name, "Linux 3.x-6.x");
+
+        // L'affichage d'une empreinte sert de ligne de base
+        let packets: Vec<NetworkPacket> = [9000, 120, 33000]
+            .into_iter()
+            .map(|id| {
+                let mut reply = template("10.0.0.5", "10.0.0.1")
+                    .with_tcp_flags(TcpFlags::SYN | TcpFlags::ACK)
+                    .with_ttl(253)
+                    .with_window(8760)
+                    .with_identification(id);
+                reply.ip_bitfield = 0x40;
+                reply.build_packet().unwrap()
+            })
+            .collect();
+        let fingerprint = TcpFingerprint::from_packets(&packets).unwrap();
+        assert_eq!(fingerprint.to_string(), "ttl=255 window=8760 ws=none options=none df=1 ipid=random");
+
+        let database = OsDatabase::parse(&format!("os \"Printer\" {}", fingerprint)).unwrap();
+        assert_eq!(database.identify(&fingerprint), vec![OsMatch { name: "Printer".into(), confidence: 100 }]);
+    }
+
+    #[test]
+    fn test_database_errors() {
+        let line = |text: &str| match OsDatabase::parse(text) {
+            Err(ParseError::InvalidLine { line, .. }) => line,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(line("# ok\nos \"A\" ttl=64\nos \"B\" ttl=abc"), 3);
+        assert_eq!(line("\nlinux ttl=64"), 2);
+        assert_eq!(line("os Linux ttl=64"), 1);
+        assert_eq!(line("os \"Linux ttl=64"), 1);
+        assert_eq!(line("os \"\" ttl=64"), 1);
+        assert_eq!(line("os \"A\" ttl"), 1);
+        assert_eq!(line("os \"A\" window=mss*x"), 1);
+        assert_eq!(line("os \"A\" ws=15"), 1);
+        assert_eq!(line("os \"A\" df=yes"), 1);
+        assert_eq!(line("os \"A\" ipid=sequential"), 1);
+        assert_eq!(line("os \"A\" mtu=1500"), 1);
+        assert!(OsDatabase::load("/nonexistent/os.db").is_err());
+    }
+
+    ///////////////////////////////////////////
+    ///       Passive and Active Tests      ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_identify_hosts() {
+        let mut packets = vec![linux_syn_ack("10.0.0.2", 63)];
+        for id in [500, 501, 502] {
+            packets.push(windows_reply("10.0.0.3", "10.0.0.1", 126, id));
+        }
+        // Segments hors poignee de main ignores
+        packets.push(template("10.0.0.9", "10.0.0.1").with_tcp_flags(TcpFlags::ACK).build_packet().unwrap());
+        // SYN-ACK IPv6 ignore : pas d'en-tete IPv4 a lire
+        let ipv6 = windows_stack(template("10.0.0.4", "10.0.0.1"), 128, 0)
+            .build_ipv6_packet(parse_ipv6("2001:db8::4").unwrap(), parse_ipv6("2001:db8::1").unwrap(), Vec::new())
+            .unwrap();
+        assert!(ipv6.ipv4().is_none());
+        packets.push(ipv6);
+
+        let hosts = OsDatabase::builtin().identify_hosts(&packets);
+        assert_eq!(hosts.keys().copied().collect::<Vec<_>>(), vec![[10, 0, 0, 2], [10, 0, 0, 3]]);
+        assert_eq!(hosts[&[10, 0, 0, 2]].best().unwrap().name, "Linux 3.x-6.x");
+        let windows = &hosts[&[10, 0, 0, 3]];
+        assert_eq!(windows.fingerprint.as_ref().unwrap().ip_id, IpIdBehavior::Incremental);
+        assert_eq!(windows.best(), Some(&OsMatch { name: "Windows 10/11".into(), confidence: 100 }));
+    }
+
+    #[test]
+    fn test_build_and_classify_probes() {
+        let prober = OsProber::new(template("10.0.0.1", "10.0.0.2"));
+        assert_eq!(prober.probes(), 3);
+        assert_eq!(prober.clone().with_probes(1).probes(), 2);
+
+        let probe = prober.build_probe(443, 2).unwrap();
+        assert_eq!((tcp(&probe).src_port, tcp(&probe).dst_port), (42002, 443));
+        assert_eq!(tcp(&probe).sequence_number, prober.probe_sequence(2));
+        assert_eq!(options_layout(&tcp(&probe).typed_options().unwrap()), "mss,sok,ts,nop,ws");
+
+        let reply = |dst_port: u16, ack: u32, flags: TcpFlags| {
+            let mut reply = template("10.0.0.2", "10.0.0.1")
+                .with_tcp_flags(flags)
+                .with_ack(ack);
+            reply.src_port = 443;
+            reply.dst_port = dst_port;
+            reply.build_packet().unwrap()
+        };
+        let syn_ack = TcpFlags::SYN | TcpFlags::ACK;
+        assert_eq!(prober.classify_reply(&reply(42001, prober.probe_sequence(1) + 1, syn_ack), 443), Some(1));
+        // Mauvais acquittement, sonde inexistante, RST, autre port
+        assert_eq!(prober.classify_reply(&reply(42001, prober.probe_sequence(0) + 1, syn_ack), 443), None);
+        assert_eq!(prober.classify_reply(&reply(42003, prober.probe_sequence(3) + 1, syn_ack), 443), None);
+        assert_eq!(prober.classify_reply(&reply(42000, prober.probe_sequence(0) + 1, TcpFlags::RST | TcpFlags::ACK), 443), None);
+        assert_eq!(prober.classify_reply(&reply(42000, prober.probe_sequence(0) + 1, syn_ack), 80), None);
+    }
+
+    #[test]
+    fn test_probe_loopback_windows() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        let host = spawn_windows_host("198.51.100.21", 3);
+        let report = OsProber::new(template("198.51.100.1", "198.51.100.21"))
+            .with_timeout_ms(300)
+            .probe("lo", 3389)
+            .unwrap();
+        host.join().unwrap();
+
+        let fingerprint = report.fingerprint.as_ref().unwrap();
+        assert_eq!((fingerprint.initial_ttl, fingerprint.window), (128, 64240));
+        assert_eq!(fingerprint.ip_id, IpIdBehavior::Incremental);
+        assert_eq!(report.best(), Some(&OsMatch { name: "Windows 10/11".into(), confidence: 100 }));
+    }
+
+    #[test]
+    fn test_probe_loopback_filtered() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        let report = OsProber::new(template("198.51.100.1", "203.0.113.1"))
+            .with_retries(0)
+            .with_timeout_ms(200)
+            .probe("lo", 22)
+            .unwrap();
+        assert_eq!(report.fingerprint, None);
+        assert!(report.matches.is_empty());
+    }
+}
@@ -14,7 +14,7 @@ mod tests {
             packet_builder::PacketBuilder,
             l4_protocol::{L4Protocol, L4Data},
             ipv4::Ipv4Addr,
-            tcp::TcpHeader,
+            tcp::{TcpFlags, TcpHeader},
         },
     };
 
@@ -111,6 +111,18 @@ mod tests {
             protocol: L4Protocol::Tcp,
             ip_bitfield: 0x00,
             payload: Some(b"test".to_vec()),
+            vlan_tags: Vec::new(),
+            ttl: 64,
+            dscp: 0,
+            ecn: 0,
+            identification: 0,
+            ip_options: Vec::new(),
+            tcp_flags: TcpFlags::SYN,
+            sequence_number: 0,
+            ack_number: 0,
+            window: 65535,
+            urgent_pointer: 0,
+            tcp_options: Vec::new(),
         };
 
         let packet = factory.build_packet(&builder).unwrap();
@@ -121,9 +133,9 @@ mod tests {
         assert_eq!(packet.ethernet.ethertype, 0x0800);
 
         // Vérifier IPv4
-        assert_eq!(packet.ipv4.src_addr, [192, 168, 1, 1]);
-        assert_eq!(packet.ipv4.dst_addr, [192, 168, 1, 2]);
-        assert_eq!(packet.ipv4.protocol, 6); // TCP
+        assert_eq!(packet.ipv4().unwrap().src_addr, [192, 168, 1, 1]);
+        assert_eq!(packet.ipv4().unwrap().dst_addr, [192, 168, 1, 2]);
+        assert_eq!(packet.ipv4().unwrap().protocol, 6); // TCP
 
         // Vérifier TCP
         match &packet.l4_data {
@@ -154,12 +166,24 @@ mod tests {
             protocol: L4Protocol::Udp,
             ip_bitfield: 0x04,
             payload: Some(b"dns query".to_vec()),
+            vlan_tags: Vec::new(),
+            ttl: 64,
+            dscp: 0,
+            ecn: 0,
+            identification: 0,
+            ip_options: Vec::new(),
+            tcp_flags: TcpFlags::SYN,
+            sequence_number: 0,
+            ack_number: 0,
+            window: 65535,
+            urgent_pointer: 0,
+            tcp_options: Vec::new(),
         };
 
         let packet = factory.build_packet(&builder).unwrap();
 
-        assert_eq!(packet.ipv4.protocol, 17);
-        assert_eq!(packet.ipv4.flags, 0);
+        assert_eq!(packet.ipv4().unwrap().protocol, 17);
+        assert_eq!(packet.ipv4().unwrap().flags, 0);
 
         match &packet.l4_data {
             L4Data::Udp(udp) => {
@@ -193,6 +217,18 @@ mod tests {
             protocol: L4Protocol::Tcp,
             ip_bitfield: 0x00,
             payload: Some(b"hello".to_vec()),
+            vlan_tags: Vec::new(),
+            ttl: 64,
+            dscp: 0,
+            ecn: 0,
+            identification: 0,
+            ip_options: Vec::new(),
+            tcp_flags: TcpFlags::SYN,
+            sequence_number: 0,
+            ack_number: 0,
+            window: 65535,
+            urgent_pointer: 0,
+            tcp_options: Vec::new(),
         };
 
         let packet = factory.build_packet(&builder).unwrap();
@@ -227,6 +263,18 @@ mod tests {
             protocol: L4Protocol::Udp,
             ip_bitfield: 0x00,
             payload: Some(b"test".to_vec()),
+            vlan_tags: Vec::new(),
+            ttl: 64,
+            dscp: 0,
+            ecn: 0,
+            identification: 0,
+            ip_options: Vec::new(),
+            tcp_flags: TcpFlags::SYN,
+            sequence_number: 0,
+            ack_number: 0,
+            window: 65535,
+            urgent_pointer: 0,
+            tcp_options: Vec::new(),
         };
 
         let packet = factory.build_packet(&builder).unwrap();
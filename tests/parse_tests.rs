@@ -4,7 +4,9 @@ mod tests {
         parsing::my_parser::{
             parse_mac, 
             parse_ipv4,
-            parse_hex
+            parse_hex,
+            parse_hex_bytes,
+            parse_escaped_bytes
         },
         structs::ipv4::Ipv4Addr,
         errors::errors::ParseError
@@ -217,5 +219,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_hex_bytes_valid() {
+        assert_eq!(
+            parse_hex_bytes("AA bb 01").unwrap(),
+            vec![0xAA, 0xBB, 0x01]
+        );
+        assert_eq!(
+            parse_hex_bytes("deadBEEF").unwrap(),
+            vec![0xDE, 0xAD, 0xBE, 0xEF]
+        );
+        assert!(
+            parse_hex_bytes("").unwrap().is_empty()
+        );
+    }
 
+    #[test]
+    fn test_parse_hex_bytes_invalid() {
+        assert_eq!(
+            parse_hex_bytes("ABC"),
+            Err(ParseError::InvalidHex)
+        );
+        assert_eq!(
+            parse_hex_bytes("GG"),
+            Err(ParseError::InvalidHex)
+        );
+    }
+
+    #[test]
+    fn test_parse_escaped_bytes() {
+        assert_eq!(parse_escaped_bytes("GET / HTTP/1.0\\r\\n\\r\\n").unwrap(), b"GET / HTTP/1.0\r\n\r\n");
+        assert_eq!(parse_escaped_bytes("\\x00\\xFFa\\t\\\\\\0").unwrap(), vec![0x00, 0xFF, b'a', b'\t', b'\\', 0]);
+        for text in ["\\", "\\q", "\\x4", "\\xZZ"] {
+            assert!(parse_escaped_bytes(text).is_err(), "{}", text);
+        }
+    }
 }
@@ -0,0 +1,241 @@
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        formats::format_factory::FormatFactory,
+        structs::{
+            packet_builder::PacketBuilder,
+            network_packet::NetworkPacket,
+            formats::FormatType,
+            pcap::{PcapOptions, PcapByteOrder, PcapPrecision, LinkType},
+            pcapng::{PcapNgWriter, PcapNgReader},
+        },
+        utils::clock::FixedClock,
+    };
+
+    fn build_packet(protocol: &str) -> NetworkPacket {
+        PacketBuilder::from_cli_args(
+            Some("192.168.56.1"),
+            Some("192.168.56.2"),
+            Some([0x02, 0x42, 0xAC, 0x11, 0x00, 0x02]),
+            Some([0x02, 0x42, 0xAC, 0x11, 0x00, 0x03]),
+            Some(5555),
+            Some(22),
+            Some(protocol),
+            None,
+            Some(b"pcapng".to_vec()),
+        ).unwrap().build_packet().unwrap()
+    }
+
+    // Construit un bloc little-endian : type, longueur, corps, longueur
+    fn block(block_type: u32, body: &[u8]) -> Vec<u8> {
+        let total = (12 + body.len()) as u32;
+        let mut out = Vec::new();
+        out.extend_from_slice(&block_type.to_le_bytes());
+        out.extend_from_slice(&total.to_le_bytes());
+        out.extend_from_slice(body);
+        out.extend_from_slice(&total.to_le_bytes());
+        out
+    }
+
+    ///////////////////////////////////////////
+    ///      PCAPNG Writer Tests            ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_pcapng_section_header() {
+        let mut writer = PcapNgWriter::new();
+        writer.write_global_header().unwrap();
+        let data = writer.get_data();
+
+        assert_eq!(data[0..4], [0x0A, 0x0D, 0x0D, 0x0A]);
+        assert_eq!(data[8..12], [0x4D, 0x3C, 0x2B, 0x1A]);
+        assert_eq!(data.len() % 4, 0);
+    }
+
+    #[test]
+    fn test_pcapng_round_trip_with_comment() {
+        let packet = build_packet("tcp");
+        let mut writer = PcapNgWriter::new();
+        writer.write_global_header().unwrap();
+        writer.write_packet_with_comment(&packet, Some("--dst_ip=192.168.56.2 --dest_port=22")).unwrap();
+        writer.write_packet(&packet).unwrap();
+
+        let mut reader = PcapNgReader::new(writer.into_data());
+        let first = reader.read_next_record().unwrap().unwrap();
+        assert_eq!(first.comment.as_deref(), Some("--dst_ip=192.168.56.2 --dest_port=22"));
+        assert_eq!(first.packet, Some(packet.clone()));
+        assert_eq!(first.caplen as usize, packet.get_packet_size());
+
+        let second = reader.read_next_record().unwrap().unwrap();
+        assert_eq!(second.comment, None);
+        assert!(reader.read_next_record().unwrap().is_none());
+        assert_eq!(reader.interfaces.len(), 1);
+        assert_eq!(reader.interfaces[0].link_type, LinkType::Ethernet);
+    }
+
+    #[test]
+    fn test_pcapng_comment_too_long() {
+        let packet = build_packet("tcp");
+        let mut writer = PcapNgWriter::new();
+        writer.write_global_header().unwrap();
+        let header_len = writer.get_data().len();
+
+        // Longueur d'option sur 16 bits : le bloc n'est pas ecrit
+        let comment = "x".repeat(u16::MAX as usize + 1);
+        assert_eq!(
+            writer.write_packet_with_comment(&packet, Some(&comment)),
+            Err(ParseError::ValueTooLarge { value: u16::MAX as u64 + 1, size: 2 })
+        );
+        assert_eq!(writer.get_data().len(), header_len);
+        writer.write_packet_with_comment(&packet, Some(&comment[1..])).unwrap();
+    }
+
+    #[test]
+    fn test_pcapng_link_type_too_large() {
+        // LinkType de l'IDB sur 16 bits : rien n'est ecrit plutot qu'un type tronque
+        let mut writer = PcapNgWriter::with_options(PcapOptions {
+            link_type: LinkType::Other(0x1_0001),
+            ..PcapOptions::default()
+        });
+        assert_eq!(
+            writer.write_global_header(),
+            Err(ParseError::ValueTooLarge { value: 0x1_0001, size: 2 })
+        );
+        assert!(writer.get_data().is_empty());
+    }
+
+    #[test]
+    fn test_pcapng_write_short_record() {
+        let mut writer = PcapNgWriter::new();
+        writer.write_global_header().unwrap();
+        let header_len = writer.get_data().len();
+
+        let mut reader = FormatFactory::new().create_reader(FormatType::PcapNg, {
+            let mut source = PcapNgWriter::new();
+            source.write_global_header().unwrap();
+            source.write_packet(&build_packet("udp")).unwrap();
+            source.into_data()
+        });
+        let mut record = reader.read_next_record().unwrap().unwrap();
+        record.data.truncate(13);
+        assert!(matches!(writer.write_record(&record), Err(ParseError::TruncatedPacket { .. })));
+        assert_eq!(writer.get_data().len(), header_len);
+    }
+
+    #[test]
+    fn test_pcapng_big_endian_nanosecond_raw() {
+        let packet = build_packet("udp");
+        let mut writer = PcapNgWriter::with_options(PcapOptions {
+            byte_order: PcapByteOrder::BigEndian,
+            precision: PcapPrecision::Nano,
+            snaplen: 1500,
+            link_type: LinkType::Raw,
+        });
+        writer.write_global_header().unwrap();
+        writer.write_packet(&packet).unwrap();
+
+        let mut reader = PcapNgReader::new(writer.into_data());
+        let record = reader.read_next_record().unwrap().unwrap();
+        assert_eq!(reader.byte_order, PcapByteOrder::BigEndian);
+        assert_eq!(reader.interfaces[0].ts_resolution, 9);
+        assert_eq!(reader.interfaces[0].snaplen, 1500);
+        let decoded = record.packet.unwrap();
+        assert_eq!(decoded.ip, packet.ip);
+        assert_eq!(decoded.l4_data, packet.l4_data);
+    }
+
+    #[test]
+    fn test_pcapng_timestamp_from_clock() {
+        let mut writer = PcapNgWriter::new()
+            .with_clock(Box::new(FixedClock { ns: 1_700_000_000_987_654_321 }));
+        writer.write_global_header().unwrap();
+        writer.write_packet(&build_packet("udp")).unwrap();
+
+        let mut reader = PcapNgReader::new(writer.into_data());
+        let record = reader.read_next_record().unwrap().unwrap();
+        assert_eq!(record.ts_sec, 1_700_000_000);
+        assert_eq!(record.ts_usec, 987_654);
+    }
+
+    ///////////////////////////////////////////
+    ///      PCAPNG Reader Tests            ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_pcapng_reader_timestamp_and_unknown_blocks() {
+        let mut shb = Vec::new();
+        shb.extend_from_slice(&0x1A2B3C4Du32.to_le_bytes());
+        shb.extend_from_slice(&[0x01, 0x00, 0x00, 0x00]);
+        shb.extend_from_slice(&[0xFF; 8]);
+
+        let mut idb = vec![0x65, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00];
+        idb.extend_from_slice(&[0x09, 0x00, 0x01, 0x00, 0x03, 0x00, 0x00, 0x00]);
+        idb.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]);
+
+        // 12_345 ms avec une resolution de 10^-3
+        let mut epb = Vec::new();
+        epb.extend_from_slice(&0u32.to_le_bytes());
+        epb.extend_from_slice(&0u32.to_le_bytes());
+        epb.extend_from_slice(&12_345u32.to_le_bytes());
+        epb.extend_from_slice(&2u32.to_le_bytes());
+        epb.extend_from_slice(&2u32.to_le_bytes());
+        epb.extend_from_slice(&[0x45, 0x00, 0x00, 0x00]);
+
+        let mut data = block(0x0A0D0D0A, &shb);
+        data.extend(block(0x00000001, &idb));
+        data.extend(block(0x00000005, &[0u8; 8]));
+        data.extend(block(0x00000006, &epb));
+
+        let factory = FormatFactory::new();
+        let mut reader = factory.create_reader(FormatType::PcapNg, data);
+        let record = reader.read_next_record().unwrap().unwrap();
+
+        assert_eq!(record.ts_sec, 12);
+        assert_eq!(record.ts_usec, 345_000);
+        assert_eq!(record.ts_nsec, 345_000_000);
+        assert_eq!(record.data, vec![0x45, 0x00]);
+        assert!(record.packet.is_none());
+        assert!(!reader.has_more_packets());
+    }
+
+    #[test]
+    fn test_pcapng_reader_unknown_interface() {
+        let mut shb = Vec::new();
+        shb.extend_from_slice(&0x1A2B3C4Du32.to_le_bytes());
+        shb.extend_from_slice(&[0x01, 0x00, 0x00, 0x00]);
+        shb.extend_from_slice(&[0xFF; 8]);
+        let mut data = block(0x0A0D0D0A, &shb);
+        data.extend(block(0x00000006, &[0u8; 20]));
+
+        let mut reader = PcapNgReader::new(data);
+        assert!(reader.read_next_record().is_err());
+    }
+
+    #[test]
+    fn test_pcapng_reader_invalid_block_length() {
+        let mut shb = Vec::new();
+        shb.extend_from_slice(&0x1A2B3C4Du32.to_le_bytes());
+        shb.extend_from_slice(&[0x01, 0x00, 0x00, 0x00]);
+        shb.extend_from_slice(&[0xFF; 8]);
+        let mut data = block(0x0A0D0D0A, &shb);
+        data.extend_from_slice(&[0x06, 0x00, 0x00, 0x00, 0x0D, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+        let mut reader = PcapNgReader::new(data);
+        assert!(reader.read_next_record().is_err());
+    }
+
+    #[test]
+    fn test_format_factory_pcapng_multiple_packets() {
+        let packets = vec![build_packet("tcp"), build_packet("udp")];
+        let factory = FormatFactory::new();
+        let data = factory.write_packets(&packets, FormatType::PcapNg).unwrap();
+
+        let mut reader = factory.create_reader(FormatType::PcapNg, data);
+        let mut decoded = Vec::new();
+        while let Some(record) = reader.read_next_record().unwrap() {
+            decoded.push(record.packet.unwrap());
+        }
+        assert_eq!(decoded, packets);
+    }
+}
@@ -5,7 +5,7 @@ mod tests {
         structs::{
             network_packet::NetworkPacket,
             ethernet::EthernetHeader,
-            ip::Ipv4Header,
+            ip::{IpHeader, Ipv4Header},
             tcp::TcpHeader,
             l4_protocol::L4Data,
         },
@@ -23,9 +23,10 @@ mod tests {
             ethernet: EthernetHeader {
                 src_mac: [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF],
                 dst_mac: [0x11, 0x22, 0x33, 0x44, 0x55, 0x66],
+                vlan_tags: Vec::new(),
                 ethertype: 0x0800,
             },
-            ipv4: Ipv4Header {
+            ip: IpHeader::V4(Ipv4Header {
                 version: 4,
                 ihl: 5,
                 dscp: 0,
@@ -39,7 +40,7 @@ mod tests {
                 src_addr: [192, 168, 1, 1],
                 dst_addr: [192, 168, 1, 2],
                 options: None,
-            },
+            }),
             l4_data: L4Data::Tcp(TcpHeader {
                 src_port: 8080,
                 dst_port: 443,
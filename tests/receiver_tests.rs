@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        formats::format_factory::FormatFactory,
+        sender::raw_socket::get_interface_index,
+        structs::{
+            formats::FormatType,
+            l4_protocol::L4Data,
+            socket::{CapturedFrame, RawSocketReceiver, RawSocketSender},
+        },
+    };
+    use std::net::UdpSocket;
+    use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+    // Ethertype experimental (IEEE 802 local) : aucun trafic parasite sur lo
+    const TEST_ETHERTYPE: u16 = 0x88B5;
+
+    // Ouvre un receiver sur lo, None si le test tourne sans CAP_NET_RAW
+    fn open_loopback(ethertype: u16) -> Option<RawSocketReceiver> {
+        let mut receiver = match RawSocketReceiver::with_protocol(ethertype) {
+            Ok(receiver) => receiver,
+            Err(_) => {
+                eprintln!("raw socket indisponible (root requis), test ignore");
+                return None;
+            }
+        };
+        receiver.bind("lo").ok()?;
+        Some(receiver)
+    }
+
+    // Attend la trame UDP entrante vers dst_port
+    fn wait_udp(receiver: &RawSocketReceiver, dst_port: u16) -> Option<CapturedFrame> {
+        let deadline = Instant::now() + std::time::Duration::from_secs(2);
+        while Instant::now() < deadline {
+            if let Some(frame) = receiver.recv_frame().unwrap()
+                && let Some(packet) = &frame.record.packet
+                && let L4Data::Udp(udp) = &packet.l4_data
+                && udp.dst_port == dst_port
+                && !frame.is_outgoing()
+            {
+                return Some(frame);
+            }
+        }
+        None
+    }
+
+    fn now_secs() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    ///////////////////////////////////////////
+    ///      Raw Socket Receiver Tests      ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_receiver_read_timeout() {
+        let Some(receiver) = open_loopback(TEST_ETHERTYPE) else { return };
+        receiver.set_read_timeout(Some(100)).unwrap();
+
+        let start = Instant::now();
+        assert!(receiver.recv_frame().unwrap().is_none());
+        assert!(start.elapsed().as_millis() >= 90);
+    }
+
+    #[test]
+    fn test_receiver_gets_raw_frame_with_kernel_timestamp() {
+        let Some(receiver) = open_loopback(TEST_ETHERTYPE) else { return };
+        receiver.set_read_timeout(Some(1000)).unwrap();
+
+        let mut frame = vec![0u8; 12];
+        frame.extend_from_slice(&TEST_ETHERTYPE.to_be_bytes());
+        frame.extend_from_slice(b"rsns receiver test");
+        let sender = RawSocketSender::new().unwrap();
+        sender.send(get_interface_index("lo").unwrap(), [0; 6], &frame).unwrap();
+
+        let captured = receiver.recv_frame().unwrap().expect("trame non recue");
+        assert_eq!(captured.record.data, frame);
+        assert_eq!(captured.record.caplen as usize, frame.len());
+        assert_eq!(captured.record.orig_len as usize, frame.len());
+        assert_eq!(Some(captured.if_index), receiver.if_index);
+        // Trame non IPv4 : pas de paquet decode
+        assert!(captured.record.packet.is_none());
+        assert!(now_secs().abs_diff(captured.record.ts_sec as u64) <= 5);
+        assert_eq!(captured.record.ts_usec, captured.record.ts_nsec / 1000);
+    }
+
+    #[test]
+    fn test_receiver_truncates_to_buffer_size() {
+        let Some(mut receiver) = open_loopback(TEST_ETHERTYPE) else { return };
+        receiver.set_read_timeout(Some(1000)).unwrap();
+        receiver.buffer_size = 20;
+
+        let mut frame = vec![0u8; 12];
+        frame.extend_from_slice(&TEST_ETHERTYPE.to_be_bytes());
+        frame.extend_from_slice(&[0xAB; 50]);
+        let sender = RawSocketSender::new().unwrap();
+        sender.send(get_interface_index("lo").unwrap(), [0; 6], &frame).unwrap();
+
+        let captured = receiver.recv_frame().unwrap().expect("trame non recue");
+        assert_eq!(captured.record.data, frame[..20].to_vec());
+        assert_eq!(captured.record.caplen, 20);
+        assert_eq!(captured.record.orig_len as usize, frame.len());
+    }
+
+    #[test]
+    fn test_receiver_decodes_loopback_udp() {
+        let Some(receiver) = open_loopback(0x0800) else { return };
+        receiver.set_read_timeout(Some(200)).unwrap();
+
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let port = server.local_addr().unwrap().port();
+        let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+        client.send_to(b"hello capture", ("127.0.0.1", port)).unwrap();
+
+        let frame = wait_udp(&receiver, port).expect("datagramme non capture");
+        let packet = frame.record.packet.unwrap();
+        assert_eq!(packet.ipv4().unwrap().src_addr, [127, 0, 0, 1]);
+        assert_eq!(packet.ipv4().unwrap().dst_addr, [127, 0, 0, 1]);
+        match packet.l4_data {
+            L4Data::Udp(udp) => {
+                assert_eq!(udp.src_port, client.local_addr().unwrap().port());
+                assert_eq!(udp.payload, Some(b"hello capture".to_vec()));
+            }
+            _ => panic!("UDP attendu"),
+        }
+    }
+
+    #[test]
+    fn test_capture_into_pcap_keeps_timestamps() {
+        let Some(receiver) = open_loopback(0x0800) else { return };
+
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let port = server.local_addr().unwrap().port();
+        let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+        for i in 0..3u8 {
+            client.send_to(&[i; 4], ("127.0.0.1", port)).unwrap();
+        }
+
+        let is_probe = |frame: &CapturedFrame| {
+            !frame.is_outgoing() && matches!(
+                frame.record.packet.as_ref().map(|p| &p.l4_data),
+                Some(L4Data::Udp(udp)) if udp.dst_port == port
+            )
+        };
+        let mut stream = FormatFactory::new()
+            .create_stream_writer(FormatType::Pcap, Vec::new())
+            .unwrap();
+        let written = receiver.capture_into(&mut stream, 3, 2000, is_probe).unwrap();
+        assert_eq!(written, 3);
+        let data = stream.finish().unwrap();
+
+        let mut reader = FormatFactory::new().create_reader(FormatType::Pcap, data);
+        for i in 0..3u8 {
+            let record = reader.read_next_record().unwrap().unwrap();
+            assert!(now_secs().abs_diff(record.ts_sec as u64) <= 5);
+            match record.packet.unwrap().l4_data {
+                L4Data::Udp(udp) => assert_eq!(udp.payload, Some(vec![i; 4])),
+                _ => panic!("UDP attendu"),
+            }
+        }
+        assert!(reader.read_next_record().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_capture_into_json() {
+        let Some(receiver) = open_loopback(0x0800) else { return };
+
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let port = server.local_addr().unwrap().port();
+        let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+        client.send_to(b"json", ("127.0.0.1", port)).unwrap();
+
+        let mut stream = FormatFactory::new()
+            .create_stream_writer(FormatType::Json, Vec::new())
+            .unwrap();
+        let written = receiver.capture_into(&mut stream, 1, 2000, |frame| {
+            !frame.is_outgoing() && matches!(
+                frame.record.packet.as_ref().map(|p| &p.l4_data),
+                Some(L4Data::Udp(udp)) if udp.dst_port == port
+            )
+        }).unwrap();
+        assert_eq!(written, 1);
+
+        let data = stream.finish().unwrap();
+        let mut reader = FormatFactory::new().create_reader(FormatType::Json, data);
+        let record = reader.read_next_record().unwrap().unwrap();
+        assert!(now_secs().abs_diff(record.ts_sec as u64) <= 5);
+    }
+}
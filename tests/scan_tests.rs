@@ -0,0 +1,236 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        parsing::my_parser::parse_ports,
+        structs::{
+            l4_protocol::L4Data,
+            network_packet::NetworkPacket,
+            packet_builder::PacketBuilder,
+            scan::{PortState, SynScanner},
+            tcp::TcpFlags,
+        },
+    };
+    use crate::common::{raw_socket_unavailable, reply_to, spawn_responder, tcp, tcp_checksum_ok};
+    use std::{thread::JoinHandle, time::Duration};
+
+    fn template(src: &str, dst: &str) -> PacketBuilder {
+        crate::common::template(src, dst, "tcp", 45000, 0)
+    }
+
+    // Fabrique une reponse de la cible a la sonde d'un port
+    fn reply(scanner: &SynScanner, port: u16, flags: u16, ack: u32) -> NetworkPacket {
+        let mut builder = scanner.template.clone();
+        core::mem::swap(&mut builder.src_ip, &mut builder.dst_ip);
+        builder.src_port = port;
+        builder.dst_port = scanner.template.src_port;
+        let mut packet = builder.build_packet().unwrap();
+        if let L4Data::Tcp(tcp) = &mut packet.l4_data {
+            tcp.flags = flags;
+            tcp.ack_nowledgment_number = ack;
+        }
+        packet
+    }
+
+    // Cible simulee : repond SYN-ACK pour open_port, RST pour les autres ports
+    fn spawn_target(target: &str, open_port: u16, probes: usize) -> JoinHandle<()> {
+        let mut answered = 0;
+        spawn_responder(target, Duration::from_secs(3), move |probe, replies| {
+            let L4Data::Tcp(syn) = &probe.l4_data else { return true };
+            if syn.tcp_flags() != TcpFlags::SYN {
+                return true;
+            }
+            let flags = if syn.dst_port == open_port { TcpFlags::SYN | TcpFlags::ACK } else { TcpFlags::RST | TcpFlags::ACK };
+            let packet = reply_to(probe)
+                .with_tcp_flags(flags)
+                .with_ack(syn.sequence_number.wrapping_add(1))
+                .build_packet()
+                .unwrap();
+            replies.push(packet.assemble_packet().unwrap());
+            answered += 1;
+            answered < probes
+        })
+    }
+
+    ///////////////////////////////////////////
+    ///          Port Parsing Tests         ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_parse_ports_list_and_ranges() {
+        assert_eq!(parse_ports("80").unwrap(), vec![80]);
+        assert_eq!(parse_ports("443,22, 20-23").unwrap(), vec![20, 21, 22, 23, 443]);
+    }
+
+    #[test]
+    fn test_parse_ports_invalid() {
+        assert!(parse_ports("0").is_err());
+        assert!(parse_ports("100-90").is_err());
+        assert!(parse_ports("70000").is_err());
+        assert!(parse_ports("http").is_err());
+        assert!(parse_ports("").is_err());
+    }
+
+    ///////////////////////////////////////////
+    ///         SYN Scanner Tests           ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_probe_is_syn_with_valid_checksum() {
+        let scanner = SynScanner::new(template("10.0.0.1", "10.0.0.2"));
+        let probe = scanner.build_probe(8443).unwrap();
+        assert_eq!(tcp(&probe).flags, 0x02);
+        assert_eq!(tcp(&probe).dst_port, 8443);
+        assert_eq!(tcp(&probe).sequence_number, scanner.probe_sequence(8443));
+        assert_ne!(scanner.probe_sequence(8443), scanner.probe_sequence(8444));
+        assert!(tcp_checksum_ok(&probe));
+    }
+
+    #[test]
+    fn test_classify_syn_ack_and_rst() {
+        let scanner = SynScanner::new(template("10.0.0.1", "10.0.0.2"));
+        let ack = scanner.probe_sequence(22).wrapping_add(1);
+        assert_eq!(scanner.classify_reply(&reply(&scanner, 22, 0x12, ack)), Some((22, PortState::Open)));
+        assert_eq!(scanner.classify_reply(&reply(&scanner, 22, 0x14, ack)), Some((22, PortState::Closed)));
+    }
+
+    #[test]
+    fn test_classify_rejects_unrelated_packets() {
+        let scanner = SynScanner::new(template("10.0.0.1", "10.0.0.2"));
+        let ack = scanner.probe_sequence(22).wrapping_add(1);
+        // Mauvais acquittement
+        assert_eq!(scanner.classify_reply(&reply(&scanner, 22, 0x12, ack + 1)), None);
+        // Acquittement d'un autre port
+        assert_eq!(scanner.classify_reply(&reply(&scanner, 23, 0x12, ack)), None);
+        // ACK seul
+        assert_eq!(scanner.classify_reply(&reply(&scanner, 22, 0x10, ack)), None);
+        // Notre propre sonde
+        assert_eq!(scanner.classify_reply(&scanner.build_probe(22).unwrap()), None);
+    }
+
+    #[test]
+    fn test_flag_probes_and_unanswered_state() {
+        let xmas = SynScanner::new(template("10.0.0.1", "10.0.0.2"))
+            .with_tcp_flags(TcpFlags::FIN | TcpFlags::PSH | TcpFlags::URG);
+        assert_eq!(tcp(&xmas.build_probe(80).unwrap()).flags, 0x29);
+        assert!(tcp_checksum_ok(&xmas.build_probe(80).unwrap()));
+        assert_eq!(xmas.unanswered_state(), PortState::OpenFiltered);
+
+        let null = SynScanner::new(template("10.0.0.1", "10.0.0.2")).with_tcp_flags(TcpFlags::empty());
+        assert_eq!(tcp(&null.build_probe(80).unwrap()).flags, 0);
+        assert_eq!(null.unanswered_state(), PortState::OpenFiltered);
+
+        // La sonde ACK porte la sequence derivee en acquittement
+        let ack = SynScanner::new(template("10.0.0.1", "10.0.0.2")).with_tcp_flags(TcpFlags::ACK);
+        let probe = ack.build_probe(80).unwrap();
+        assert_eq!(tcp(&probe).flags, 0x10);
+        assert_eq!(tcp(&probe).ack_nowledgment_number, ack.probe_sequence(80));
+        assert_eq!(ack.unanswered_state(), PortState::Filtered);
+        assert_eq!(SynScanner::new(template("10.0.0.1", "10.0.0.2")).unanswered_state(), PortState::Filtered);
+    }
+
+    #[test]
+    fn test_classify_fin_null_and_ack_replies() {
+        // FIN : le RST acquitte la sequence + 1 ; NULL : la sequence
+        let fin = SynScanner::new(template("10.0.0.1", "10.0.0.2")).with_tcp_flags(TcpFlags::FIN);
+        let sequence = fin.probe_sequence(22);
+        assert_eq!(fin.classify_reply(&reply(&fin, 22, 0x14, sequence.wrapping_add(1))), Some((22, PortState::Closed)));
+        assert_eq!(fin.classify_reply(&reply(&fin, 22, 0x14, sequence)), None);
+        let null = SynScanner::new(template("10.0.0.1", "10.0.0.2")).with_tcp_flags(TcpFlags::empty());
+        assert_eq!(null.classify_reply(&reply(&null, 22, 0x14, null.probe_sequence(22))), Some((22, PortState::Closed)));
+
+        // ACK : le RST reprend l'acquittement de la sonde comme sequence
+        let ack = SynScanner::new(template("10.0.0.1", "10.0.0.2")).with_tcp_flags(TcpFlags::ACK);
+        let mut rst = reply(&ack, 22, 0x04, 0);
+        if let L4Data::Tcp(tcp) = &mut rst.l4_data {
+            tcp.sequence_number = ack.probe_sequence(22);
+        }
+        assert_eq!(ack.classify_reply(&rst), Some((22, PortState::Unfiltered)));
+        if let L4Data::Tcp(tcp) = &mut rst.l4_data {
+            tcp.sequence_number = ack.probe_sequence(22).wrapping_add(1);
+        }
+        assert_eq!(ack.classify_reply(&rst), None);
+    }
+
+    #[test]
+    fn test_fin_scan_loopback_closed_and_open_filtered() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        // Cible simulee : RST pour le port 444, le port 443 (ouvert) ignore le FIN
+        let responder = spawn_responder("198.51.100.8", Duration::from_secs(3), |probe, replies| {
+            let L4Data::Tcp(fin) = &probe.l4_data else { return true };
+            if fin.tcp_flags() != TcpFlags::FIN || fin.dst_port != 444 {
+                return true;
+            }
+            let packet = reply_to(probe)
+                .with_tcp_flags(TcpFlags::RST | TcpFlags::ACK)
+                .with_ack(fin.sequence_number.wrapping_add(1))
+                .build_packet()
+                .unwrap();
+            replies.push(packet.assemble_packet().unwrap());
+            false
+        });
+
+        let scanner = SynScanner::new(template("198.51.100.1", "198.51.100.8"))
+            .with_tcp_flags(TcpFlags::FIN)
+            .with_timeout_ms(300)
+            .with_retries(0);
+        let results = scanner.scan("lo", &[443, 444]).unwrap();
+        responder.join().unwrap();
+
+        assert_eq!(results[0].state, PortState::OpenFiltered);
+        assert_eq!(results[1].state, PortState::Closed);
+    }
+
+    #[test]
+    fn test_scan_loopback_open_closed() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        let responder = spawn_target("198.51.100.7", 443, 2);
+
+        let scanner = SynScanner::new(template("198.51.100.1", "198.51.100.7"))
+            .with_timeout_ms(500)
+            .with_retries(1);
+        let results = scanner.scan("lo", &[443, 444]).unwrap();
+        responder.join().unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].port, 443);
+        assert_eq!(results[0].state, PortState::Open);
+        assert_eq!(results[1].port, 444);
+        assert_eq!(results[1].state, PortState::Closed);
+        assert_eq!(results[0].attempts, 1);
+    }
+
+    #[test]
+    fn test_scan_unanswered_is_filtered_after_retries() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        // Adresse non locale (TEST-NET-3) injectee sur lo : aucune reponse
+        let scanner = SynScanner::new(template("127.0.0.1", "203.0.113.1"))
+            .with_timeout_ms(100)
+            .with_retries(2);
+        let results = scanner.scan("lo", &[9]).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].state, PortState::Filtered);
+        assert_eq!(results[0].attempts, 3);
+    }
+
+    #[test]
+    fn test_scan_max_retries_counts_every_wave() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        // 256 vagues : le compteur ne doit pas deborder
+        let scanner = SynScanner::new(template("127.0.0.1", "203.0.113.1"))
+            .with_timeout_ms(0)
+            .with_retries(u8::MAX);
+        let results = scanner.scan("lo", &[9]).unwrap();
+        assert_eq!(results[0].state, PortState::Filtered);
+        assert_eq!(results[0].attempts, 256);
+    }
+}
@@ -0,0 +1,343 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        structs::{
+            l4_protocol::{L4Data, L4Protocol},
+            packet_builder::PacketBuilder,
+            scan::PortState,
+            service::{ServiceDatabase, ServiceDetector, ServiceMatch, ServiceReport, ServiceSignature},
+            tcp::TcpFlags,
+        },
+    };
+    use crate::common::{raw_socket_unavailable, reply_to, spawn_responder, tcp};
+    use std::{thread::JoinHandle, time::Duration};
+
+    const SERVER_ISS: u32 = 9000;
+
+    fn template(src: &str, dst: &str, protocol: &str) -> PacketBuilder {
+        crate::common::template(src, dst, protocol, 42000, 0)
+    }
+
+    fn service(service: &str, product: Option<&str>, version: Option<&str>, info: Option<&str>) -> ServiceMatch {
+        ServiceMatch {
+            service: service.into(),
+            product: product.map(Into::into),
+            version: version.map(Into::into),
+            info: info.map(Into::into),
+        }
+    }
+
+    // Reponse CH TXT a la requete version.bind de la base integree
+    fn version_bind_reply(version: &[u8]) -> Vec<u8> {
+        let mut reply = vec![0x13, 0x37, 0x85, 0x80, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00];
+        reply.extend_from_slice(b"\x07version\x04bind\x00\x00\x10\x00\x03");
+        reply.extend_from_slice(&[0xC0, 0x0C, 0x00, 0x10, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00]);
+        reply.extend_from_slice(&(version.len() as u16 + 1).to_be_bytes());
+        reply.push(version.len() as u8);
+        reply.extend_from_slice(version);
+        reply
+    }
+
+    // Serveur TCP simule : `greeting` envoye des la poignee de main, `response` a
+    // reception d'une requete, suivis d'un FIN ; s'arrete apres `connections` RST
+    fn spawn_tcp_server(
+        target: &str,
+        greeting: Option<&'static [u8]>,
+        response: Option<&'static [u8]>,
+        connections: usize,
+    ) -> JoinHandle<()> {
+        let mut resets = 0;
+        spawn_responder(target, Duration::from_secs(5), move |segment, replies| {
+            let L4Data::Tcp(client) = &segment.l4_data else { return true };
+            let mut send = |flags: TcpFlags, seq: u32, ack: u32, payload: &[u8]| {
+                let mut builder = reply_to(segment).with_tcp_flags(flags).with_sequence(seq).with_ack(ack);
+                builder.payload = (!payload.is_empty()).then(|| payload.to_vec());
+                replies.push(builder.build_packet().unwrap().assemble_packet().unwrap());
+            };
+            // Donnees puis FIN, la connexion se termine par le RST du client
+            let mut answer = |data: &[u8], ack: u32| {
+                send(TcpFlags::PSH | TcpFlags::ACK, SERVER_ISS + 1, ack, data);
+                send(TcpFlags::FIN | TcpFlags::ACK, SERVER_ISS + 1 + data.len() as u32, ack, b"");
+            };
+            let flags = client.tcp_flags();
+            let payload = client.payload.as_deref().unwrap_or(&[]);
+            let next = client.sequence_number.wrapping_add(payload.len() as u32);
+
+            if flags == TcpFlags::SYN {
+                send(TcpFlags::SYN | TcpFlags::ACK, SERVER_ISS, next.wrapping_add(1), b"");
+            } else if flags.contains(TcpFlags::RST) {
+                resets += 1;
+            } else if !payload.is_empty() {
+                if let Some(response) = response {
+                    answer(response, next);
+                }
+            } else if flags == TcpFlags::ACK && client.ack_nowledgment_number == SERVER_ISS + 1
+                && let Some(greeting) = greeting
+            {
+                answer(greeting, next);
+            }
+            resets < connections
+        })
+    }
+
+    // Serveur DNS simule : repond a la requete version.bind
+    fn spawn_dns_server(target: &str, version: &'static [u8]) -> JoinHandle<()> {
+        spawn_responder(target, Duration::from_secs(3), move |datagram, replies| {
+            let L4Data::Udp(query) = &datagram.l4_data else { return true };
+            if query.dst_port != 53 {
+                return true;
+            }
+            let mut reply = reply_to(datagram);
+            reply.payload = Some(version_bind_reply(version));
+            replies.push(reply.build_packet().unwrap().assemble_packet().unwrap());
+            false
+        })
+    }
+
+    ///////////////////////////////////////////
+    ///        Service Database Tests       ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_builtin_probes() {
+        let database = ServiceDatabase::builtin();
+        let names = |protocol, port| -> Vec<String> {
+            database.probes_for(protocol, port).iter().map(|probe| probe.name.clone()).collect()
+        };
+        assert_eq!(names(L4Protocol::Tcp, 22), vec!["NULL"]);
+        assert_eq!(names(L4Protocol::Tcp, 8080), vec!["NULL", "GetRequest"]);
+        assert_eq!(names(L4Protocol::Tcp, 443), vec!["NULL", "TLSClientHello"]);
+        assert_eq!(names(L4Protocol::Udp, 53), vec!["DNSVersionBindReq", "UdpEmpty"]);
+
+        assert_eq!(database.probe("GetRequest").unwrap().payload, b"GET / HTTP/1.0\r\n\r\n");
+        // ClientHello : record de 5 octets + handshake de 0x33 octets
+        let hello = &database.probe("TLSClientHello").unwrap().payload;
+        assert_eq!(hello.len(), 5 + 0x33);
+        assert_eq!(hello[..6], [0x16, 0x03, 0x01, 0x00, 0x33, 0x01]);
+    }
+
+    #[test]
+    fn test_match_banners() {
+        let database = ServiceDatabase::builtin();
+        assert_eq!(
+            database.match_response("NULL", b"SSH-2.0-OpenSSH_9.6p1 Ubuntu-3ubuntu13.5\r\n"),
+            Some(service("ssh", Some("OpenSSH"), Some("9.6p1"), Some("protocol 2.0")))
+        );
+        assert_eq!(
+            database.match_response("NULL", b"SSH-2.0-libssh_0.10.6\r\n"),
+            Some(service("ssh", Some("libssh_0.10.6"), None, Some("protocol 2.0")))
+        );
+        assert_eq!(
+            database.match_response("NULL", b"220 mail.example.org ESMTP Postfix (Debian/GNU)\r\n"),
+            Some(service("smtp", Some("Postfix smtpd"), None, Some("hostname mail.example.org")))
+        );
+        // Drapeau i : insensible a la casse
+        assert_eq!(
+            database.match_response("NULL", b"220 mx.local esmtp ready\r\n"),
+            Some(service("smtp", None, None, Some("hostname mx.local")))
+        );
+        assert_eq!(database.match_response("NULL", b"* OK [CAPABILITY IMAP4rev1] ready\r\n").unwrap().service, "imap");
+        // Paquet d'accueil MySQL : longueur sur 3 octets, numero 0, protocole 10
+        assert_eq!(
+            database.match_response("NULL", b"\x4a\x00\x00\x00\x0a8.0.36-0ubuntu0.22.04.1\x00"),
+            Some(service("mysql", Some("MySQL"), Some("8.0.36-0ubuntu0.22.04.1"), None))
+        );
+        assert_eq!(database.match_response("NULL", b"\x00\x01garbage"), None);
+    }
+
+    #[test]
+    fn test_match_http_tls_dns() {
+        let database = ServiceDatabase::builtin();
+        let apache = b"HTTP/1.1 200 OK\r\nDate: Mon, 01 Jan 2024 00:00:00 GMT\r\nServer: Apache/2.4.58 (Ubuntu)\r\n\r\n";
+        assert_eq!(
+            database.match_response("GetRequest", apache),
+            Some(service("http", Some("Apache httpd"), Some("2.4.58"), None))
+        );
+        assert_eq!(
+            database.match_response("GetRequest", b"HTTP/1.0 404 Not Found\r\nServer: lighttpd/1.4.73\r\n\r\n"),
+            Some(service("http", Some("lighttpd/1.4.73"), None, None))
+        );
+        assert_eq!(database.match_response("GetRequest", b"HTTP/1.1 400 Bad Request\r\n\r\n"), Some(service("http", None, None, None)));
+        // Les signatures sont propres a leur sonde
+        assert_eq!(database.match_response("NULL", apache), None);
+
+        let server_hello = [0x16, 0x03, 0x03, 0x00, 0x31, 0x02, 0x00, 0x00, 0x2D, 0x03, 0x03];
+        assert_eq!(database.match_response("TLSClientHello", &server_hello), Some(service("ssl", None, None, Some("TLS handshake"))));
+        let alert = [0x15, 0x03, 0x03, 0x00, 0x02, 0x02, 0x28];
+        assert_eq!(database.match_response("TLSClientHello", &alert), Some(service("ssl", None, None, Some("TLS alert"))));
+
+        assert_eq!(
+            database.match_response("DNSVersionBindReq", &version_bind_reply(b"9.18.24")),
+            Some(service("domain", None, Some("9.18.24"), None))
+        );
+        // Version refusee (REFUSED, sans reponse) : service seul
+        assert_eq!(
+            database.match_response("DNSVersionBindReq", &[0x13, 0x37, 0x81, 0x05, 0, 1, 0, 0, 0, 0, 0, 0]),
+            Some(service("domain", None, None, None))
+        );
+    }
+
+    #[test]
+    fn test_user_database_merge() {
+        let user = ServiceDatabase::parse(concat!(
+            "# base utilisateur\n",
+            "\n",
+            "probe GetRequest tcp 80,9090 \"GET /status HTTP/1.0\\r\\n\\r\\n\"\n",
+            "probe Redis tcp 6379 \"PING\\r\\n\"\n",
+            "match redis Redis m/^\\+PONG/ p/Redis key-value store/\n",
+            "match http GetRequest m|^HTTP/1\\.1 200.*X-App: (\\w+) (\\S+)|s p/$1/ v/$2/ i/$3 $$/\n",
+            "match echo * m/^ECHO/\n",
+        )).unwrap();
+        assert_eq!(user.probes.len(), 2);
+        assert_eq!(user.signatures.len(), 3);
+
+        let mut database = ServiceDatabase::builtin();
+        let builtin_probes = database.probes.len();
+        database.merge(user);
+        // GetRequest remplacee, Redis ajoutee
+        assert_eq!(database.probes.len(), builtin_probes + 1);
+        assert_eq!(database.probe("GetRequest").unwrap().payload, b"GET /status HTTP/1.0\r\n\r\n");
+        assert_eq!(database.probes_for(L4Protocol::Tcp, 6379).len(), 2);
+
+        assert_eq!(database.match_response("Redis", b"+PONG\r\n"), Some(service("redis", Some("Redis key-value store"), None, None)));
+        // Signature utilisateur prioritaire ; groupe absent ($3) vide, `$` litteral conserve
+        assert_eq!(
+            database.match_response("GetRequest", b"HTTP/1.1 200 OK\r\nServer: nginx/1.24.0\r\nX-App: grafana 10.2.3\r\n\r\n"),
+            Some(service("http", Some("grafana"), Some("10.2.3"), Some("$$")))
+        );
+        // Sonde `*` : toutes les sondes
+        assert_eq!(database.match_response("TLSClientHello", b"ECHO").unwrap().service, "echo");
+
+        // Ajout programmatique
+        database.add_signature(
+            ServiceSignature::new("telnet", "NULL", r"^\xff[\xfb-\xfe]").unwrap().with_info("negotiation")
+        );
+        assert_eq!(database.match_response("NULL", b"\xff\xfd\x18"), Some(service("telnet", None, None, Some("negotiation"))));
+    }
+
+    #[test]
+    fn test_database_errors() {
+        let line = |text: &str| match ServiceDatabase::parse(text) {
+            Err(ParseError::InvalidLine { line, .. }) => line,
+            other => panic!("{:?}", other.map(|database| database.probes.len())),
+        };
+        assert_eq!(line("# ok\nprobe NULL tcp * \"\"\nprobe X sctp * \"\""), 3);
+        assert_eq!(line("\n\nfoo bar"), 3);
+        assert_eq!(line("probe X tcp 0 \"\""), 1);
+        assert_eq!(line("probe X tcp * GET"), 1);
+        assert_eq!(line("probe X tcp * \"\\q\""), 1);
+        assert_eq!(line("probe X"), 1);
+        assert_eq!(line("match ssh NULL m/(unclosed/"), 1);
+        assert_eq!(line("match ssh NULL m/^SSH/x"), 1);
+        assert_eq!(line("match ssh NULL m/^SSH"), 1);
+        assert_eq!(line("match ssh NULL m/^SSH/ p/OpenSSH"), 1);
+        assert_eq!(line("match ssh NULL m/^SSH/ d/x/"), 1);
+        assert_eq!(line("match ssh NULL p/OpenSSH/"), 1);
+        assert!(ServiceSignature::new("x", "*", "[").is_err());
+        assert!(ServiceDatabase::load("/nonexistent/services.db").is_err());
+    }
+
+    #[test]
+    fn test_report_display() {
+        let mut report = ServiceReport {
+            port: 22,
+            protocol: L4Protocol::Tcp,
+            state: PortState::Open,
+            probe: Some("NULL".into()),
+            service: Some(service("ssh", Some("OpenSSH"), Some("9.6p1"), Some("protocol 2.0"))),
+            response: Vec::new(),
+        };
+        assert_eq!(report.to_string(), "22/tcp open ssh OpenSSH 9.6p1 (protocol 2.0)");
+        report.service = None;
+        assert_eq!(report.to_string(), "22/tcp open unknown");
+        report.state = PortState::Closed;
+        assert_eq!(report.to_string(), "22/tcp closed");
+        report.protocol = L4Protocol::Udp;
+        report.state = PortState::OpenFiltered;
+        assert_eq!(report.to_string(), "22/udp open|filtered unknown");
+    }
+
+    #[test]
+    fn test_build_probes() {
+        let detector = ServiceDetector::new(template("10.0.0.1", "10.0.0.2", "tcp"));
+        let probes = detector.build_probes(L4Protocol::Tcp, 443).unwrap();
+        // Une connexion par sonde, port source decale
+        let ports: Vec<_> = probes.iter().map(|probe| (tcp(probe).src_port, tcp(probe).dst_port)).collect();
+        assert_eq!(ports, vec![(42000, 443), (42001, 443)]);
+
+        let detector = ServiceDetector::new(template("10.0.0.1", "10.0.0.2", "udp"));
+        let probes = detector.build_probes(L4Protocol::Udp, 53).unwrap();
+        let L4Data::Udp(udp) = &probes[0].l4_data else { panic!("expected UDP") };
+        assert_eq!(udp.payload.as_ref(), Some(&ServiceDatabase::builtin().probe("DNSVersionBindReq").unwrap().payload));
+
+        let empty = ServiceDetector::new(template("10.0.0.1", "10.0.0.2", "tcp")).with_database(ServiceDatabase::new());
+        assert!(matches!(empty.build_probes(L4Protocol::Tcp, 22), Err(ParseError::MissingRequiredField(_))));
+        assert!(detector.build_probes(L4Protocol::Icmp, 22).is_err());
+    }
+
+    ///////////////////////////////////////////
+    ///       Service Detection Tests       ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_detect_loopback_ssh_greeting() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        let server = spawn_tcp_server("198.51.100.11", Some(b"SSH-2.0-OpenSSH_9.6p1 Debian-4\r\n"), None, 1);
+        let report = ServiceDetector::new(template("198.51.100.1", "198.51.100.11", "tcp"))
+            .with_timeout_ms(300)
+            .detect("lo", L4Protocol::Tcp, 22)
+            .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(report.state, PortState::Open);
+        assert_eq!(report.probe.as_deref(), Some("NULL"));
+        assert_eq!(report.service, Some(service("ssh", Some("OpenSSH"), Some("9.6p1"), Some("protocol 2.0"))));
+        assert_eq!(report.response, b"SSH-2.0-OpenSSH_9.6p1 Debian-4\r\n");
+    }
+
+    #[test]
+    fn test_detect_loopback_http_after_null_probe() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        // Serveur muet tant qu'il ne recoit pas de requete : la sonde NULL n'obtient rien
+        let server = spawn_tcp_server(
+            "198.51.100.12",
+            None,
+            Some(b"HTTP/1.0 200 OK\r\nServer: nginx/1.24.0\r\nContent-Length: 0\r\n\r\n"),
+            2,
+        );
+        let report = ServiceDetector::new(template("198.51.100.1", "198.51.100.12", "tcp"))
+            .with_timeout_ms(300)
+            .detect("lo", L4Protocol::Tcp, 8080)
+            .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(report.state, PortState::Open);
+        assert_eq!(report.probe.as_deref(), Some("GetRequest"));
+        assert_eq!(report.service, Some(service("http", Some("nginx"), Some("1.24.0"), None)));
+        assert_eq!(report.to_string(), "8080/tcp open http nginx 1.24.0");
+    }
+
+    #[test]
+    fn test_detect_loopback_dns_version() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        let server = spawn_dns_server("198.51.100.13", b"9.18.24-1-Debian");
+        let report = ServiceDetector::new(template("198.51.100.1", "198.51.100.13", "udp"))
+            .with_timeout_ms(300)
+            .detect("lo", L4Protocol::Udp, 53)
+            .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(report.state, PortState::Open);
+        assert_eq!(report.probe.as_deref(), Some("DNSVersionBindReq"));
+        assert_eq!(report.service, Some(service("domain", None, Some("9.18.24-1-Debian"), None)));
+    }
+}
@@ -0,0 +1,198 @@
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::{ParseError, Result},
+        formats::{
+            format_factory::FormatFactory,
+            stream::ByteSink,
+        },
+        structs::{
+            packet_builder::PacketBuilder,
+            network_packet::NetworkPacket,
+            formats::FormatType,
+            json::JsonDeserializer,
+            stream::{IoSink, StreamWriter},
+            pcap::PcapWriter,
+        },
+    };
+
+    fn build_packet(protocol: &str, payload: &[u8]) -> NetworkPacket {
+        PacketBuilder::from_cli_args(
+            Some("10.1.1.1"),
+            Some("10.1.1.2"),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            Some(40000),
+            Some(443),
+            Some(protocol),
+            None,
+            Some(payload.to_vec()),
+        ).unwrap().build_packet().unwrap()
+    }
+
+    // Sink qui garde chaque ecriture separement
+    struct ChunkSink {
+        chunks: Vec<Vec<u8>>,
+        flushed: bool,
+    }
+
+    impl ByteSink for ChunkSink {
+        fn write_bytes(&mut self, data: &[u8]) -> Result<()> {
+            self.chunks.push(data.to_vec());
+            Ok(())
+        }
+
+        fn flush_bytes(&mut self) -> Result<()> {
+            self.flushed = true;
+            Ok(())
+        }
+    }
+
+    // Sink toujours plein
+    struct FullSink;
+
+    impl ByteSink for FullSink {
+        fn write_bytes(&mut self, _data: &[u8]) -> Result<()> {
+            Err(ParseError::IoError("sink plein".to_string()))
+        }
+    }
+
+    ///////////////////////////////////////////
+    ///         Stream Writer Tests         ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_stream_pushes_each_packet_to_sink() {
+        let packets = [build_packet("tcp", b"a"), build_packet("udp", b"bb")];
+        let sink = ChunkSink { chunks: Vec::new(), flushed: false };
+        let mut stream = FormatFactory::new()
+            .create_stream_writer(FormatType::Pcap, sink)
+            .unwrap();
+
+        // L'entete global part des la creation
+        assert_eq!(stream.sink.chunks.len(), 1);
+        assert_eq!(stream.sink.chunks[0].len(), 24);
+
+        stream.write_packet(&packets[0]).unwrap();
+        assert_eq!(stream.sink.chunks.len(), 2);
+        stream.write_packet(&packets[1]).unwrap();
+        assert_eq!(stream.packets_written, 2);
+
+        let sink = stream.finish().unwrap();
+        assert_eq!(sink.chunks.len(), 3);
+        assert!(sink.flushed);
+        assert_eq!(sink.chunks[1].len(), 16 + packets[0].get_packet_size());
+    }
+
+    #[test]
+    fn test_stream_pcap_readable() {
+        let packets = [build_packet("tcp", b"one"), build_packet("udp", b"two")];
+        let mut stream = FormatFactory::new()
+            .create_stream_writer(FormatType::Pcap, Vec::new())
+            .unwrap();
+        stream.write_packets(&packets).unwrap();
+        let data = stream.finish().unwrap();
+
+        let mut reader = FormatFactory::new().create_reader(FormatType::Pcap, data);
+        for packet in &packets {
+            let record = reader.read_next_record().unwrap().unwrap();
+            assert_eq!(record.packet.as_ref(), Some(packet));
+        }
+        assert!(reader.read_next_record().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stream_json_array() {
+        let packets = [build_packet("tcp", b"x"), build_packet("udp", b"y"), build_packet("tcp", b"z")];
+        let mut stream = FormatFactory::new()
+            .create_stream_writer(FormatType::Json, Vec::new())
+            .unwrap();
+        stream.write_packets(&packets).unwrap();
+        let data = stream.finish().unwrap();
+
+        assert_eq!(data.first(), Some(&b'['));
+        assert_eq!(data.last(), Some(&b']'));
+        let parsed = JsonDeserializer::new()
+            .deserialize_network_packets(core::str::from_utf8(&data).unwrap())
+            .unwrap();
+        assert_eq!(parsed, packets.to_vec());
+    }
+
+    #[test]
+    fn test_stream_json_empty_array() {
+        let stream = FormatFactory::new()
+            .create_stream_writer(FormatType::Json, Vec::new())
+            .unwrap();
+        assert_eq!(stream.finish().unwrap(), b"[]".to_vec());
+    }
+
+    #[test]
+    fn test_stream_borrowed_sink() {
+        let mut out = Vec::new();
+        let packet = build_packet("udp", b"borrowed");
+        {
+            let mut stream = StreamWriter::new(Box::new(PcapWriter::new()), &mut out).unwrap();
+            stream.write_packet(&packet).unwrap();
+        }
+        assert_eq!(out.len(), 24 + 16 + packet.get_packet_size());
+    }
+
+    #[test]
+    fn test_stream_pcapng_to_file() {
+        let path = std::env::temp_dir().join(format!("rsns_stream_{}.pcapng", std::process::id()));
+        let packet = build_packet("tcp", b"file");
+        let file = std::fs::File::create(&path).unwrap();
+        let mut stream = FormatFactory::new()
+            .create_stream_writer(FormatType::PcapNg, IoSink::new(std::io::BufWriter::new(file)))
+            .unwrap();
+        stream.write_packet(&packet).unwrap();
+        stream.finish().unwrap();
+
+        let data = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let mut reader = FormatFactory::new().create_reader(FormatType::PcapNg, data);
+        let record = reader.read_next_record().unwrap().unwrap();
+        assert_eq!(record.packet, Some(packet));
+    }
+
+    #[test]
+    fn test_stream_sink_error_propagates() {
+        let result = FormatFactory::new().create_stream_writer(FormatType::Pcap, FullSink);
+        assert!(matches!(result, Err(ParseError::IoError(_))));
+    }
+
+    #[test]
+    fn test_json_format_writer_keeps_data() {
+        let packet = build_packet("tcp", b"kept");
+        let mut writer = FormatFactory::new().create_writer(FormatType::Json);
+        writer.write_header().unwrap();
+        writer.write_packet(&packet).unwrap();
+        writer.finish().unwrap();
+
+        let data = writer.into_data();
+        let parsed = JsonDeserializer::new()
+            .deserialize_network_packets(core::str::from_utf8(&data).unwrap())
+            .unwrap();
+        assert_eq!(parsed, vec![packet]);
+    }
+
+    #[test]
+    fn test_json_format_writer_without_header_or_finish() {
+        let packet = build_packet("tcp", b"lazy");
+        let mut writer = FormatFactory::new().create_writer(FormatType::Json);
+        writer.write_packet(&packet).unwrap();
+        writer.write_packet(&packet).unwrap();
+        assert_eq!(writer.get_data()[0], b'[');
+
+        // Tableau ferme par into_data, meme sans finish
+        let data = writer.into_data();
+        let parsed = JsonDeserializer::new()
+            .deserialize_network_packets(core::str::from_utf8(&data).unwrap())
+            .unwrap();
+        assert_eq!(parsed, vec![packet.clone(), packet]);
+
+        let mut empty = FormatFactory::new().create_writer(FormatType::Json);
+        empty.finish().unwrap();
+        assert_eq!(empty.into_data(), b"[]");
+    }
+}
@@ -0,0 +1,160 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        packets::{
+            builder::{packet_dissector::PacketDissector, tcp_builder::TcpBuilder},
+            l4::tcp::{pack_tcp, unpack_tcp},
+        },
+        parsing::my_parser::{parse_ipv4, parse_ipv6, parse_tcp_flags},
+        structs::{
+            json::{JsonDeserializer, JsonSerializer},
+            packet_builder::PacketBuilder,
+            scan::SynScanner,
+            tcp::TcpFlags,
+        },
+    };
+    use crate::common::{tcp, tcp_checksum_ok};
+
+    fn builder() -> PacketBuilder {
+        PacketBuilder::from_cli_args(
+            Some("10.0.0.1"),
+            Some("10.0.0.2"),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            Some(40000),
+            Some(22),
+            Some("tcp"),
+            None,
+            None,
+        ).unwrap()
+    }
+
+    ///////////////////////////////////////////
+    ///           TcpFlags Tests            ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_flag_bits() {
+        let syn_ack = TcpFlags::SYN | TcpFlags::ACK;
+        assert_eq!(syn_ack.bits(), 0x012);
+        assert!(syn_ack.contains(TcpFlags::SYN));
+        assert!(!syn_ack.contains(TcpFlags::SYN | TcpFlags::RST));
+        assert!(syn_ack.intersects(TcpFlags::RST | TcpFlags::ACK));
+        assert_eq!(syn_ack & TcpFlags::ACK, TcpFlags::ACK);
+        assert_eq!((!TcpFlags::empty()).bits(), 0x1FF);
+        assert_eq!(TcpFlags::NS.bits(), 0x100);
+
+        let mut flags = TcpFlags::empty();
+        assert!(flags.is_empty());
+        flags.insert(TcpFlags::FIN);
+        flags |= TcpFlags::PSH;
+        flags.remove(TcpFlags::FIN);
+        assert_eq!(flags, TcpFlags::PSH);
+        // Les bits reserves au-dela de NS sont ignores
+        assert_eq!(TcpFlags::from_bits(0xFE12), syn_ack);
+    }
+
+    #[test]
+    fn test_flags_display() {
+        assert_eq!((TcpFlags::SYN | TcpFlags::ACK).to_string(), "SA");
+        assert_eq!((TcpFlags::URG | TcpFlags::PSH | TcpFlags::FIN).to_string(), "FPU");
+        assert_eq!(TcpFlags::from_bits(0x1FF).to_string(), "FSRPAUECN");
+        assert_eq!(TcpFlags::empty().to_string(), "none");
+    }
+
+    #[test]
+    fn test_parse_tcp_flags() {
+        assert_eq!(parse_tcp_flags("SA").unwrap(), TcpFlags::SYN | TcpFlags::ACK);
+        assert_eq!(parse_tcp_flags("fpu").unwrap(), TcpFlags::FIN | TcpFlags::PSH | TcpFlags::URG);
+        assert_eq!(parse_tcp_flags("SSA").unwrap(), TcpFlags::SYN | TcpFlags::ACK);
+        assert_eq!(parse_tcp_flags("none").unwrap(), TcpFlags::empty());
+        assert_eq!(parse_tcp_flags("").unwrap(), TcpFlags::empty());
+        for flags in ["SX", "0x12", "S A"] {
+            assert!(parse_tcp_flags(flags).is_err(), "{}", flags);
+        }
+        // Aller-retour par l'affichage
+        for bits in [0x001, 0x029, 0x0C2, 0x1FF] {
+            let flags = TcpFlags::from_bits(bits);
+            assert_eq!(parse_tcp_flags(&flags.to_string()).unwrap(), flags);
+        }
+    }
+
+    ///////////////////////////////////////////
+    ///       TCP Header Control Tests      ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_builder_defaults() {
+        let packet = builder().build_packet().unwrap();
+        let header = tcp(&packet);
+        assert_eq!(header.tcp_flags(), TcpFlags::SYN);
+        assert_eq!((header.sequence_number, header.ack_nowledgment_number, header.window, header.urgent_pointer), (0, 0, 65535, 0));
+    }
+
+    #[test]
+    fn test_stealth_scan_probes() {
+        for flags in ["", "F", "FPU", "A"] {
+            let packet = builder().with_tcp_flags(parse_tcp_flags(flags).unwrap()).build_packet().unwrap();
+            let bytes = packet.assemble_packet().unwrap();
+            // Octet 13 de l'header TCP : drapeaux FIN..CWR
+            assert_eq!(bytes[34 + 13] as u16, parse_tcp_flags(flags).unwrap().bits(), "{}", flags);
+            assert!(tcp_checksum_ok(&packet), "{}", flags);
+            assert_eq!(PacketDissector::new().dissect_packet(&bytes).unwrap(), packet);
+        }
+    }
+
+    #[test]
+    fn test_rst_injection_fields() {
+        let packet = builder()
+            .with_tcp_flags(TcpFlags::RST | TcpFlags::ACK)
+            .with_sequence(0xDEADBEEF)
+            .with_ack(0x01020304)
+            .with_window(0)
+            .build_packet()
+            .unwrap();
+        let bytes = packet.assemble_packet().unwrap();
+        assert_eq!(bytes[38..46], [0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x02, 0x03, 0x04]);
+        assert_eq!(bytes[48..50], [0, 0]);
+        assert!(tcp_checksum_ok(&packet));
+
+        let json = JsonSerializer::new().serialize_packet(&packet).unwrap();
+        assert_eq!(JsonDeserializer::new().deserialize_network_packet(&json).unwrap(), packet);
+    }
+
+    #[test]
+    fn test_urgent_pointer_and_ns_flag() {
+        let header = TcpBuilder::new(parse_ipv4("10.0.0.1").unwrap(), parse_ipv4("10.0.0.2").unwrap())
+            .with_flags(TcpFlags::URG | TcpFlags::PSH | TcpFlags::NS)
+            .with_urgent_pointer(3)
+            .build_tcp_header(1, 2, Some(b"abc".to_vec()))
+            .unwrap();
+        let segment = pack_tcp(&header).unwrap();
+        // NS dans le bit de poids faible de l'octet data offset
+        assert_eq!(segment[12..14], [0x51, 0x28]);
+        assert_eq!(segment[18..20], [0, 3]);
+        let mut decoded = unpack_tcp(&segment).unwrap();
+        assert_eq!(decoded, header);
+
+        decoded.set_tcp_flags(TcpFlags::FIN);
+        assert_eq!(decoded.flags, 0x001);
+    }
+
+    #[test]
+    fn test_ipv6_and_scan_probe_flags() {
+        let packet = builder()
+            .with_tcp_flags(TcpFlags::FIN)
+            .build_ipv6_packet(parse_ipv6("2001:db8::1").unwrap(), parse_ipv6("2001:db8::2").unwrap(), Vec::new())
+            .unwrap();
+        assert_eq!(tcp(&packet).tcp_flags(), TcpFlags::FIN);
+
+        // Le scan SYN envoie toujours des SYN, quel que soit le template
+        let scanner = SynScanner::new(builder().with_tcp_flags(TcpFlags::FIN).with_ack(5));
+        let probe = scanner.build_probe(80).unwrap();
+        assert_eq!(tcp(&probe).tcp_flags(), TcpFlags::SYN);
+        assert_eq!(tcp(&probe).sequence_number, scanner.probe_sequence(80));
+        assert_eq!(tcp(&probe).ack_nowledgment_number, 0);
+        assert!(tcp_checksum_ok(&probe));
+    }
+}
@@ -0,0 +1,190 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        packets::{
+            builder::{packet_dissector::PacketDissector, tcp_builder::TcpBuilder},
+            l4::tcp::{pack_tcp, pack_tcp_options, unpack_tcp, unpack_tcp_options},
+        },
+        parsing::my_parser::{parse_ipv4, parse_tcp_options},
+        structs::{
+            json::{JsonDeserializer, JsonSerializer},
+            l4_protocol::L4Data,
+            network_packet::NetworkPacket,
+            packet_builder::PacketBuilder,
+            tcp::TcpOption,
+        },
+    };
+    use crate::common::{tcp, tcp_checksum_ok};
+
+    // SYN type Linux : MSS, SACK permis, timestamps, NOP, window scale
+    fn linux_syn_options() -> Vec<TcpOption> {
+        vec![
+            TcpOption::Mss(1460),
+            TcpOption::SackPermitted,
+            TcpOption::Timestamps { value: 0x01020304, echo_reply: 0 },
+            TcpOption::NoOperation,
+            TcpOption::WindowScale(7),
+        ]
+    }
+
+    fn syn(options: Vec<TcpOption>) -> NetworkPacket {
+        PacketBuilder::from_cli_args(
+            Some("10.0.0.1"),
+            Some("10.0.0.2"),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            Some(40000),
+            Some(443),
+            Some("tcp"),
+            None,
+            Some(b"hello".to_vec()),
+        ).unwrap()
+            .with_tcp_options(options)
+            .build_packet()
+            .unwrap()
+    }
+
+    ///////////////////////////////////////////
+    ///         TCP Options Tests           ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_pack_linux_syn_options() {
+        let bytes = pack_tcp_options(&linux_syn_options()).unwrap();
+        assert_eq!(bytes, vec![
+            0x02, 4, 0x05, 0xB4,
+            0x04, 2,
+            0x08, 10, 1, 2, 3, 4, 0, 0, 0, 0,
+            0x01,
+            0x03, 3, 7,
+        ]);
+        assert_eq!(unpack_tcp_options(&bytes).unwrap(), linux_syn_options());
+    }
+
+    #[test]
+    fn test_options_padding() {
+        // MSS + window scale : 7 octets completes par End of List
+        let bytes = pack_tcp_options(&[TcpOption::Mss(536), TcpOption::WindowScale(2)]).unwrap();
+        assert_eq!(bytes, vec![0x02, 4, 0x02, 0x18, 0x03, 3, 2, 0x00]);
+        assert_eq!(pack_tcp_options(&[]).unwrap(), Vec::<u8>::new());
+        assert_eq!(pack_tcp_options(&[TcpOption::SackPermitted]).unwrap(), vec![0x04, 2, 0, 0]);
+        // Ce qui suit End of List est ignore
+        assert_eq!(unpack_tcp_options(&[0x01, 0x00, 0x02, 0x04]).unwrap(), vec![TcpOption::NoOperation]);
+    }
+
+    #[test]
+    fn test_sack_fast_open_roundtrip() {
+        let options = vec![
+            TcpOption::NoOperation,
+            TcpOption::NoOperation,
+            TcpOption::Sack(vec![(1000, 2000), (3000, 4000)]),
+            TcpOption::FastOpen(vec![0xDE, 0xAD, 0xBE, 0xEF]),
+            TcpOption::FastOpen(Vec::new()),
+            TcpOption::Unknown { kind: 30, data: vec![0x10] },
+        ];
+        let bytes = pack_tcp_options(&options).unwrap();
+        assert!(bytes.len().is_multiple_of(4));
+        assert_eq!(bytes[2..4], [0x05, 18]);
+        assert_eq!(unpack_tcp_options(&bytes).unwrap(), options);
+    }
+
+    #[test]
+    fn test_options_size_limits() {
+        // 4 blocs SACK : 34 octets, un MSS tient encore, un timestamp depasse 40
+        let sack = TcpOption::Sack(vec![(1, 2); 4]);
+        assert_eq!(pack_tcp_options(core::slice::from_ref(&sack)).unwrap().len(), 36);
+        assert_eq!(pack_tcp_options(&[sack, TcpOption::Mss(1460)]).unwrap().len(), 40);
+        assert!(matches!(
+            pack_tcp_options(&[TcpOption::Sack(vec![(1, 2); 4]), TcpOption::Timestamps { value: 1, echo_reply: 2 }]),
+            Err(ParseError::InvalidHeaderLength { layer: "TCP options", length: 44 })
+        ));
+        assert!(pack_tcp_options(&[TcpOption::Sack(vec![(1, 2); 5])]).is_err());
+    }
+
+    #[test]
+    fn test_unpack_options_errors() {
+        assert!(matches!(unpack_tcp_options(&[0x02]), Err(ParseError::TruncatedPacket { layer: "TCP option", .. })));
+        assert!(matches!(unpack_tcp_options(&[0x08, 10, 0, 0]), Err(ParseError::TruncatedPacket { layer: "TCP option", .. })));
+        assert!(matches!(unpack_tcp_options(&[0x02, 1, 0, 0]), Err(ParseError::InvalidHeaderLength { length: 1, .. })));
+        // Longueur incoherente pour une option connue
+        assert!(matches!(unpack_tcp_options(&[0x02, 3, 5, 0]), Err(ParseError::InvalidHeaderLength { layer: "TCP option", length: 3 })));
+        assert!(unpack_tcp_options(&[0x05, 6, 0, 0, 0, 1, 0, 0]).is_err());
+    }
+
+    ///////////////////////////////////////////
+    ///       TCP Builder Options Tests     ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_syn_with_options_checksum_and_offset() {
+        let packet = syn(linux_syn_options());
+        let header = tcp(&packet);
+        assert_eq!(header.data_offset, 10);
+        assert_eq!(header.header_len(), 40);
+        assert_eq!(header.typed_options().unwrap(), linux_syn_options());
+        assert!(tcp_checksum_ok(&packet));
+
+        assert_eq!(packet.ipv4().unwrap().total_length as usize, 20 + 40 + 5);
+        let bytes = packet.assemble_packet().unwrap();
+        assert_eq!(bytes.len(), packet.get_packet_size());
+        assert_eq!(bytes[46] >> 4, 10);
+        assert_eq!(PacketDissector::new().dissect_packet(&bytes).unwrap(), packet);
+
+        // Sans option : header de 20 octets
+        let plain = syn(Vec::new());
+        assert_eq!(tcp(&plain).data_offset, 5);
+        assert!(tcp_checksum_ok(&plain));
+    }
+
+    #[test]
+    fn test_update_checksum_and_set_options() {
+        let builder = TcpBuilder::new(parse_ipv4("10.0.0.1").unwrap(), parse_ipv4("10.0.0.2").unwrap());
+        let mut packet = syn(Vec::new());
+        let L4Data::Tcp(header) = &mut packet.l4_data else { panic!("expected TCP") };
+        header.set_options(&[TcpOption::Mss(1200), TcpOption::WindowScale(0)]).unwrap();
+        header.sequence_number = 0xCAFEBABE;
+        builder.update_checksum(header).unwrap();
+        assert_eq!(header.data_offset, 7);
+        packet.ipv4_mut().unwrap().total_length += 8;
+        assert!(tcp_checksum_ok(&packet));
+
+        let segment = pack_tcp(tcp(&packet)).unwrap();
+        assert_eq!(&unpack_tcp(&segment).unwrap(), tcp(&packet));
+    }
+
+    #[test]
+    fn test_tcp_options_json_roundtrip() {
+        let packet = syn(vec![TcpOption::Mss(1460), TcpOption::FastOpen(Vec::new())]);
+        let json = JsonSerializer::new().serialize_packet(&packet).unwrap();
+        assert!(json.contains("\"options\":{\"String\":\"02 04 05 B4 22 02 00 00\"}"));
+        assert_eq!(JsonDeserializer::new().deserialize_network_packet(&json).unwrap(), packet);
+    }
+
+    ///////////////////////////////////////////
+    ///       TCP Options Parsing Tests     ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_parse_tcp_options() {
+        assert_eq!(parse_tcp_options("mss:1460,sackok,ts:16909060:0,nop,ws:7").unwrap(), linux_syn_options());
+        assert_eq!(
+            parse_tcp_options("nop, nop, sack:1-2/3-4, tfo:deadbeef, tfo, ts, eol").unwrap(),
+            vec![
+                TcpOption::NoOperation,
+                TcpOption::NoOperation,
+                TcpOption::Sack(vec![(1, 2), (3, 4)]),
+                TcpOption::FastOpen(vec![0xDE, 0xAD, 0xBE, 0xEF]),
+                TcpOption::FastOpen(Vec::new()),
+                TcpOption::Timestamps { value: 0, echo_reply: 0 },
+                TcpOption::EndOfList,
+            ]
+        );
+
+        for options in ["", "mss", "mss:70000", "ws:15", "sack:1", "sack:1-x", "tfo:abc", "ts:1:2:3", "sackok:1", "md5", "mss:1460,"] {
+            assert!(parse_tcp_options(options).is_err(), "{}", options);
+        }
+    }
+}
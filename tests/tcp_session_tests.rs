@@ -0,0 +1,285 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::structs::{
+        l4_protocol::L4Data,
+        network_packet::NetworkPacket,
+        packet_builder::PacketBuilder,
+        scan::PortState,
+        tcp::{TcpFlags, TcpOption},
+        tcp_session::{BannerGrabber, TcpSession, TcpState, Teardown},
+    };
+    use crate::common::{raw_socket_unavailable, reply_to, spawn_responder, tcp};
+    use std::{thread::JoinHandle, time::Duration};
+
+    // Sequence initiale du serveur simule
+    const SERVER_ISS: u32 = 7000;
+
+    fn template(src: &str, dst: &str, src_port: u16, dst_port: u16) -> PacketBuilder {
+        crate::common::template(src, dst, "tcp", src_port, dst_port)
+    }
+
+    // Segment du serveur 10.0.0.2:22 vers le client 10.0.0.1:40000
+    fn server(flags: TcpFlags, seq: u32, ack: u32, payload: &[u8]) -> NetworkPacket {
+        let mut builder = template("10.0.0.2", "10.0.0.1", 22, 40000)
+            .with_tcp_flags(flags)
+            .with_sequence(seq)
+            .with_ack(ack);
+        if !payload.is_empty() {
+            builder.payload = Some(payload.to_vec());
+        }
+        builder.build_packet().unwrap()
+    }
+
+    // Session cliente etablie : iss 1000, irs SERVER_ISS
+    fn established() -> TcpSession {
+        let mut session = TcpSession::new(template("10.0.0.1", "10.0.0.2", 40000, 22), 1000);
+        session.connect().unwrap();
+        session.handle_segment(&server(TcpFlags::SYN | TcpFlags::ACK, SERVER_ISS, 1001, b"")).unwrap();
+        assert_eq!(session.state, TcpState::Established);
+        session
+    }
+
+    // Serveur simule : SYN-ACK (ou RST|ACK si `open` est faux), banniere en deux
+    // segments inverses apres la requete du client, puis FIN
+    fn spawn_server(target: &str, banner: &'static [u8], open: bool) -> JoinHandle<()> {
+        let end = SERVER_ISS + 1 + banner.len() as u32;
+        spawn_responder(target, Duration::from_secs(3), move |segment, replies| {
+            let L4Data::Tcp(client) = &segment.l4_data else { return true };
+            let mut send = |flags: TcpFlags, seq: u32, ack: u32, payload: &[u8]| {
+                let mut builder = reply_to(segment).with_tcp_flags(flags).with_sequence(seq).with_ack(ack);
+                builder.payload = (!payload.is_empty()).then(|| payload.to_vec());
+                replies.push(builder.build_packet().unwrap().assemble_packet().unwrap());
+            };
+            let flags = client.tcp_flags();
+            let next = client.sequence_number
+                .wrapping_add(client.payload.as_ref().map_or(0, |payload| payload.len() as u32));
+
+            if flags == TcpFlags::SYN {
+                if !open {
+                    send(TcpFlags::RST | TcpFlags::ACK, 0, next.wrapping_add(1), b"");
+                    return false;
+                }
+                send(TcpFlags::SYN | TcpFlags::ACK, SERVER_ISS, next.wrapping_add(1), b"");
+            } else if client.payload.as_ref().is_some_and(|payload| !payload.is_empty()) {
+                let (head, tail) = banner.split_at(banner.len() / 2);
+                send(TcpFlags::PSH | TcpFlags::ACK, SERVER_ISS + 1 + head.len() as u32, next, tail);
+                send(TcpFlags::PSH | TcpFlags::ACK, SERVER_ISS + 1, next, head);
+                send(TcpFlags::FIN | TcpFlags::ACK, end, next, b"");
+            } else if flags.contains(TcpFlags::FIN) {
+                send(TcpFlags::ACK, end + 1, next.wrapping_add(1), b"");
+                return false;
+            }
+            true
+        })
+    }
+
+    ///////////////////////////////////////////
+    ///         TCP Session Tests           ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_handshake() {
+        let mut session = TcpSession::new(
+            template("10.0.0.1", "10.0.0.2", 40000, 22).with_tcp_options(vec![TcpOption::Mss(1460)]),
+            1000,
+        );
+        let syn = session.connect().unwrap();
+        assert_eq!(tcp(&syn).tcp_flags(), TcpFlags::SYN);
+        assert_eq!(tcp(&syn).sequence_number, 1000);
+        assert_eq!(tcp(&syn).typed_options().unwrap(), vec![TcpOption::Mss(1460)]);
+        assert_eq!(session.state, TcpState::SynSent);
+
+        // SYN-ACK n'acquittant pas notre SYN : ignore
+        let wrong = server(TcpFlags::SYN | TcpFlags::ACK, SERVER_ISS, 1005, b"");
+        assert_eq!(session.handle_segment(&wrong).unwrap(), None);
+        assert_eq!(session.state, TcpState::SynSent);
+        // Retransmission : meme SYN
+        assert_eq!(session.connect().unwrap(), syn);
+
+        let ack = session
+            .handle_segment(&server(TcpFlags::SYN | TcpFlags::ACK, SERVER_ISS, 1001, b""))
+            .unwrap()
+            .unwrap();
+        assert_eq!(session.state, TcpState::Established);
+        assert_eq!(tcp(&ack).tcp_flags(), TcpFlags::ACK);
+        assert_eq!((tcp(&ack).sequence_number, tcp(&ack).ack_nowledgment_number), (1001, SERVER_ISS + 1));
+        // Les options ne sont emises que sur le SYN
+        assert_eq!(tcp(&ack).data_offset, 5);
+        assert!(session.connect().is_err());
+    }
+
+    #[test]
+    fn test_send_and_reorder() {
+        let mut session = established();
+        let data = session.send(b"HEAD / HTTP/1.0\r\n\r\n").unwrap();
+        assert_eq!(tcp(&data).tcp_flags(), TcpFlags::PSH | TcpFlags::ACK);
+        assert_eq!(tcp(&data).sequence_number, 1001);
+        assert_eq!(session.snd_nxt, 1001 + 19);
+
+        // "world" arrive avant "hello" : mis en attente, ACK du trou
+        let ack = session.handle_segment(&server(TcpFlags::ACK, SERVER_ISS + 6, 1020, b"world")).unwrap().unwrap();
+        assert_eq!(tcp(&ack).ack_nowledgment_number, SERVER_ISS + 1);
+        assert!(session.received.is_empty());
+
+        let ack = session.handle_segment(&server(TcpFlags::ACK, SERVER_ISS + 1, 1020, b"hello")).unwrap().unwrap();
+        assert_eq!(session.received, b"helloworld");
+        assert_eq!(tcp(&ack).ack_nowledgment_number, SERVER_ISS + 11);
+        assert_eq!(session.snd_una, 1020);
+
+        // Doublon et chevauchement : rien de plus n'est livre
+        session.handle_segment(&server(TcpFlags::ACK, SERVER_ISS + 1, 1020, b"hello")).unwrap();
+        session.handle_segment(&server(TcpFlags::ACK, SERVER_ISS + 4, 1020, b"loworld!")).unwrap();
+        assert_eq!(session.received, b"helloworld!");
+        assert_eq!(session.rcv_nxt, SERVER_ISS + 12);
+    }
+
+    #[test]
+    fn test_passive_close() {
+        let mut session = established();
+        // FIN recu avant les donnees qui le precedent
+        session.handle_segment(&server(TcpFlags::FIN | TcpFlags::ACK, SERVER_ISS + 4, 1001, b"")).unwrap();
+        assert!(!session.peer_closed());
+        assert_eq!(session.state, TcpState::Established);
+
+        let ack = session.handle_segment(&server(TcpFlags::ACK, SERVER_ISS + 1, 1001, b"abc")).unwrap().unwrap();
+        assert!(session.peer_closed());
+        assert_eq!(session.state, TcpState::CloseWait);
+        assert_eq!(tcp(&ack).ack_nowledgment_number, SERVER_ISS + 5);
+
+        let fin = session.close().unwrap();
+        assert_eq!(tcp(&fin).tcp_flags(), TcpFlags::FIN | TcpFlags::ACK);
+        assert_eq!(session.state, TcpState::LastAck);
+        assert_eq!(session.handle_segment(&server(TcpFlags::ACK, SERVER_ISS + 5, 1002, b"")).unwrap(), None);
+        assert_eq!(session.state, TcpState::Closed);
+        assert!(session.is_finished() && !session.reset);
+    }
+
+    #[test]
+    fn test_active_close() {
+        let mut session = established();
+        session.close().unwrap();
+        assert_eq!(session.state, TcpState::FinWait1);
+        assert!(session.send(b"late").is_err());
+
+        session.handle_segment(&server(TcpFlags::ACK, SERVER_ISS + 1, 1002, b"")).unwrap();
+        assert_eq!(session.state, TcpState::FinWait2);
+        let ack = session.handle_segment(&server(TcpFlags::FIN | TcpFlags::ACK, SERVER_ISS + 1, 1002, b"bye")).unwrap().unwrap();
+        assert_eq!(session.state, TcpState::TimeWait);
+        assert_eq!(tcp(&ack).ack_nowledgment_number, SERVER_ISS + 5);
+        assert_eq!(session.received, b"bye");
+        assert!(session.is_finished());
+
+        // FIN croises
+        let mut session = established();
+        session.close().unwrap();
+        session.handle_segment(&server(TcpFlags::FIN | TcpFlags::ACK, SERVER_ISS + 1, 1001, b"")).unwrap();
+        assert_eq!(session.state, TcpState::Closing);
+        session.handle_segment(&server(TcpFlags::ACK, SERVER_ISS + 2, 1002, b"")).unwrap();
+        assert_eq!(session.state, TcpState::TimeWait);
+    }
+
+    #[test]
+    fn test_reset_and_abort() {
+        // Port ferme : RST|ACK en reponse au SYN
+        let mut session = TcpSession::new(template("10.0.0.1", "10.0.0.2", 40000, 22), 1000);
+        session.connect().unwrap();
+        session.handle_segment(&server(TcpFlags::RST | TcpFlags::ACK, 0, 1001, b"")).unwrap();
+        assert_eq!(session.state, TcpState::Closed);
+        assert!(session.reset);
+        assert!(session.connect().is_err());
+
+        // RST hors fenetre ignore, RST dans la fenetre accepte
+        let mut session = established();
+        session.handle_segment(&server(TcpFlags::RST, SERVER_ISS.wrapping_sub(10), 0, b"")).unwrap();
+        assert_eq!(session.state, TcpState::Established);
+        session.handle_segment(&server(TcpFlags::RST, SERVER_ISS + 1, 0, b"")).unwrap();
+        assert!(session.reset && session.is_finished());
+
+        let mut session = established();
+        let rst = session.abort().unwrap();
+        assert_eq!(tcp(&rst).tcp_flags(), TcpFlags::RST | TcpFlags::ACK);
+        assert_eq!((tcp(&rst).sequence_number, tcp(&rst).ack_nowledgment_number), (1001, SERVER_ISS + 1));
+        assert_eq!(session.state, TcpState::Closed);
+    }
+
+    #[test]
+    fn test_session_errors_and_foreign_segments() {
+        let mut session = TcpSession::new(template("10.0.0.1", "10.0.0.2", 40000, 22), 1000);
+        assert!(session.send(b"x").is_err());
+        assert!(session.close().is_err());
+
+        let mut session = established();
+        // Autre port source : pas pour cette connexion
+        let mut foreign = server(TcpFlags::ACK, SERVER_ISS + 1, 1001, b"intrus");
+        if let L4Data::Tcp(tcp) = &mut foreign.l4_data {
+            tcp.src_port = 23;
+        }
+        assert!(!session.matches(&foreign));
+        assert_eq!(session.handle_segment(&foreign).unwrap(), None);
+        assert!(session.received.is_empty());
+        // Notre propre segment n'est pas une reponse
+        assert!(!session.matches(&session.clone().send(b"x").unwrap()));
+    }
+
+    ///////////////////////////////////////////
+    ///        Banner Grabbing Tests        ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_grab_loopback_banner() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        let server = spawn_server("198.51.100.9", b"HTTP/1.0 200 OK\r\nServer: test\r\n\r\n", true);
+
+        let grabber = BannerGrabber::new(template("198.51.100.1", "198.51.100.9", 41000, 0))
+            .with_timeout_ms(500)
+            .with_payload(b"HEAD / HTTP/1.0\r\n\r\n".to_vec());
+        let banner = grabber.grab("lo", 80).unwrap();
+        server.join().unwrap();
+
+        assert_eq!(banner.port, 80);
+        assert_eq!(banner.state, PortState::Open);
+        assert_eq!(banner.data, b"HTTP/1.0 200 OK\r\nServer: test\r\n\r\n");
+        // Le dernier segment est l'ACK de notre FIN
+        let last = banner.segments.last().unwrap();
+        assert_eq!(tcp(last).tcp_flags(), TcpFlags::ACK);
+        assert_eq!(tcp(last).src_port, 80);
+        assert!(banner.segments.iter().any(|segment| tcp(segment).tcp_flags() == TcpFlags::FIN | TcpFlags::ACK && tcp(segment).src_port == 41000));
+    }
+
+    #[test]
+    fn test_grab_loopback_closed_and_rst_teardown() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        let server = spawn_server("198.51.100.10", b"", false);
+        let banner = BannerGrabber::new(template("198.51.100.1", "198.51.100.10", 41001, 0))
+            .with_timeout_ms(500)
+            .with_teardown(Teardown::Rst)
+            .grab("lo", 22)
+            .unwrap();
+        server.join().unwrap();
+        assert_eq!(banner.state, PortState::Closed);
+        assert!(banner.data.is_empty());
+        assert_eq!(banner.segments.len(), 2);
+    }
+
+    #[test]
+    fn test_grab_unanswered_is_filtered() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        let banner = BannerGrabber::new(template("127.0.0.1", "203.0.113.1", 41002, 0))
+            .with_timeout_ms(100)
+            .with_retries(2)
+            .grab("lo", 9)
+            .unwrap();
+        assert_eq!(banner.state, PortState::Filtered);
+        // Trois SYN identiques
+        assert_eq!(banner.segments.len(), 3);
+        assert!(banner.segments.iter().all(|segment| *segment == banner.segments[0]));
+    }
+}
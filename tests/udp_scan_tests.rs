@@ -0,0 +1,207 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        parsing::my_parser::parse_ipv4,
+        scanner::udp_scan::udp_probe_payload,
+        structs::{
+            formats::PacketRecord,
+            pcap::LinkType,
+            l4_protocol::L4Data,
+            network_packet::NetworkPacket,
+            packet_builder::PacketBuilder,
+            scan::{PortState, UdpScanner},
+        },
+        utils::checksum::internet_checksum,
+    };
+    use crate::common::{raw_socket_unavailable, reply_to, spawn_responder};
+    use std::{
+        thread::JoinHandle,
+        time::{Duration, Instant},
+    };
+
+    fn template(src: &str, dst: &str) -> PacketBuilder {
+        crate::common::template(src, dst, "udp", 45001, 0)
+    }
+
+    fn record(frame: Vec<u8>) -> PacketRecord {
+        PacketRecord {
+            ts_sec: 0,
+            ts_usec: 0,
+            ts_nsec: 0,
+            caplen: frame.len() as u32,
+            orig_len: frame.len() as u32,
+            packet: NetworkPacket::parse(&frame).ok(),
+            data: frame,
+            link_type: LinkType::Ethernet,
+            comment: None,
+        }
+    }
+
+    // Reponse UDP de la cible a la sonde
+    fn udp_reply(probe: &NetworkPacket, payload: &[u8]) -> Vec<u8> {
+        let mut reply = reply_to(probe);
+        reply.payload = Some(payload.to_vec());
+        reply.build_packet().unwrap().assemble_packet().unwrap()
+    }
+
+    // ICMP destination unreachable citant la sonde, emis par `from`
+    fn icmp_unreachable(probe: &NetworkPacket, from: [u8; 4], code: u8) -> Vec<u8> {
+        let quoted = probe.assemble_packet().unwrap();
+        let mut icmp = vec![3, code, 0, 0, 0, 0, 0, 0];
+        icmp.extend_from_slice(&quoted[14..14 + 28]);
+        let checksum = internet_checksum(&icmp);
+        icmp[2..4].copy_from_slice(&checksum.to_be_bytes());
+
+        let total_length = (20 + icmp.len()) as u16;
+        let mut ip = vec![0x45, 0, 0, 0, 0, 0, 0, 0, 64, 1, 0, 0];
+        ip[2..4].copy_from_slice(&total_length.to_be_bytes());
+        ip.extend_from_slice(&from);
+        ip.extend_from_slice(&probe.ipv4().unwrap().src_addr);
+        let checksum = internet_checksum(&ip);
+        ip[10..12].copy_from_slice(&checksum.to_be_bytes());
+
+        let mut frame = vec![0u8; 12];
+        frame.extend_from_slice(&[0x08, 0x00]);
+        frame.extend_from_slice(&ip);
+        frame.extend_from_slice(&icmp);
+        frame
+    }
+
+    // Cible simulee : 53 ouvert, 54 ferme, 55 filtre, le reste muet
+    fn spawn_target(target: &str, probes: usize) -> JoinHandle<()> {
+        let octets = parse_ipv4(target).unwrap().octets;
+        let mut seen = 0;
+        spawn_responder(target, Duration::from_secs(3), move |probe, replies| {
+            let L4Data::Udp(udp) = &probe.l4_data else { return true };
+            seen += 1;
+            match udp.dst_port {
+                53 => replies.push(udp_reply(probe, b"answer")),
+                54 => replies.push(icmp_unreachable(probe, octets, 3)),
+                55 => replies.push(icmp_unreachable(probe, [198, 51, 100, 254], 13)),
+                _ => {}
+            }
+            seen < probes
+        })
+    }
+
+    ///////////////////////////////////////////
+    ///          UDP Probe Tests            ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_probe_payloads_per_service() {
+        // DNS : une question
+        assert_eq!(&udp_probe_payload(53)[4..6], &[0x00, 0x01]);
+        // NTP : client version 4
+        let ntp = udp_probe_payload(123);
+        assert_eq!(ntp.len(), 48);
+        assert_eq!(ntp[0], 0x23);
+        // SNMP : longueur de la SEQUENCE BER coherente
+        let snmp = udp_probe_payload(161);
+        assert_eq!(snmp[0], 0x30);
+        assert_eq!(snmp[1] as usize, snmp.len() - 2);
+        // NetBIOS : nom encode sur 32 octets
+        let nbstat = udp_probe_payload(137);
+        assert_eq!(nbstat[12], 0x20);
+        assert_eq!(nbstat.len(), 12 + 1 + 32 + 1 + 4);
+        // Port inconnu : datagramme vide
+        assert!(udp_probe_payload(40000).is_empty());
+        // Charge utile fournie : prioritaire sur la sonde integree
+        let probe = UdpScanner::new(template("10.0.0.1", "10.0.0.2"))
+            .with_payload(53, b"custom".to_vec())
+            .build_probe(53)
+            .unwrap();
+        let L4Data::Udp(udp) = &probe.l4_data else { panic!("UDP attendu") };
+        assert_eq!(udp.payload, Some(b"custom".to_vec()));
+    }
+
+    #[test]
+    fn test_classify_udp_and_icmp_replies() {
+        let scanner = UdpScanner::new(template("10.0.0.1", "10.0.0.2"));
+        let probe = scanner.build_probe(161).unwrap();
+
+        let open = record(udp_reply(&probe, b"x"));
+        assert_eq!(scanner.classify_reply(&open), Some((161, PortState::Open)));
+
+        let closed = record(icmp_unreachable(&probe, [10, 0, 0, 2], 3));
+        assert_eq!(scanner.classify_reply(&closed), Some((161, PortState::Closed)));
+
+        let filtered = record(icmp_unreachable(&probe, [10, 0, 0, 254], 13));
+        assert_eq!(scanner.classify_reply(&filtered), Some((161, PortState::Filtered)));
+
+        // Fragmentation necessaire : ignore
+        let other = record(icmp_unreachable(&probe, [10, 0, 0, 254], 4));
+        assert_eq!(scanner.classify_reply(&other), None);
+    }
+
+    #[test]
+    fn test_classify_ignores_unrelated() {
+        let scanner = UdpScanner::new(template("10.0.0.1", "10.0.0.2"));
+        // Notre propre sonde
+        assert_eq!(scanner.classify_reply(&record(scanner.build_probe(53).unwrap().assemble_packet().unwrap())), None);
+        // ICMP citant une sonde vers une autre cible
+        let other_scanner = UdpScanner::new(template("10.0.0.1", "10.0.0.3"));
+        let probe = other_scanner.build_probe(53).unwrap();
+        assert_eq!(scanner.classify_reply(&record(icmp_unreachable(&probe, [10, 0, 0, 3], 3))), None);
+    }
+
+    ///////////////////////////////////////////
+    ///       UDP Scan Loopback Tests       ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_udp_scan_loopback_states() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        let responder = spawn_target("198.51.100.9", 4);
+
+        let scanner = UdpScanner::new(template("198.51.100.1", "198.51.100.9"))
+            .with_timeout_ms(300)
+            .with_retries(0)
+            .with_rate_limit(50);
+        let results = scanner.scan("lo", &[53, 54, 55, 56]).unwrap();
+        responder.join().unwrap();
+
+        let states: Vec<_> = results.iter().map(|r| (r.port, r.state)).collect();
+        assert_eq!(states, vec![
+            (53, PortState::Open),
+            (54, PortState::Closed),
+            (55, PortState::Filtered),
+            (56, PortState::OpenFiltered),
+        ]);
+    }
+
+    #[test]
+    fn test_udp_scan_rate_limit_spacing() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        // 5 sondes a 10/s : au moins 400 ms entre la premiere et la derniere
+        let scanner = UdpScanner::new(template("127.0.0.1", "203.0.113.2"))
+            .with_timeout_ms(0)
+            .with_retries(0)
+            .with_rate_limit(10);
+        let start = Instant::now();
+        let results = scanner.scan("lo", &[1, 2, 3, 4, 5]).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(400));
+        assert!(results.iter().all(|r| r.state == PortState::OpenFiltered && r.attempts == 1));
+    }
+
+    #[test]
+    fn test_udp_scan_max_retries_counts_every_wave() {
+        if raw_socket_unavailable() {
+            return;
+        }
+        // 256 vagues sans reponse : le compteur ne doit pas deborder
+        let scanner = UdpScanner::new(template("127.0.0.1", "203.0.113.2"))
+            .with_timeout_ms(0)
+            .with_retries(u8::MAX)
+            .with_rate_limit(0);
+        let results = scanner.scan("lo", &[7]).unwrap();
+        assert_eq!(results[0].state, PortState::OpenFiltered);
+        assert_eq!(results[0].attempts, 256);
+    }
+}
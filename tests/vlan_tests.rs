@@ -0,0 +1,202 @@
+#[cfg(test)]
+mod tests {
+    use projet_rsns_morissetlarresacha::{
+        errors::errors::ParseError,
+        formats::format_factory::FormatFactory,
+        packets::{
+            builder::{ethernet_builder::EthernetBuilder, packet_dissector::PacketDissector},
+            ethernet::{pack_ethernet, unpack_ethernet},
+        },
+        parsing::my_parser::{parse_ipv6, parse_vlan_tags},
+        structs::{
+            ethernet::{EthernetHeader, VlanTag, ETHERTYPE_QINQ, ETHERTYPE_QINQ_LEGACY, ETHERTYPE_VLAN},
+            formats::FormatType,
+            json::{JsonDeserializer, JsonSerializer},
+            network_packet::NetworkPacket,
+            packet_builder::PacketBuilder,
+            pcap::{LinkType, PcapOptions, PcapReader, PcapWriter},
+        },
+    };
+
+    const SRC_MAC: [u8; 6] = [0x02, 0x00, 0x00, 0x00, 0x00, 0x01];
+    const DST_MAC: [u8; 6] = [0x02, 0x00, 0x00, 0x00, 0x00, 0x02];
+
+    fn tagged_packet(vlan: &str, protocol: &str) -> NetworkPacket {
+        PacketBuilder::from_cli_args(
+            Some("10.0.0.1"),
+            Some("10.0.0.2"),
+            Some(SRC_MAC),
+            Some(DST_MAC),
+            Some(40000),
+            Some(80),
+            Some(protocol),
+            None,
+            Some(b"trunk".to_vec()),
+        ).unwrap()
+            .with_vlan_tags(parse_vlan_tags(vlan).unwrap())
+            .build_packet()
+            .unwrap()
+    }
+
+    ///////////////////////////////////////////
+    ///         VLAN Header Tests           ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_pack_single_tag_layout() {
+        let header = EthernetBuilder::new()
+            .with_vlan(VlanTag::new(100).with_pcp(5).with_dei(true))
+            .build_ethernet_header(SRC_MAC, DST_MAC);
+        assert_eq!(header.header_len(), 18);
+        assert_eq!(header.vlan_id(), Some(100));
+
+        let frame = pack_ethernet(&header, &[0xAA]).unwrap();
+        assert_eq!(frame.len(), 19);
+        // TPID 0x8100, TCI = PCP 5 | DEI | VID 100 = 0xB064, puis l'ethertype IPv4
+        assert_eq!(frame[12..18], [0x81, 0x00, 0xB0, 0x64, 0x08, 0x00]);
+        assert_eq!(frame[18], 0xAA);
+    }
+
+    #[test]
+    fn test_qinq_roundtrip() {
+        let header = EthernetBuilder::new()
+            .with_vlan(VlanTag::service(300).with_pcp(7))
+            .with_vlan(VlanTag::new(4095))
+            .build_ethernet_header_with_type(SRC_MAC, DST_MAC, 0x86DD);
+        let frame = pack_ethernet(&header, &[1, 2, 3]).unwrap();
+        assert_eq!(frame[12..14], [0x88, 0xA8]);
+        assert_eq!(frame[16..18], [0x81, 0x00]);
+        assert_eq!(frame[20..22], [0x86, 0xDD]);
+
+        let (decoded, payload) = unpack_ethernet(&frame).unwrap();
+        assert_eq!(decoded, header);
+        assert_eq!(decoded.vlan_id(), Some(4095));
+        assert_eq!(payload, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_unpack_legacy_and_truncated_tags() {
+        let mut frame = vec![0u8; 12];
+        frame.extend_from_slice(&[0x91, 0x00, 0x00, 0x0A, 0x81, 0x00, 0x20, 0x14, 0x08, 0x06]);
+        let (header, payload) = unpack_ethernet(&frame).unwrap();
+        assert_eq!(header.vlan_tags, vec![
+            VlanTag { tpid: ETHERTYPE_QINQ_LEGACY, pcp: 0, dei: false, vid: 10 },
+            VlanTag { tpid: ETHERTYPE_VLAN, pcp: 1, dei: false, vid: 20 },
+        ]);
+        assert_eq!(header.ethertype, 0x0806);
+        assert!(payload.is_empty());
+
+        assert!(matches!(
+            unpack_ethernet(&frame[..16]),
+            Err(ParseError::TruncatedPacket { layer: "VLAN", needed: 18, available: 16 })
+        ));
+    }
+
+    #[test]
+    fn test_pack_rejects_invalid_tags() {
+        let header = |tag| EthernetHeader {
+            dst_mac: DST_MAC,
+            src_mac: SRC_MAC,
+            vlan_tags: vec![tag],
+            ethertype: 0x0800,
+        };
+        assert!(pack_ethernet(&header(VlanTag::new(4096)), &[]).is_err());
+        assert!(pack_ethernet(&header(VlanTag::new(1).with_pcp(8)), &[]).is_err());
+        assert!(pack_ethernet(&header(VlanTag { tpid: 0x0800, ..VlanTag::new(1) }), &[]).is_err());
+    }
+
+    ///////////////////////////////////////////
+    ///       Tagged Packet Tests           ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_tagged_packet_size_and_dissect() {
+        for (vlan, header_len) in [("10", 18), ("20,10", 22)] {
+            for protocol in ["tcp", "udp", "icmp"] {
+                let packet = tagged_packet(vlan, protocol);
+                let bytes = packet.assemble_packet().unwrap();
+                assert_eq!(bytes.len(), packet.get_packet_size(), "{} {}", vlan, protocol);
+                // L'header IPv4 suit directement les tags
+                assert_eq!(bytes[header_len] >> 4, 4);
+                assert_eq!(PacketDissector::new().dissect_packet(&bytes).unwrap(), packet);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tagged_ipv6_packet() {
+        let packet = PacketBuilder::from_cli_args(None, None, Some(SRC_MAC), Some(DST_MAC), None, None, Some("udp"), None, None)
+            .unwrap()
+            .with_vlan_tags(vec![VlanTag::new(42)])
+            .build_ipv6_packet(parse_ipv6("2001:db8::1").unwrap(), parse_ipv6("2001:db8::2").unwrap(), Vec::new())
+            .unwrap();
+        let bytes = packet.assemble_packet().unwrap();
+        assert_eq!(bytes[16..18], [0x86, 0xDD]);
+        assert_eq!(bytes.len(), packet.get_packet_size());
+        assert_eq!(NetworkPacket::parse(&bytes).unwrap(), packet);
+    }
+
+    #[test]
+    fn test_tagged_packet_formats_roundtrip() {
+        let packet = tagged_packet("200,100:3:1", "udp");
+        let json = JsonSerializer::new().serialize_packet(&packet).unwrap();
+        assert!(json.contains("\"vlan_tags\":[{\"vid\":200,\"pcp\":0,\"dei\":false,\"tpid\":34984}"));
+        assert_eq!(JsonDeserializer::new().deserialize_network_packet(&json).unwrap(), packet);
+
+        let data = FormatFactory::new().write_packet(&packet, FormatType::Pcap).unwrap();
+        let record = PcapReader::new(data).read_next_record().unwrap().unwrap();
+        assert_eq!(record.packet, Some(packet));
+
+        // Trame non taguee : pas de champ vlan_tags
+        let json = JsonSerializer::new().serialize_packet(&tagged_packet("1", "tcp")).unwrap();
+        let untagged = json.replace(",\"vlan_tags\":[{\"vid\":1,\"pcp\":0,\"dei\":false,\"tpid\":33024}]", "");
+        let decoded = JsonDeserializer::new().deserialize_network_packet(&untagged).unwrap();
+        assert!(decoded.ethernet.vlan_tags.is_empty());
+        assert!(!JsonSerializer::new().serialize_packet(&decoded).unwrap().contains("vlan_tags"));
+    }
+
+    #[test]
+    fn test_tagged_frame_written_as_sll_roundtrip() {
+        let packet = tagged_packet("200,100:3", "tcp");
+        for link_type in [LinkType::LinuxSll, LinkType::LinuxSll2, LinkType::Raw] {
+            let mut writer = PcapWriter::with_options(PcapOptions {
+                link_type,
+                ..PcapOptions::default()
+            });
+            writer.write_global_header().unwrap();
+            writer.write_packet(&packet).unwrap();
+
+            // Tags retires : l'ethertype ecrit est l'IPv4 interne, pas le TPID externe
+            let record = PcapReader::new(writer.into_data()).read_next_record().unwrap().unwrap();
+            let link_header_len = match link_type {
+                LinkType::LinuxSll => 16,
+                LinkType::LinuxSll2 => 20,
+                _ => 0,
+            };
+            // 14 octets d'en-tete Ethernet et 2 tags de 4 octets remplaces
+            assert_eq!(record.caplen as usize, packet.get_packet_size() - 22 + link_header_len);
+            let decoded = record.packet.unwrap();
+            assert_eq!(decoded.ethernet.ethertype, 0x0800);
+            assert!(decoded.ethernet.vlan_tags.is_empty());
+            assert_eq!(decoded.ip, packet.ip);
+            assert_eq!(decoded.l4_data, packet.l4_data);
+        }
+    }
+
+    ///////////////////////////////////////////
+    ///         VLAN Parsing Tests          ///
+    ///////////////////////////////////////////
+
+    #[test]
+    fn test_parse_vlan_tags() {
+        assert_eq!(parse_vlan_tags("100").unwrap(), vec![VlanTag::new(100)]);
+        assert_eq!(parse_vlan_tags("100:6:1").unwrap(), vec![VlanTag::new(100).with_pcp(6).with_dei(true)]);
+        let qinq = parse_vlan_tags("200:1, 100").unwrap();
+        assert_eq!(qinq, vec![VlanTag::service(200).with_pcp(1), VlanTag::new(100)]);
+        assert_eq!(qinq[0].tpid, ETHERTYPE_QINQ);
+
+        for vlan in ["", "4096", "100:8", "100:1:2", "100:1:0:0", "abc", "100,"] {
+            assert!(parse_vlan_tags(vlan).is_err(), "{}", vlan);
+        }
+    }
+}